@@ -76,6 +76,10 @@ pub struct Build {
     /// Prints compilation steps.
     #[clap(short, long)]
     verbose: bool,
+
+    /// Also exports the ABI of each package as a JSON file next to its RPD file.
+    #[clap(long)]
+    export_abi: bool,
 }
 
 impl Build {
@@ -159,9 +163,24 @@ impl Build {
             );
         }
 
-        compiler_builder
+        let build_artifacts = compiler_builder
             .compile()
-            .map(|_| ())
-            .map_err(|e| Error::BuildError(BuildError::ScryptoCompilerError(e)).into())
+            .map_err(|e| Error::BuildError(BuildError::ScryptoCompilerError(e)))?;
+
+        if self.export_abi {
+            for build_artifact in build_artifacts {
+                let abi_path = build_artifact
+                    .package_definition
+                    .path
+                    .with_extension("abi.json");
+                let abi_json =
+                    package_definition_to_abi_json(&build_artifact.package_definition.content);
+                std::fs::write(&abi_path, format!("{:#}", abi_json)).map_err(|e| {
+                    Error::BuildError(BuildError::IOErrorAtPath(e, abi_path.clone()))
+                })?;
+            }
+        }
+
+        Ok(())
     }
 }
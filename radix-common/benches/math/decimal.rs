@@ -5,7 +5,7 @@ use criterion::{BenchmarkId, Criterion};
 use radix_common::math::*;
 
 use crate::macros::QUICK;
-use crate::{bench_ops, ops_fn, ops_root_fn, process_op};
+use crate::{bench_ops, ops_fn, ops_root_fn, ops_transcendental_fn, process_op};
 
 const ADD_OPERANDS: [(&str, &str); 4] = [
     (
@@ -82,6 +82,22 @@ const POW_OPERANDS: [(&str, &str); 4] = [
     ("9", "2"),
 ];
 
+const EXP_OPERANDS: [&str; 4] = ["90.123123123", "-12.123123123", "1.123123123", "0.5"];
+
+const LN_OPERANDS: [&str; 4] = [
+    "3138550867693340381917894711603833208051.177722232017256447",
+    "12379879872423987.123123123",
+    "1.123123123",
+    "0.000000000123123123",
+];
+
+const POWD_OPERANDS: [(&str, &str); 4] = [
+    ("12.123123123", "13.5"),
+    ("1.123123123", "-5.25"),
+    ("4", "0.5"),
+    ("-9", "3"),
+];
+
 const TO_STRING_OPERANDS: [&str; 4] = [
     "3138550867693340381917894711603833208051.177722232017256447",
     "-11237987890123090890328.1928379813",
@@ -98,11 +114,15 @@ const FROM_STRING_OPERANDS: [&str; 4] = [
 
 ops_fn!(Decimal, checked_powi, i64, "clone");
 ops_root_fn!(Decimal, checked_nth_root, "clone");
+ops_transcendental_fn!(Decimal);
 bench_ops!(Decimal, "add");
 bench_ops!(Decimal, "sub");
 bench_ops!(Decimal, "mul");
 bench_ops!(Decimal, "div");
 bench_ops!(Decimal, "root", u32);
 bench_ops!(Decimal, "pow", i64);
+bench_ops!(Decimal, "exp");
+bench_ops!(Decimal, "ln");
+bench_ops!(Decimal, "powd");
 bench_ops!(Decimal, "to_string");
 bench_ops!(Decimal, "from_string");
@@ -85,8 +85,35 @@ macro_rules! ops_root_fn {
     };
 }
 
+#[macro_export]
+macro_rules! ops_transcendental_fn {
+    ($t:ty) => {
+        paste::item! {
+            fn [< $t:lower _exp >](a: &$t, _: &str) {
+                let _ = a.checked_exp();
+            }
+
+            fn [< $t:lower _ln >](a: &$t, _: &str) {
+                let _ = a.checked_ln();
+            }
+
+            fn [< $t:lower _powd >](a: &$t, exp: &$t) {
+                let _ = a.checked_powd(*exp);
+            }
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! process_op {
+    ($t:ty, $i:ident, $op:ident, $bid:ident, "exp") => {
+        let $bid = format!("{}", $i);
+        let $op = (<$t>::from_str(*$op).unwrap(), "_");
+    };
+    ($t:ty, $i:ident, $op:ident, $bid:ident, "ln") => {
+        let $bid = format!("{}", $i);
+        let $op = (<$t>::from_str(*$op).unwrap(), "_");
+    };
     ($t:ty, $i:ident, $op:ident, $bid:ident, "to_string") => {
         let $bid = format!("{}", $i);
         let $op = (<$t>::from_str(*$op).unwrap(), "_");
@@ -5,7 +5,7 @@ use criterion::{BenchmarkId, Criterion};
 use radix_common::math::*;
 
 use crate::macros::QUICK;
-use crate::{bench_ops, ops_fn, ops_root_fn, process_op};
+use crate::{bench_ops, ops_fn, ops_root_fn, ops_transcendental_fn, process_op};
 /*
             "57896044618658097711785492504343953926634.992332820282019728792003956564819967"
 */
@@ -93,6 +93,22 @@ const POW_OPERANDS: [(&str, &str); 4] = [
     ("9", "2"),
 ];
 
+const EXP_OPERANDS: [&str; 4] = ["90.123123123", "-12.123123123", "1.123123123", "0.5"];
+
+const LN_OPERANDS: [&str; 4] = [
+    "3138550867693340381917894711603833208051.177722232017256447",
+    "12379879872423987.123123123",
+    "1.123123123",
+    "0.000000000123123123",
+];
+
+const POWD_OPERANDS: [(&str, &str); 4] = [
+    ("12.123123123", "13.5"),
+    ("1.123123123", "-5.25"),
+    ("4", "0.5"),
+    ("-9", "3"),
+];
+
 const TO_STRING_OPERANDS: [&str; 4] = [
     "57896044618658097711785492504343953926634.992332820282019728792003956564819967",
     "578918658097711785492504343953926634.792003956564817",
@@ -109,11 +125,15 @@ const FROM_STRING_OPERANDS: [&str; 4] = [
 
 ops_fn!(PreciseDecimal, checked_powi, i64, "clone");
 ops_root_fn!(PreciseDecimal, checked_nth_root, "clone");
+ops_transcendental_fn!(PreciseDecimal);
 bench_ops!(PreciseDecimal, "add");
 bench_ops!(PreciseDecimal, "sub");
 bench_ops!(PreciseDecimal, "mul");
 bench_ops!(PreciseDecimal, "div");
 bench_ops!(PreciseDecimal, "root", u32);
 bench_ops!(PreciseDecimal, "pow", i64);
+bench_ops!(PreciseDecimal, "exp");
+bench_ops!(PreciseDecimal, "ln");
+bench_ops!(PreciseDecimal, "powd");
 bench_ops!(PreciseDecimal, "to_string");
 bench_ops!(PreciseDecimal, "from_string");
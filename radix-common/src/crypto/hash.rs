@@ -9,9 +9,10 @@ use sbor::rust::vec::Vec;
 use sbor::*;
 
 /// Represents a 32-byte hash digest.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Sbor)]
 #[sbor(transparent)]
-pub struct Hash(pub [u8; Self::LENGTH]);
+pub struct Hash(#[cfg_attr(feature = "serde", serde(with = "hex::serde"))] pub [u8; Self::LENGTH]);
 
 impl Hash {
     pub const LENGTH: usize = 32;
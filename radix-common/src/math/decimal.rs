@@ -13,6 +13,7 @@ use crate::data::scrypto::*;
 use crate::math::bnum_integer::*;
 use crate::math::rounding_mode::*;
 use crate::math::traits::*;
+use crate::math::transcendental;
 use crate::math::PreciseDecimal;
 use crate::well_known_scrypto_custom_type;
 use crate::*;
@@ -295,6 +296,34 @@ impl Decimal {
             Some(Decimal(nth_root))
         }
     }
+
+    /// Natural exponential of a Decimal, `e^self`.
+    ///
+    /// The result is within one subunit of the exact value. Returns `None` on overflow, which
+    /// happens above `ln(Decimal::MAX)`, about 90.94.
+    pub fn checked_exp(&self) -> Option<Self> {
+        let exp = transcendental::exp(BigInt::from(self.0), Self::SCALE)?;
+        I192::try_from(exp).ok().map(Self)
+    }
+
+    /// Natural logarithm of a Decimal.
+    ///
+    /// The result is within one subunit of the exact value. Returns `None` if the number isn't
+    /// positive.
+    pub fn checked_ln(&self) -> Option<Self> {
+        let ln = transcendental::ln(BigInt::from(self.0), Self::SCALE)?;
+        I192::try_from(ln).ok().map(Self)
+    }
+
+    /// Calculates power with a Decimal exponent, as `e^(exp * ln(self))`.
+    ///
+    /// The result is within one subunit of the exact value, including for integer exponents,
+    /// for which [`checked_powi`](Self::checked_powi) truncates each intermediate product.
+    /// Negative bases are only supported with integer exponents.
+    pub fn checked_powd(&self, exp: Self) -> Option<Self> {
+        let pow = transcendental::pow(BigInt::from(self.0), BigInt::from(exp.0), Self::SCALE)?;
+        I192::try_from(pow).ok().map(Self)
+    }
 }
 
 macro_rules! from_primitive_type {
@@ -1972,6 +2001,79 @@ mod tests {
         assert_eq!(root_0, None);
     }
 
+    #[test]
+    fn test_exp() {
+        assert_eq!(test_dec!(0).checked_exp().unwrap(), test_dec!(1));
+        assert_eq!(
+            test_dec!(1).checked_exp().unwrap(),
+            test_dec!("2.718281828459045235")
+        );
+        assert_eq!(
+            test_dec!("-1").checked_exp().unwrap(),
+            test_dec!("0.367879441171442321")
+        );
+        assert_eq!(
+            test_dec!("90").checked_exp().unwrap(),
+            test_dec!("1220403294317840802002710035136369753970.746421099767546244")
+        );
+        assert_eq!(test_dec!("91").checked_exp(), None);
+        assert_eq!(test_dec!("-100").checked_exp().unwrap(), test_dec!(0));
+    }
+
+    #[test]
+    fn test_ln() {
+        assert_eq!(test_dec!(1).checked_ln().unwrap(), test_dec!(0));
+        assert_eq!(
+            test_dec!(10).checked_ln().unwrap(),
+            test_dec!("2.302585092994045684")
+        );
+        assert_eq!(
+            test_dec!("0.5").checked_ln().unwrap(),
+            test_dec!("-0.693147180559945309")
+        );
+        assert_eq!(
+            Decimal::MAX.checked_ln().unwrap(),
+            test_dec!("90.944579813056731786")
+        );
+        assert_eq!(
+            Decimal::ONE_ATTO.checked_ln().unwrap(),
+            test_dec!("-41.446531673892822312")
+        );
+        assert_eq!(test_dec!(0).checked_ln(), None);
+        assert_eq!(test_dec!("-1").checked_ln(), None);
+    }
+
+    #[test]
+    fn test_powd() {
+        assert_eq!(
+            test_dec!(2).checked_powd(test_dec!("0.5")).unwrap(),
+            test_dec!("1.414213562373095048")
+        );
+        assert_eq!(
+            test_dec!("1.05").checked_powd(test_dec!(10)).unwrap(),
+            test_dec!("1.628894626777441406")
+        );
+        assert_eq!(
+            test_dec!(4).checked_powd(test_dec!("-0.5")).unwrap(),
+            test_dec!("0.5")
+        );
+        assert_eq!(
+            test_dec!("-2").checked_powd(test_dec!(3)).unwrap(),
+            test_dec!("-8")
+        );
+        assert_eq!(test_dec!("-2").checked_powd(test_dec!("0.5")), None);
+        assert_eq!(
+            test_dec!(0).checked_powd(test_dec!(0)).unwrap(),
+            test_dec!(1)
+        );
+        assert_eq!(
+            test_dec!(0).checked_powd(test_dec!(2)).unwrap(),
+            test_dec!(0)
+        );
+        assert_eq!(test_dec!(0).checked_powd(test_dec!("-1")), None);
+        assert_eq!(test_dec!(10).checked_powd(test_dec!(40)), None);
+    }
+
     #[test]
     fn no_panic_with_18_decimal_places() {
         // Arrange
@@ -12,6 +12,7 @@ use crate::math::bnum_integer::*;
 use crate::math::decimal::*;
 use crate::math::rounding_mode::*;
 use crate::math::traits::*;
+use crate::math::transcendental;
 use crate::well_known_scrypto_custom_type;
 use crate::*;
 
@@ -307,6 +308,34 @@ impl PreciseDecimal {
             Some(Self(nth_root))
         }
     }
+
+    /// Natural exponential of a PreciseDecimal, `e^self`.
+    ///
+    /// The result is within one subunit of the exact value. Returns `None` on overflow, which
+    /// happens above `ln(PreciseDecimal::MAX)`, about 93.86.
+    pub fn checked_exp(&self) -> Option<Self> {
+        let exp = transcendental::exp(BigInt::from(self.0), Self::SCALE)?;
+        I256::try_from(exp).ok().map(Self)
+    }
+
+    /// Natural logarithm of a PreciseDecimal.
+    ///
+    /// The result is within one subunit of the exact value. Returns `None` if the number isn't
+    /// positive.
+    pub fn checked_ln(&self) -> Option<Self> {
+        let ln = transcendental::ln(BigInt::from(self.0), Self::SCALE)?;
+        I256::try_from(ln).ok().map(Self)
+    }
+
+    /// Calculates power with a PreciseDecimal exponent, as `e^(exp * ln(self))`.
+    ///
+    /// The result is within one subunit of the exact value, including for integer exponents,
+    /// for which [`checked_powi`](Self::checked_powi) truncates each intermediate product.
+    /// Negative bases are only supported with integer exponents.
+    pub fn checked_powd(&self, exp: Self) -> Option<Self> {
+        let pow = transcendental::pow(BigInt::from(self.0), BigInt::from(exp.0), Self::SCALE)?;
+        I256::try_from(pow).ok().map(Self)
+    }
 }
 
 macro_rules! from_primitive_type {
@@ -2160,6 +2189,41 @@ mod tests {
         assert_eq!(root_0, None);
     }
 
+    #[test]
+    fn test_exp() {
+        assert_eq!(test_pdec!(0).checked_exp().unwrap(), test_pdec!(1));
+        assert_eq!(
+            test_pdec!(1).checked_exp().unwrap(),
+            test_pdec!("2.718281828459045235360287471352662497")
+        );
+        assert_eq!(test_pdec!("94").checked_exp(), None);
+        assert_eq!(test_pdec!("-100").checked_exp().unwrap(), test_pdec!(0));
+    }
+
+    #[test]
+    fn test_ln() {
+        assert_eq!(test_pdec!(1).checked_ln().unwrap(), test_pdec!(0));
+        assert_eq!(
+            test_pdec!(10).checked_ln().unwrap(),
+            test_pdec!("2.302585092994045684017991454684364207")
+        );
+        assert_eq!(test_pdec!(0).checked_ln(), None);
+        assert_eq!(test_pdec!("-1").checked_ln(), None);
+    }
+
+    #[test]
+    fn test_powd() {
+        assert_eq!(
+            test_pdec!(2).checked_powd(test_pdec!("0.5")).unwrap(),
+            test_pdec!("1.414213562373095048801688724209698078")
+        );
+        assert_eq!(
+            test_pdec!("-2").checked_powd(test_pdec!(3)).unwrap(),
+            test_pdec!("-8")
+        );
+        assert_eq!(test_pdec!("-2").checked_powd(test_pdec!("0.5")), None);
+    }
+
     #[test]
     fn no_panic_with_36_decimal_places() {
         // Arrange
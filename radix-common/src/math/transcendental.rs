@@ -0,0 +1,173 @@
+//! Fixed-point exponential and natural logarithm on [`BigInt`]s, shared by [`Decimal`] and
+//! [`PreciseDecimal`].
+//!
+//! Values are passed as subunits at the scale of the caller, and are computed at a working scale
+//! with [`GUARD_DIGITS`] more digits, so that after truncating back to the scale of the caller the
+//! result is within one subunit of the exact value.
+//!
+//! [`Decimal`]: super::Decimal
+//! [`PreciseDecimal`]: super::PreciseDecimal
+
+use crate::internal_prelude::*;
+use num_bigint::BigInt;
+use num_bigint::Sign;
+use num_traits::{One, Pow, Signed, ToPrimitive, Zero};
+
+/// The extra digits of the working scale. The results of `exp` can have up to 41 integer digits,
+/// so this covers those plus the error accumulated by the range reduction.
+const GUARD_DIGITS: u32 = 64;
+
+/// Beyond this magnitude, `exp` either overflows or truncates to zero for both decimal types.
+const EXP_INPUT_LIMIT: u32 = 200;
+
+/// The number of squarings which undo the reduction of the input of `exp`.
+const EXP_SQUARINGS: u32 = 10;
+
+/// `e^x`, with `x` and the result as subunits at the given scale.
+pub(crate) fn exp(x: BigInt, scale: u32) -> Option<BigInt> {
+    let work = scale + GUARD_DIGITS;
+    let one = BigInt::from(10).pow(work);
+    let x = x * BigInt::from(10).pow(GUARD_DIGITS);
+    if x.abs() > BigInt::from(EXP_INPUT_LIMIT) * &one {
+        return if x.is_negative() {
+            Some(BigInt::zero())
+        } else {
+            None
+        };
+    }
+
+    Some(exp_at_work_scale(&x, &one) / BigInt::from(10).pow(GUARD_DIGITS))
+}
+
+/// `ln(x)`, with `x` and the result as subunits at the given scale.
+pub(crate) fn ln(x: BigInt, scale: u32) -> Option<BigInt> {
+    if !x.is_positive() {
+        return None;
+    }
+    let work = scale + GUARD_DIGITS;
+    let one = BigInt::from(10).pow(work);
+    let x = x * BigInt::from(10).pow(GUARD_DIGITS);
+
+    Some(ln_at_work_scale(&x, &one) / BigInt::from(10).pow(GUARD_DIGITS))
+}
+
+/// `base^exponent`, with both and the result as subunits at the given scale.
+///
+/// Negative bases are only supported for integer exponents.
+pub(crate) fn pow(base: BigInt, exponent: BigInt, scale: u32) -> Option<BigInt> {
+    let scale_one = BigInt::from(10).pow(scale);
+    if base.is_zero() {
+        return match exponent.sign() {
+            Sign::Plus => Some(BigInt::zero()),
+            Sign::NoSign => Some(scale_one),
+            Sign::Minus => None,
+        };
+    }
+    let negate = if base.is_negative() {
+        if !(&exponent % &scale_one).is_zero() {
+            return None;
+        }
+        !(&exponent / &scale_one % BigInt::from(2)).is_zero()
+    } else {
+        false
+    };
+
+    // The error of the logarithm is multiplied by the exponent, so its integer digits are added to
+    // the working scale.
+    let exponent_digits = (&exponent / &scale_one).abs().to_string().len() as u32;
+    let guard_digits = GUARD_DIGITS + exponent_digits;
+    let one = BigInt::from(10).pow(scale + guard_digits);
+    let base = base.abs() * BigInt::from(10).pow(guard_digits);
+    let exponent = exponent * BigInt::from(10).pow(guard_digits);
+
+    let x = exponent * ln_at_work_scale(&base, &one) / &one;
+    if x.abs() > BigInt::from(EXP_INPUT_LIMIT) * &one {
+        return if x.is_negative() {
+            Some(BigInt::zero())
+        } else {
+            None
+        };
+    }
+    let result = exp_at_work_scale(&x, &one) / BigInt::from(10).pow(guard_digits);
+
+    Some(if negate { -result } else { result })
+}
+
+/// `e^x`, for `|x| <= EXP_INPUT_LIMIT`, at the working scale of `one`.
+fn exp_at_work_scale(x: &BigInt, one: &BigInt) -> BigInt {
+    // e^x = 2^k * e^r, where x = k * ln(2) + r and |r| < ln(2).
+    let ln_2 = ln_2(one);
+    let k = x / &ln_2;
+    let r = x - &k * &ln_2;
+    let k = k.to_i64().expect("Bounded by the input limit");
+
+    // e^r = (e^(r / 2^n))^(2^n), where the Taylor series of e^(r / 2^n) converges quickly.
+    let r = r / BigInt::from(1u32 << EXP_SQUARINGS);
+    let mut sum = one.clone();
+    let mut term = one.clone();
+    let mut n = 1u32;
+    loop {
+        term = term * &r / one / n;
+        if term.is_zero() {
+            break;
+        }
+        sum += &term;
+        n += 1;
+    }
+    for _ in 0..EXP_SQUARINGS {
+        sum = &sum * &sum / one;
+    }
+
+    if k >= 0 {
+        sum << k as usize
+    } else {
+        sum >> (-k) as usize
+    }
+}
+
+/// `ln(x)`, for positive `x`, at the working scale of `one`.
+fn ln_at_work_scale(x: &BigInt, one: &BigInt) -> BigInt {
+    // ln(x) = k * ln(2) + ln(m), where x = 2^k * m and 3/4 <= m <= 3/2.
+    let mut k = x.bits() as i64 - one.bits() as i64;
+    let mut m = if k >= 0 {
+        x >> k as usize
+    } else {
+        x << (-k) as usize
+    };
+    let three = BigInt::from(3);
+    while &m * 2 > &three * one {
+        m /= 2;
+        k += 1;
+    }
+    while &m * 4 < &three * one {
+        m *= 2;
+        k -= 1;
+    }
+
+    // ln(m) = 2 * atanh((m - 1) / (m + 1)), where |(m - 1) / (m + 1)| <= 1/5.
+    let z = (&m - one) * one / (&m + one);
+    BigInt::from(2) * atanh(&z, one) + BigInt::from(k) * ln_2(one)
+}
+
+/// `ln(2) = 2 * atanh(1/3)`, at the working scale of `one`.
+fn ln_2(one: &BigInt) -> BigInt {
+    BigInt::from(2) * atanh(&(one / 3), one)
+}
+
+/// `atanh(z)`, for `|z| < 1`, at the working scale of `one`.
+fn atanh(z: &BigInt, one: &BigInt) -> BigInt {
+    let z_squared = z * z / one;
+    let mut sum = z.clone();
+    let mut power = z.clone();
+    let mut n = BigInt::one();
+    loop {
+        power = power * &z_squared / one;
+        n += 2;
+        let term = &power / &n;
+        if term.is_zero() {
+            break;
+        }
+        sum += term;
+    }
+    sum
+}
@@ -1,3 +1,7 @@
 pub trait SystemExecutionTraceApi<E> {
     fn update_instruction_index(&mut self, new_index: usize) -> Result<(), E>;
+
+    /// Marks the end of the current instruction, so that nothing executed until the next call to
+    /// [`Self::update_instruction_index`] is attributed to it.
+    fn clear_instruction_index(&mut self) -> Result<(), E>;
 }
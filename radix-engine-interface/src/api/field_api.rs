@@ -44,6 +44,9 @@ pub trait SystemFieldApi<E: Debug> {
     }
 
     /// Write a value to a field
+    ///
+    /// The value is validated against the field's type in the blueprint schema, and the write
+    /// is rejected if it does not conform.
     fn field_write(&mut self, handle: FieldHandle, buffer: Vec<u8>) -> Result<(), E>;
 
     /// Write a value to a field
@@ -45,6 +45,15 @@ pub struct RemoteKeyValueStoreDataSchema {
     pub allow_ownership: bool,
 }
 
+/// A page of keys returned by a key value store scan
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct KeyValueStoreKeys {
+    pub keys: Vec<Vec<u8>>,
+    /// The last scanned key to continue the scan after, or `None` if the end of the store was
+    /// reached
+    pub next_cursor: Option<Vec<u8>>,
+}
+
 impl LocalKeyValueStoreDataSchema {
     pub fn new_with_self_package_replacement<K: ScryptoDescribe, V: ScryptoDescribe>(
         package_address: PackageAddress,
@@ -155,4 +164,15 @@ pub trait SystemKeyValueStoreApi<E> {
         node_id: &NodeId,
         key: &Vec<u8>,
     ) -> Result<Vec<u8>, E>;
+
+    /// Scans up to `limit` entries of a key value store after the `cursor` key, or from the
+    /// start if it's `None`, and returns the keys of those which currently hold a value.
+    /// Removed entries count towards the `limit`, so a page may be short before the end of the
+    /// store, which is only reached once `next_cursor` is `None`.
+    fn key_value_store_scan_keys(
+        &mut self,
+        node_id: &NodeId,
+        cursor: Option<Vec<u8>>,
+        limit: u32,
+    ) -> Result<KeyValueStoreKeys, E>;
 }
@@ -14,6 +14,12 @@ use sbor::basic_well_known_types::ANY_TYPE;
 use sbor::rust::prelude::*;
 use sbor::LocalTypeId;
 
+/// The blueprint of all packages.
+///
+/// A package's code and blueprint definitions are immutable once published, and components
+/// always execute the code of the package they were instantiated from. A new version of an
+/// application is published as a new package; callers can be migrated to it through a proxy
+/// component which forwards calls to a configurable target.
 pub const PACKAGE_BLUEPRINT: &str = "Package";
 
 pub const PACKAGE_PUBLISH_WASM_IDENT: &str = "publish_wasm";
@@ -112,6 +118,10 @@ impl Default for BlueprintType {
     }
 }
 
+/// A reserved feature which, when in a blueprint's feature set, rejects any invocation of a main
+/// method on one of its global objects while another main method of that object is on the stack.
+pub const BLUEPRINT_NON_REENTRANT_FEATURE: &str = "non_reentrant";
+
 /// Structure which defines static interface qualities of a Blueprint
 #[derive(Debug, Clone, Eq, PartialEq, ScryptoSbor, ManifestSbor)]
 pub struct BlueprintDefinitionInit {
@@ -33,6 +33,9 @@ impl From<&str> for MethodKey {
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, ScryptoSbor, ManifestSbor)]
 pub enum MethodAccessibility {
     /// Method is accessible to all
+    ///
+    /// The auth module resolves such methods to "allow all" before looking at any access rule,
+    /// so calling them evaluates no rules and requires no proofs (e.g. for read-only methods).
     Public,
     /// Only outer objects have access to a given method. Currently used by Validator blueprint
     /// to only allow ConsensusManager to access some methods.
@@ -63,6 +63,10 @@ mod basic {
         pub fn remove(&mut self, key: String) -> Option<String> {
             self.map.remove(&key)
         }
+
+        pub fn keys(&self, cursor: Option<String>, limit: u32) -> (Vec<String>, Option<String>) {
+            self.map.keys(cursor.as_ref(), limit)
+        }
     }
 }
 
@@ -11,6 +11,10 @@ mod logger {
             info!("{}", message)
         }
 
+        pub fn emit_log_with_fields(message: String) {
+            info!(length = message.len(), message = message; "Received message")
+        }
+
         pub fn rust_panic(message: String) {
             panic!("{}", message)
         }
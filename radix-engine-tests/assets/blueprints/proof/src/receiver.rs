@@ -40,5 +40,28 @@ mod receiver {
         pub fn check_with_message_if_xrd(proof: Proof) {
             proof.check_with_message(RORK, "Not RORK proof");
         }
+
+        pub fn validate_contains(
+            proof: Proof,
+            resource_address: ResourceAddress,
+            amount: Decimal,
+        ) -> Result<Decimal, ProofValidationError> {
+            proof
+                .validate(ProofValidationMode::Contains(resource_address, amount))
+                .map(|proof| proof.amount())
+        }
+
+        pub fn validate_contains_non_fungibles(
+            proof: Proof,
+            resource_address: ResourceAddress,
+            ids: IndexSet<NonFungibleLocalId>,
+        ) -> Result<Decimal, ProofValidationError> {
+            proof
+                .validate(ProofValidationMode::ContainsNonFungibles(
+                    resource_address,
+                    ids,
+                ))
+                .map(|proof| proof.amount())
+        }
     }
 }
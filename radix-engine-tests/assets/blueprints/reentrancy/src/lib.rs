@@ -29,3 +29,28 @@ mod reentrant_component {
         }
     }
 }
+
+#[blueprint]
+#[non_reentrant]
+mod non_reentrant_component {
+    struct NonReentrantComponent {}
+
+    impl NonReentrantComponent {
+        pub fn new() -> Global<NonReentrantComponent> {
+            Self {}
+                .instantiate()
+                .prepare_to_globalize(OwnerRole::None)
+                .globalize()
+        }
+
+        pub fn func(&self) {}
+
+        pub fn call_self(&self, address: ComponentAddress) {
+            ScryptoVmV1Api::object_call(&address.into(), "func", scrypto_args!());
+        }
+
+        pub fn call_other(&self, address: ComponentAddress) {
+            ScryptoVmV1Api::object_call(&address.into(), "func", scrypto_args!());
+        }
+    }
+}
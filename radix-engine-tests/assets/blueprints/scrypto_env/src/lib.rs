@@ -32,6 +32,14 @@ mod scrypto_env_test {
         pub fn bech32_encode_address(address: ComponentAddress) -> String {
             Runtime::bech32_encode_address(address)
         }
+
+        pub fn network() -> String {
+            Runtime::network()
+        }
+
+        pub fn current_time(precision: TimePrecision) -> Instant {
+            Runtime::current_time(precision)
+        }
     }
 }
 
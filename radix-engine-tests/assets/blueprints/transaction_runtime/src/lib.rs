@@ -16,6 +16,10 @@ mod tx_runtime {
             Runtime::generate_ruid()
         }
 
+        pub fn random(seed_domain: Vec<u8>) -> [u8; 32] {
+            Runtime::random(&seed_domain)
+        }
+
         pub fn test_instance_of_and_blueprint_id() {
             let x = TransactionRuntimeTest {}
                 .instantiate()
@@ -74,3 +74,67 @@ fn create_and_pass_multiple_proofs() -> Hash {
 
     ledger.get_state_hash()
 }
+
+#[test]
+fn same_executions_result_in_byte_identical_receipts() {
+    let encoded_receipts = (0..5)
+        .map(|_| execute_complex_transaction())
+        .collect::<IndexSet<Vec<u8>>>();
+    assert_eq!(
+        encoded_receipts.len(),
+        1,
+        "non-deterministic receipt: {} distinct encodings",
+        encoded_receipts.len()
+    );
+}
+
+/// Executes a transaction which creates resources, moves them through buckets and proofs, and
+/// deposits them into several accounts, touching many nodes of the heap and the track.
+/// Returns the SBOR encoding of the fee summary and the result of its receipt.
+fn execute_complex_transaction() -> Vec<u8> {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().with_state_hashing().build();
+    let (public_key, _, account) = ledger.new_allocated_account();
+    let other_accounts: Vec<_> = (0..3).map(|_| ledger.new_allocated_account().2).collect();
+    let resource_address =
+        ledger.create_fungible_resource(100.into(), DIVISIBILITY_MAXIMUM, account);
+    let package_address = ledger.publish_package_simple(PackageLoader::get("proof"));
+
+    // Act
+    let mut builder = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .get_free_xrd_from_faucet()
+        .withdraw_from_account(account, resource_address, 30);
+    let mut proof_ids: Vec<_> = vec![];
+    for _ in 0..5 {
+        let proof_name = builder.generate_proof_name("proof");
+        builder = builder
+            .create_proof_from_account_of_amount(account, resource_address, 1)
+            .pop_from_auth_zone(&proof_name);
+        proof_ids.push(builder.proof(proof_name));
+    }
+    builder = builder.call_function(
+        package_address,
+        "VaultProof",
+        "receive_proofs",
+        manifest_args!(proof_ids),
+    );
+    for other_account in &other_accounts {
+        let bucket_name = builder.generate_bucket_name("bucket");
+        builder = builder
+            .take_from_worktop(resource_address, 10, &bucket_name)
+            .try_deposit_or_abort(*other_account, None, bucket_name);
+    }
+    let manifest = builder
+        .try_deposit_entire_worktop_or_abort(account, None)
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+
+    // Assert
+    receipt.expect_commit_success();
+
+    scrypto_encode(&(&receipt.fee_summary, &receipt.result)).unwrap()
+}
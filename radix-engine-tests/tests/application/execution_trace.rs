@@ -159,6 +159,59 @@ fn test_trace_non_fungible_resource_transfers_within_blueprint() {
             && r.amount == Decimal::from(total_fee_paid).checked_neg().unwrap()));
 }
 
+#[test]
+fn test_trace_resource_changes_by_component() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (public_key, _, account) = ledger.new_allocated_account();
+    let package_address = ledger.publish_package_simple(PackageLoader::get("execution_trace"));
+    let transfer_amount = 10u8;
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee(account, 500)
+        .call_function(
+            package_address,
+            "ExecutionTraceBp",
+            "transfer_fungible_resource_between_two_components",
+            manifest_args!(transfer_amount, false),
+        )
+        .build();
+    let receipt = ledger.preview_manifest(
+        manifest,
+        vec![public_key.clone().into()],
+        0,
+        PreviewFlags::default(),
+    );
+
+    // Assert
+    let (resource_address, source_component, target_component): (
+        ResourceAddress,
+        ComponentAddress,
+        ComponentAddress,
+    ) = receipt.expect_commit(true).output(1);
+    let resource_changes = receipt
+        .expect_commit_success()
+        .resource_changes_by_component()
+        .unwrap();
+    let total_fee_paid = receipt.fee_summary.total_cost();
+
+    assert_eq!(
+        resource_changes,
+        indexmap!(
+            account.into_node_id() => indexmap!(
+                RORK => total_fee_paid.checked_neg().unwrap()
+            ),
+            source_component.into_node_id() => indexmap!(
+                resource_address => Decimal::from(transfer_amount).checked_neg().unwrap()
+            ),
+            target_component.into_node_id() => indexmap!(
+                resource_address => Decimal::from(transfer_amount)
+            ),
+        )
+    );
+}
+
 #[test]
 fn test_trace_fee_payments() {
     // Arrange
@@ -109,6 +109,41 @@ fn test_transaction_preview_without_locking_fee() {
     assert_eq!(fee_summary.total_royalty_cost_in_xrd, dec!("0"));
 }
 
+#[test]
+fn test_transaction_preview_respects_cost_breakdown_override() {
+    for (mut ledger, expect_cost_breakdown) in [
+        (LedgerSimulatorBuilder::new().build(), true),
+        (
+            LedgerSimulatorBuilder::new()
+                .without_cost_breakdown()
+                .build(),
+            false,
+        ),
+    ] {
+        // Arrange
+        let flags = PreviewFlags {
+            use_free_credit: true,
+            assume_all_signature_proofs: false,
+            skip_epoch_check: false,
+            disable_auth: false,
+        };
+        let (_, preview) = prepare_complex_matching_transaction_and_preview_transaction(
+            &mut ledger,
+            TransactionBuildConfig {
+                should_sign: true,
+                should_lock_fee: false,
+            },
+        );
+
+        // Act
+        let receipt = ledger.preview_v2(preview, flags);
+
+        // Assert
+        receipt.expect_commit_success();
+        assert_eq!(receipt.fee_details.is_some(), expect_cost_breakdown);
+    }
+}
+
 #[test]
 fn test_assume_all_signature_proofs_flag_method_authorization() {
     // Arrange
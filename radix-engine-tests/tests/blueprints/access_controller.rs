@@ -501,6 +501,214 @@ pub fn minting_of_recovery_badges_fails_for_confirmation_role() {
     receipt.expect_specific_failure(is_auth_unauthorized_error);
 }
 
+#[test]
+pub fn initiate_recovery_as_primary_manifest_builder_method_proposes_a_recovery() {
+    // Arrange
+    let mut ledger = AccessControllerLedgerSimulator::new(Some(10));
+    let manifest = ledger
+        .manifest_builder(Role::Primary)
+        .initiate_recovery_as_primary(ledger.access_controller_address, rork_rule_set(), Some(10))
+        .build();
+
+    // Act
+    let receipt = ledger.execute_manifest(manifest);
+
+    // Assert
+    receipt.expect_commit_success();
+    ledger
+        .quick_confirm_recovery(
+            Role::Recovery,
+            Role::Primary,
+            rule!(require(RORK)),
+            rule!(require(RORK)),
+            rule!(require(RORK)),
+            Some(10),
+        )
+        .expect_commit_success();
+}
+
+#[test]
+pub fn initiate_recovery_as_recovery_manifest_builder_method_proposes_a_recovery() {
+    // Arrange
+    let mut ledger = AccessControllerLedgerSimulator::new(Some(10));
+    let manifest = ledger
+        .manifest_builder(Role::Recovery)
+        .initiate_recovery_as_recovery(ledger.access_controller_address, rork_rule_set(), Some(10))
+        .build();
+
+    // Act
+    let receipt = ledger.execute_manifest(manifest);
+
+    // Assert
+    receipt.expect_commit_success();
+    ledger
+        .quick_confirm_recovery(
+            Role::Primary,
+            Role::Recovery,
+            rule!(require(RORK)),
+            rule!(require(RORK)),
+            rule!(require(RORK)),
+            Some(10),
+        )
+        .expect_commit_success();
+}
+
+#[test]
+pub fn quick_confirm_primary_role_recovery_proposal_manifest_builder_method_confirms_the_proposal()
+{
+    // Arrange
+    let mut ledger = AccessControllerLedgerSimulator::new(Some(10));
+    ledger
+        .initiate_recovery(
+            Role::Primary,
+            rule!(require(RORK)),
+            rule!(require(RORK)),
+            rule!(require(RORK)),
+            Some(10),
+        )
+        .expect_commit_success();
+    let manifest = ledger
+        .manifest_builder(Role::Recovery)
+        .quick_confirm_primary_role_recovery_proposal(
+            ledger.access_controller_address,
+            rork_rule_set(),
+            Some(10),
+        )
+        .build();
+
+    // Act
+    let receipt = ledger.execute_manifest(manifest);
+
+    // Assert
+    receipt.expect_commit_success();
+}
+
+#[test]
+pub fn quick_confirm_recovery_role_recovery_proposal_manifest_builder_method_confirms_the_proposal()
+{
+    // Arrange
+    let mut ledger = AccessControllerLedgerSimulator::new(Some(10));
+    ledger
+        .initiate_recovery(
+            Role::Recovery,
+            rule!(require(RORK)),
+            rule!(require(RORK)),
+            rule!(require(RORK)),
+            Some(10),
+        )
+        .expect_commit_success();
+    let manifest = ledger
+        .manifest_builder(Role::Primary)
+        .quick_confirm_recovery_role_recovery_proposal(
+            ledger.access_controller_address,
+            rork_rule_set(),
+            Some(10),
+        )
+        .build();
+
+    // Act
+    let receipt = ledger.execute_manifest(manifest);
+
+    // Assert
+    receipt.expect_commit_success();
+}
+
+#[test]
+pub fn timed_confirm_recovery_manifest_builder_method_confirms_the_proposal_after_the_delay() {
+    // Arrange
+    let mut ledger = AccessControllerLedgerSimulator::new(Some(10));
+    ledger
+        .initiate_recovery(
+            Role::Recovery,
+            rule!(require(RORK)),
+            rule!(require(RORK)),
+            rule!(require(RORK)),
+            Some(10),
+        )
+        .expect_commit_success();
+    ledger.set_current_minute(10);
+    let manifest = ledger
+        .manifest_builder(Role::Recovery)
+        .timed_confirm_recovery(ledger.access_controller_address, rork_rule_set(), Some(10))
+        .build();
+
+    // Act
+    let receipt = ledger.execute_manifest(manifest);
+
+    // Assert
+    receipt.expect_commit_success();
+}
+
+#[test]
+pub fn cancel_primary_role_recovery_proposal_manifest_builder_method_cancels_the_proposal() {
+    // Arrange
+    let mut ledger = AccessControllerLedgerSimulator::new(Some(10));
+    ledger
+        .initiate_recovery(
+            Role::Primary,
+            rule!(require(RORK)),
+            rule!(require(RORK)),
+            rule!(require(RORK)),
+            Some(10),
+        )
+        .expect_commit_success();
+    let manifest = ledger
+        .manifest_builder(Role::Primary)
+        .cancel_primary_role_recovery_proposal(ledger.access_controller_address)
+        .build();
+
+    // Act
+    let receipt = ledger.execute_manifest(manifest);
+
+    // Assert
+    receipt.expect_commit_success();
+    ledger
+        .quick_confirm_recovery(
+            Role::Recovery,
+            Role::Primary,
+            rule!(require(RORK)),
+            rule!(require(RORK)),
+            rule!(require(RORK)),
+            Some(10),
+        )
+        .expect_specific_failure(is_no_recovery_exists_for_proposer_error);
+}
+
+#[test]
+pub fn cancel_recovery_role_recovery_proposal_manifest_builder_method_cancels_the_proposal() {
+    // Arrange
+    let mut ledger = AccessControllerLedgerSimulator::new(Some(10));
+    ledger
+        .initiate_recovery(
+            Role::Recovery,
+            rule!(require(RORK)),
+            rule!(require(RORK)),
+            rule!(require(RORK)),
+            Some(10),
+        )
+        .expect_commit_success();
+    let manifest = ledger
+        .manifest_builder(Role::Recovery)
+        .cancel_recovery_role_recovery_proposal(ledger.access_controller_address)
+        .build();
+
+    // Act
+    let receipt = ledger.execute_manifest(manifest);
+
+    // Assert
+    receipt.expect_commit_success();
+    ledger
+        .quick_confirm_recovery(
+            Role::Primary,
+            Role::Recovery,
+            rule!(require(RORK)),
+            rule!(require(RORK)),
+            rule!(require(RORK)),
+            Some(10),
+        )
+        .expect_specific_failure(is_no_recovery_exists_for_proposer_error);
+}
+
 //=============
 // State Tests
 //=============
@@ -1552,6 +1760,14 @@ mod recovery_mode_with_primary_locked {
 
 type ErrorCheckFunction = fn(&RuntimeError) -> bool;
 
+fn rork_rule_set() -> RuleSet {
+    RuleSet {
+        primary_role: rule!(require(RORK)),
+        recovery_role: rule!(require(RORK)),
+        confirmation_role: rule!(require(RORK)),
+    }
+}
+
 fn is_auth_unauthorized_error(error: &RuntimeError) -> bool {
     matches!(
         error,
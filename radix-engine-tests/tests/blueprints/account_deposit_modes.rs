@@ -456,6 +456,38 @@ fn disallow_all_permits_deposit_of_resource_in_allow_list() {
     }
 }
 
+#[test]
+fn disallow_all_refunds_deposit_of_resource_not_in_allow_list() {
+    // Arrange
+    for is_preallocated in [true, false] {
+        let mut ledger = AccountDepositModesLedgerSimulator::new(is_preallocated);
+        let resource_address = ledger.freely_mintable_resource();
+        ledger
+            .transition_default_deposit_rule(DefaultDepositRule::Reject, true)
+            .expect_commit_success();
+
+        // Act
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .mint_fungible(resource_address, 1)
+            .try_deposit_entire_worktop_or_refund(ledger.account_address, None)
+            .assert_worktop_contains(resource_address, 1)
+            .burn_all_from_worktop(resource_address)
+            .build();
+        let receipt = ledger.execute_manifest(manifest, false);
+
+        // Assert
+        receipt.expect_commit_success();
+        let account_address = ledger.account_address;
+        assert_eq!(
+            ledger
+                .ledger
+                .get_component_balance(account_address, resource_address),
+            Decimal::ZERO
+        );
+    }
+}
+
 #[test]
 fn cannot_trick_account_allow_existing_by_adding_empty_bucket_to_account() {
     // Arrange
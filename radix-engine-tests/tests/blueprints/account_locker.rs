@@ -3056,13 +3056,13 @@ pub impl DefaultLedgerSimulator {
         disable_auth: bool,
         disable_costing: bool,
     ) -> TransactionReceipt {
-        let mut execution_config =
-            ExecutionConfig::for_notarized_transaction(NetworkDefinition::mainnet());
-        execution_config.system_overrides = Some(SystemOverrides {
-            disable_auth,
-            disable_costing,
-            ..SystemOverrides::with_network(NetworkDefinition::mainnet())
-        });
+        let execution_config =
+            ExecutionConfig::for_notarized_transaction(NetworkDefinition::mainnet())
+                .update_system_overrides(|mut overrides| {
+                    overrides.disable_auth = disable_auth;
+                    overrides.disable_costing = disable_costing;
+                    overrides
+                });
 
         let nonce = self.next_transaction_nonce();
         let test_transaction =
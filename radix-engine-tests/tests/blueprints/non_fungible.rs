@@ -930,6 +930,58 @@ fn can_get_total_supply() {
     receipt.expect_commit_success();
 }
 
+#[test]
+fn total_supply_is_updated_on_mint_and_burn() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (public_key, _, account) = ledger.new_allocated_account();
+    let resource_address = ledger.create_freely_mintable_and_burnable_non_fungible_resource(
+        OwnerRole::None,
+        NonFungibleIdType::Integer,
+        Some(vec![
+            (NonFungibleLocalId::integer(1), EmptyNonFungibleData {}),
+            (NonFungibleLocalId::integer(2), EmptyNonFungibleData {}),
+        ]),
+        account,
+    );
+    assert_eq!(
+        ledger.get_non_fungible_resource_total_supply(resource_address),
+        dec!(2)
+    );
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .mint_non_fungible(
+            resource_address,
+            vec![
+                (NonFungibleLocalId::integer(3), EmptyNonFungibleData {}),
+                (NonFungibleLocalId::integer(4), EmptyNonFungibleData {}),
+            ],
+        )
+        .burn_non_fungible_from_worktop(NonFungibleGlobalId::new(
+            resource_address,
+            NonFungibleLocalId::integer(3),
+        ))
+        .deposit_entire_worktop(account)
+        .burn_non_fungible_in_account(
+            account,
+            NonFungibleGlobalId::new(resource_address, NonFungibleLocalId::integer(1)),
+        )
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(
+        ledger.get_non_fungible_resource_total_supply(resource_address),
+        dec!(2)
+    );
+}
+
 #[test]
 fn cannot_get_total_supply_when_track_total_supply_disable() {
     // Arrange
@@ -3,7 +3,7 @@ use radix_engine::errors::{ApplicationError, RuntimeError, SystemModuleError};
 use radix_engine::system::system_modules::auth::AuthError;
 use radix_engine_interface::types::FromPublicKey;
 use radix_engine_tests::common::*;
-use scrypto::resource::DIVISIBILITY_MAXIMUM;
+use scrypto::resource::{ProofValidationError, DIVISIBILITY_MAXIMUM};
 use scrypto_test::prelude::*;
 
 #[test]
@@ -1017,3 +1017,105 @@ fn test_proof_check_with_message() {
         _ => false,
     });
 }
+
+#[test]
+fn test_proof_validate_contains() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (public_key, _, account) = ledger.new_allocated_account();
+    let resource_address = ledger.create_fungible_resource(dec!(100), 0, account);
+    let package_address = ledger.publish_package_simple(PackageLoader::get("proof"));
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_amount(account, resource_address, 5)
+        .create_proof_from_auth_zone_of_amount(resource_address, dec!(5), "proof1")
+        .create_proof_from_auth_zone_of_amount(resource_address, dec!(5), "proof2")
+        .with_name_lookup(|builder, lookup| {
+            builder
+                .call_function(
+                    package_address,
+                    "Receiver",
+                    "validate_contains",
+                    manifest_args!(lookup.proof("proof1"), resource_address, dec!(5)),
+                )
+                .call_function(
+                    package_address,
+                    "Receiver",
+                    "validate_contains",
+                    manifest_args!(lookup.proof("proof2"), resource_address, dec!(6)),
+                )
+        })
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+
+    // Assert
+    let commit = receipt.expect_commit_success();
+    assert_eq!(
+        commit.output::<Result<Decimal, ProofValidationError>>(4),
+        Ok(dec!(5))
+    );
+    assert_eq!(
+        commit.output::<Result<Decimal, ProofValidationError>>(5),
+        Err(ProofValidationError::InsufficientAmount {
+            required: dec!(6),
+            actual: dec!(5),
+        })
+    );
+}
+
+#[test]
+fn test_proof_validate_contains_non_fungibles() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (public_key, _, account) = ledger.new_allocated_account();
+    let resource_address = ledger.create_non_fungible_resource(account);
+    let package_address = ledger.publish_package_simple(PackageLoader::get("proof"));
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_non_fungibles(
+            account,
+            resource_address,
+            [
+                NonFungibleLocalId::integer(1),
+                NonFungibleLocalId::integer(2),
+            ],
+        )
+        .create_proof_from_auth_zone_of_all(resource_address, "proof")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_function(
+                package_address,
+                "Receiver",
+                "validate_contains_non_fungibles",
+                manifest_args!(
+                    lookup.proof("proof"),
+                    resource_address,
+                    indexset!(
+                        NonFungibleLocalId::integer(2),
+                        NonFungibleLocalId::integer(3)
+                    )
+                ),
+            )
+        })
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+
+    // Assert
+    assert_eq!(
+        receipt
+            .expect_commit_success()
+            .output::<Result<Decimal, ProofValidationError>>(3),
+        Err(ProofValidationError::MissingNonFungibles(indexset!(
+            NonFungibleLocalId::integer(3)
+        )))
+    );
+}
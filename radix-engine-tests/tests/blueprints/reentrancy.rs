@@ -1,5 +1,8 @@
 use radix_common::prelude::*;
-use radix_engine::errors::{CallFrameError, KernelError, RuntimeError};
+use radix_engine::blueprints::package::PackageError;
+use radix_engine::errors::{
+    ApplicationError, CallFrameError, KernelError, RuntimeError, SystemUpstreamError,
+};
 use radix_engine::kernel::call_frame::OpenSubstateError;
 use radix_engine_tests::common::*;
 use scrypto_test::prelude::*;
@@ -113,3 +116,81 @@ fn read_then_mut_reentrancy_should_not_be_possible() {
         )
     });
 }
+
+fn instantiate(ledger: &mut DefaultLedgerSimulator, blueprint_name: &str) -> ComponentAddress {
+    let package_address = ledger.publish_package_simple(PackageLoader::get("reentrancy"));
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(package_address, blueprint_name, "new", manifest_args!())
+        .build();
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+    receipt.expect_commit(true).new_component_addresses()[0]
+}
+
+#[test]
+fn read_reentrancy_should_not_be_possible_if_blueprint_is_non_reentrant() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let component_address = instantiate(&mut ledger, "NonReentrantComponent");
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(
+            component_address,
+            "call_self",
+            manifest_args!(component_address),
+        )
+        .build();
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        e == &RuntimeError::SystemUpstreamError(SystemUpstreamError::ReentrancyDetected {
+            address: component_address.into(),
+        })
+    });
+}
+
+#[test]
+fn non_reentrant_component_can_call_another_component_of_its_blueprint() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let component_address = instantiate(&mut ledger, "NonReentrantComponent");
+    let other_component_address = instantiate(&mut ledger, "NonReentrantComponent");
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(
+            component_address,
+            "call_other",
+            manifest_args!(other_component_address),
+        )
+        .build();
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+
+    // Assert
+    receipt.expect_commit_success();
+}
+
+#[test]
+fn non_reentrant_blueprint_can_not_be_published_before_the_reentrancy_guard_is_enabled() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new()
+        .with_custom_protocol(|builder| builder.from_bootstrap_to(ProtocolVersion::CuttlefishPart2))
+        .build();
+
+    // Act
+    let receipt = ledger.try_publish_package(PackageLoader::get("reentrancy"));
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::PackageError(
+                PackageError::FeatureNotYetSupported(..)
+            ))
+        )
+    });
+}
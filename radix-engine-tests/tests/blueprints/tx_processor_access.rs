@@ -1,10 +1,16 @@
 use radix_common::prelude::*;
-use radix_engine::errors::{RuntimeError, SystemModuleError};
+use radix_engine::errors::{RuntimeError, SystemModuleError, SystemUpstreamError};
+use radix_engine::kernel::kernel_api::{KernelNodeApi, KernelSubstateApi};
+use radix_engine::system::system_callback::SystemLockData;
 use radix_engine::system::system_modules::auth::AuthError;
+use radix_engine::vm::{OverridePackageCode, VmApi, VmInvoke};
+use radix_engine_interface::api::SystemApi;
+use radix_engine_interface::blueprints::package::PackageDefinition;
 use radix_engine_interface::blueprints::transaction_processor::{
     TRANSACTION_PROCESSOR_BLUEPRINT, TRANSACTION_PROCESSOR_RUN_IDENT,
 };
 use radix_engine_tests::common::*;
+use radix_native_sdk::transaction_processor::{TransactionProcessor, TransactionProcessorRunError};
 use scrypto::prelude::FromPublicKey;
 use scrypto_test::prelude::*;
 
@@ -110,3 +116,228 @@ fn should_not_be_able_to_steal_money_through_tx_processor_call() {
     let final_balance = ledger.get_component_balance(account0, RORK);
     assert_eq!(initial_balance, final_balance);
 }
+
+fn execute_manifest_in_nested_tx_processor(
+    ledger: &mut DefaultLedgerSimulator,
+    package_address: PackageAddress,
+    instructions: Vec<InstructionV1>,
+    references: Vec<ComponentAddress>,
+    initial_proofs: Vec<NonFungibleGlobalId>,
+) -> TransactionReceipt {
+    let manifest_encoded_instructions = manifest_encode(&instructions).unwrap();
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(
+            package_address,
+            "ExecuteManifest",
+            "execute_manifest",
+            manifest_args!(manifest_encoded_instructions, references),
+        )
+        .build();
+    let mut execution_config = ExecutionConfig::for_test_transaction();
+    execution_config.system_overrides = Some(
+        SystemOverrides::with_network(NetworkDefinition::simulator())
+            .set_allow_nested_transaction_processor(),
+    );
+    ledger.execute_manifest_with_execution_config(manifest, initial_proofs, execution_config)
+}
+
+#[test]
+fn should_be_able_to_call_tx_processor_from_scrypto_if_nested_tx_processor_is_allowed() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (_, _, account) = ledger.new_account(true);
+    let package_address = ledger.publish_package_simple(PackageLoader::get("tx_processor_access"));
+    let initial_balance = ledger.get_component_balance(account, RORK);
+    let instructions = ManifestBuilder::new()
+        .get_free_xrd_from_faucet()
+        .try_deposit_entire_worktop_or_abort(account, None)
+        .build()
+        .instructions;
+
+    // Act
+    let receipt = execute_manifest_in_nested_tx_processor(
+        &mut ledger,
+        package_address,
+        instructions,
+        vec![FAUCET, account],
+        vec![],
+    );
+
+    // Assert
+    receipt.expect_commit_success();
+    let final_balance = ledger.get_component_balance(account, RORK);
+    assert_eq!(final_balance, initial_balance + dec!(10000));
+}
+
+#[test]
+fn nested_tx_processor_should_not_have_access_to_transaction_signer_proofs() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (pub_key, _, account0) = ledger.new_account(true);
+    let (_, _, account1) = ledger.new_account(true);
+    let package_address = ledger.publish_package_simple(PackageLoader::get("tx_processor_access"));
+    let initial_balance = ledger.get_component_balance(account0, RORK);
+    let instructions = ManifestBuilder::new()
+        .withdraw_from_account(account0, RORK, 10)
+        .try_deposit_entire_worktop_or_abort(account1, None)
+        .build()
+        .instructions;
+
+    // Act
+    let receipt = execute_manifest_in_nested_tx_processor(
+        &mut ledger,
+        package_address,
+        instructions,
+        vec![account0, account1],
+        vec![NonFungibleGlobalId::from_public_key(&pub_key)],
+    );
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::SystemModuleError(SystemModuleError::AuthError(AuthError::Unauthorized(
+                ..
+            )))
+        )
+    });
+    let final_balance = ledger.get_component_balance(account0, RORK);
+    assert_eq!(initial_balance, final_balance);
+}
+
+#[test]
+fn nested_tx_processor_should_fail_if_resources_are_left_on_its_worktop() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let package_address = ledger.publish_package_simple(PackageLoader::get("tx_processor_access"));
+    let instructions = ManifestBuilder::new()
+        .get_free_xrd_from_faucet()
+        .build()
+        .instructions;
+
+    // Act
+    let receipt = execute_manifest_in_nested_tx_processor(
+        &mut ledger,
+        package_address,
+        instructions,
+        vec![FAUCET],
+        vec![],
+    );
+
+    // Assert
+    receipt.expect_failure();
+}
+
+fn run_manifest_in_native_nested_tx_processor(
+    allow_nested_transaction_processor: bool,
+) -> (TransactionReceipt, Decimal, Decimal) {
+    const BLUEPRINT_NAME: &str = "NativeExecuteManifest";
+    const CUSTOM_PACKAGE_CODE_ID: u64 = 1024;
+    #[derive(Clone)]
+    struct TestInvoke;
+    impl VmInvoke for TestInvoke {
+        fn invoke<
+            Y: SystemApi<RuntimeError> + KernelNodeApi + KernelSubstateApi<SystemLockData>,
+            V: VmApi,
+        >(
+            &mut self,
+            export_name: &str,
+            input: &IndexedScryptoValue,
+            api: &mut Y,
+            _vm_api: &V,
+        ) -> Result<IndexedScryptoValue, RuntimeError> {
+            match export_name {
+                "execute_manifest" => {
+                    let (manifest_encoded_instructions, references): (Vec<u8>, Vec<Reference>) =
+                        input.as_typed().unwrap();
+                    let outputs = TransactionProcessor::run(
+                        manifest_encoded_instructions,
+                        references,
+                        index_map_new(),
+                        api,
+                    )
+                    .map_err(|e| match e {
+                        TransactionProcessorRunError::ApiError(e) => e,
+                        TransactionProcessorRunError::OutputDecodeError(e) => {
+                            RuntimeError::SystemUpstreamError(
+                                SystemUpstreamError::OutputDecodeError(e),
+                            )
+                        }
+                    })?;
+                    Ok(IndexedScryptoValue::from_typed(&outputs))
+                }
+                _ => Ok(IndexedScryptoValue::from_typed(&())),
+            }
+        }
+    }
+    let mut ledger = LedgerSimulatorBuilder::new()
+        .with_custom_extension(OverridePackageCode::new(CUSTOM_PACKAGE_CODE_ID, TestInvoke))
+        .build();
+    let (_, _, account) = ledger.new_account(true);
+    let package_address = ledger.publish_native_package(
+        CUSTOM_PACKAGE_CODE_ID,
+        PackageDefinition::new_functions_only_test_definition(
+            BLUEPRINT_NAME,
+            vec![("execute_manifest", "execute_manifest", false)],
+        ),
+    );
+    let initial_balance = ledger.get_component_balance(account, RORK);
+    let instructions = ManifestBuilder::new()
+        .get_free_xrd_from_faucet()
+        .try_deposit_entire_worktop_or_abort(account, None)
+        .build()
+        .instructions;
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(
+            package_address,
+            BLUEPRINT_NAME,
+            "execute_manifest",
+            manifest_args!(
+                manifest_encode(&instructions).unwrap(),
+                vec![FAUCET, account]
+            ),
+        )
+        .build();
+    let mut system_overrides = SystemOverrides::with_network(NetworkDefinition::simulator());
+    if allow_nested_transaction_processor {
+        system_overrides = system_overrides.set_allow_nested_transaction_processor();
+    }
+    let mut execution_config = ExecutionConfig::for_test_transaction();
+    execution_config.system_overrides = Some(system_overrides);
+
+    let receipt = ledger.execute_manifest_with_execution_config(manifest, vec![], execution_config);
+    let final_balance = ledger.get_component_balance(account, RORK);
+    (receipt, initial_balance, final_balance)
+}
+
+#[test]
+fn native_blueprint_should_be_able_to_run_nested_manifest_if_nested_tx_processor_is_allowed() {
+    // Act
+    let (receipt, initial_balance, final_balance) =
+        run_manifest_in_native_nested_tx_processor(true);
+
+    // Assert
+    let outputs: Vec<InstructionOutput> = receipt.expect_commit_success().output(1);
+    assert_eq!(outputs.len(), 2);
+    assert_eq!(final_balance, initial_balance + dec!(10000));
+}
+
+#[test]
+fn native_blueprint_should_not_be_able_to_run_nested_manifest_by_default() {
+    // Act
+    let (receipt, initial_balance, final_balance) =
+        run_manifest_in_native_nested_tx_processor(false);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::SystemModuleError(SystemModuleError::AuthError(AuthError::Unauthorized(
+                ..
+            )))
+        )
+    });
+    assert_eq!(final_balance, initial_balance);
+}
@@ -0,0 +1,85 @@
+use radix_engine::transaction::*;
+use radix_engine::updates::*;
+use radix_engine::vm::VmModules;
+use radix_substate_store_impls::memory_db::*;
+use radix_substate_store_impls::read_through_store::*;
+use radix_substate_store_impls::substate_database_overlay::*;
+use radix_substate_store_interface::interface::*;
+use radix_transactions::builder::*;
+use radix_transactions::manifest::*;
+use radix_transactions::validation::*;
+use scrypto::prelude::*;
+use std::num::NonZeroUsize;
+
+struct InMemoryFetcher(InMemorySubstateDatabase);
+
+impl SubstateFetcher for InMemoryFetcher {
+    fn fetch_substate(
+        &self,
+        partition_key: &DbPartitionKey,
+        sort_key: &DbSortKey,
+    ) -> Option<DbSubstateValue> {
+        self.0.get_raw_substate_by_db_key(partition_key, sort_key)
+    }
+
+    fn fetch_partition_page(
+        &self,
+        partition_key: &DbPartitionKey,
+        from_sort_key: Option<&DbSortKey>,
+        limit: usize,
+    ) -> Vec<PartitionEntry> {
+        self.0
+            .list_raw_values_from_db_key(partition_key, from_sort_key)
+            .take(limit)
+            .collect()
+    }
+}
+
+fn execute_faucet_transactions<S: SubstateDatabase + CommittableSubstateDatabase>(
+    database: &mut S,
+) -> Vec<TransactionReceipt> {
+    let account = ComponentAddress::preallocated_account_from_public_key(
+        &Secp256k1PrivateKey::from_u64(1).unwrap().public_key(),
+    );
+    (1..=3)
+        .map(|nonce| {
+            let validator = TransactionValidator::new(&*database, &NetworkDefinition::simulator());
+            let executable = ManifestBuilder::new()
+                .lock_fee_from_faucet()
+                .get_free_xrd_from_faucet()
+                .try_deposit_entire_worktop_or_abort(account, None)
+                .build()
+                .into_executable_with_proofs(nonce, btreeset!(), &validator)
+                .unwrap();
+            execute_and_commit_transaction(
+                database,
+                &VmModules::default(),
+                &ExecutionConfig::for_test_transaction(),
+                executable,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn transactions_executed_against_a_read_through_store_match_the_remote_database() {
+    // Arrange
+    let mut remote_database = InMemorySubstateDatabase::standard();
+    ProtocolBuilder::for_simulator()
+        .from_bootstrap_to_latest()
+        .commit_each_protocol_update(&mut remote_database);
+    let mut expected_database = remote_database.clone();
+    let store = ReadThroughSubstateStore::new(InMemoryFetcher(remote_database))
+        .with_partition_page_size(NonZeroUsize::new(2).unwrap());
+    let mut overlay = SubstateDatabaseOverlay::new_unmergeable(&store);
+
+    // Act
+    let receipts = execute_faucet_transactions(&mut overlay);
+
+    // Assert
+    let expected_receipts = execute_faucet_transactions(&mut expected_database);
+    for (receipt, expected_receipt) in receipts.iter().zip(expected_receipts.iter()) {
+        receipt.expect_commit_success();
+        assert_eq!(receipt, expected_receipt);
+    }
+}
@@ -2,14 +2,15 @@ use radix_common::prelude::*;
 use radix_engine::errors::*;
 use radix_engine::kernel::call_frame::*;
 use radix_engine::kernel::id_allocator::IdAllocator;
-use radix_engine::kernel::kernel::Kernel;
+use radix_engine::kernel::kernel::{Kernel, KernelSnapshot};
 use radix_engine::kernel::kernel_api::*;
 use radix_engine::kernel::kernel_callback_api::*;
+use radix_engine::kernel::substate_io::{StoreCheckpoint, StoreCheckpointError};
 use radix_engine::track::*;
 use radix_engine_interface::prelude::*;
 use scrypto_test::prelude::*;
 
-#[derive(Default)]
+#[derive(Debug, Default, Clone, ScryptoSbor)]
 struct TestCallFrameData;
 
 impl CallFrameReferences for TestCallFrameData {
@@ -28,6 +29,10 @@ impl CallFrameReferences for TestCallFrameData {
     fn len(&self) -> usize {
         0usize
     }
+
+    fn actor_description(&self) -> String {
+        "Test".to_owned()
+    }
 }
 
 struct TestCallbackObject;
@@ -430,3 +435,499 @@ fn kernel_close_substate_should_fail_if_opened_child_exists() {
         ))
     );
 }
+
+fn suspend_kernel_with_heap_and_track_state(
+    database: &InMemorySubstateDatabase,
+) -> (NodeId, NodeId, NodeId, KernelSnapshot<TestCallFrameData>) {
+    let mut track = Track::new(database);
+    let mut id_allocator = IdAllocator::new(Hash([0u8; Hash::LENGTH]));
+    let mut callback = TestCallbackObject;
+    let mut kernel = Kernel::new_no_refs(&mut track, &mut id_allocator, &mut callback);
+
+    let heap_node_id = kernel
+        .kernel_allocate_node_id(EntityType::InternalKeyValueStore)
+        .unwrap();
+    kernel
+        .kernel_create_node(
+            heap_node_id,
+            btreemap!(
+                PartitionNumber(0u8) => btreemap!(
+                    SubstateKey::Field(0u8) => IndexedScryptoValue::from_typed(&"heap")
+                )
+            ),
+        )
+        .unwrap();
+    let track_node_id = kernel
+        .kernel_allocate_node_id(EntityType::GlobalAccount)
+        .unwrap();
+    kernel
+        .kernel_create_node(
+            track_node_id,
+            btreemap!(
+                PartitionNumber(0u8) => btreemap!(
+                    SubstateKey::Field(0u8) => IndexedScryptoValue::from_typed(&"track")
+                )
+            ),
+        )
+        .unwrap();
+
+    let snapshot = kernel.suspend().unwrap();
+    let next_node_id = kernel
+        .kernel_allocate_node_id(EntityType::InternalKeyValueStore)
+        .unwrap();
+
+    (heap_node_id, track_node_id, next_node_id, snapshot)
+}
+
+fn read_field(
+    kernel: &mut impl KernelApi<CallbackObject = TestCallbackObject>,
+    node_id: &NodeId,
+) -> String {
+    let handle = kernel
+        .kernel_open_substate(
+            node_id,
+            PartitionNumber(0u8),
+            &SubstateKey::Field(0u8),
+            LockFlags::read_only(),
+            (),
+        )
+        .unwrap();
+    let value: String = kernel
+        .kernel_read_substate(handle)
+        .unwrap()
+        .as_typed()
+        .unwrap();
+    kernel.kernel_close_substate(handle).unwrap();
+    value
+}
+
+#[test]
+fn kernel_resume_should_restore_heap_and_track_state() {
+    // Arrange
+    let database = InMemorySubstateDatabase::standard();
+    let (heap_node_id, track_node_id, next_node_id, snapshot) =
+        suspend_kernel_with_heap_and_track_state(&database);
+    let snapshot: KernelSnapshot<TestCallFrameData> =
+        scrypto_decode(&scrypto_encode(&snapshot).unwrap()).unwrap();
+    let mut track = Track::new(&database);
+    let mut id_allocator = IdAllocator::new(Hash([1u8; Hash::LENGTH]));
+    let mut callback = TestCallbackObject;
+    let mut kernel = Kernel::new_no_refs(&mut track, &mut id_allocator, &mut callback);
+
+    // Act
+    kernel.resume(snapshot).unwrap();
+
+    // Assert
+    assert_eq!(read_field(&mut kernel, &heap_node_id), "heap");
+    assert_eq!(read_field(&mut kernel, &track_node_id), "track");
+    assert_eq!(
+        kernel
+            .kernel_allocate_node_id(EntityType::InternalKeyValueStore)
+            .unwrap(),
+        next_node_id
+    );
+}
+
+#[test]
+fn kernel_suspend_should_fail_if_substate_is_open() {
+    // Arrange
+    let database = InMemorySubstateDatabase::standard();
+    let mut track = Track::new(&database);
+    let mut id_allocator = IdAllocator::new(Hash([0u8; Hash::LENGTH]));
+    let mut callback = TestCallbackObject;
+    let mut kernel = Kernel::new_no_refs(&mut track, &mut id_allocator, &mut callback);
+    let node_id = kernel
+        .kernel_allocate_node_id(EntityType::InternalKeyValueStore)
+        .unwrap();
+    let substates = btreemap!(
+        PartitionNumber(0u8) => btreemap!(
+            SubstateKey::Field(0u8) => IndexedScryptoValue::from_typed(&())
+        )
+    );
+    kernel.kernel_create_node(node_id, substates).unwrap();
+    kernel
+        .kernel_open_substate(
+            &node_id,
+            PartitionNumber(0u8),
+            &SubstateKey::Field(0u8),
+            LockFlags::read_only(),
+            (),
+        )
+        .unwrap();
+
+    // Act
+    let result = kernel.suspend();
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(RuntimeError::KernelError(KernelError::SnapshotError(
+            KernelSnapshotError::CallFrameSnapshotError(CallFrameSnapshotError::SubstateOpen(..))
+        )))
+    );
+}
+
+#[test]
+fn kernel_resume_should_fail_if_stack_index_is_invalid() {
+    // Arrange
+    let database = InMemorySubstateDatabase::standard();
+    let (_, _, _, mut snapshot) = suspend_kernel_with_heap_and_track_state(&database);
+    snapshot.current_stack_index = snapshot.stacks.len();
+    let mut track = Track::new(&database);
+    let mut id_allocator = IdAllocator::new(Hash([0u8; Hash::LENGTH]));
+    let mut callback = TestCallbackObject;
+    let mut kernel = Kernel::new_no_refs(&mut track, &mut id_allocator, &mut callback);
+
+    // Act
+    let result = kernel.resume(snapshot);
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(RuntimeError::KernelError(KernelError::StackError(
+            StackError::InvalidStackId
+        )))
+    );
+}
+
+#[test]
+fn kernel_resume_should_fail_if_stack_has_no_frames() {
+    // Arrange
+    let database = InMemorySubstateDatabase::standard();
+    let (_, _, _, mut snapshot) = suspend_kernel_with_heap_and_track_state(&database);
+    snapshot.stacks[0].frames.clear();
+    let mut track = Track::new(&database);
+    let mut id_allocator = IdAllocator::new(Hash([0u8; Hash::LENGTH]));
+    let mut callback = TestCallbackObject;
+    let mut kernel = Kernel::new_no_refs(&mut track, &mut id_allocator, &mut callback);
+
+    // Act
+    let result = kernel.resume(snapshot);
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(RuntimeError::KernelError(KernelError::SnapshotError(
+            KernelSnapshotError::EmptyStack
+        )))
+    );
+}
+
+fn create_global_node(
+    kernel: &mut impl KernelApi<CallbackObject = TestCallbackObject>,
+    value: &str,
+) -> NodeId {
+    let node_id = kernel
+        .kernel_allocate_node_id(EntityType::GlobalAccount)
+        .unwrap();
+    kernel
+        .kernel_create_node(
+            node_id,
+            btreemap!(
+                PartitionNumber(0u8) => btreemap!(
+                    SubstateKey::Field(0u8) => IndexedScryptoValue::from_typed(&value)
+                )
+            ),
+        )
+        .unwrap();
+    node_id
+}
+
+fn write_field(
+    kernel: &mut impl KernelApi<CallbackObject = TestCallbackObject>,
+    node_id: &NodeId,
+    value: &str,
+) {
+    let handle = kernel
+        .kernel_open_substate(
+            node_id,
+            PartitionNumber(0u8),
+            &SubstateKey::Field(0u8),
+            LockFlags::MUTABLE,
+            (),
+        )
+        .unwrap();
+    kernel
+        .kernel_write_substate(handle, IndexedScryptoValue::from_typed(&value))
+        .unwrap();
+    kernel.kernel_close_substate(handle).unwrap();
+}
+
+fn store_checkpoint_error(error: StoreCheckpointError) -> RuntimeError {
+    RuntimeError::KernelError(KernelError::StoreCheckpointError(error))
+}
+
+/// Writes twice under nested checkpoints and rolls back to the outer one, using only the
+/// [`KernelApi`] as system modules and native blueprints do.
+fn write_and_rollback_to_outer_checkpoint(
+    kernel: &mut impl KernelApi<CallbackObject = TestCallbackObject>,
+    node_id: &NodeId,
+) -> StoreCheckpoint {
+    let checkpoint = kernel.kernel_create_store_checkpoint().unwrap();
+    write_field(kernel, node_id, "after");
+    let nested_checkpoint = kernel.kernel_create_store_checkpoint().unwrap();
+    write_field(kernel, node_id, "later");
+    kernel.kernel_rollback_store_to(checkpoint).unwrap();
+    nested_checkpoint
+}
+
+#[test]
+fn kernel_rollback_store_to_checkpoint_should_revert_later_changes() {
+    // Arrange
+    let database = InMemorySubstateDatabase::standard();
+    let mut track = Track::new(&database);
+    let mut id_allocator = IdAllocator::new(Hash([0u8; Hash::LENGTH]));
+    let mut callback = TestCallbackObject;
+    let mut kernel = Kernel::new_no_refs(&mut track, &mut id_allocator, &mut callback);
+    let node_id = create_global_node(&mut kernel, "before");
+
+    // Act
+    let nested_checkpoint = write_and_rollback_to_outer_checkpoint(&mut kernel, &node_id);
+
+    // Assert
+    assert_eq!(read_field(&mut kernel, &node_id), "before");
+    assert_eq!(
+        kernel.kernel_rollback_store_to(nested_checkpoint),
+        Err(store_checkpoint_error(
+            StoreCheckpointError::TrackCheckpointError(TrackCheckpointError::CheckpointNotFound(
+                TrackCheckpoint(1)
+            ))
+        ))
+    );
+}
+
+#[test]
+fn kernel_release_store_checkpoint_should_keep_later_changes() {
+    // Arrange
+    let database = InMemorySubstateDatabase::standard();
+    let mut track = Track::new(&database);
+    let mut id_allocator = IdAllocator::new(Hash([0u8; Hash::LENGTH]));
+    let mut callback = TestCallbackObject;
+    let mut kernel = Kernel::new_no_refs(&mut track, &mut id_allocator, &mut callback);
+    let node_id = create_global_node(&mut kernel, "before");
+    let checkpoint = kernel.kernel_create_store_checkpoint().unwrap();
+    write_field(&mut kernel, &node_id, "after");
+
+    // Act
+    kernel.kernel_release_store_checkpoint(checkpoint).unwrap();
+
+    // Assert
+    assert_eq!(read_field(&mut kernel, &node_id), "after");
+    assert_matches!(
+        kernel.kernel_rollback_store_to(checkpoint),
+        Err(RuntimeError::KernelError(
+            KernelError::StoreCheckpointError(StoreCheckpointError::TrackCheckpointError(
+                TrackCheckpointError::CheckpointNotFound(..)
+            ))
+        ))
+    );
+}
+
+#[test]
+fn kernel_rollback_store_to_checkpoint_should_revert_changes_of_released_nested_checkpoint() {
+    // Arrange
+    let database = InMemorySubstateDatabase::standard();
+    let mut track = Track::new(&database);
+    let mut id_allocator = IdAllocator::new(Hash([0u8; Hash::LENGTH]));
+    let mut callback = TestCallbackObject;
+    let mut kernel = Kernel::new_no_refs(&mut track, &mut id_allocator, &mut callback);
+    let node_id = create_global_node(&mut kernel, "before");
+    let checkpoint = kernel.kernel_create_store_checkpoint().unwrap();
+    let nested_checkpoint = kernel.kernel_create_store_checkpoint().unwrap();
+    write_field(&mut kernel, &node_id, "after");
+    kernel
+        .kernel_release_store_checkpoint(nested_checkpoint)
+        .unwrap();
+
+    // Act
+    kernel.kernel_rollback_store_to(checkpoint).unwrap();
+
+    // Assert
+    assert_eq!(read_field(&mut kernel, &node_id), "before");
+}
+
+#[test]
+fn kernel_rollback_store_to_checkpoint_should_fail_if_node_persisted_after_it() {
+    // Arrange
+    let database = InMemorySubstateDatabase::standard();
+    let mut track = Track::new(&database);
+    let mut id_allocator = IdAllocator::new(Hash([0u8; Hash::LENGTH]));
+    let mut callback = TestCallbackObject;
+    let mut kernel = Kernel::new_no_refs(&mut track, &mut id_allocator, &mut callback);
+    let node_id = create_global_node(&mut kernel, "before");
+    let checkpoint = kernel.kernel_create_store_checkpoint().unwrap();
+    write_field(&mut kernel, &node_id, "after");
+    let new_node_id = create_global_node(&mut kernel, "new");
+
+    // Act
+    let result = kernel.kernel_rollback_store_to(checkpoint);
+
+    // Assert
+    assert_eq!(
+        result,
+        Err(store_checkpoint_error(
+            StoreCheckpointError::TrackCheckpointError(
+                TrackCheckpointError::NodeCreatedAfterCheckpoint(new_node_id)
+            )
+        ))
+    );
+    assert_eq!(read_field(&mut kernel, &node_id), "after");
+    assert_eq!(read_field(&mut kernel, &new_node_id), "new");
+}
+
+#[test]
+fn kernel_rollback_store_to_checkpoint_should_fail_if_substate_force_written_after_it() {
+    // Arrange
+    let database = InMemorySubstateDatabase::standard();
+    let mut track = Track::new(&database);
+    let mut id_allocator = IdAllocator::new(Hash([0u8; Hash::LENGTH]));
+    let mut callback = TestCallbackObject;
+    let mut kernel = Kernel::new_no_refs(&mut track, &mut id_allocator, &mut callback);
+    let node_id = create_global_node(&mut kernel, "before");
+    let checkpoint = kernel.kernel_create_store_checkpoint().unwrap();
+    let handle = kernel
+        .kernel_open_substate(
+            &node_id,
+            PartitionNumber(0u8),
+            &SubstateKey::Field(0u8),
+            LockFlags::MUTABLE | LockFlags::FORCE_WRITE,
+            (),
+        )
+        .unwrap();
+    kernel
+        .kernel_write_substate(handle, IndexedScryptoValue::from_typed(&"fee locked"))
+        .unwrap();
+    kernel.kernel_close_substate(handle).unwrap();
+
+    // Act
+    let result = kernel.kernel_rollback_store_to(checkpoint);
+
+    // Assert
+    assert_eq!(
+        result,
+        Err(store_checkpoint_error(
+            StoreCheckpointError::TrackCheckpointError(
+                TrackCheckpointError::ForceWriteAfterCheckpoint(node_id)
+            )
+        ))
+    );
+    assert_eq!(read_field(&mut kernel, &node_id), "fee locked");
+}
+
+#[test]
+fn kernel_rollback_store_to_checkpoint_should_fail_if_substate_opened_after_it_is_open() {
+    // Arrange
+    let database = InMemorySubstateDatabase::standard();
+    let mut track = Track::new(&database);
+    let mut id_allocator = IdAllocator::new(Hash([0u8; Hash::LENGTH]));
+    let mut callback = TestCallbackObject;
+    let mut kernel = Kernel::new_no_refs(&mut track, &mut id_allocator, &mut callback);
+    let node_id = create_global_node(&mut kernel, "before");
+    let checkpoint = kernel.kernel_create_store_checkpoint().unwrap();
+    let handle = kernel
+        .kernel_open_substate(
+            &node_id,
+            PartitionNumber(0u8),
+            &SubstateKey::Field(0u8),
+            LockFlags::read_only(),
+            (),
+        )
+        .unwrap();
+
+    // Act
+    let result = kernel.kernel_rollback_store_to(checkpoint);
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(RuntimeError::KernelError(
+            KernelError::StoreCheckpointError(StoreCheckpointError::SubstateOpenedAfterCheckpoint(
+                ..
+            ))
+        ))
+    );
+    kernel.kernel_close_substate(handle).unwrap();
+    kernel.kernel_rollback_store_to(checkpoint).unwrap();
+}
+
+#[test]
+fn kernel_rollback_store_to_checkpoint_should_fail_if_substate_is_open_for_writing() {
+    // Arrange
+    let database = InMemorySubstateDatabase::standard();
+    let mut track = Track::new(&database);
+    let mut id_allocator = IdAllocator::new(Hash([0u8; Hash::LENGTH]));
+    let mut callback = TestCallbackObject;
+    let mut kernel = Kernel::new_no_refs(&mut track, &mut id_allocator, &mut callback);
+    let node_id = create_global_node(&mut kernel, "before");
+    kernel
+        .kernel_open_substate(
+            &node_id,
+            PartitionNumber(0u8),
+            &SubstateKey::Field(0u8),
+            LockFlags::MUTABLE,
+            (),
+        )
+        .unwrap();
+    let checkpoint = kernel.kernel_create_store_checkpoint().unwrap();
+
+    // Act
+    let result = kernel.kernel_rollback_store_to(checkpoint);
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(RuntimeError::KernelError(
+            KernelError::StoreCheckpointError(StoreCheckpointError::SubstateOpenedForWrite(..))
+        ))
+    );
+}
+
+#[test]
+fn kernel_open_substate_should_report_lock_holders_if_substate_is_locked() {
+    // Arrange
+    let database = InMemorySubstateDatabase::standard();
+    let mut track = Track::new(&database);
+    let mut id_allocator = IdAllocator::new(Hash([0u8; Hash::LENGTH]));
+    let mut callback = TestCallbackObject;
+    let mut kernel = Kernel::new_no_refs(&mut track, &mut id_allocator, &mut callback);
+    let node_id = create_global_node(&mut kernel, "before");
+    let handle = kernel
+        .kernel_open_substate(
+            &node_id,
+            PartitionNumber(0u8),
+            &SubstateKey::Field(0u8),
+            LockFlags::MUTABLE,
+            (),
+        )
+        .unwrap();
+
+    // Act
+    let result = kernel.kernel_open_substate(
+        &node_id,
+        PartitionNumber(0u8),
+        &SubstateKey::Field(0u8),
+        LockFlags::read_only(),
+        (),
+    );
+
+    // Assert
+    assert_eq!(
+        result,
+        Err(RuntimeError::KernelError(KernelError::CallFrameError(
+            CallFrameError::OpenSubstateError(OpenSubstateError::SubstateLocked(Box::new(
+                SubstateLockContention {
+                    node_id: node_id.into(),
+                    partition_num: PartitionNumber(0u8),
+                    substate_key: SubstateKey::Field(0u8),
+                    holders: vec![SubstateLockHolder {
+                        flags: LockFlags::MUTABLE,
+                        stack_id: 0,
+                        call_frame_depth: 0,
+                        handle,
+                        actor: "Test".to_owned(),
+                    }],
+                }
+            )))
+        )))
+    );
+}
@@ -1,5 +1,8 @@
+use radix_engine::system::system_modules::debugger::DebuggerModule;
+use radix_engine::system::system_modules::execution_audit::ExecutionAuditModule;
 use radix_engine::system::system_modules::kernel_trace::KernelTraceModule;
 use radix_engine::system::system_modules::limits::LimitsModule;
+use radix_engine::system::system_modules::metrics::MetricsModule;
 use radix_engine::system::system_modules::transaction_runtime::TransactionRuntimeModule;
 use scrypto_test::prelude::*;
 
@@ -40,6 +43,7 @@ pub fn test_open_substate_of_invisible_package_address() {
             LimitsModule::babylon_genesis(),
             CostingModule {
                 current_depth: 0,
+                current_instruction: None,
                 fee_reserve: SystemLoanFeeReserve::default(),
                 fee_table: FeeTable::latest(),
                 tx_payload_len: executable.payload_size(),
@@ -50,6 +54,9 @@ pub fn test_open_substate_of_invisible_package_address() {
                 on_apply_cost: Default::default(),
             },
             ExecutionTraceModule::new(MAX_EXECUTION_TRACE_DEPTH),
+            ExecutionAuditModule::default(),
+            DebuggerModule::default(),
+            MetricsModule::default(),
         ),
         SystemFinalization::no_nullifications(),
     );
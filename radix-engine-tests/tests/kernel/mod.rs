@@ -7,3 +7,5 @@ mod panics;
 mod test_environment;
 mod transaction_executor;
 mod transaction_multi_threaded;
+mod transaction_parallel_executor;
+mod transaction_replay;
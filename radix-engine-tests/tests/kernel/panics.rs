@@ -2,6 +2,7 @@ use radix_common::prelude::*;
 use radix_engine::errors::*;
 use radix_engine::kernel::call_frame::*;
 use radix_engine::kernel::kernel_api::*;
+use radix_engine::kernel::substate_io::StoreCheckpoint;
 #[cfg(not(feature = "alloc"))]
 use radix_engine::system::system::SystemService;
 use radix_engine::system::system_callback::*;
@@ -67,6 +68,10 @@ impl<E: KernelTransactionExecutor> KernelStackApi for MockKernel<E> {
     fn kernel_get_owned_nodes(&mut self) -> Result<Vec<NodeId>, RuntimeError> {
         panic1!()
     }
+
+    fn kernel_get_call_frame_data_stack_uncosted(&self) -> Vec<&E::CallFrameData> {
+        panic1!()
+    }
 }
 
 impl<E: KernelTransactionExecutor> KernelNodeApi for MockKernel<E> {
@@ -177,6 +182,16 @@ impl<E: KernelTransactionExecutor> KernelSubstateApi<E::LockData> for MockKernel
         panic1!()
     }
 
+    fn kernel_scan_keys_after<F: SubstateKeyContent>(
+        &mut self,
+        _: &NodeId,
+        _: PartitionNumber,
+        _: Option<&SubstateKey>,
+        _: u32,
+    ) -> Result<Vec<SubstateKey>, RuntimeError> {
+        panic1!()
+    }
+
     fn kernel_drain_substates<F: SubstateKeyContent>(
         &mut self,
         _: &NodeId,
@@ -223,4 +238,22 @@ impl<E: KernelTransactionExecutor> KernelInternalApi for MockKernel<E> {
     ) -> Option<&IndexedScryptoValue> {
         panic1!()
     }
+
+    fn kernel_get_owned_nodes_uncosted(&self) -> Vec<NodeId> {
+        panic1!()
+    }
+}
+
+impl<E: KernelTransactionExecutor> KernelStoreCheckpointApi for MockKernel<E> {
+    fn kernel_create_store_checkpoint(&mut self) -> Result<StoreCheckpoint, RuntimeError> {
+        panic1!()
+    }
+
+    fn kernel_rollback_store_to(&mut self, _: StoreCheckpoint) -> Result<(), RuntimeError> {
+        panic1!()
+    }
+
+    fn kernel_release_store_checkpoint(&mut self, _: StoreCheckpoint) -> Result<(), RuntimeError> {
+        panic1!()
+    }
 }
@@ -268,6 +268,54 @@ fn can_read_kv_entries_from_a_store_read_from_state() {
     .unwrap();
 }
 
+#[test]
+fn can_scan_kv_entry_keys_from_a_store_read_from_state() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let _ = env
+        .call_method_typed::<_, _, Bucket>(FAUCET, "free", &())
+        .unwrap();
+
+    env.with_component_state::<(Vault, Own), _, _, _>(FAUCET, |(_, kv_store), env| {
+        // Act
+        let keys = env
+            .key_value_store_scan_keys(kv_store.as_node_id(), None, 10)
+            .unwrap()
+            .keys;
+
+        // Assert
+        assert!(keys.contains(&scrypto_encode(&Hash([0; 32])).unwrap()))
+    })
+    .unwrap();
+}
+
+#[test]
+fn removed_kv_entries_are_not_returned_when_scanning_keys() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let _ = env
+        .call_method_typed::<_, _, Bucket>(FAUCET, "free", &())
+        .unwrap();
+
+    env.with_component_state::<(Vault, Own), _, _, _>(FAUCET, |(_, kv_store), env| {
+        let key = scrypto_encode(&Hash([0; 32])).unwrap();
+
+        // Act
+        let removed = env
+            .key_value_store_remove_entry(kv_store.as_node_id(), &key)
+            .unwrap();
+        let keys = env
+            .key_value_store_scan_keys(kv_store.as_node_id(), None, 10)
+            .unwrap()
+            .keys;
+
+        // Assert
+        assert!(scrypto_decode::<Option<Epoch>>(&removed).unwrap().is_some());
+        assert!(!keys.contains(&key))
+    })
+    .unwrap();
+}
+
 #[test]
 fn can_get_and_set_epoch() {
     // Arrange
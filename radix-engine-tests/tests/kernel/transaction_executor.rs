@@ -189,3 +189,28 @@ fn test_normal_transaction_flow() {
     // Assert
     receipt.expect_commit_success();
 }
+
+#[test]
+#[should_panic(expected = "must not be allowed for committed transactions")]
+fn nested_transaction_processor_cannot_be_allowed_for_committed_transactions() {
+    // Arrange
+    let vm_modules = VmModules::default();
+    let mut substate_db = InMemorySubstateDatabase::standard();
+    ProtocolBuilder::for_simulator()
+        .from_bootstrap_to_latest()
+        .commit_each_protocol_update(&mut substate_db);
+
+    let execution_config = ExecutionConfig::for_test_transaction()
+        .update_system_overrides(|overrides| overrides.set_allow_nested_transaction_processor());
+    let executable = TestTransaction::new_v1_from_nonce(
+        ManifestBuilder::new().lock_fee_from_faucet().build(),
+        1,
+        btreeset!(),
+    )
+    .prepare(&PreparationSettings::latest())
+    .unwrap()
+    .into_unvalidated_executable();
+
+    // Act
+    execute_and_commit_transaction(&mut substate_db, &vm_modules, &execution_config, executable);
+}
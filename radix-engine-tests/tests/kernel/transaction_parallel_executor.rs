@@ -0,0 +1,229 @@
+use radix_common::prelude::*;
+use radix_engine::transaction::*;
+use radix_engine::updates::ProtocolBuilder;
+use radix_engine::vm::VmModules;
+use radix_engine_interface::prelude::*;
+use radix_substate_store_impls::memory_db::InMemorySubstateDatabase;
+use radix_transactions::model::TestTransaction;
+use radix_transactions::prelude::*;
+
+fn bootstrapped_database() -> InMemorySubstateDatabase {
+    let mut substate_db = InMemorySubstateDatabase::standard();
+    ProtocolBuilder::for_simulator()
+        .from_bootstrap_to_latest()
+        .commit_each_protocol_update(&mut substate_db);
+    substate_db
+}
+
+#[test]
+fn parallel_execution_matches_sequential_execution() {
+    // Arrange
+    let vm_modules = VmModules::default();
+    let execution_config = ExecutionConfig::for_test_transaction();
+    let initial_db = bootstrapped_database();
+    let public_key = Secp256k1PrivateKey::from_u64(1).unwrap().public_key();
+    let account = ComponentAddress::preallocated_account_from_public_key(&public_key);
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .get_free_xrd_from_faucet()
+        .try_deposit_entire_worktop_or_abort(account, None)
+        .build();
+    let executables = (0..4)
+        .map(|nonce| {
+            TestTransaction::new_v1(
+                manifest.clone(),
+                hash(format!("Fill account: {}", nonce)),
+                btreeset![NonFungibleGlobalId::from_public_key(&public_key)],
+            )
+            .into_executable_unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let mut sequential_db = initial_db.clone();
+    let sequential_results = executables
+        .iter()
+        .map(|executable| {
+            execute_and_commit_transaction(
+                &mut sequential_db,
+                &vm_modules,
+                &execution_config,
+                executable,
+            )
+            .result
+        })
+        .collect::<Vec<_>>();
+
+    // Act
+    let mut parallel_db = initial_db;
+    let executed = execute_and_commit_transactions_in_parallel(
+        &mut parallel_db,
+        &vm_modules,
+        &execution_config,
+        &executables,
+        2,
+    );
+
+    // Assert
+    let parallel_results = executed
+        .iter()
+        .map(|executed| executed.receipt.result.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(parallel_results, sequential_results);
+    assert_eq!(
+        executed
+            .iter()
+            .map(|executed| executed.mode)
+            .collect::<Vec<_>>(),
+        vec![
+            ParallelExecutionMode::Parallel,
+            ParallelExecutionMode::StaticConflict,
+            ParallelExecutionMode::StaticConflict,
+            ParallelExecutionMode::StaticConflict,
+        ]
+    );
+}
+
+fn execute_sequentially_and_in_parallel(
+    initial_db: &InMemorySubstateDatabase,
+    executables: &[ExecutableTransaction],
+) -> Vec<ParallelExecutionMode> {
+    let vm_modules = VmModules::default();
+    let execution_config = ExecutionConfig::for_test_transaction();
+
+    let mut sequential_db = initial_db.clone();
+    let sequential_results = executables
+        .iter()
+        .map(|executable| {
+            execute_and_commit_transaction(
+                &mut sequential_db,
+                &vm_modules,
+                &execution_config,
+                executable,
+            )
+            .result
+        })
+        .collect::<Vec<_>>();
+
+    let mut parallel_db = initial_db.clone();
+    let executed = execute_and_commit_transactions_in_parallel(
+        &mut parallel_db,
+        &vm_modules,
+        &execution_config,
+        executables,
+        2,
+    );
+    let parallel_results = executed
+        .iter()
+        .map(|executed| executed.receipt.result.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(parallel_results, sequential_results);
+
+    executed.iter().map(|executed| executed.mode).collect()
+}
+
+fn funded_accounts(
+    substate_db: &mut InMemorySubstateDatabase,
+    count: u64,
+) -> Vec<(Secp256k1PublicKey, ComponentAddress)> {
+    (1..=count)
+        .map(|key| {
+            let public_key = Secp256k1PrivateKey::from_u64(key).unwrap().public_key();
+            let account = ComponentAddress::preallocated_account_from_public_key(&public_key);
+            let manifest = ManifestBuilder::new()
+                .lock_fee_from_faucet()
+                .get_free_xrd_from_faucet()
+                .try_deposit_entire_worktop_or_abort(account, None)
+                .build();
+            execute_and_commit_transaction(
+                substate_db,
+                &VmModules::default(),
+                &ExecutionConfig::for_test_transaction(),
+                TestTransaction::new_v1(
+                    manifest,
+                    hash(format!("Fund account: {}", key)),
+                    btreeset![],
+                )
+                .into_executable_unwrap(),
+            )
+            .expect_commit_success();
+            (public_key, account)
+        })
+        .collect()
+}
+
+fn lock_fee_from_account(
+    public_key: &Secp256k1PublicKey,
+    account: ComponentAddress,
+) -> ExecutableTransaction {
+    let manifest = ManifestBuilder::new().lock_fee(account, 10).build();
+    TestTransaction::new_v1(
+        manifest,
+        hash(format!("Lock fee: {:?}", account)),
+        btreeset![NonFungibleGlobalId::from_public_key(public_key)],
+    )
+    .into_executable_unwrap()
+}
+
+#[test]
+fn transactions_paying_fees_from_different_accounts_are_executed_in_parallel() {
+    // Arrange
+    let mut initial_db = bootstrapped_database();
+    let executables = funded_accounts(&mut initial_db, 3)
+        .iter()
+        .map(|(public_key, account)| lock_fee_from_account(public_key, *account))
+        .collect::<Vec<_>>();
+
+    // Act
+    let modes = execute_sequentially_and_in_parallel(&initial_db, &executables);
+
+    // Assert
+    assert_eq!(
+        modes,
+        vec![
+            ParallelExecutionMode::Parallel,
+            ParallelExecutionMode::Parallel,
+            ParallelExecutionMode::Parallel,
+        ]
+    );
+}
+
+#[test]
+fn transactions_reading_state_changed_by_earlier_transaction_are_re_executed() {
+    // Arrange
+    let mut initial_db = bootstrapped_database();
+    let accounts = funded_accounts(&mut initial_db, 2);
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(
+            CONSENSUS_MANAGER,
+            CONSENSUS_MANAGER_NEXT_ROUND_IDENT,
+            ConsensusManagerNextRoundInput::successful(Round::of(1), 0, 1000),
+        )
+        .build();
+    let round_change = TestTransaction::new_v1(
+        manifest,
+        hash("Round change"),
+        btreeset![system_execution(SystemExecution::Validator)],
+    )
+    .into_executable_unwrap();
+    let mut executables = vec![round_change];
+    executables.extend(
+        accounts
+            .iter()
+            .map(|(public_key, account)| lock_fee_from_account(public_key, *account)),
+    );
+
+    // Act
+    let modes = execute_sequentially_and_in_parallel(&initial_db, &executables);
+
+    // Assert
+    // The fee payments read the consensus manager state, which the round change updates
+    assert_eq!(
+        modes,
+        vec![
+            ParallelExecutionMode::Parallel,
+            ParallelExecutionMode::RuntimeConflict,
+            ParallelExecutionMode::RuntimeConflict,
+        ]
+    );
+}
@@ -0,0 +1,139 @@
+use radix_common::prelude::*;
+use radix_engine::transaction::*;
+use radix_engine::updates::ProtocolBuilder;
+use radix_engine::vm::{DefaultVmModules, VmModules};
+use radix_substate_store_impls::memory_db::InMemorySubstateDatabase;
+use radix_transactions::model::TestTransaction;
+use radix_transactions::prelude::*;
+
+fn bootstrapped_database() -> InMemorySubstateDatabase {
+    let mut substate_db = InMemorySubstateDatabase::standard();
+    ProtocolBuilder::for_simulator()
+        .from_bootstrap_to_latest()
+        .commit_each_protocol_update(&mut substate_db);
+    substate_db
+}
+
+fn commit_and_record_transactions(
+    substate_db: &mut InMemorySubstateDatabase,
+    vm_modules: &DefaultVmModules,
+) -> Vec<CommittedTransactionRecord> {
+    let public_key = Secp256k1PrivateKey::from_u64(1).unwrap().public_key();
+    let account = ComponentAddress::preallocated_account_from_public_key(&public_key);
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .get_free_xrd_from_faucet()
+        .try_deposit_entire_worktop_or_abort(account, None)
+        .build();
+    (0..2)
+        .map(|nonce| {
+            let executable = TestTransaction::new_v1(
+                manifest.clone(),
+                hash(format!("Fill account: {}", nonce)),
+                btreeset![NonFungibleGlobalId::from_public_key(&public_key)],
+            )
+            .into_executable_unwrap();
+            let receipt = execute_and_commit_transaction(
+                substate_db,
+                vm_modules,
+                &ExecutionConfig::for_test_transaction(),
+                &executable,
+            );
+            receipt.expect_commit_success();
+            CommittedTransactionRecord {
+                executable,
+                result: receipt.result,
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn replaying_committed_transactions_does_not_diverge() {
+    // Arrange
+    let vm_modules = VmModules::default();
+    let initial_db = bootstrapped_database();
+    let mut substate_db = initial_db.clone();
+    let transactions = commit_and_record_transactions(&mut substate_db, &vm_modules);
+
+    // Act
+    let mut replay_db = initial_db;
+    let result = replay_committed_transactions(
+        &mut replay_db,
+        &vm_modules,
+        &ExecutionConfig::for_test_transaction(),
+        &transactions,
+    );
+
+    // Assert
+    assert_eq!(result, Ok(2));
+}
+
+#[test]
+fn replaying_committed_transactions_reports_first_divergence() {
+    // Arrange
+    let vm_modules = VmModules::default();
+    let initial_db = bootstrapped_database();
+    let mut substate_db = initial_db.clone();
+    let mut transactions = commit_and_record_transactions(&mut substate_db, &vm_modules);
+    let TransactionResult::Commit(commit) = &mut transactions[1].result else {
+        panic!("Expected a committed transaction");
+    };
+    let dropped_event = commit.application_events.pop().unwrap();
+    let dropped_event_index = commit.application_events.len();
+
+    // Act
+    let mut replay_db = initial_db;
+    let result = replay_committed_transactions(
+        &mut replay_db,
+        &vm_modules,
+        &ExecutionConfig::for_test_transaction(),
+        &transactions,
+    );
+
+    // Assert
+    assert_eq!(
+        result,
+        Err(ReplayDivergence {
+            transaction_index: 1,
+            kind: ReplayDivergenceKind::Event {
+                event_index: dropped_event_index,
+                expected: None,
+                actual: Some(dropped_event),
+            },
+        })
+    );
+}
+
+#[test]
+fn replaying_committed_transactions_treats_missing_partition_updates_as_empty() {
+    // Arrange
+    let vm_modules = VmModules::default();
+    let initial_db = bootstrapped_database();
+    let mut substate_db = initial_db.clone();
+    let mut transactions = commit_and_record_transactions(&mut substate_db, &vm_modules);
+    let TransactionResult::Commit(commit) = &mut transactions[1].result else {
+        panic!("Expected a committed transaction");
+    };
+    commit
+        .state_updates
+        .of_node(FAUCET)
+        .of_partition(PartitionNumber(200));
+
+    // Act
+    let mut replay_db = initial_db;
+    let result = replay_committed_transactions(
+        &mut replay_db,
+        &vm_modules,
+        &ExecutionConfig::for_test_transaction(),
+        &transactions,
+    );
+
+    // Assert
+    let divergence = result.unwrap_err();
+    assert_eq!(divergence.transaction_index, 1);
+    assert!(matches!(
+        divergence.kind,
+        ReplayDivergenceKind::Result { .. }
+    ));
+}
@@ -106,6 +106,18 @@ fn can_withdraw_from_my_2_of_3_account_with_2_signatures() {
     test_auth_rule(&mut ledger, &auth_2_of_3, &[pk1.into(), pk2.into()], true);
 }
 
+#[test]
+fn cannot_withdraw_from_my_2_of_3_account_with_single_signature() {
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (pk0, _, auth0) = ledger.new_key_pair_with_auth_address();
+    let (pk1, _, auth1) = ledger.new_key_pair_with_auth_address();
+    let (pk2, _, auth2) = ledger.new_key_pair_with_auth_address();
+    let auth_2_of_3 = rule!(require_n_of(2, vec![auth0, auth1, auth2]));
+    for pk in [pk0, pk1, pk2] {
+        test_auth_rule(&mut ledger, &auth_2_of_3, &[pk.into()], false);
+    }
+}
+
 #[test]
 fn can_withdraw_from_my_complex_account() {
     let mut ledger = LedgerSimulatorBuilder::new().build();
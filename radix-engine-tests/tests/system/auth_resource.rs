@@ -198,3 +198,58 @@ fn cannot_deposit_with_wrong_auth() {
 fn can_deposit_with_right_auth() {
     test_resource_auth(Action::Deposit, true, true, false);
 }
+
+#[test]
+fn cannot_withdraw_or_unlock_soulbound_resource() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (public_key, _, account) = ledger.new_allocated_account();
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_fungible_resource(
+            OwnerRole::None,
+            false,
+            18,
+            FungibleResourceRoles {
+                withdraw_roles: withdraw_roles! {
+                    withdrawer => rule!(deny_all);
+                    withdrawer_updater => rule!(deny_all);
+                },
+                ..Default::default()
+            },
+            metadata!(),
+            Some(dec!(10)),
+        )
+        .try_deposit_entire_worktop_or_abort(account, None)
+        .build();
+    let soulbound_token = ledger
+        .execute_manifest(manifest, vec![])
+        .expect_commit_success()
+        .new_resource_addresses()[0];
+
+    // Act
+    let withdraw_receipt = ledger.execute_manifest(
+        ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(account, soulbound_token, dec!(1))
+            .try_deposit_entire_worktop_or_abort(account, None)
+            .build(),
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+    let unlock_receipt = ledger.execute_manifest(
+        ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .set_role(
+                soulbound_token,
+                ModuleId::Main,
+                RoleKey::new(WITHDRAWER_ROLE),
+                rule!(allow_all),
+            )
+            .build(),
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+
+    // Assert
+    withdraw_receipt.expect_specific_failure(is_auth_error);
+    unlock_receipt.expect_specific_failure(is_auth_error);
+}
@@ -265,3 +265,72 @@ fn test_balance_changes_when_transferring_non_fungibles() {
         ),
     );
 }
+
+#[test]
+fn test_balance_change_and_event_assertions() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (public_key, _, account) = ledger.new_allocated_account();
+    let (_, _, other_account) = ledger.new_allocated_account();
+
+    // Act
+    let receipt = ledger.execute_manifest(
+        ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(account, RORK, dec!("1"))
+            .try_deposit_entire_worktop_or_abort(other_account, None)
+            .build(),
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+
+    // Assert
+    let result = receipt.expect_commit_success();
+    ledger.expect_balance_change(result, account, RORK, BalanceChange::Fungible(dec!("-1")));
+    ledger.expect_balance_change(
+        result,
+        other_account,
+        RORK,
+        BalanceChange::Fungible(dec!("1")),
+    );
+    let (emitter, event) = result
+        .expect_single_application_event_of::<radix_engine::blueprints::account::WithdrawEvent>();
+    assert_eq!(
+        emitter,
+        &Emitter::Method(account.into_node_id(), ModuleId::Main)
+    );
+    assert_eq!(
+        event,
+        radix_engine::blueprints::account::WithdrawEvent::Fungible(RORK, dec!("1"))
+    );
+}
+
+#[test]
+fn test_resource_supply_changes() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (_, _, account) = ledger.new_allocated_account();
+    let resource = ledger.create_freely_mintable_and_burnable_fungible_resource(
+        OwnerRole::None,
+        None,
+        DIVISIBILITY_MAXIMUM,
+        account,
+    );
+
+    // Act
+    let receipt = ledger.execute_manifest(
+        ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .mint_fungible(resource, dec!("10"))
+            .burn_from_worktop(dec!("4"), resource)
+            .try_deposit_entire_worktop_or_abort(account, None)
+            .build(),
+        vec![],
+    );
+
+    // Assert
+    let result = receipt.expect_commit_success();
+    assert_eq!(
+        result.resource_supply_changes().get(&resource),
+        Some(&BalanceChange::Fungible(dec!("6")))
+    );
+}
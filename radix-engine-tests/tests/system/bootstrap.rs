@@ -9,7 +9,7 @@ use radix_engine::system::checkers::{
 use radix_engine::system::system_db_reader::{ObjectCollectionKey, SystemDatabaseReader};
 use radix_engine::system::system_modules::auth::AuthError;
 use radix_engine::transaction::{BalanceChange, CommitResult, SystemStructure};
-use radix_engine::updates::{BabylonSettings, ProtocolBuilder};
+use radix_engine::updates::{BabylonSettings, GenesisBuilder, ProtocolBuilder};
 use radix_engine_interface::object_modules::metadata::{MetadataValue, UncheckedUrl};
 use radix_engine_interface::prelude::*;
 use radix_substate_store_impls::memory_db::InMemorySubstateDatabase;
@@ -759,3 +759,51 @@ fn test_bootstrap_should_create_consensus_manager_with_sorted_validator_index()
         }
     );
 }
+
+#[test]
+fn test_genesis_builder_allocations() {
+    // Arrange
+    let validator_key = Secp256k1PrivateKey::from_u64(1).unwrap().public_key();
+    let staker = ComponentAddress::preallocated_account_from_public_key(&validator_key);
+    let token_holder = ComponentAddress::preallocated_account_from_public_key(
+        &Secp256k1PrivateKey::from_u64(2).unwrap().public_key(),
+    );
+    let resource_address = GenesisBuilder::resource_address_from_seed(&[1, 2, 3]);
+    let genesis = || {
+        GenesisBuilder::new()
+            .with_validator(validator_key)
+            .with_stake(validator_key, staker, dec!("10"))
+            .with_resource(GenesisResource {
+                reserved_resource_address: resource_address,
+                metadata: vec![(
+                    "symbol".to_string(),
+                    MetadataValue::String("TST".to_string()),
+                )],
+                owner: None,
+            })
+            .with_resource_balance(resource_address, token_holder, dec!("105"))
+            .with_xrd_balance(token_holder, dec!("20"))
+            .build()
+    };
+    assert_eq!(
+        scrypto_encode(&genesis()).unwrap(),
+        scrypto_encode(&genesis()).unwrap()
+    );
+
+    // Act
+    let mut ledger = LedgerSimulatorBuilder::new()
+        .with_custom_protocol(|builder| {
+            builder
+                .configure_babylon(|_| genesis())
+                .from_bootstrap_to_latest()
+        })
+        .build();
+
+    // Assert
+    ledger.get_active_validator_with_key(&validator_key);
+    assert_eq!(
+        ledger.get_component_balance(token_holder, resource_address),
+        dec!("105")
+    );
+    assert_eq!(ledger.get_component_balance(token_holder, RORK), dec!("20"));
+}
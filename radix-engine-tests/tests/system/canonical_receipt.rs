@@ -0,0 +1,165 @@
+#![cfg(feature = "std")]
+
+use radix_transaction_scenarios::executor::*;
+use scrypto_test::prelude::*;
+use std::path::PathBuf;
+
+#[test]
+fn canonical_receipt_sbor_encoding_can_be_decoded() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (_, _, account) = ledger.new_account(false);
+    let receipt = ledger.execute_manifest(
+        ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .get_free_xrd_from_faucet()
+            .try_deposit_entire_worktop_or_abort(account, None)
+            .build(),
+        vec![],
+    );
+    receipt.expect_commit_success();
+
+    // Act
+    let encoded = receipt.to_canonical_sbor();
+    let decoded: VersionedCanonicalTransactionReceipt = scrypto_decode(&encoded).unwrap();
+
+    // Assert
+    assert_eq!(decoded, receipt.to_canonical());
+    assert_eq!(hash(&encoded), receipt.canonical_hash());
+}
+
+#[test]
+fn canonical_receipt_json_can_be_converted_back_to_sbor_encoding() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (_, _, account) = ledger.new_account(false);
+    let receipt = ledger.execute_manifest(
+        ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .get_free_xrd_from_faucet()
+            .try_deposit_entire_worktop_or_abort(account, None)
+            .build(),
+        vec![],
+    );
+    receipt.expect_commit_success();
+    let network_definition = NetworkDefinition::simulator();
+
+    // Act
+    let json = receipt.to_canonical_json(&network_definition);
+    let encoded =
+        VersionedCanonicalTransactionReceipt::canonical_sbor_from_json(&json, &network_definition)
+            .unwrap();
+
+    // Assert
+    assert_eq!(encoded, receipt.to_canonical_sbor());
+}
+
+#[test]
+fn stored_canonical_receipt_json_matches_stored_hash() {
+    let folder = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("assets")
+        .join("canonical_receipts")
+        .join("transfer_xrd");
+    let network_definition = NetworkDefinition::simulator();
+
+    for entry in std::fs::read_dir(&folder).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().and_then(|extension| extension.to_str()) != Some("json") {
+            continue;
+        }
+        let json = std::fs::read_to_string(&path).unwrap();
+        let expected_hash = std::fs::read_to_string(path.with_extension("hash")).unwrap();
+
+        let encoded = VersionedCanonicalTransactionReceipt::canonical_sbor_from_json(
+            &json,
+            &network_definition,
+        )
+        .unwrap();
+
+        assert_eq!(hash(&encoded).to_string(), expected_hash.trim(), "{path:?}");
+    }
+}
+
+#[test]
+fn canonical_receipt_excludes_execution_config_dependent_debug_data() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (_, _, account) = ledger.new_account(false);
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .get_free_xrd_from_faucet()
+        .try_deposit_entire_worktop_or_abort(account, None)
+        .build();
+
+    // Act
+    let receipt = ledger.execute_transaction_no_commit(
+        TestTransaction::new_v1_from_nonce(manifest.clone(), 1, btreeset!()),
+        ExecutionConfig::for_test_transaction(),
+    );
+    let audited_receipt = ledger.execute_transaction_no_commit(
+        TestTransaction::new_v1_from_nonce(manifest, 1, btreeset!()),
+        ExecutionConfig::for_test_transaction().with_execution_audit(true),
+    );
+
+    // Assert
+    assert!(audited_receipt.execution_audit.is_some());
+    assert_eq!(receipt.canonical_hash(), audited_receipt.canonical_hash());
+}
+
+#[test]
+fn assert_canonical_receipts_of_transfer_xrd_scenario() {
+    run_transfer_xrd_scenario(AlignerExecutionMode::Assert);
+}
+
+#[test]
+#[ignore = "Run this test to update the generated files"]
+fn update_canonical_receipts_of_transfer_xrd_scenario() {
+    run_transfer_xrd_scenario(AlignerExecutionMode::Write);
+}
+
+fn run_transfer_xrd_scenario(mode: AlignerExecutionMode) {
+    struct Hooks {
+        folder: FolderContentAligner,
+    }
+    impl<S: SubstateDatabase> ScenarioExecutionHooks<S> for Hooks {
+        fn on_transaction_executed(
+            &mut self,
+            OnScenarioTransactionExecuted {
+                transaction,
+                receipt,
+                network_definition,
+                ..
+            }: OnScenarioTransactionExecuted<S>,
+        ) {
+            self.folder.put_file(
+                format!("{}.json", transaction.logical_name),
+                receipt.to_canonical_json(network_definition),
+            );
+            self.folder.put_file(
+                format!("{}.hash", transaction.logical_name),
+                receipt.canonical_hash().to_string(),
+            );
+        }
+    }
+
+    let folder = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("assets")
+        .join("canonical_receipts")
+        .join("transfer_xrd");
+    let mut hooks = Hooks {
+        folder: FolderContentAligner::new(folder, mode, AlignerFolderMode::ExpectNoOtherContent),
+    };
+    TransactionScenarioExecutor::new(
+        InMemorySubstateDatabase::standard(),
+        NetworkDefinition::simulator(),
+    )
+    .execute_protocol_updates_and_scenarios(
+        |builder| builder.from_bootstrap_to_latest(),
+        ScenarioTrigger::AfterCompletionOfAllProtocolUpdates,
+        ScenarioFilter::SpecificScenariosByName(btreeset!("transfer_xrd".to_string())),
+        &mut hooks,
+        &mut (),
+        &VmModules::default(),
+    )
+    .expect("Must succeed!");
+}
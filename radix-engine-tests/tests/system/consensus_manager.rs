@@ -3734,3 +3734,117 @@ fn test_tips_and_fee_distribution_when_one_validator_has_zero_stake() {
     let vault_id = ledger.get_component_vaults(CONSENSUS_MANAGER, RORK)[0];
     assert_close_to!(ledger.inspect_vault_balance(vault_id).unwrap(), dec!(0));
 }
+
+fn ledger_with_single_validator(
+    validator_pub_key: Secp256k1PublicKey,
+) -> (DefaultLedgerSimulator, ComponentAddress, ComponentAddress) {
+    let validator_account_address =
+        ComponentAddress::preallocated_account_from_public_key(&validator_pub_key);
+    let genesis = BabylonSettings::single_validator_and_staker(
+        validator_pub_key,
+        Decimal::one(),
+        Decimal::ZERO,
+        validator_account_address,
+        Epoch::of(5),
+        ConsensusManagerConfig::test_default(),
+    );
+    let ledger = LedgerSimulatorBuilder::new()
+        .with_custom_protocol(|builder| {
+            builder
+                .configure_babylon(|_| genesis)
+                .from_bootstrap_to_latest()
+        })
+        .build();
+    let validator_address = ledger.get_active_validator_with_key(&validator_pub_key);
+    (ledger, validator_address, validator_account_address)
+}
+
+fn validator_owner_manifest_builder(
+    validator_address: ComponentAddress,
+    validator_account_address: ComponentAddress,
+) -> ManifestBuilder {
+    ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_non_fungibles(
+            validator_account_address,
+            VALIDATOR_OWNER_BADGE,
+            [NonFungibleLocalId::bytes(validator_address.as_node_id().0).unwrap()],
+        )
+}
+
+#[test]
+fn update_validator_key_manifest_builder_method_updates_the_key() {
+    // Arrange
+    let validator_pub_key = Secp256k1PrivateKey::from_u64(2u64).unwrap().public_key();
+    let (mut ledger, validator_address, validator_account_address) =
+        ledger_with_single_validator(validator_pub_key);
+    let next_validator_pub_key = Secp256k1PrivateKey::from_u64(3u64).unwrap().public_key();
+
+    // Act
+    let receipt = ledger.execute_manifest(
+        validator_owner_manifest_builder(validator_address, validator_account_address)
+            .update_validator_key(validator_address, next_validator_pub_key)
+            .build(),
+        vec![NonFungibleGlobalId::from_public_key(&validator_pub_key)],
+    );
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(
+        ledger.get_validator_info(validator_address).key,
+        next_validator_pub_key
+    );
+}
+
+#[test]
+fn update_validator_fee_manifest_builder_method_requests_a_fee_change() {
+    // Arrange
+    let validator_pub_key = Secp256k1PrivateKey::from_u64(2u64).unwrap().public_key();
+    let (mut ledger, validator_address, validator_account_address) =
+        ledger_with_single_validator(validator_pub_key);
+
+    // Act
+    let receipt = ledger.execute_manifest(
+        validator_owner_manifest_builder(validator_address, validator_account_address)
+            .update_validator_fee(validator_address, dec!("0.25"))
+            .build(),
+        vec![NonFungibleGlobalId::from_public_key(&validator_pub_key)],
+    );
+
+    // Assert
+    receipt.expect_commit_success();
+    let fee_change_request = ledger
+        .get_validator_info(validator_address)
+        .validator_fee_change_request
+        .expect("The fee change should have been requested");
+    assert_eq!(fee_change_request.new_fee_factor, dec!("0.25"));
+}
+
+#[test]
+fn update_validator_accept_delegated_stake_manifest_builder_method_updates_the_flag() {
+    // Arrange
+    let validator_pub_key = Secp256k1PrivateKey::from_u64(2u64).unwrap().public_key();
+    let (mut ledger, validator_address, validator_account_address) =
+        ledger_with_single_validator(validator_pub_key);
+    assert!(
+        ledger
+            .get_validator_info(validator_address)
+            .accepts_delegated_stake
+    );
+
+    // Act
+    let receipt = ledger.execute_manifest(
+        validator_owner_manifest_builder(validator_address, validator_account_address)
+            .update_validator_accept_delegated_stake(validator_address, false)
+            .build(),
+        vec![NonFungibleGlobalId::from_public_key(&validator_pub_key)],
+    );
+
+    // Assert
+    receipt.expect_commit_success();
+    assert!(
+        !ledger
+            .get_validator_info(validator_address)
+            .accepts_delegated_stake
+    );
+}
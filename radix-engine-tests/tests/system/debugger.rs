@@ -0,0 +1,93 @@
+use radix_engine::system::actor::*;
+use radix_engine::system::system_modules::debugger::*;
+use scrypto_test::prelude::*;
+use std::sync::mpsc;
+
+fn execute_with_debugger(
+    ledger: &mut DefaultLedgerSimulator,
+    breakpoints: Vec<Breakpoint>,
+) -> Vec<DebuggerBreak> {
+    let (sender, receiver) = mpsc::channel();
+    let debugger = DebuggerConfig {
+        breakpoints,
+        ..DebuggerConfig::new(move |debugger_break| {
+            sender.send(debugger_break.clone()).unwrap();
+        })
+    };
+    let (_, _, account) = ledger.new_allocated_account();
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .get_free_xrd_from_faucet()
+        .try_deposit_entire_worktop_or_refund(account, None)
+        .build();
+    let receipt = ledger.execute_transaction_no_commit(
+        TestTransaction::new_v1_from_nonce(manifest, 1, btreeset!()),
+        ExecutionConfig::for_test_transaction().with_debugger(debugger),
+    );
+    receipt.expect_commit_success();
+    receiver.try_iter().collect()
+}
+
+#[test]
+fn invocation_breakpoint_pauses_before_matching_method() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+
+    // Act
+    let breaks = execute_with_debugger(
+        &mut ledger,
+        vec![Breakpoint::Invocation {
+            package_address: FAUCET_PACKAGE,
+            blueprint_name: Some(FAUCET_BLUEPRINT.to_string()),
+            ident: Some("free".to_string()),
+        }],
+    );
+
+    // Assert
+    assert_eq!(breaks.len(), 1);
+    assert_eq!(breaks[0].breakpoint_index, 0);
+    let DebuggerEvent::Invocation { callee, .. } = &breaks[0].event else {
+        panic!("Expected an invocation break: {:?}", breaks[0].event);
+    };
+    assert_matches!(
+        callee.as_ref(),
+        Actor::Method(MethodActor { node_id, ident, .. })
+            if *node_id == FAUCET.into_node_id() && ident == "free"
+    );
+}
+
+#[test]
+fn substate_access_breakpoint_pauses_before_matching_substate_is_opened() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+
+    // Act
+    let breaks = execute_with_debugger(
+        &mut ledger,
+        vec![
+            Breakpoint::Invocation {
+                package_address: IDENTITY_PACKAGE,
+                blueprint_name: None,
+                ident: None,
+            },
+            Breakpoint::SubstateAccess {
+                node_id: FAUCET.into_node_id(),
+                partition_num: Some(MAIN_BASE_PARTITION),
+            },
+        ],
+    );
+
+    // Assert
+    assert!(!breaks.is_empty());
+    for debugger_break in breaks {
+        assert_eq!(debugger_break.breakpoint_index, 1);
+        assert_matches!(
+            debugger_break.event,
+            DebuggerEvent::SubstateAccess {
+                node_id,
+                partition_num: MAIN_BASE_PARTITION,
+                ..
+            } if node_id == FAUCET.into_node_id()
+        );
+    }
+}
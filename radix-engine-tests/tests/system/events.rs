@@ -381,6 +381,30 @@ fn locking_fee_against_a_vault_emits_correct_events() {
     }
 }
 
+#[test]
+fn application_events_of_returns_decoded_events_of_the_given_type() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+
+    let manifest = ManifestBuilder::new().lock_fee(FAUCET, 500).build();
+
+    // Act
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let commit = receipt.expect_commit_success();
+    let lock_fee_events = commit.application_events_of::<fungible_vault::LockFeeEvent>();
+    assert_eq!(lock_fee_events.len(), 1);
+    assert_matches!(
+        lock_fee_events[0],
+        (Emitter::Method(_, ModuleId::Main), fungible_vault::LockFeeEvent { amount })
+            if amount == dec!(500)
+    );
+    assert!(commit
+        .application_events_of::<fungible_vault::RecallEvent>()
+        .is_empty());
+}
+
 #[test]
 fn vault_fungible_recall_emits_correct_events() {
     // Arrange
@@ -0,0 +1,70 @@
+use radix_engine::system::system_modules::execution_audit::*;
+use scrypto_test::prelude::*;
+
+fn execute_with_audit(
+    ledger: &mut DefaultLedgerSimulator,
+    account: ComponentAddress,
+    amount: u32,
+) -> ExecutionAudit {
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .get_free_xrd_from_faucet()
+        .take_from_worktop(RORK, amount, "bucket")
+        .return_to_worktop("bucket")
+        .try_deposit_entire_worktop_or_abort(account, None)
+        .build();
+    let receipt = ledger.execute_transaction_no_commit(
+        TestTransaction::new_v1_from_nonce(manifest, 1, btreeset!()),
+        ExecutionConfig::for_test_transaction().with_execution_audit(true),
+    );
+    receipt.expect_commit_success();
+    receipt.execution_audit.unwrap()
+}
+
+#[test]
+fn execution_audit_is_only_recorded_when_enabled() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+
+    // Act
+    let receipt =
+        ledger.execute_manifest(ManifestBuilder::new().lock_fee_from_faucet().build(), []);
+
+    // Assert
+    receipt.expect_commit_success();
+    assert!(receipt.execution_audit.is_none());
+}
+
+#[test]
+fn execution_audit_is_identical_for_repeated_execution() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (_, _, account) = ledger.new_allocated_account();
+
+    // Act
+    let audit = execute_with_audit(&mut ledger, account, 5);
+    let repeated_audit = execute_with_audit(&mut ledger, account, 5);
+
+    // Assert
+    assert!(!audit.entries.is_empty());
+    assert_eq!(audit.first_difference(&repeated_audit), None);
+}
+
+#[test]
+fn execution_audit_pinpoints_first_diverging_kernel_call() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (_, _, account) = ledger.new_allocated_account();
+
+    // Act
+    let audit = execute_with_audit(&mut ledger, account, 5);
+    let other_audit = execute_with_audit(&mut ledger, account, 6);
+
+    // Assert
+    let index = audit.first_difference(&other_audit).unwrap();
+    assert_eq!(audit.entries[..index], other_audit.entries[..index]);
+    assert_eq!(
+        audit.entries[index].event_type,
+        ExecutionAuditEventType::Invoke
+    );
+}
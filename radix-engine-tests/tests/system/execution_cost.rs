@@ -63,6 +63,84 @@ fn executing_transactions_with_debug_information_outputs_the_detailed_cost_break
     );
 }
 
+#[test]
+fn executing_transactions_with_debug_information_outputs_the_cost_breakdown_by_instruction() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (pk, _, account) = ledger.new_account(false);
+
+    // Act
+    let receipt = ledger.execute_manifest_with_execution_config(
+        ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .get_free_xrd_from_faucet()
+            .deposit_entire_worktop(account)
+            .build(),
+        vec![NonFungibleGlobalId::from_public_key(&pk)],
+        ExecutionConfig::for_debug_transaction(),
+    );
+
+    // Assert
+    receipt.expect_commit_success();
+    let breakdown = receipt
+        .execution_cost_breakdown_by_instruction()
+        .expect("Debug information is not available when it should.");
+    assert_eq!(
+        breakdown
+            .iter()
+            .map(|cost| (cost.intent_index, cost.instruction_index))
+            .collect::<Vec<_>>(),
+        vec![(0, 0), (0, 1), (0, 2)]
+    );
+    assert!(breakdown.iter().all(|cost| cost.cost_units > 0));
+    let instruction_cost_units: u32 = breakdown.iter().map(|cost| cost.cost_units).sum();
+    assert!(instruction_cost_units < receipt.fee_summary.total_execution_cost_units_consumed);
+}
+
+#[test]
+fn executing_transactions_with_subintents_outputs_the_cost_breakdown_by_intent_and_instruction() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (_, _, account) = ledger.new_account(false);
+    let mut builder = TestTransaction::new_v2_builder(ledger.next_transaction_nonce());
+    let child = builder.add_subintent(
+        ManifestBuilder::new_subintent_v2()
+            .get_free_xrd_from_faucet()
+            .try_deposit_entire_worktop_or_abort(account, None)
+            .yield_to_parent(())
+            .build(),
+        [],
+    );
+    let transaction = builder.finish_with_root_intent(
+        ManifestBuilder::new_v2()
+            .use_child("child", child)
+            .lock_fee_from_faucet()
+            .yield_to_child("child", ())
+            .build(),
+        [],
+    );
+
+    // Act
+    let receipt = ledger.execute_transaction(transaction, ExecutionConfig::for_debug_transaction());
+
+    // Assert
+    receipt.expect_commit_success();
+    let breakdown = receipt
+        .execution_cost_breakdown_by_instruction()
+        .expect("Debug information is not available when it should.");
+    // The yields do no work of their own, and the intent switches they cause are not attributed
+    // to either intent, so only the instructions doing work are listed.
+    assert_eq!(
+        breakdown
+            .iter()
+            .map(|cost| (cost.intent_index, cost.instruction_index))
+            .collect::<Vec<_>>(),
+        vec![(0, 0), (1, 0), (1, 1)]
+    );
+    let instruction_cost_units: u32 = breakdown.iter().map(|cost| cost.cost_units).sum();
+    assert!(instruction_cost_units < receipt.fee_summary.total_execution_cost_units_consumed);
+}
+
 #[test]
 fn assert_flamegraph_of_faucet_lock_fee_method() {
     run_faucet_lock_fee(AlignerExecutionMode::Assert);
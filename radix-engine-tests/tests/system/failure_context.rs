@@ -0,0 +1,117 @@
+use scrypto_test::prelude::*;
+
+fn call_path_of(receipt: &TransactionReceipt) -> Vec<String> {
+    receipt
+        .failure_context
+        .as_ref()
+        .expect("Failure context should be recorded")
+        .call_path
+        .iter()
+        .map(|actor| match actor {
+            Actor::Method(MethodActor {
+                ident, object_info, ..
+            }) => format!(
+                "{}::{}",
+                object_info.blueprint_info.blueprint_id.blueprint_name, ident
+            ),
+            Actor::Function(FunctionActor {
+                blueprint_id,
+                ident,
+                ..
+            }) => format!("{}::{}", blueprint_id.blueprint_name, ident),
+            Actor::BlueprintHook(BlueprintHookActor {
+                blueprint_id, hook, ..
+            }) => format!("{}::{:?}", blueprint_id.blueprint_name, hook),
+            Actor::Root => "Root".to_string(),
+        })
+        .collect()
+}
+
+#[test]
+fn failure_context_locates_error_in_nested_call() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .get_free_xrd_from_faucet()
+        .call_method(FAUCET, "lock_fee", manifest_args!(dec!("1000000000000")))
+        .build();
+
+    // Act
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+
+    // Assert
+    receipt.expect_commit_failure();
+    let failure_context = receipt.failure_context.as_ref().unwrap();
+    assert_eq!(failure_context.intent_index, 0);
+    assert_eq!(failure_context.instruction_index, Some(2));
+    assert!(matches!(
+        &failure_context.instruction,
+        Some(InstructionV2::CallMethod(CallMethod { method_name, .. })) if method_name == "lock_fee"
+    ));
+    assert_eq!(
+        call_path_of(&receipt),
+        vec![
+            "TransactionProcessor::run",
+            "Faucet::lock_fee",
+            "FungibleVault::lock_fee"
+        ]
+    );
+}
+
+#[test]
+fn failure_context_locates_error_in_subintent() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (public_key, _, account) = ledger.new_allocated_account();
+    let mut builder = TestTransaction::new_v2_builder(ledger.next_transaction_nonce());
+    let child = builder.add_subintent(
+        ManifestBuilder::new_subintent_v2()
+            .assert_worktop_contains(RORK, 1)
+            .yield_to_parent(())
+            .build(),
+        [],
+    );
+    let transaction = builder.finish_with_root_intent(
+        ManifestBuilder::new_v2()
+            .use_child("child", child)
+            .lock_standard_test_fee(account)
+            .yield_to_child("child", ())
+            .build(),
+        [public_key.signature_proof()],
+    );
+
+    // Act
+    let receipt = ledger.execute_test_transaction(transaction);
+
+    // Assert
+    receipt.expect_commit_failure();
+    let failure_context = receipt.failure_context.as_ref().unwrap();
+    assert_eq!(failure_context.intent_index, 1);
+    assert_eq!(failure_context.instruction_index, Some(0));
+    assert!(matches!(
+        failure_context.instruction,
+        Some(InstructionV2::AssertWorktopContains(..))
+    ));
+    assert_eq!(
+        call_path_of(&receipt),
+        vec![
+            "TransactionProcessor::run",
+            "Worktop::Worktop_assert_contains_amount"
+        ]
+    );
+}
+
+#[test]
+fn successful_transaction_has_no_failure_context() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let manifest = ManifestBuilder::new().lock_fee_from_faucet().build();
+
+    // Act
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+
+    // Assert
+    receipt.expect_commit_success();
+    assert!(receipt.failure_context.is_none());
+}
@@ -3,7 +3,7 @@ use radix_engine::blueprints::resource::WorktopError;
 use radix_engine::errors::RuntimeError;
 use radix_engine::errors::{ApplicationError, CallFrameError, KernelError};
 use radix_engine::kernel::call_frame::OpenSubstateError;
-use radix_engine::transaction::{FeeLocks, TransactionReceipt};
+use radix_engine::transaction::{FeeLocks, LockedFee, TransactionReceipt};
 use radix_engine_interface::types::FromPublicKey;
 use radix_engine_tests::common::*;
 use radix_transactions::prelude::PreviewFlags;
@@ -492,6 +492,100 @@ fn test_contingent_fee_accounting_failure() {
     assert_eq!(account2_new_balance, account2_balance);
 }
 
+#[test]
+fn test_multiple_fee_vaults_are_used_in_reverse_order_of_locking() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (public_key1, _, account1) = ledger.new_allocated_account();
+    let (public_key2, _, account2) = ledger.new_allocated_account();
+    let (public_key3, _, account3) = ledger.new_allocated_account();
+    let vault1 = ledger.get_component_vaults(account1, RORK)[0];
+    let vault2 = ledger.get_component_vaults(account2, RORK)[0];
+    let vault3 = ledger.get_component_vaults(account3, RORK)[0];
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee(account1, 500)
+        .lock_fee(account2, dec!("0.01"))
+        .lock_fee(account3, dec!("0.01"))
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![
+            NonFungibleGlobalId::from_public_key(&public_key1),
+            NonFungibleGlobalId::from_public_key(&public_key2),
+            NonFungibleGlobalId::from_public_key(&public_key3),
+        ],
+    );
+
+    // Assert
+    let commit = receipt.expect_commit(true);
+    let total_cost = receipt.fee_summary.total_cost();
+    assert_eq!(
+        commit.fee_source.paying_vaults,
+        indexmap!(
+            vault3 => dec!("0.01"),
+            vault2 => dec!("0.01"),
+            vault1 => total_cost.checked_sub(dec!("0.02")).unwrap(),
+        )
+    );
+}
+
+#[test]
+fn test_fees_locked_mid_manifest_are_reported_in_order_of_locking() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (public_key1, _, account1) = ledger.new_allocated_account();
+    let (public_key2, _, account2) = ledger.new_allocated_account();
+    let vault1 = ledger.get_component_vaults(account1, RORK)[0];
+    let vault2 = ledger.get_component_vaults(account2, RORK)[0];
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee(account1, 500)
+        .get_free_xrd_from_faucet()
+        .try_deposit_entire_worktop_or_abort(account1, None)
+        .lock_contingent_fee(account2, dec!("0.01"))
+        .lock_fee(account2, dec!("0.02"))
+        .assert_worktop_contains(RORK, 1)
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![
+            NonFungibleGlobalId::from_public_key(&public_key1),
+            NonFungibleGlobalId::from_public_key(&public_key2),
+        ],
+    );
+
+    // Assert
+    let commit = receipt.expect_commit(false);
+    let total_cost = receipt.fee_summary.total_cost();
+    assert_eq!(
+        commit.locked_fees,
+        vec![
+            LockedFee {
+                vault_id: vault1,
+                locked: dec!(500),
+                contingent: false,
+                paid: total_cost.checked_sub(dec!("0.02")).unwrap(),
+            },
+            // Contingent fees are refunded in full on failure
+            LockedFee {
+                vault_id: vault2,
+                locked: dec!("0.01"),
+                contingent: true,
+                paid: Decimal::ZERO,
+            },
+            LockedFee {
+                vault_id: vault2,
+                locked: dec!("0.02"),
+                contingent: false,
+                paid: dec!("0.02"),
+            },
+        ]
+    );
+}
+
 #[test]
 fn locked_fees_are_correct_in_execution_trace() {
     // Arrange
@@ -529,3 +529,184 @@ fn nested_kv_stores_works() {
     // Assert
     receipt.expect_commit_success();
 }
+
+#[test]
+fn component_state_diff_contains_inserted_kv_store_entry() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let package_address = ledger.publish_package_simple(PackageLoader::get("kv_store"));
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(package_address, "Basic", "new", manifest_args!())
+        .build();
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+    let component = receipt.expect_commit_success().new_component_addresses()[0];
+    let before = ledger.component_state_snapshot(component);
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(
+            component,
+            "insert",
+            manifest_args!("key".to_string(), "value".to_string()),
+        )
+        .build();
+    ledger
+        .execute_manifest(manifest, vec![])
+        .expect_commit_success();
+    let after = ledger.component_state_snapshot(component);
+
+    // Assert
+    let kv_store_id = *before.kv_stores.keys().next().unwrap();
+    assert_eq!(
+        before.diff(&after),
+        vec![ComponentStateChange::KeyValueEntry {
+            kv_store_id,
+            key: scrypto_decode(&scrypto_encode(&"key".to_string()).unwrap()).unwrap(),
+            old: None,
+            new: Some(scrypto_decode(&scrypto_encode(&"value".to_string()).unwrap()).unwrap()),
+        }]
+    );
+    assert!(after.diff(&after).is_empty());
+}
+
+#[test]
+fn component_state_snapshot_contains_vaults_in_kv_stores() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let package_address = ledger.publish_package_simple(PackageLoader::get("kv_store"));
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(package_address, "KVVault", "new", manifest_args!())
+        .build();
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+    let component = receipt.expect_commit_success().new_component_addresses()[0];
+    let snapshot = ledger.component_state_snapshot(component);
+
+    // Assert
+    assert_eq!(snapshot.kv_stores.len(), 1);
+    assert_eq!(
+        snapshot
+            .vault_balances
+            .values()
+            .cloned()
+            .collect::<Vec<_>>(),
+        vec![dec!(1)]
+    );
+}
+
+#[test]
+fn keys_of_stored_map_can_be_scanned_in_pages() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let package_address = ledger.publish_package_simple(PackageLoader::get("kv_store"));
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(package_address, "Basic", "new", manifest_args!())
+        .build();
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+    let component = receipt.expect_commit_success().new_component_addresses()[0];
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(
+            component,
+            "insert",
+            manifest_args!("a".to_string(), "1".to_string()),
+        )
+        .call_method(
+            component,
+            "insert",
+            manifest_args!("b".to_string(), "2".to_string()),
+        )
+        .call_method(
+            component,
+            "insert",
+            manifest_args!("c".to_string(), "3".to_string()),
+        )
+        .build();
+    ledger
+        .execute_manifest(manifest, vec![])
+        .expect_commit_success();
+
+    // Act
+    let mut keys = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_method(component, "keys", manifest_args!(cursor, 1u32))
+            .build();
+        let receipt = ledger.execute_manifest(manifest, vec![]);
+        let (page, next_cursor): (Vec<String>, Option<String>) =
+            receipt.expect_commit_success().output(1);
+        assert!(page.len() <= 1);
+        keys.extend(page);
+        cursor = next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+
+    // Assert
+    keys.sort();
+    assert_eq!(
+        keys,
+        vec!["a".to_string(), "b".to_string(), "c".to_string()]
+    );
+}
+
+#[test]
+fn removed_entries_are_not_returned_when_scanning_keys() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let package_address = ledger.publish_package_simple(PackageLoader::get("kv_store"));
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(package_address, "Basic", "new", manifest_args!())
+        .build();
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+    let component = receipt.expect_commit_success().new_component_addresses()[0];
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(
+            component,
+            "insert",
+            manifest_args!("a".to_string(), "1".to_string()),
+        )
+        .call_method(
+            component,
+            "insert",
+            manifest_args!("b".to_string(), "2".to_string()),
+        )
+        .call_method(
+            component,
+            "insert",
+            manifest_args!("c".to_string(), "3".to_string()),
+        )
+        .build();
+    ledger
+        .execute_manifest(manifest, vec![])
+        .expect_commit_success();
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(component, "remove", manifest_args!("a".to_string()))
+        .call_method(component, "remove", manifest_args!("b".to_string()))
+        .call_method(component, "keys", manifest_args!(None::<String>, 3u32))
+        .call_method(component, "keys", manifest_args!(None::<String>, 10u32))
+        .build();
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let result = receipt.expect_commit_success();
+    let (full_page, full_page_cursor): (Vec<String>, Option<String>) = result.output(3);
+    let (all_keys, next_cursor): (Vec<String>, Option<String>) = result.output(4);
+    assert_eq!(full_page, vec!["c".to_string()]);
+    assert!(full_page_cursor.is_some());
+    assert_eq!(all_keys, vec!["c".to_string()]);
+    assert_eq!(next_cursor, None);
+}
@@ -0,0 +1,98 @@
+use radix_engine::system::system_modules::metrics::*;
+use scrypto_test::prelude::*;
+use std::sync::mpsc;
+
+fn execute_with_metrics(
+    ledger: &mut DefaultLedgerSimulator,
+    manifest: TransactionManifestV1,
+) -> (TransactionReceipt, Vec<ExecutionMetrics>) {
+    let (sender, receiver) = mpsc::sync_channel(16);
+    let receipt = ledger.execute_transaction_no_commit(
+        TestTransaction::new_v1_from_nonce(manifest, 1, btreeset!()),
+        ExecutionConfig::for_test_transaction().with_metrics_sink(Arc::new(
+            move |metrics: &ExecutionMetrics| {
+                sender.send(metrics.clone()).unwrap();
+            },
+        )),
+    );
+    (receipt, receiver.try_iter().collect())
+}
+
+#[test]
+fn metrics_are_pushed_once_per_transaction() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (_, _, account) = ledger.new_allocated_account();
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .get_free_xrd_from_faucet()
+        .try_deposit_entire_worktop_or_abort(account, None)
+        .build();
+
+    // Act
+    let (receipt, metrics) = execute_with_metrics(&mut ledger, manifest);
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(metrics.len(), 1);
+    let metrics = &metrics[0];
+    assert!(metrics.invocations > 0);
+    assert!(metrics.lock_acquisitions > 0);
+    assert!(metrics.substate_reads > 0);
+    assert!(metrics.substate_writes > 0);
+    assert!(metrics.node_creations > 0);
+}
+
+#[test]
+fn metrics_are_pushed_for_failed_transaction() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .assert_worktop_contains(RORK, 1)
+        .build();
+
+    // Act
+    let (receipt, metrics) = execute_with_metrics(&mut ledger, manifest);
+
+    // Assert
+    receipt.expect_commit_failure();
+    assert_eq!(metrics.len(), 1);
+    assert!(metrics[0].invocations > 0);
+}
+
+#[test]
+fn metrics_count_wasm_instantiations() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let package_address = ledger.publish_package_simple(PackageLoader::get("logger"));
+    let (_, baseline_metrics) = execute_with_metrics(
+        &mut ledger,
+        ManifestBuilder::new().lock_fee_from_faucet().build(),
+    );
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(
+            package_address,
+            "Logger",
+            "emit_log",
+            manifest_args!("Hello".to_owned()),
+        )
+        .call_function(
+            package_address,
+            "Logger",
+            "emit_log",
+            manifest_args!("World".to_owned()),
+        )
+        .build();
+
+    // Act
+    let (receipt, metrics) = execute_with_metrics(&mut ledger, manifest);
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(
+        metrics[0].wasm_instantiations,
+        baseline_metrics[0].wasm_instantiations + 2
+    );
+}
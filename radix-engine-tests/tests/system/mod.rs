@@ -17,17 +17,21 @@ mod auth_zone;
 mod balance_changes;
 mod bootstrap;
 mod bucket;
+mod canonical_receipt;
 mod component;
 mod consensus_manager;
 mod core;
 mod crypto_utils;
 mod data_validation;
+mod debugger;
 mod deep_sbor;
 mod epoch;
 mod error_injection;
 mod events;
+mod execution_audit;
 mod execution_cost;
 mod external_bridge;
+mod failure_context;
 mod faucet;
 mod fee;
 mod fee_reserve_states;
@@ -43,6 +47,7 @@ mod metadata_component;
 mod metadata_identity;
 mod metadata_package;
 mod metadata_validator;
+mod metrics;
 mod module;
 mod nullification;
 mod package;
@@ -1,6 +1,6 @@
 use radix_engine::{
     errors::{RuntimeError, SystemModuleError, VmError},
-    system::system_modules::limits::TransactionLimitsError,
+    system::system_modules::limits::{TransactionLimitsError, TransactionQuotas},
     transaction::{CostingParameters, ExecutionConfig},
     vm::wasm::WasmRuntimeError,
 };
@@ -47,17 +47,15 @@ fn test_read_non_existent_entries_from_kv_store_exceeding_limit() {
     let transaction = TestTransaction::new_v1_from_nonce(manifest, 10, btreeset!());
 
     let execution_config = {
-        let mut execution_config = ExecutionConfig::for_test_transaction();
         let fee_config =
             CostingParameters::babylon_genesis().with_execution_cost_unit_limit(1_000_000_000);
         let mut limit_parameters = LimitParameters::babylon_genesis();
         limit_parameters.max_track_substate_total_bytes = code_len * 2 + definition_len + 10 * 1024;
-        execution_config.system_overrides = Some(SystemOverrides {
-            limit_parameters: Some(limit_parameters),
-            costing_parameters: Some(fee_config),
-            ..Default::default()
-        });
-        execution_config
+        ExecutionConfig::for_test_transaction().update_system_overrides(|overrides| {
+            overrides
+                .set_limit_parameters(Some(limit_parameters))
+                .set_costing_parameters(Some(fee_config))
+        })
     };
 
     let receipt = ledger.execute_transaction(transaction, execution_config);
@@ -112,18 +110,15 @@ fn test_write_entries_to_kv_store_exceeding_limit() {
     let transaction =
         TestTransaction::new_v1_from_nonce(manifest, 10, btreeset!()).into_executable_unwrap();
     let execution_config = {
-        let mut execution_config = ExecutionConfig::for_test_transaction();
         let mut limit_parameters = LimitParameters::babylon_genesis();
         limit_parameters.max_track_substate_total_bytes = code_len * 2 + definition_len + 10 * 1024;
         let fee_config =
             CostingParameters::babylon_genesis().with_execution_cost_unit_limit(1_000_000_000);
-        execution_config.system_overrides = Some(SystemOverrides {
-            limit_parameters: Some(limit_parameters),
-            costing_parameters: Some(fee_config),
-            ..Default::default()
-        });
-
-        execution_config
+        ExecutionConfig::for_test_transaction().update_system_overrides(|overrides| {
+            overrides
+                .set_limit_parameters(Some(limit_parameters))
+                .set_costing_parameters(Some(fee_config))
+        })
     };
 
     let receipt = ledger.execute_transaction(transaction, execution_config);
@@ -162,17 +157,15 @@ fn test_write_entries_to_heap_kv_store_exceeding_limit() {
     let transaction = TestTransaction::new_v1_from_nonce(manifest, 10, btreeset!());
 
     let execution_config = {
-        let mut execution_config = ExecutionConfig::for_test_transaction();
         let mut limit_parameters = LimitParameters::babylon_genesis();
         limit_parameters.max_heap_substate_total_bytes = 1024 * 1024;
         let fee_config =
             CostingParameters::babylon_genesis().with_execution_cost_unit_limit(1_000_000_000);
-        execution_config.system_overrides = Some(SystemOverrides {
-            limit_parameters: Some(limit_parameters),
-            costing_parameters: Some(fee_config),
-            ..Default::default()
-        });
-        execution_config
+        ExecutionConfig::for_test_transaction().update_system_overrides(|overrides| {
+            overrides
+                .set_limit_parameters(Some(limit_parameters))
+                .set_costing_parameters(Some(fee_config))
+        })
     };
 
     let receipt = ledger.execute_transaction(transaction, execution_config);
@@ -402,3 +395,92 @@ fn test_allocating_buffers_exceeding_limit() {
         )
     });
 }
+
+fn transfer_transaction(ledger: &mut DefaultLedgerSimulator) -> TestTransaction {
+    let (public_key, _, account) = ledger.new_allocated_account();
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(account, RORK, dec!(1))
+        .try_deposit_entire_worktop_or_abort(account, None)
+        .build();
+    TestTransaction::new_v1_from_nonce(
+        manifest,
+        10,
+        btreeset!(NonFungibleGlobalId::from_public_key(&public_key)),
+    )
+}
+
+fn execute_transfer_with_transaction_quotas(quotas: TransactionQuotas) -> TransactionReceipt {
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let transaction = transfer_transaction(&mut ledger);
+
+    ledger.execute_transaction_no_commit(
+        transaction,
+        ExecutionConfig::for_preview_with_transaction_quotas(
+            NetworkDefinition::simulator(),
+            quotas,
+        ),
+    )
+}
+
+#[test]
+#[should_panic(expected = "must not be set for committed transactions")]
+fn transaction_quotas_cannot_be_used_for_committed_transactions() {
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let transaction = transfer_transaction(&mut ledger);
+
+    ledger.execute_transaction(
+        transaction,
+        ExecutionConfig::for_preview_with_transaction_quotas(
+            NetworkDefinition::simulator(),
+            TransactionQuotas::unlimited(),
+        ),
+    );
+}
+
+#[test]
+fn test_transaction_quotas() {
+    execute_transfer_with_transaction_quotas(TransactionQuotas::unlimited())
+        .expect_commit_success();
+
+    for (quotas, expected_error) in [
+        (
+            TransactionQuotas {
+                max_number_of_syscalls: 10,
+                ..TransactionQuotas::unlimited()
+            },
+            TransactionLimitsError::TooManySyscalls { max: 10 },
+        ),
+        (
+            TransactionQuotas {
+                max_number_of_substate_writes: 1,
+                ..TransactionQuotas::unlimited()
+            },
+            TransactionLimitsError::TooManySubstateWrites { max: 1 },
+        ),
+        (
+            TransactionQuotas {
+                max_number_of_new_nodes: 1,
+                ..TransactionQuotas::unlimited()
+            },
+            TransactionLimitsError::TooManyNewNodes { max: 1 },
+        ),
+    ] {
+        let receipt = execute_transfer_with_transaction_quotas(quotas);
+
+        // Depending on the quota, it's exceeded before or after the system loan is repaid.
+        let error = match &receipt.result {
+            TransactionResult::Reject(RejectResult {
+                reason: RejectionReason::ErrorBeforeLoanAndDeferredCostsRepaid(error),
+            }) => error,
+            _ => receipt.expect_failure(),
+        };
+        assert_eq!(
+            error,
+            &RuntimeError::SystemModuleError(SystemModuleError::TransactionLimitsError(
+                expected_error
+            ))
+        );
+    }
+}
@@ -63,6 +63,44 @@ fn test_generate_ruid() {
     assert_ne!(ruid1, ruid2);
 }
 
+#[test]
+fn test_random() {
+    // Arrange
+    let execute_random_transaction = || {
+        let mut ledger = LedgerSimulatorBuilder::new().build();
+        let package_address =
+            ledger.publish_package_simple(PackageLoader::get("transaction_runtime"));
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_function(
+                package_address,
+                "TransactionRuntimeTest",
+                "random",
+                manifest_args!(b"dice".to_vec()),
+            )
+            .call_function(
+                package_address,
+                "TransactionRuntimeTest",
+                "random",
+                manifest_args!(b"dice".to_vec()),
+            )
+            .build();
+        ledger.execute_manifest(manifest, vec![])
+    };
+
+    // Act
+    let receipt = execute_random_transaction();
+    let replayed_receipt = execute_random_transaction();
+
+    // Assert
+    let random1: [u8; 32] = receipt.expect_commit_success().output(1);
+    let random2: [u8; 32] = receipt.expect_commit_success().output(2);
+    assert_ne!(random1, random2);
+    let replayed_random1: [u8; 32] = replayed_receipt.expect_commit_success().output(1);
+    let replayed_random2: [u8; 32] = replayed_receipt.expect_commit_success().output(2);
+    assert_eq!((random1, random2), (replayed_random1, replayed_random2));
+}
+
 #[test]
 fn test_instance_of_and_blueprint_id() {
     // Arrange
@@ -102,6 +102,43 @@ fn cannot_withdraw_from_frozen_fungible_vault() {
     });
 }
 
+#[test]
+fn vault_frozen_with_multiple_flags_stays_frozen_for_remaining_flags() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (key, _priv, account) = ledger.new_account(true);
+    let token_address = ledger.create_freezeable_token(account);
+    let vaults = ledger.get_component_vaults(account, token_address);
+    let vault_address = InternalAddress::new_or_panic(vaults[0].into());
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .freeze_vault(
+            vault_address,
+            VaultFreezeFlags::WITHDRAW | VaultFreezeFlags::DEPOSIT,
+        )
+        .unfreeze_vault(vault_address, VaultFreezeFlags::WITHDRAW)
+        .build();
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+    receipt.expect_commit_success();
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(account, token_address, 1)
+        .deposit_entire_worktop(account)
+        .build();
+    let receipt =
+        ledger.execute_manifest(manifest, vec![NonFungibleGlobalId::from_public_key(&key)]);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::VaultError(VaultError::VaultIsFrozen))
+        )
+    });
+}
+
 #[test]
 fn can_recall_from_frozen_fungible_vault() {
     // Arrange
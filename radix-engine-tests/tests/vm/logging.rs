@@ -65,6 +65,29 @@ fn test_emit_log() {
     }
 }
 
+#[test]
+fn test_emit_log_with_fields() {
+    // Arrange
+    let function_name = "emit_log_with_fields";
+    let message = "Hello";
+
+    // Act
+    let receipt = call(function_name, message);
+
+    // Assert
+    {
+        receipt.expect_commit_success();
+
+        let logs = receipt.expect_commit(true).application_logs.clone();
+        let expected_logs = vec![(
+            Level::Info,
+            "Received message length=5 message=\"Hello\"".to_owned(),
+        )];
+
+        assert_eq!(expected_logs, logs)
+    }
+}
+
 #[test]
 fn test_rust_panic() {
     // Arrange
@@ -9,9 +9,12 @@ use radix_engine::system::system::*;
 use radix_engine::system::system_callback::*;
 use radix_engine::system::system_modules::auth::AuthModule;
 use radix_engine::system::system_modules::costing::*;
+use radix_engine::system::system_modules::debugger::DebuggerModule;
+use radix_engine::system::system_modules::execution_audit::ExecutionAuditModule;
 use radix_engine::system::system_modules::execution_trace::ExecutionTraceModule;
 use radix_engine::system::system_modules::kernel_trace::KernelTraceModule;
 use radix_engine::system::system_modules::limits::LimitsModule;
+use radix_engine::system::system_modules::metrics::MetricsModule;
 use radix_engine::system::system_modules::transaction_runtime::TransactionRuntimeModule;
 use radix_engine::system::system_modules::*;
 use radix_engine::track::*;
@@ -80,6 +83,7 @@ fn panics_can_be_caught_in_the_native_vm_and_converted_into_results() {
             LimitsModule::babylon_genesis(),
             CostingModule {
                 current_depth: 0,
+                current_instruction: None,
                 fee_reserve: SystemLoanFeeReserve::default(),
                 fee_table: FeeTable::latest(),
                 tx_payload_len: 0,
@@ -90,6 +94,9 @@ fn panics_can_be_caught_in_the_native_vm_and_converted_into_results() {
                 on_apply_cost: Default::default(),
             },
             ExecutionTraceModule::new(MAX_EXECUTION_TRACE_DEPTH),
+            ExecutionAuditModule::default(),
+            DebuggerModule::default(),
+            MetricsModule::default(),
         ),
         SystemFinalization::no_nullifications(),
     );
@@ -148,6 +155,7 @@ fn any_panics_can_be_caught_in_the_native_vm_and_converted_into_results() {
             LimitsModule::babylon_genesis(),
             CostingModule {
                 current_depth: 0,
+                current_instruction: None,
                 fee_reserve: SystemLoanFeeReserve::default(),
                 fee_table: FeeTable::latest(),
                 tx_payload_len: 0,
@@ -158,6 +166,9 @@ fn any_panics_can_be_caught_in_the_native_vm_and_converted_into_results() {
                 on_apply_cost: Default::default(),
             },
             ExecutionTraceModule::new(MAX_EXECUTION_TRACE_DEPTH),
+            ExecutionAuditModule::default(),
+            DebuggerModule::default(),
+            MetricsModule::default(),
         ),
         SystemFinalization::no_nullifications(),
     );
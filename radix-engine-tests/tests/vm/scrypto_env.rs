@@ -212,3 +212,54 @@ fn should_be_able_to_bech32_encode_address() {
     let result = receipt.expect_commit_success();
     let _bech32_encoded: String = result.output(1);
 }
+
+#[test]
+fn should_be_able_to_get_network() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let package_address = ledger.publish_package_simple(PackageLoader::get("scrypto_env"));
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(
+            package_address,
+            "ScryptoEnvTest",
+            "network",
+            manifest_args!(),
+        )
+        .build();
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let result = receipt.expect_commit_success();
+    let network: String = result.output(1);
+    assert_eq!(network, NetworkDefinition::simulator().hrp_suffix);
+}
+
+#[test]
+fn should_be_able_to_get_current_time() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let package_address = ledger.publish_package_simple(PackageLoader::get("scrypto_env"));
+    ledger
+        .advance_to_round_at_timestamp(Round::of(1), 1_700_000_013_337)
+        .expect_commit_success();
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(
+            package_address,
+            "ScryptoEnvTest",
+            "current_time",
+            manifest_args!(TimePrecision::Minute),
+        )
+        .build();
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let result = receipt.expect_commit_success();
+    let current_time: Instant = result.output(1);
+    assert_eq!(current_time, ledger.get_current_time(TimePrecision::Minute));
+}
@@ -145,6 +145,8 @@ pub enum PackageError {
     RoyaltyAmountIsNegative(RoyaltyAmount),
 
     ReservedRoleKeyIsNotDefined(String),
+
+    FeatureNotYetSupported(String),
 }
 
 impl From<InvalidNameError> for PackageError {
@@ -572,6 +574,30 @@ fn validate_auth(
     Ok(())
 }
 
+fn validate_reserved_features(
+    definition: &PackageDefinition,
+    system_version: SystemVersion,
+) -> Result<(), PackageError> {
+    // The non-reentrant feature is only honoured once the reentrancy guard is enabled,
+    // so it can't be declared before then.
+    if system_version.reject_reentrancy_into_non_reentrant_blueprints() {
+        return Ok(());
+    }
+
+    for (_, bp_init) in definition.blueprints.iter() {
+        if bp_init
+            .feature_set
+            .contains(BLUEPRINT_NON_REENTRANT_FEATURE)
+        {
+            return Err(PackageError::FeatureNotYetSupported(
+                BLUEPRINT_NON_REENTRANT_FEATURE.to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 fn validate_names(definition: &PackageDefinition) -> Result<(), PackageError> {
     for (bp_name, bp_init) in definition.blueprints.iter() {
         check_name(bp_name)?;
@@ -952,6 +978,7 @@ impl PackageNativePackage {
         export_name: &str,
         input: &IndexedScryptoValue,
         version: PackageV1MinorVersion,
+        system_version: SystemVersion,
         api: &mut Y,
         vm_api: &V,
     ) -> Result<IndexedScryptoValue, RuntimeError> {
@@ -965,6 +992,9 @@ impl PackageNativePackage {
                 let input: PackagePublishNativeInput = input.as_typed().map_err(|e| {
                     RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
                 })?;
+                validate_reserved_features(&input.definition, system_version).map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::PackageError(e))
+                })?;
 
                 let rtn = Self::publish_native(
                     input.package_address,
@@ -982,6 +1012,9 @@ impl PackageNativePackage {
                 let input: PackagePublishWasmInput = input.as_typed().map_err(|e| {
                     RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
                 })?;
+                validate_reserved_features(&input.definition, system_version).map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::PackageError(e))
+                })?;
 
                 let rtn = Self::publish_wasm(
                     input.code,
@@ -998,6 +1031,9 @@ impl PackageNativePackage {
                 let input: PackagePublishWasmAdvancedInput = input.as_typed().map_err(|e| {
                     RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
                 })?;
+                validate_reserved_features(&input.definition, system_version).map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::PackageError(e))
+                })?;
 
                 let rtn = Self::publish_wasm_advanced(
                     input.package_address,
@@ -1592,7 +1628,15 @@ impl PackageAuthNativeBlueprint {
             FunctionAuth::AllowAll => Ok(ResolvedPermission::AllowAll),
             FunctionAuth::RootOnly => {
                 let is_root = api.kernel_get_system_state().current_call_frame.is_root();
-                if is_root {
+                // A nested transaction processor runs with its own worktop and auth zone, so it
+                // can't use the resources or proofs of its caller
+                let is_allowed_nested_transaction_processor = receiver
+                    .eq(TRANSACTION_PROCESSOR_PACKAGE.as_node_id())
+                    && api
+                        .kernel_get_system()
+                        .modules
+                        .is_nested_transaction_processor_allowed();
+                if is_root || is_allowed_nested_transaction_processor {
                     Ok(ResolvedPermission::AllowAll)
                 } else {
                     Ok(ResolvedPermission::AccessRule(AccessRule::DenyAll))
@@ -16,11 +16,13 @@ use crate::blueprints::transaction_processor::TransactionProcessorError;
 use crate::internal_prelude::*;
 use crate::kernel::call_frame::{
     CallFrameDrainSubstatesError, CallFrameRemoveSubstateError, CallFrameScanKeysError,
-    CallFrameScanSortedSubstatesError, CallFrameSetSubstateError, CloseSubstateError,
-    CreateFrameError, CreateNodeError, DropNodeError, MarkTransientSubstateError,
-    MovePartitionError, OpenSubstateError, PassMessageError, PinNodeError, ReadSubstateError,
-    WriteSubstateError,
+    CallFrameScanSortedSubstatesError, CallFrameSetSubstateError, CallFrameSnapshotError,
+    CloseSubstateError, CreateFrameError, CreateNodeError, DropNodeError,
+    MarkTransientSubstateError, MovePartitionError, OpenSubstateError, PassMessageError,
+    PinNodeError, ReadSubstateError, WriteSubstateError,
 };
+use crate::kernel::heap::HeapSnapshotError;
+use crate::kernel::substate_io::StoreCheckpointError;
 use crate::object_modules::metadata::MetadataError;
 use crate::object_modules::role_assignment::RoleAssignmentError;
 use crate::object_modules::royalty::ComponentRoyaltyError;
@@ -100,9 +102,10 @@ lazy_static::lazy_static! {
     /// See [`HISTORIC_RUNTIME_ERROR_SCHEMAS`] for more information.
     ///
     /// Although the RejectionReason isn't used on the node, we do a similar thing anyway.
-    static ref HISTORIC_REJECTION_REASON_SCHEMAS: [ScryptoSingleTypeSchema; 1] = {
+    static ref HISTORIC_REJECTION_REASON_SCHEMAS: [ScryptoSingleTypeSchema; 2] = {
         [
             ScryptoSingleTypeSchema::from(include_bytes!("rejection_reason_cuttlefish_schema.bin")),
+            ScryptoSingleTypeSchema::from(include_bytes!("rejection_reason_dugong_schema.bin")),
         ]
     };
 }
@@ -283,10 +286,11 @@ lazy_static::lazy_static! {
     /// in a node version. This is to ensure that we can always decode old errors.
     ///
     /// New schemas can be generated with `#[sbor_assert(fixed("FILE:xxx"))]` generator above.
-    static ref HISTORIC_RUNTIME_ERROR_SCHEMAS: [ScryptoSingleTypeSchema; 2] = {
+    static ref HISTORIC_RUNTIME_ERROR_SCHEMAS: [ScryptoSingleTypeSchema; 3] = {
         [
             ScryptoSingleTypeSchema::from(include_bytes!("runtime_error_pre_cuttlefish_schema.bin")),
             ScryptoSingleTypeSchema::from(include_bytes!("runtime_error_cuttlefish_schema.bin")),
+            ScryptoSingleTypeSchema::from(include_bytes!("runtime_error_dugong_schema.bin")),
         ]
     };
 }
@@ -355,6 +359,75 @@ impl RuntimeError {
             encoded_error: scrypto_decode(&scrypto_encode(self).unwrap()).unwrap(),
         }
     }
+
+    pub fn code(&self) -> RuntimeErrorCode {
+        self.create_persistable().code()
+    }
+
+    pub fn class(&self) -> RuntimeErrorClass {
+        match self {
+            RuntimeError::ApplicationError(..)
+            | RuntimeError::SystemUpstreamError(..)
+            | RuntimeError::VmError(VmError::Wasm(..)) => RuntimeErrorClass::Application,
+            RuntimeError::SystemModuleError(SystemModuleError::AuthError(..)) => {
+                RuntimeErrorClass::Auth
+            }
+            RuntimeError::SystemModuleError(SystemModuleError::CostingError(..))
+            | RuntimeError::FinalizationCostingError(..) => RuntimeErrorClass::Fees,
+            RuntimeError::SystemModuleError(SystemModuleError::TransactionLimitsError(..)) => {
+                RuntimeErrorClass::Limits
+            }
+            RuntimeError::KernelError(..)
+            | RuntimeError::SystemError(..)
+            | RuntimeError::SystemModuleError(SystemModuleError::EventError(..))
+            | RuntimeError::VmError(VmError::Native(..))
+            | RuntimeError::VmError(VmError::ScryptoVmVersion(..)) => RuntimeErrorClass::System,
+        }
+    }
+
+    pub fn is_application_error(&self) -> bool {
+        self.class() == RuntimeErrorClass::Application
+    }
+}
+
+/// A coarse classification of a [`RuntimeError`], for clients which need to react to failures
+/// without matching on the full error.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, ScryptoSbor)]
+pub enum RuntimeErrorClass {
+    /// The logic of a blueprint failed, such as a panic, a failed assertion or an insufficient
+    /// balance, or it was invoked with invalid arguments.
+    Application,
+    /// An authorization check failed.
+    Auth,
+    /// The fees could not be paid.
+    Fees,
+    /// A limit on the resources used by the transaction was exceeded.
+    Limits,
+    /// The system was used in an invalid way by a blueprint, or the engine itself failed.
+    System,
+}
+
+/// The discriminators of the nested error variants of a runtime error, from the outermost, e.g.
+/// `5.15.0.0` for `ApplicationError(VaultError(ResourceError(InsufficientBalance { .. })))`.
+///
+/// The variants are followed through the error's schema, only into a variant whose single field
+/// is itself an error enum, so the code doesn't depend on the payload of the error. As the
+/// historic runtime error schemas may only be extended, an error has the same code under each
+/// schema it's part of.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, ScryptoSbor)]
+#[sbor(transparent)]
+pub struct RuntimeErrorCode(pub Vec<u8>);
+
+impl fmt::Display for RuntimeErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, discriminator) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ".")?;
+            }
+            write!(f, "{}", discriminator)?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, ScryptoSbor)]
@@ -366,6 +439,52 @@ pub struct PersistableRuntimeError {
     pub encoded_error: ScryptoOwnedRawValue,
 }
 
+impl PersistableRuntimeError {
+    pub fn code(&self) -> RuntimeErrorCode {
+        let mut discriminators = Vec::new();
+        let Some(schema) = HISTORIC_RUNTIME_ERROR_SCHEMAS.get(self.schema_index as usize) else {
+            return RuntimeErrorCode(discriminators);
+        };
+        let mut type_id = schema.type_id;
+        let schema = schema.schema.as_unique_version();
+        let mut value: ScryptoValue =
+            scrypto_decode(&scrypto_encode(&self.encoded_error).unwrap()).unwrap();
+        while let (
+            Some(TypeKind::Enum { variants }),
+            ScryptoValue::Enum {
+                discriminator,
+                fields,
+            },
+        ) = (schema.resolve_type_kind(type_id), value)
+        {
+            discriminators.push(discriminator);
+            match (
+                variants.get(&discriminator).map(Vec::as_slice),
+                fields.len(),
+            ) {
+                (Some([field_type_id]), 1) if is_error_type(schema, *field_type_id) => {
+                    type_id = *field_type_id;
+                    value = fields.into_iter().next().unwrap();
+                }
+                _ => break,
+            }
+        }
+        RuntimeErrorCode(discriminators)
+    }
+}
+
+/// Whether the type is one of the nested error enums of a runtime error, rather than part of the
+/// payload of one of them.
+fn is_error_type(schema: &SchemaV1<ScryptoCustomSchema>, type_id: LocalTypeId) -> bool {
+    matches!(
+        schema.resolve_type_kind(type_id),
+        Some(TypeKind::Enum { .. })
+    ) && schema
+        .resolve_type_metadata(type_id)
+        .and_then(|metadata| metadata.get_name())
+        .is_some_and(|name| name.ends_with("Error"))
+}
+
 /// This is used to render the error message, with a fallback if an invalid schema
 /// is associated with the error.
 ///
@@ -458,6 +577,10 @@ pub enum KernelError {
     OrphanedNodes(Vec<error_models::OwnedNodeId>),
 
     StackError(StackError),
+
+    SnapshotError(KernelSnapshotError),
+
+    StoreCheckpointError(StoreCheckpointError),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
@@ -519,6 +642,13 @@ pub enum StackError {
     InvalidStackId,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub enum KernelSnapshotError {
+    CallFrameSnapshotError(CallFrameSnapshotError),
+    HeapSnapshotError(HeapSnapshotError),
+    EmptyStack,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
 pub enum SystemError {
     NoBlueprintId,
@@ -584,6 +714,7 @@ pub enum SystemError {
 
     CannotLockFeeInChildSubintent(usize),
     IntentError(IntentError),
+    InvalidKeyValueEntrySubstate(DecodeError),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
@@ -621,6 +752,8 @@ pub enum SystemUpstreamError {
 
     OutputDecodeError(DecodeError),
     OutputSchemaNotMatch(String, String),
+
+    ReentrancyDetected { address: GlobalAddress },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
@@ -963,6 +1096,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use radix_engine_interface::blueprints::resource::ResourceError;
 
     #[test]
     fn the_current_runtime_error_schema_is_last_on_historic_list() {
@@ -978,6 +1112,80 @@ mod tests {
         .assert_valid("latest", "current");
     }
 
+    #[test]
+    fn runtime_error_code_is_made_of_nested_discriminators() {
+        let error = RuntimeError::ApplicationError(ApplicationError::VaultError(
+            VaultError::ResourceError(ResourceError::InsufficientBalance {
+                requested: dec!(2),
+                actual: dec!(1),
+            }),
+        ));
+
+        assert_eq!(error.code(), RuntimeErrorCode(vec![5, 15, 0, 0]));
+        assert_eq!(error.code().to_string(), "5.15.0.0");
+        assert_eq!(error.create_persistable().code(), error.code());
+        assert_eq!(error.class(), RuntimeErrorClass::Application);
+        assert!(error.is_application_error());
+    }
+
+    #[test]
+    fn runtime_errors_are_classified() {
+        let error = RuntimeError::SystemModuleError(SystemModuleError::TransactionLimitsError(
+            TransactionLimitsError::TooManyLogs,
+        ));
+        assert_eq!(error.code().to_string(), "2.2.9");
+        assert_eq!(error.class(), RuntimeErrorClass::Limits);
+        assert!(!error.is_application_error());
+
+        let error = RuntimeError::SystemError(SystemError::InvalidLockFlags);
+        assert_eq!(error.class(), RuntimeErrorClass::System);
+        assert!(!error.is_application_error());
+    }
+
+    #[test]
+    fn runtime_error_codes_are_the_same_under_each_historic_schema() {
+        let errors = [
+            (
+                RuntimeError::ApplicationError(ApplicationError::VaultError(
+                    VaultError::ResourceError(ResourceError::InsufficientBalance {
+                        requested: dec!(2),
+                        actual: dec!(1),
+                    }),
+                )),
+                "5.15.0.0",
+            ),
+            (
+                RuntimeError::SystemModuleError(SystemModuleError::TransactionLimitsError(
+                    TransactionLimitsError::TooManyLogs,
+                )),
+                "2.2.9",
+            ),
+            // The payloads aren't part of the code, even if they're enums
+            (
+                RuntimeError::SystemError(SystemError::MissingModule(ModuleId::Metadata)),
+                "1.27",
+            ),
+            (
+                RuntimeError::SystemError(SystemError::MissingModule(ModuleId::Royalty)),
+                "1.27",
+            ),
+            (
+                RuntimeError::ApplicationError(ApplicationError::PanicMessage("Panic".into())),
+                "5.2",
+            ),
+        ];
+
+        for (error, expected_code) in errors {
+            for schema_index in 0..HISTORIC_RUNTIME_ERROR_SCHEMAS.len() as u32 {
+                let persistable = PersistableRuntimeError {
+                    schema_index,
+                    ..error.create_persistable()
+                };
+                assert_eq!(persistable.code().to_string(), expected_code);
+            }
+        }
+    }
+
     #[test]
     fn the_current_runtime_error_schema_has_no_raw_node_ids() {
         let current = generate_single_type_schema::<RuntimeError, ScryptoCustomSchema>();
@@ -1,6 +1,7 @@
 use crate::internal_prelude::*;
 use crate::kernel::kernel_api::DroppedNode;
 use crate::kernel::kernel_callback_api::CallFrameReferences;
+use crate::kernel::node_visitor::{walk_node_substates, NodeSubstatesVisitor};
 use crate::kernel::substate_io::{
     IOAccessHandler, SubstateDevice, SubstateIO, SubstateReadHandler,
 };
@@ -213,7 +214,7 @@ impl SubstateDiff {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ScryptoSbor)]
 pub enum StableReferenceType {
     Global,
     DirectAccess,
@@ -350,6 +351,59 @@ impl<'g, C, L, E, H: CallFrameIOAccessHandler<C, L, E>> IOAccessHandler<E>
     }
 }
 
+/// Processes the substates of a node dropped from the heap, so that the call frame no longer
+/// tracks the nodes they own and reference.
+struct DroppedNodeVisitor<
+    'a,
+    'g,
+    's,
+    C,
+    L,
+    E,
+    S: CommitableSubstateStore,
+    H: CallFrameIOAccessHandler<C, L, E>,
+> {
+    adapter: &'a mut CallFrameToIOAccessAdapter<'g, C, L, E, H>,
+    substate_io: &'a mut SubstateIO<'s, S>,
+}
+
+impl<
+        'a,
+        'g,
+        's,
+        C,
+        L: Clone,
+        E,
+        S: CommitableSubstateStore,
+        H: CallFrameIOAccessHandler<C, L, E>,
+    > NodeSubstatesVisitor for DroppedNodeVisitor<'a, 'g, 's, C, L, E, S, H>
+{
+    type Error = CallbackError<DropNodeError, E>;
+
+    fn visit_substate(
+        &mut self,
+        _partition_number: PartitionNumber,
+        _substate_key: &SubstateKey,
+        value: &IndexedScryptoValue,
+    ) -> Result<(), Self::Error> {
+        let diff = SubstateDiff::from_drop_substate(value);
+        self.adapter
+            .call_frame
+            .process_substate_diff(
+                self.substate_io,
+                self.adapter.handler,
+                SubstateDevice::Heap,
+                &diff,
+            )
+            .map_err(|e| match e {
+                CallbackError::Error(e) => {
+                    CallbackError::Error(DropNodeError::ProcessSubstateError(e))
+                }
+                CallbackError::CallbackError(e) => CallbackError::CallbackError(e),
+            })
+    }
+}
+
 struct CallFrameToIOSubstateReadAdapter<'g, C, L, H: CallFrameSubstateReadHandler<C, L>> {
     handler: &'g mut H,
     call_frame: &'g CallFrame<C, L>,
@@ -403,6 +457,24 @@ pub struct CallFrame<C, L> {
     always_visible_global_nodes: &'static IndexSet<NodeId>,
 }
 
+/// A serializable capture of a call frame which holds no open substates.
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct CallFrameSnapshot<C> {
+    pub stack_id: usize,
+    pub depth: usize,
+    pub call_frame_data: C,
+    pub owned_root_nodes: IndexSet<NodeId>,
+    pub stable_references: BTreeMap<NodeId, StableReferenceType>,
+    pub next_handle: SubstateHandle,
+}
+
+/// Represents an error when capturing a call frame.
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub enum CallFrameSnapshotError {
+    SubstateOpen(SubstateHandle),
+    TransientReferencesHeld,
+}
+
 /// Represents an error when creating a new frame.
 #[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
 pub enum CreateFrameError {
@@ -478,12 +550,41 @@ pub enum OpenSubstateError {
     SubstateFault,
     InvalidDefaultValue,
     ProcessSubstateKeyError(ProcessSubstateKeyError),
-    SubstateLocked(error_models::OwnedNodeId, PartitionNumber, SubstateKey),
+    SubstateLocked(Box<SubstateLockContention>),
     LockUnmodifiedBaseOnHeapNode,
     LockUnmodifiedBaseOnNewSubstate(error_models::OwnedNodeId, PartitionNumber, SubstateKey),
     LockUnmodifiedBaseOnOnUpdatedSubstate(error_models::OwnedNodeId, PartitionNumber, SubstateKey),
 }
 
+/// Represents an error when upgrading a substate lock to a mutable one.
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub enum UpgradeSubstateLockError {
+    HandleNotFound(SubstateHandle),
+    SubstateLocked(error_models::OwnedNodeId, PartitionNumber, SubstateKey),
+}
+
+/// The locks held on a substate which could not be opened.
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct SubstateLockContention {
+    pub node_id: error_models::OwnedNodeId,
+    pub partition_num: PartitionNumber,
+    pub substate_key: SubstateKey,
+    /// Filled in by the kernel, as only it can see the call frames of every stack.
+    pub holders: Vec<SubstateLockHolder>,
+}
+
+/// A lock held on a substate, and the call frame holding it.
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct SubstateLockHolder {
+    pub flags: LockFlags,
+    pub stack_id: usize,
+    pub call_frame_depth: usize,
+    /// The handle of the lock in the holding call frame.
+    pub handle: SubstateHandle,
+    /// The actor of the holding call frame.
+    pub actor: String,
+}
+
 /// Represents an error when reading substates.
 #[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
 pub enum ReadSubstateError {
@@ -596,6 +697,51 @@ impl<C, L: Clone> CallFrame<C, L> {
         call_frame
     }
 
+    pub fn from_snapshot(
+        snapshot: CallFrameSnapshot<C>,
+        always_visible_global_nodes: &'static IndexSet<NodeId>,
+    ) -> Self {
+        Self {
+            stack_id: snapshot.stack_id,
+            depth: snapshot.depth,
+            call_frame_data: snapshot.call_frame_data,
+            stable_references: snapshot.stable_references,
+            transient_references: NonIterMap::new(),
+            owned_root_nodes: snapshot.owned_root_nodes,
+            next_handle: snapshot.next_handle,
+            open_substates: index_map_new(),
+            always_visible_global_nodes,
+        }
+    }
+
+    /// Captures this frame, which is only possible once all its substates have been closed
+    /// and all transient references released, as these are tied to locks held elsewhere.
+    pub fn snapshot(&self) -> Result<CallFrameSnapshot<C>, CallFrameSnapshotError>
+    where
+        C: Clone,
+    {
+        self.ensure_no_open_substates()?;
+        if !self.transient_references.is_empty() {
+            return Err(CallFrameSnapshotError::TransientReferencesHeld);
+        }
+
+        Ok(CallFrameSnapshot {
+            stack_id: self.stack_id,
+            depth: self.depth,
+            call_frame_data: self.call_frame_data.clone(),
+            owned_root_nodes: self.owned_root_nodes.clone(),
+            stable_references: self.stable_references.clone(),
+            next_handle: self.next_handle,
+        })
+    }
+
+    pub fn ensure_no_open_substates(&self) -> Result<(), CallFrameSnapshotError> {
+        match self.open_substates.keys().next() {
+            Some(handle) => Err(CallFrameSnapshotError::SubstateOpen(*handle)),
+            None => Ok(()),
+        }
+    }
+
     pub fn new_child_from_parent<S: CommitableSubstateStore>(
         substate_io: &SubstateIO<S>,
         parent: &mut CallFrame<C, L>,
@@ -687,6 +833,10 @@ impl<C, L: Clone> CallFrame<C, L> {
         self.depth
     }
 
+    pub fn always_visible_global_nodes(&self) -> &'static IndexSet<NodeId> {
+        self.always_visible_global_nodes
+    }
+
     pub fn data(&self) -> &C {
         &self.call_frame_data
     }
@@ -819,25 +969,13 @@ impl<C, L: Clone> CallFrame<C, L> {
                 CallbackError::Error(e) => CallbackError::Error(e),
                 CallbackError::CallbackError(e) => CallbackError::CallbackError(e),
             })?;
-        for (_partition_number, module) in &substates {
-            for (_substate_key, substate_value) in module {
-                let diff = SubstateDiff::from_drop_substate(&substate_value);
-                adapter
-                    .call_frame
-                    .process_substate_diff(
-                        substate_io,
-                        adapter.handler,
-                        SubstateDevice::Heap,
-                        &diff,
-                    )
-                    .map_err(|e| match e {
-                        CallbackError::Error(e) => {
-                            CallbackError::Error(DropNodeError::ProcessSubstateError(e))
-                        }
-                        CallbackError::CallbackError(e) => CallbackError::CallbackError(e),
-                    })?;
-            }
-        }
+        walk_node_substates(
+            &substates,
+            &mut DroppedNodeVisitor {
+                adapter: &mut adapter,
+                substate_io,
+            },
+        )?;
 
         let pinned_to_heap = substate_io.pinned_to_heap.remove(node_id);
 
@@ -1133,6 +1271,44 @@ impl<C, L: Clone> CallFrame<C, L> {
         Ok(())
     }
 
+    /// Upgrades a read lock held by this call frame to a mutable one.
+    ///
+    /// The upgrade fails, rather than waits, if any other lock is held on the substate, including
+    /// one held by this call frame.
+    pub fn upgrade_substate_lock<S: CommitableSubstateStore>(
+        &mut self,
+        substate_io: &mut SubstateIO<S>,
+        lock_handle: SubstateHandle,
+    ) -> Result<(), UpgradeSubstateLockError> {
+        let global_substate_handle = self
+            .open_substates
+            .get(&lock_handle)
+            .ok_or(UpgradeSubstateLockError::HandleNotFound(lock_handle))?
+            .global_substate_handle;
+
+        substate_io
+            .upgrade_substate_lock(global_substate_handle)
+            .map_err(|_| {
+                let (node_id, partition_num, substate_key, _) =
+                    substate_io.substate_locks.get(global_substate_handle);
+                UpgradeSubstateLockError::SubstateLocked(
+                    (*node_id).into(),
+                    *partition_num,
+                    substate_key.clone(),
+                )
+            })
+    }
+
+    /// The handle of a substate lock in this call frame, if it holds the lock.
+    pub fn substate_handle_of(&self, global_substate_handle: u32) -> Option<SubstateHandle> {
+        self.open_substates
+            .iter()
+            .find(|(_, open_substate)| {
+                open_substate.global_substate_handle == global_substate_handle
+            })
+            .map(|(handle, _)| *handle)
+    }
+
     pub fn open_substates(&self) -> Vec<u32> {
         self.open_substates.keys().cloned().into_iter().collect()
     }
@@ -1273,6 +1449,44 @@ impl<C, L: Clone> CallFrame<C, L> {
         Ok(keys)
     }
 
+    pub fn scan_keys_after<K: SubstateKeyContent, S: CommitableSubstateStore, E>(
+        &mut self,
+        substate_io: &mut SubstateIO<S>,
+        node_id: &NodeId,
+        partition_num: PartitionNumber,
+        after: Option<&SubstateKey>,
+        limit: u32,
+        handler: &mut impl CallFrameIOAccessHandler<C, L, E>,
+    ) -> Result<Vec<SubstateKey>, CallbackError<CallFrameScanKeysError, E>> {
+        // Check node visibility
+        let (_ref_origin, device) = self.get_node_ref(node_id).ok_or_else(|| {
+            CallbackError::Error(CallFrameScanKeysError::NodeNotVisible((*node_id).into()))
+        })?;
+
+        let mut adapter = CallFrameToIOAccessAdapter {
+            call_frame: self,
+            handler,
+            phantom: PhantomData,
+        };
+
+        let keys = substate_io.scan_keys_after::<K, E>(
+            device,
+            node_id,
+            partition_num,
+            after,
+            limit,
+            &mut adapter,
+        )?;
+
+        for key in &keys {
+            self.process_output_substate_key(key).map_err(|e| {
+                CallbackError::Error(CallFrameScanKeysError::ProcessSubstateKeyError(e))
+            })?;
+        }
+
+        Ok(keys)
+    }
+
     pub fn drain_substates<'f, K: SubstateKeyContent, S: CommitableSubstateStore, E>(
         &mut self,
         substate_io: &'f mut SubstateIO<S>,
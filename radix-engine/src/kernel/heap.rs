@@ -1,6 +1,9 @@
 use crate::internal_prelude::*;
+use crate::kernel::node_visitor::{walk_node_substates, NodeSubstatesVisitor};
 use crate::track::interface::IOAccess;
 use crate::track::interface::{CallbackError, CanonicalSubstateKey, NodeSubstates};
+use sbor::rust::convert::Infallible;
+use sbor::rust::ops::Bound;
 
 pub struct Heap {
     nodes: NonIterMap<NodeId, NodeSubstates>,
@@ -17,6 +20,52 @@ pub enum HeapRemoveNodeError {
     NodeNotFound(error_models::ReferencedNodeId),
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub enum HeapSnapshotError {
+    NodeNotFound(error_models::OwnedNodeId),
+    UnreachableNodes,
+}
+
+/// A serializable capture of all nodes on the heap, in the order they were reached.
+#[derive(Debug, Clone, PartialEq, Eq, Default, ScryptoSbor)]
+pub struct HeapSnapshot {
+    pub nodes: IndexMap<NodeId, BTreeMap<PartitionNumber, Vec<(SubstateKey, ScryptoValue)>>>,
+}
+
+/// Captures the substates of a node, and the nodes it owns.
+#[derive(Default)]
+struct HeapSnapshotVisitor {
+    partitions: BTreeMap<PartitionNumber, Vec<(SubstateKey, ScryptoValue)>>,
+    children: Vec<NodeId>,
+}
+
+impl NodeSubstatesVisitor for HeapSnapshotVisitor {
+    type Error = Infallible;
+
+    fn visit_partition(&mut self, partition_number: PartitionNumber) -> Result<(), Self::Error> {
+        self.partitions.insert(partition_number, Vec::new());
+        Ok(())
+    }
+
+    fn visit_substate(
+        &mut self,
+        partition_number: PartitionNumber,
+        substate_key: &SubstateKey,
+        value: &IndexedScryptoValue,
+    ) -> Result<(), Self::Error> {
+        self.partitions
+            .get_mut(&partition_number)
+            .expect("Partition is visited before its substates")
+            .push((substate_key.clone(), value.to_scrypto_value()));
+        Ok(())
+    }
+
+    fn visit_owned_node(&mut self, node_id: &NodeId) -> Result<(), Self::Error> {
+        self.children.push(*node_id);
+        Ok(())
+    }
+}
+
 impl Heap {
     pub fn new() -> Self {
         Self {
@@ -28,6 +77,62 @@ impl Heap {
         self.nodes.is_empty()
     }
 
+    pub fn from_snapshot(snapshot: HeapSnapshot) -> Self {
+        let nodes = snapshot
+            .nodes
+            .into_iter()
+            .map(|(node_id, partitions)| {
+                let node_substates = partitions
+                    .into_iter()
+                    .map(|(partition_number, substates)| {
+                        let substates = substates
+                            .into_iter()
+                            .map(|(substate_key, value)| {
+                                (substate_key, IndexedScryptoValue::from_scrypto_value(value))
+                            })
+                            .collect();
+                        (partition_number, substates)
+                    })
+                    .collect();
+                (node_id, node_substates)
+            })
+            .collect();
+
+        Self { nodes }
+    }
+
+    /// Captures the heap by walking the ownership tree from the given root nodes.
+    ///
+    /// As the heap can't be iterated over, the root nodes must account for every node on the
+    /// heap, otherwise the snapshot would silently lose state.
+    pub fn snapshot<'a>(
+        &self,
+        root_nodes: impl IntoIterator<Item = &'a NodeId>,
+    ) -> Result<HeapSnapshot, HeapSnapshotError> {
+        let mut snapshot = HeapSnapshot::default();
+        let mut to_visit: Vec<NodeId> = root_nodes.into_iter().cloned().collect();
+        to_visit.reverse();
+
+        while let Some(node_id) = to_visit.pop() {
+            let node_substates = self
+                .nodes
+                .get(&node_id)
+                .ok_or_else(|| HeapSnapshotError::NodeNotFound(node_id.into()))?;
+
+            let mut visitor = HeapSnapshotVisitor::default();
+            walk_node_substates(node_substates, &mut visitor).unwrap_or_else(|e| match e {});
+            snapshot.nodes.insert(node_id, visitor.partitions);
+
+            to_visit.extend(visitor.children.into_iter().rev());
+        }
+
+        if snapshot.nodes.len() != self.nodes.len() {
+            return Err(HeapSnapshotError::UnreachableNodes);
+        }
+
+        Ok(snapshot)
+    }
+
     pub fn remove_partition<E, F: FnMut(&Heap, IOAccess) -> Result<(), E>>(
         &mut self,
         node_id: &NodeId,
@@ -180,6 +285,31 @@ impl Heap {
         }
     }
 
+    /// Scans the keys of a node's partition, starting after the given key. On an non-existing
+    /// node/partition, this will return an empty vector
+    pub fn scan_keys_after(
+        &self,
+        node_id: &NodeId,
+        partition_num: PartitionNumber,
+        after: Option<&SubstateKey>,
+        count: u32,
+    ) -> Vec<SubstateKey> {
+        let node_substates = self.nodes.get(node_id).and_then(|n| n.get(&partition_num));
+        if let Some(substates) = node_substates {
+            let range = (
+                after.map_or(Bound::Unbounded, Bound::Excluded),
+                Bound::Unbounded,
+            );
+            substates
+                .range::<SubstateKey, _>(range)
+                .map(|(key, _value)| key.clone())
+                .take(count.try_into().unwrap())
+                .collect()
+        } else {
+            vec![]
+        }
+    }
+
     /// Drains the substates from a node's partition. On an non-existing node/partition, this
     /// will return an empty vector
     pub fn drain_substates<E, F: FnMut(&Heap, IOAccess) -> Result<(), E>>(
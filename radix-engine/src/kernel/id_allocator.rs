@@ -2,7 +2,7 @@ use crate::errors::{IdAllocationError, KernelError, RuntimeError};
 use crate::internal_prelude::*;
 
 /// An ID allocator defines how identities are generated.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
 pub struct IdAllocator {
     transaction_hash: Hash,
     next_id: u32,
@@ -1,18 +1,19 @@
-use super::heap::Heap;
+use super::heap::{Heap, HeapSnapshot};
 use super::id_allocator::IdAllocator;
 use crate::errors::*;
 use crate::internal_prelude::*;
 use crate::kernel::call_frame::*;
 use crate::kernel::kernel_api::*;
 use crate::kernel::kernel_callback_api::*;
-use crate::kernel::substate_io::{SubstateDevice, SubstateIO};
+use crate::kernel::substate_io::{StoreCheckpoint, SubstateDevice, SubstateIO};
 use crate::kernel::substate_locks::SubstateLocks;
 use crate::track::interface::*;
-use crate::track::Track;
+use crate::track::{Track, TrackSnapshot};
 use radix_engine_interface::api::field_api::LockFlags;
 use radix_engine_profiling_derive::trace_resources;
 use radix_substate_store_interface::db_key_mapper::SubstateKeyContent;
 use radix_substate_store_interface::interface::SubstateDatabase;
+use sbor::rust::iter;
 use sbor::rust::mem;
 
 macro_rules! as_read_only {
@@ -328,6 +329,149 @@ impl<M: KernelCallbackObject> KernelStacks<M> {
     pub fn previous_frames_mut(&mut self) -> &mut Vec<CallFrame<M::CallFrameData, M::LockData>> {
         &mut self.current_stack_mut().prev_frames
     }
+
+    /// Finds the call frame, in any stack, which holds the given substate lock, along with the
+    /// handle of the lock in that call frame.
+    pub fn frame_holding_substate_lock(
+        &self,
+        global_lock_handle: u32,
+    ) -> Option<(&CallFrame<M::CallFrameData, M::LockData>, SubstateHandle)> {
+        self.stacks
+            .iter()
+            .flat_map(|stack| {
+                stack
+                    .prev_frames
+                    .iter()
+                    .chain(iter::once(&stack.current_frame))
+            })
+            .find_map(|frame| {
+                frame
+                    .substate_handle_of(global_lock_handle)
+                    .map(|handle| (frame, handle))
+            })
+    }
+
+    /// Captures all stacks, provided that no frame holds an open substate
+    pub fn snapshot(&self) -> Result<Vec<KernelStackSnapshot<M::CallFrameData>>, RuntimeError>
+    where
+        M::CallFrameData: Clone,
+    {
+        self.stacks
+            .iter()
+            .map(|stack| {
+                let frames = stack
+                    .prev_frames
+                    .iter()
+                    .chain(core::iter::once(&stack.current_frame))
+                    .map(|frame| frame.snapshot())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| {
+                        RuntimeError::KernelError(KernelError::SnapshotError(
+                            KernelSnapshotError::CallFrameSnapshotError(e),
+                        ))
+                    })?;
+                Ok(KernelStackSnapshot { frames })
+            })
+            .collect()
+    }
+
+    /// Rebuilds all stacks from a snapshot, switching to the given stack
+    pub fn from_snapshot(
+        current_stack_index: usize,
+        stacks: Vec<KernelStackSnapshot<M::CallFrameData>>,
+        always_visible_global_nodes: &'static IndexSet<NodeId>,
+    ) -> Result<Self, RuntimeError> {
+        if current_stack_index >= stacks.len() {
+            return Err(RuntimeError::KernelError(KernelError::StackError(
+                StackError::InvalidStackId,
+            )));
+        }
+
+        let stacks = stacks
+            .into_iter()
+            .enumerate()
+            .map(|(stack_id, stack)| {
+                let mut prev_frames = stack
+                    .frames
+                    .into_iter()
+                    .map(|frame| {
+                        if frame.stack_id != stack_id {
+                            return Err(RuntimeError::KernelError(KernelError::StackError(
+                                StackError::InvalidStackId,
+                            )));
+                        }
+                        Ok(CallFrame::from_snapshot(frame, always_visible_global_nodes))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let current_frame = prev_frames.pop().ok_or(RuntimeError::KernelError(
+                    KernelError::SnapshotError(KernelSnapshotError::EmptyStack),
+                ))?;
+                Ok(KernelStack {
+                    current_frame,
+                    prev_frames,
+                })
+            })
+            .collect::<Result<Vec<_>, RuntimeError>>()?;
+
+        Ok(Self {
+            current_stack_index,
+            stacks,
+        })
+    }
+
+    fn ensure_no_open_substates(&self) -> Result<(), RuntimeError> {
+        for stack in &self.stacks {
+            for frame in stack
+                .prev_frames
+                .iter()
+                .chain(core::iter::once(&stack.current_frame))
+            {
+                frame.ensure_no_open_substates().map_err(|e| {
+                    RuntimeError::KernelError(KernelError::SnapshotError(
+                        KernelSnapshotError::CallFrameSnapshotError(e),
+                    ))
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    fn owned_root_nodes(&self) -> Vec<NodeId> {
+        self.stacks
+            .iter()
+            .flat_map(|stack| {
+                stack
+                    .prev_frames
+                    .iter()
+                    .chain(core::iter::once(&stack.current_frame))
+            })
+            .flat_map(|frame| frame.owned_nodes())
+            .collect()
+    }
+}
+
+/// A serializable capture of the kernel state between two substate accesses, as produced by
+/// [`Kernel::suspend`].
+///
+/// This covers the call frames of all stacks, the heap, the substates tracked by the store
+/// and the position of the id allocator, which together are sufficient to [`Kernel::resume`]
+/// execution in a fresh kernel over the same substate database. State of the upper layer
+/// (i.e. the system) is not captured and is left to the embedder.
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct KernelSnapshot<C> {
+    pub current_stack_index: usize,
+    pub stacks: Vec<KernelStackSnapshot<C>>,
+    pub heap: HeapSnapshot,
+    pub heap_transient_substates: BTreeMap<NodeId, BTreeSet<(PartitionNumber, SubstateKey)>>,
+    pub pinned_to_heap: BTreeSet<NodeId>,
+    pub track: TrackSnapshot,
+    pub id_allocator: IdAllocator,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct KernelStackSnapshot<C> {
+    /// The frames of the stack, from the root frame to the current frame.
+    pub frames: Vec<CallFrameSnapshot<C>>,
 }
 
 pub struct Kernel<
@@ -396,6 +540,67 @@ impl<'g, M: KernelCallbackObject, S: CommitableSubstateStore> Kernel<'g, M, S> {
             callback,
         }
     }
+
+    /// Captures the kernel state so that execution can later be resumed from this point.
+    ///
+    /// All substates must have been closed, as substate locks can't be carried over.
+    pub fn suspend(&self) -> Result<KernelSnapshot<M::CallFrameData>, RuntimeError>
+    where
+        M::CallFrameData: Clone,
+    {
+        let stacks = self.stacks.snapshot()?;
+        let heap = self
+            .substate_io
+            .heap
+            .snapshot(self.stacks.owned_root_nodes().iter())
+            .map_err(|e| {
+                RuntimeError::KernelError(KernelError::SnapshotError(
+                    KernelSnapshotError::HeapSnapshotError(e),
+                ))
+            })?;
+
+        Ok(KernelSnapshot {
+            current_stack_index: self.stacks.current_stack_index,
+            stacks,
+            heap,
+            heap_transient_substates: self
+                .substate_io
+                .heap_transient_substates
+                .transient_substates
+                .clone(),
+            pinned_to_heap: self.substate_io.pinned_to_heap.clone(),
+            track: self.substate_io.store.snapshot(),
+            id_allocator: self.id_allocator.clone(),
+        })
+    }
+
+    /// Replaces the kernel state with a previously captured snapshot, discarding any
+    /// progress made by this kernel.
+    pub fn resume(
+        &mut self,
+        snapshot: KernelSnapshot<M::CallFrameData>,
+    ) -> Result<(), RuntimeError> {
+        self.stacks.ensure_no_open_substates()?;
+
+        let always_visible_global_nodes = self.stacks.current_frame().always_visible_global_nodes();
+        self.stacks = KernelStacks::from_snapshot(
+            snapshot.current_stack_index,
+            snapshot.stacks,
+            always_visible_global_nodes,
+        )?;
+
+        self.substate_io.heap = Heap::from_snapshot(snapshot.heap);
+        self.substate_io.non_global_node_refs = NonGlobalNodeRefs::new();
+        self.substate_io.substate_locks = SubstateLocks::new();
+        self.substate_io.heap_transient_substates = TransientSubstates {
+            transient_substates: snapshot.heap_transient_substates,
+        };
+        self.substate_io.pinned_to_heap = snapshot.pinned_to_heap;
+        self.substate_io.store.restore(snapshot.track);
+        *self.id_allocator = snapshot.id_allocator;
+
+        Ok(())
+    }
 }
 
 struct KernelHandler<
@@ -680,6 +885,10 @@ impl<'g, M: KernelCallbackObject, S: CommitableSubstateStore> KernelInternalApi
             .heap
             .get_substate(node_id, partition_num, substate_key)
     }
+
+    fn kernel_get_owned_nodes_uncosted(&self) -> Vec<NodeId> {
+        self.stacks.current_frame().owned_nodes()
+    }
 }
 
 struct KernelReadOnly<'g, M>
@@ -730,6 +939,10 @@ impl<'g, M: KernelCallbackObject> KernelInternalApi for KernelReadOnly<'g, M> {
     ) -> Option<&IndexedScryptoValue> {
         self.heap.get_substate(node_id, partition_num, substate_key)
     }
+
+    fn kernel_get_owned_nodes_uncosted(&self) -> Vec<NodeId> {
+        self.current_frame.owned_nodes()
+    }
 }
 
 impl<'g, M, S> KernelSubstateApi<M::LockData> for Kernel<'g, M, S>
@@ -828,27 +1041,21 @@ where
                             &mut handler,
                         )
                         .map_err(|e| match e {
-                            CallbackError::Error(e) => RuntimeError::KernelError(
-                                KernelError::CallFrameError(CallFrameError::OpenSubstateError(e)),
-                            ),
+                            CallbackError::Error(e) => self.open_substate_error(e),
                             CallbackError::CallbackError(e) => e,
                         })?
                 } else {
                     return maybe_lock_handle
                         .map(|(lock_handle, _)| lock_handle)
                         .map_err(|e| match e {
-                            CallbackError::Error(e) => RuntimeError::KernelError(
-                                KernelError::CallFrameError(CallFrameError::OpenSubstateError(e)),
-                            ),
+                            CallbackError::Error(e) => self.open_substate_error(e),
                             CallbackError::CallbackError(e) => e,
                         });
                 }
             }
             Err(err) => {
                 let runtime_error = match err {
-                    CallbackError::Error(e) => RuntimeError::KernelError(
-                        KernelError::CallFrameError(CallFrameError::OpenSubstateError(e.clone())),
-                    ),
+                    CallbackError::Error(e) => self.open_substate_error(e.clone()),
                     CallbackError::CallbackError(e) => e.clone(),
                 };
                 return Err(runtime_error);
@@ -1084,6 +1291,45 @@ where
         Ok(keys)
     }
 
+    #[trace_resources]
+    fn kernel_scan_keys_after<K: SubstateKeyContent>(
+        &mut self,
+        node_id: &NodeId,
+        partition_num: PartitionNumber,
+        after: Option<&SubstateKey>,
+        limit: u32,
+    ) -> Result<Vec<SubstateKey>, RuntimeError> {
+        M::on_scan_keys(ScanKeysEvent::Start, &mut as_read_only!(self))?;
+
+        let (cur_frame, prev_frame) = self.stacks.mut_current_frame_and_previous_frame();
+
+        let mut handler = KernelHandler {
+            callback: self.callback,
+            prev_frame,
+            on_io_access: |api, io_access| {
+                M::on_scan_keys(ScanKeysEvent::IOAccess(&io_access), api)
+            },
+        };
+
+        let keys = cur_frame
+            .scan_keys_after::<K, _, _>(
+                &mut self.substate_io,
+                node_id,
+                partition_num,
+                after,
+                limit,
+                &mut handler,
+            )
+            .map_err(|e| match e {
+                CallbackError::Error(e) => RuntimeError::KernelError(KernelError::CallFrameError(
+                    CallFrameError::ScanSubstatesError(e),
+                )),
+                CallbackError::CallbackError(e) => e,
+            })?;
+
+        Ok(keys)
+    }
+
     #[trace_resources(log=limit)]
     fn kernel_drain_substates<K: SubstateKeyContent>(
         &mut self,
@@ -1305,6 +1551,42 @@ impl<'g, M: KernelCallbackObject, S: CommitableSubstateStore> KernelStackApi for
 
         Ok(self.stacks.current_frame().owned_nodes())
     }
+
+    fn kernel_get_call_frame_data_stack_uncosted(&self) -> Vec<&M::CallFrameData> {
+        let stack = self.stacks.current_stack();
+        stack
+            .prev_frames
+            .iter()
+            .chain(iter::once(&stack.current_frame))
+            .map(|frame| frame.data())
+            .collect()
+    }
+}
+
+impl<'g, M: KernelCallbackObject, S: CommitableSubstateStore> KernelStoreCheckpointApi
+    for Kernel<'g, M, S>
+{
+    fn kernel_create_store_checkpoint(&mut self) -> Result<StoreCheckpoint, RuntimeError> {
+        Ok(self.substate_io.create_store_checkpoint())
+    }
+
+    fn kernel_rollback_store_to(
+        &mut self,
+        checkpoint: StoreCheckpoint,
+    ) -> Result<(), RuntimeError> {
+        self.substate_io
+            .rollback_store_to(checkpoint)
+            .map_err(|e| RuntimeError::KernelError(KernelError::StoreCheckpointError(e)))
+    }
+
+    fn kernel_release_store_checkpoint(
+        &mut self,
+        checkpoint: StoreCheckpoint,
+    ) -> Result<(), RuntimeError> {
+        self.substate_io
+            .release_store_checkpoint(checkpoint)
+            .map_err(|e| RuntimeError::KernelError(KernelError::StoreCheckpointError(e)))
+    }
 }
 
 impl<'g, M: KernelCallbackObject, S: CommitableSubstateStore> KernelApi for Kernel<'g, M, S> {
@@ -1338,6 +1620,45 @@ where
     }
 }
 
+impl<'g, M: KernelCallbackObject, S: CommitableSubstateStore> Kernel<'g, M, S> {
+    /// Converts an error from opening a substate, reporting the holders of the lock if the
+    /// substate is locked.
+    fn open_substate_error(&self, error: OpenSubstateError) -> RuntimeError {
+        let error = match error {
+            OpenSubstateError::SubstateLocked(mut contention) => {
+                contention.holders = self
+                    .substate_io
+                    .substate_locks
+                    .holders(
+                        &contention.node_id.0,
+                        contention.partition_num,
+                        &contention.substate_key,
+                    )
+                    .into_iter()
+                    .filter_map(|(global_lock_handle, lock_data)| {
+                        let (frame, handle) = self
+                            .stacks
+                            .frame_holding_substate_lock(global_lock_handle)?;
+                        Some(SubstateLockHolder {
+                            flags: lock_data.flags,
+                            stack_id: frame.stack_id(),
+                            call_frame_depth: frame.depth(),
+                            handle,
+                            actor: frame.data().actor_description(),
+                        })
+                    })
+                    .collect();
+                OpenSubstateError::SubstateLocked(contention)
+            }
+            error => error,
+        };
+
+        RuntimeError::KernelError(KernelError::CallFrameError(
+            CallFrameError::OpenSubstateError(error),
+        ))
+    }
+}
+
 #[cfg(feature = "radix_engine_tests")]
 impl<'g, M: KernelCallbackObject, S: CommitableSubstateStore> Kernel<'g, M, S> {
     pub fn kernel_current_frame(
@@ -2,6 +2,7 @@ use super::call_frame::*;
 use crate::errors::*;
 use crate::internal_prelude::*;
 use crate::kernel::kernel_callback_api::*;
+use crate::kernel::substate_io::StoreCheckpoint;
 use crate::track::interface::*;
 use radix_engine_interface::api::field_api::*;
 use radix_substate_store_interface::db_key_mapper::*;
@@ -142,6 +143,16 @@ pub trait KernelSubstateApi<L> {
         count: u32,
     ) -> Result<Vec<SubstateKey>, RuntimeError>;
 
+    /// Scans the keys of a partition in database sort key order, starting after the given key,
+    /// so that a scan can be continued from the last key of a previous scan
+    fn kernel_scan_keys_after<K: SubstateKeyContent>(
+        &mut self,
+        node_id: &NodeId,
+        partition_num: PartitionNumber,
+        after: Option<&SubstateKey>,
+        count: u32,
+    ) -> Result<Vec<SubstateKey>, RuntimeError>;
+
     fn kernel_drain_substates<K: SubstateKeyContent>(
         &mut self,
         node_id: &NodeId,
@@ -193,7 +204,41 @@ pub trait KernelStackApi {
         -> Result<(), RuntimeError>;
 
     /// Returns the owned nodes of the current call frame
+    ///
+    /// These are the root nodes held by the frame itself (e.g. buckets and proofs), and do not
+    /// include nodes owned by the substates of the actor's object (e.g. a component's vaults).
     fn kernel_get_owned_nodes(&mut self) -> Result<Vec<NodeId>, RuntimeError>;
+
+    /// Returns the call frame data of the current stack, from its root frame to the current frame
+    ///
+    /// This is not costed: the stack is bounded by the maximum call depth, and walking it is
+    /// covered by the cost of the invocation. It may be used by consensus logic, such as the
+    /// detection of the re-entering of non-reentrant objects, as well as to describe the call
+    /// path of a failed transaction.
+    fn kernel_get_call_frame_data_stack_uncosted(&self) -> Vec<&Self::CallFrameData>;
+}
+
+/// API for rolling back the changes made to the store, e.g. to retry a failed callback
+pub trait KernelStoreCheckpointApi {
+    /// Creates a savepoint which the changes made to the store after it can be rolled back to.
+    ///
+    /// Checkpoints nest, with later checkpoints being discarded along with earlier ones.
+    fn kernel_create_store_checkpoint(&mut self) -> Result<StoreCheckpoint, RuntimeError>;
+
+    /// Reverts all changes made to the store since the given checkpoint was created.
+    ///
+    /// Fails, keeping all changes, if a store substate opened after the checkpoint or opened for
+    /// writing is still open, if a node was persisted after the checkpoint, or if a fee was
+    /// locked after the checkpoint.
+    fn kernel_rollback_store_to(&mut self, checkpoint: StoreCheckpoint)
+        -> Result<(), RuntimeError>;
+
+    /// Discards the given checkpoint along with all checkpoints created after it, keeping all
+    /// changes made to the store since.
+    fn kernel_release_store_checkpoint(
+        &mut self,
+        checkpoint: StoreCheckpoint,
+    ) -> Result<(), RuntimeError>;
 }
 
 pub struct SystemState<'a, M: KernelCallbackObject> {
@@ -243,6 +288,11 @@ pub trait KernelInternalApi {
         partition_num: PartitionNumber,
         substate_key: &SubstateKey,
     ) -> Option<&IndexedScryptoValue>;
+
+    /// Returns the nodes owned by the current call frame.
+    ///
+    /// Used by debugger system module only.
+    fn kernel_get_owned_nodes_uncosted(&self) -> Vec<NodeId>;
 }
 
 pub trait KernelApi:
@@ -251,6 +301,7 @@ pub trait KernelApi:
     + KernelInvokeApi<<Self::CallbackObject as KernelCallbackObject>::CallFrameData>
     + KernelStackApi<CallFrameData = <Self::CallbackObject as KernelCallbackObject>::CallFrameData>
     + KernelInternalApi<System = Self::CallbackObject>
+    + KernelStoreCheckpointApi
 {
     type CallbackObject: KernelCallbackObject;
 }
@@ -17,6 +17,9 @@ pub trait CallFrameReferences {
     fn stable_transient_references(&self) -> Vec<NodeId>;
 
     fn len(&self) -> usize;
+
+    /// Describes the actor of the call frame, e.g. when reporting which call frame holds a lock.
+    fn actor_description(&self) -> String;
 }
 
 // TODO: Replace Events with separate callback functions
@@ -4,6 +4,7 @@ pub mod id_allocator;
 pub mod kernel;
 pub mod kernel_api;
 pub mod kernel_callback_api;
+pub mod node_visitor;
 #[cfg(all(target_os = "linux", feature = "std", feature = "cpu_ram_metrics"))]
 pub mod resources_tracker;
 pub mod substate_io;
@@ -0,0 +1,52 @@
+use crate::internal_prelude::*;
+use crate::track::interface::NodeSubstates;
+
+/// Visits the substates of a node, and the nodes they own and reference.
+///
+/// All methods do nothing by default, so that visitors only implement what they're interested in.
+pub trait NodeSubstatesVisitor {
+    type Error;
+
+    fn visit_partition(&mut self, _partition_number: PartitionNumber) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn visit_substate(
+        &mut self,
+        _partition_number: PartitionNumber,
+        _substate_key: &SubstateKey,
+        _value: &IndexedScryptoValue,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn visit_owned_node(&mut self, _node_id: &NodeId) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn visit_reference(&mut self, _node_id: &NodeId) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Walks the partitions of a node in order, visiting each partition before its substates, and
+/// each substate before the nodes it owns and then the nodes it references.
+pub fn walk_node_substates<V: NodeSubstatesVisitor>(
+    node_substates: &NodeSubstates,
+    visitor: &mut V,
+) -> Result<(), V::Error> {
+    for (partition_number, substates) in node_substates {
+        visitor.visit_partition(*partition_number)?;
+        for (substate_key, value) in substates {
+            visitor.visit_substate(*partition_number, substate_key, value)?;
+            for node_id in value.owned_nodes() {
+                visitor.visit_owned_node(node_id)?;
+            }
+            for node_id in value.references() {
+                visitor.visit_reference(node_id)?;
+            }
+        }
+    }
+
+    Ok(())
+}
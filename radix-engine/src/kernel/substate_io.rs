@@ -1,13 +1,15 @@
 use crate::kernel::call_frame::{
     CallFrameDrainSubstatesError, CallFrameRemoveSubstateError, CallFrameScanKeysError,
     CallFrameScanSortedSubstatesError, CallFrameSetSubstateError, CreateNodeError, DropNodeError,
-    MovePartitionError, NonGlobalNodeRefs, OpenSubstateError, PersistNodeError, TransientSubstates,
-    WriteSubstateError,
+    MovePartitionError, NonGlobalNodeRefs, OpenSubstateError, PersistNodeError,
+    SubstateLockContention, TransientSubstates, WriteSubstateError,
 };
 use crate::kernel::heap::{Heap, HeapRemoveNodeError};
-use crate::kernel::substate_locks::SubstateLocks;
+use crate::kernel::node_visitor::{walk_node_substates, NodeSubstatesVisitor};
+use crate::kernel::substate_locks::{SubstateLockError, SubstateLocks};
 use crate::track::interface::{
-    CallbackError, CommitableSubstateStore, IOAccess, NodeSubstates, TrackedSubstateInfo,
+    CallbackError, CommitableSubstateStore, IOAccess, NodeSubstates, TrackCheckpoint,
+    TrackCheckpointError, TrackedSubstateInfo,
 };
 use radix_common::prelude::{NodeId, PartitionNumber};
 use radix_common::types::{SortedKey, SubstateKey};
@@ -20,6 +22,26 @@ use sbor::rust::collections::BTreeSet;
 use sbor::rust::collections::LinkedList;
 use sbor::Sbor;
 
+/// Queues the nodes owned by a node being persisted, and checks that it only references global
+/// nodes.
+struct PersistedNodeVisitor<'q>(&'q mut LinkedList<NodeId>);
+
+impl<'q> NodeSubstatesVisitor for PersistedNodeVisitor<'q> {
+    type Error = PersistNodeError;
+
+    fn visit_owned_node(&mut self, node_id: &NodeId) -> Result<(), Self::Error> {
+        self.0.push_back(*node_id);
+        Ok(())
+    }
+
+    fn visit_reference(&mut self, node_id: &NodeId) -> Result<(), Self::Error> {
+        if !node_id.is_global() {
+            return Err(PersistNodeError::ContainsNonGlobalRef((*node_id).into()));
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Copy, Clone, Sbor, PartialEq, Eq, PartialOrd, Ord)]
 pub enum SubstateDevice {
     Heap,
@@ -56,6 +78,24 @@ pub enum ProcessSubstateIOWriteError {
     PersistNodeError(PersistNodeError),
 }
 
+/// A savepoint of the substates in the store, as created by [`SubstateIO::create_store_checkpoint`].
+///
+/// Only the store is covered, as the heap is owned by the call frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StoreCheckpoint {
+    track: TrackCheckpoint,
+    next_lock_handle: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub enum StoreCheckpointError {
+    /// A store substate opened after the checkpoint is still open, and would no longer be tracked.
+    SubstateOpenedAfterCheckpoint(NodeId, PartitionNumber, SubstateKey),
+    /// A store substate is open for writing, so writes its holder made may be reverted.
+    SubstateOpenedForWrite(NodeId, PartitionNumber, SubstateKey),
+    TrackCheckpointError(TrackCheckpointError),
+}
+
 pub struct SubstateIO<'g, S: CommitableSubstateStore> {
     pub heap: Heap,
     pub store: &'g mut S,
@@ -172,21 +212,8 @@ impl<'g, S: CommitableSubstateStore + 'g> SubstateIO<'g, S> {
                 }
             };
 
-            for (_partition_num, module_substates) in &node_substates {
-                for (_substate_key, substate_value) in module_substates {
-                    for reference in substate_value.references() {
-                        if !reference.is_global() {
-                            return Err(CallbackError::Error(
-                                PersistNodeError::ContainsNonGlobalRef(reference.clone().into()),
-                            ));
-                        }
-                    }
-
-                    for node_id in substate_value.owned_nodes() {
-                        queue.push_back(*node_id);
-                    }
-                }
-            }
+            walk_node_substates(&node_substates, &mut PersistedNodeVisitor(&mut queue))
+                .map_err(CallbackError::Error)?;
 
             if let Some(transient_substates) = self
                 .heap_transient_substates
@@ -409,9 +436,12 @@ impl<'g, S: CommitableSubstateStore + 'g> SubstateIO<'g, S> {
             Some(handle) => handle,
             None => {
                 return Err(CallbackError::Error(OpenSubstateError::SubstateLocked(
-                    node_id.clone().into(),
-                    partition_num,
-                    substate_key.clone(),
+                    Box::new(SubstateLockContention {
+                        node_id: (*node_id).into(),
+                        partition_num,
+                        substate_key: substate_key.clone(),
+                        holders: vec![],
+                    }),
                 )));
             }
         };
@@ -495,6 +525,23 @@ impl<'g, S: CommitableSubstateStore + 'g> SubstateIO<'g, S> {
         Ok(())
     }
 
+    /// Upgrades a lock to a mutable one, which fails if any other lock is held on the substate.
+    pub fn upgrade_substate_lock(
+        &mut self,
+        global_lock_handle: u32,
+    ) -> Result<(), SubstateLockError> {
+        let (.., lock_data) = self.substate_locks.get(global_lock_handle);
+        if lock_data.flags.contains(LockFlags::MUTABLE) {
+            return Ok(());
+        }
+
+        self.substate_locks.upgrade(global_lock_handle)?;
+        let (.., lock_data) = self.substate_locks.get_mut(global_lock_handle);
+        lock_data.flags.insert(LockFlags::MUTABLE);
+
+        Ok(())
+    }
+
     pub fn close_substate(
         &mut self,
         global_lock_handle: u32,
@@ -510,6 +557,62 @@ impl<'g, S: CommitableSubstateStore + 'g> SubstateIO<'g, S> {
         (node_id, partition_num, substate_key, lock_data.flags)
     }
 
+    /// Creates a savepoint which the changes made to the store after it can be rolled back to.
+    pub fn create_store_checkpoint(&mut self) -> StoreCheckpoint {
+        StoreCheckpoint {
+            track: self.store.create_checkpoint(),
+            next_lock_handle: self.substate_locks.next_handle(),
+        }
+    }
+
+    /// Reverts all changes made to the store since the given checkpoint was created.
+    ///
+    /// Fails if any store substate opened after the checkpoint is still open, or if any store
+    /// substate is open for writing, as its holder would otherwise observe the substate change
+    /// underneath it. Also fails if the store can't undo its changes, see
+    /// [`CommitableSubstateStore::rollback_to`].
+    pub fn rollback_store_to(
+        &mut self,
+        checkpoint: StoreCheckpoint,
+    ) -> Result<(), StoreCheckpointError> {
+        for (handle, (node_id, partition_num, substate_key, lock_data)) in
+            self.substate_locks.iter()
+        {
+            if lock_data.device != SubstateDevice::Store {
+                continue;
+            }
+
+            if handle >= checkpoint.next_lock_handle {
+                return Err(StoreCheckpointError::SubstateOpenedAfterCheckpoint(
+                    *node_id,
+                    *partition_num,
+                    substate_key.clone(),
+                ));
+            }
+            if lock_data.flags.contains(LockFlags::MUTABLE) {
+                return Err(StoreCheckpointError::SubstateOpenedForWrite(
+                    *node_id,
+                    *partition_num,
+                    substate_key.clone(),
+                ));
+            }
+        }
+
+        self.store
+            .rollback_to(checkpoint.track)
+            .map_err(StoreCheckpointError::TrackCheckpointError)
+    }
+
+    /// Discards the given checkpoint, keeping all changes made to the store since.
+    pub fn release_store_checkpoint(
+        &mut self,
+        checkpoint: StoreCheckpoint,
+    ) -> Result<(), StoreCheckpointError> {
+        self.store
+            .release(checkpoint.track)
+            .map_err(StoreCheckpointError::TrackCheckpointError)
+    }
+
     pub fn set_substate<'f, E>(
         &mut self,
         device: SubstateDevice,
@@ -611,6 +714,34 @@ impl<'g, S: CommitableSubstateStore + 'g> SubstateIO<'g, S> {
         Ok(keys)
     }
 
+    pub fn scan_keys_after<K: SubstateKeyContent, E>(
+        &mut self,
+        device: SubstateDevice,
+        node_id: &NodeId,
+        partition_num: PartitionNumber,
+        after: Option<&SubstateKey>,
+        count: u32,
+        handler: &mut impl IOAccessHandler<E>,
+    ) -> Result<Vec<SubstateKey>, CallbackError<CallFrameScanKeysError, E>> {
+        let keys = match device {
+            SubstateDevice::Heap => self
+                .heap
+                .scan_keys_after(node_id, partition_num, after, count),
+            SubstateDevice::Store => self
+                .store
+                .scan_keys_after::<K, E, _>(
+                    node_id,
+                    partition_num,
+                    after,
+                    count,
+                    &mut |io_access| handler.on_io_access(&self.heap, io_access),
+                )
+                .map_err(|e| CallbackError::CallbackError(e))?,
+        };
+
+        Ok(keys)
+    }
+
     pub fn drain_substates<K: SubstateKeyContent, E>(
         &mut self,
         device: SubstateDevice,
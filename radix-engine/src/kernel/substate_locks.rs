@@ -37,6 +37,18 @@ impl SubstateLockState {
         Ok(())
     }
 
+    /// Upgrades a read lock to a write lock, which is only possible if the caller holds the only
+    /// read lock. This fails rather than waits, so two readers upgrading can't deadlock.
+    fn try_upgrade(&mut self) -> Result<(), SubstateLockError> {
+        match self {
+            SubstateLockState::Read(1usize) => {
+                *self = SubstateLockState::Write;
+                Ok(())
+            }
+            _ => Err(SubstateLockError),
+        }
+    }
+
     fn unlock(&mut self) {
         match self {
             SubstateLockState::Read(n) => {
@@ -131,6 +143,48 @@ impl<D> SubstateLocks<D> {
         Some(handle)
     }
 
+    /// Upgrades the given read lock to a write lock.
+    ///
+    /// Fails if the lock is already a write lock, or if the substate has other read locks.
+    pub fn upgrade(&mut self, handle: u32) -> Result<(), SubstateLockError> {
+        let (node_id, partition_num, substate_key, _) = self.locks.get(&handle).unwrap();
+        self.substate_lock_states
+            .get_mut(&(*node_id, *partition_num, substate_key.clone()))
+            .unwrap()
+            .try_upgrade()
+    }
+
+    /// Returns the handles and data of all locks held on a substate.
+    pub fn holders(
+        &self,
+        node_id: &NodeId,
+        partition_num: PartitionNumber,
+        substate_key: &SubstateKey,
+    ) -> Vec<(u32, &D)> {
+        self.locks
+            .iter()
+            .filter(
+                |(_, (locked_node_id, locked_partition_num, locked_substate_key, _))| {
+                    locked_node_id == node_id
+                        && *locked_partition_num == partition_num
+                        && locked_substate_key == substate_key
+                },
+            )
+            .map(|(handle, (.., data))| (*handle, data))
+            .collect()
+    }
+
+    /// Returns the handle which the next lock will be given. Handles are never reused, so all
+    /// locks acquired from now on have a handle greater than or equal to it.
+    pub fn next_handle(&self) -> u32 {
+        self.next_lock_id
+    }
+
+    /// Returns the handles and substates of all locks held.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &(NodeId, PartitionNumber, SubstateKey, D))> {
+        self.locks.iter().map(|(handle, lock)| (*handle, lock))
+    }
+
     pub fn get(&self, handle: u32) -> &(NodeId, PartitionNumber, SubstateKey, D) {
         self.locks.get(&handle).unwrap()
     }
@@ -152,3 +206,37 @@ impl<D> SubstateLocks<D> {
         (full_key.0, full_key.1, full_key.2, data)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_sole_reader_can_upgrade_its_lock() {
+        let mut locks = SubstateLocks::<()>::new();
+        let node_id = NodeId([0; NodeId::LENGTH]);
+        let key = SubstateKey::Field(0u8);
+
+        let first = locks
+            .lock(&node_id, MAIN_BASE_PARTITION, &key, true, ())
+            .unwrap();
+        let second = locks
+            .lock(&node_id, MAIN_BASE_PARTITION, &key, true, ())
+            .unwrap();
+        assert_eq!(locks.holders(&node_id, MAIN_BASE_PARTITION, &key).len(), 2);
+        assert!(locks.upgrade(first).is_err());
+
+        locks.unlock(second);
+        assert!(locks.upgrade(first).is_ok());
+        assert!(locks.upgrade(first).is_err());
+        assert!(locks
+            .lock(&node_id, MAIN_BASE_PARTITION, &key, true, ())
+            .is_none());
+
+        locks.unlock(first);
+        assert!(!locks.is_locked(&node_id, MAIN_BASE_PARTITION, &key));
+        assert!(locks
+            .holders(&node_id, MAIN_BASE_PARTITION, &key)
+            .is_empty());
+    }
+}
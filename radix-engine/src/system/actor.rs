@@ -159,6 +159,26 @@ impl CallFrameReferences for Actor {
             }
         }
     }
+
+    fn actor_description(&self) -> String {
+        match self {
+            Actor::Root => "Root".to_owned(),
+            Actor::Method(method_actor) => format!(
+                "Method {:?}::{} on {:?}",
+                method_actor.get_blueprint_id(),
+                method_actor.ident,
+                method_actor.node_id
+            ),
+            Actor::Function(FunctionActor {
+                blueprint_id,
+                ident,
+                ..
+            }) => format!("Function {:?}::{}", blueprint_id, ident),
+            Actor::BlueprintHook(BlueprintHookActor {
+                blueprint_id, hook, ..
+            }) => format!("Blueprint Hook {:?}::{:?}", blueprint_id, hook),
+        }
+    }
 }
 
 impl Actor {
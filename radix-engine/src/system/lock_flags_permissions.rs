@@ -0,0 +1,204 @@
+//! The lock flags with which the system lets each kind of substate be opened, defined as a table
+//! rather than as conditions scattered over the system, so that changes to them are auditable.
+
+use crate::errors::{RuntimeError, SystemError};
+use crate::internal_prelude::*;
+use radix_engine_interface::api::LockFlags;
+
+/// The kind of substate being opened through the system.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum LockedSubstateKind {
+    /// A field of the actor's object.
+    ActorField,
+    /// An entry of a key value collection of the actor's object. Its flags are checked before the
+    /// blueprint of the actor is resolved, so only permissions of any blueprint apply.
+    ActorKeyValueEntry,
+    /// An entry of a key value store.
+    KeyValueStoreEntry,
+}
+
+impl LockedSubstateKind {
+    pub const ALL: [LockedSubstateKind; 3] = [
+        LockedSubstateKind::ActorField,
+        LockedSubstateKind::ActorKeyValueEntry,
+        LockedSubstateKind::KeyValueStoreEntry,
+    ];
+}
+
+/// The blueprints a [`LockFlagsPermission`] applies to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LockFlagsPermissionScope {
+    /// Substates of any blueprint, and of key value stores.
+    Any,
+    /// Substates of objects of the given blueprint.
+    Blueprint(PackageAddress, &'static str),
+}
+
+impl LockFlagsPermissionScope {
+    fn applies_to(&self, blueprint_id: Option<&BlueprintId>) -> bool {
+        match self {
+            LockFlagsPermissionScope::Any => true,
+            LockFlagsPermissionScope::Blueprint(package_address, blueprint_name) => blueprint_id
+                .is_some_and(|blueprint_id| {
+                    blueprint_id.package_address.eq(package_address)
+                        && blueprint_id.blueprint_name.eq(blueprint_name)
+                }),
+        }
+    }
+}
+
+/// Allows a kind of substate to be opened with any subset of the given flags.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct LockFlagsPermission {
+    pub substate_kind: LockedSubstateKind,
+    pub scope: LockFlagsPermissionScope,
+    pub allowed_flags: LockFlags,
+}
+
+/// The lock flags permissions enforced by the system.
+pub const LOCK_FLAGS_PERMISSIONS: [LockFlagsPermission; 4] = [
+    LockFlagsPermission {
+        substate_kind: LockedSubstateKind::ActorField,
+        scope: LockFlagsPermissionScope::Any,
+        allowed_flags: LockFlags::MUTABLE,
+    },
+    // Fees are locked in fungible vaults, and must be paid even if the transaction fails
+    LockFlagsPermission {
+        substate_kind: LockedSubstateKind::ActorField,
+        scope: LockFlagsPermissionScope::Blueprint(RESOURCE_PACKAGE, FUNGIBLE_VAULT_BLUEPRINT),
+        allowed_flags: LockFlags::MUTABLE
+            .union(LockFlags::UNMODIFIED_BASE)
+            .union(LockFlags::FORCE_WRITE),
+    },
+    LockFlagsPermission {
+        substate_kind: LockedSubstateKind::ActorKeyValueEntry,
+        scope: LockFlagsPermissionScope::Any,
+        allowed_flags: LockFlags::MUTABLE,
+    },
+    LockFlagsPermission {
+        substate_kind: LockedSubstateKind::KeyValueStoreEntry,
+        scope: LockFlagsPermissionScope::Any,
+        allowed_flags: LockFlags::MUTABLE,
+    },
+];
+
+/// Returns whether any of the given permissions allows the kind of substate, of the given
+/// blueprint if it belongs to an object, to be opened with the given flags.
+pub fn is_lock_flags_permitted(
+    permissions: &[LockFlagsPermission],
+    substate_kind: LockedSubstateKind,
+    blueprint_id: Option<&BlueprintId>,
+    flags: LockFlags,
+) -> bool {
+    permissions.iter().any(|permission| {
+        permission.substate_kind == substate_kind
+            && permission.scope.applies_to(blueprint_id)
+            && permission.allowed_flags.contains(flags)
+    })
+}
+
+/// Checks the flags against the [`LOCK_FLAGS_PERMISSIONS`].
+pub fn check_lock_flags(
+    substate_kind: LockedSubstateKind,
+    blueprint_id: Option<&BlueprintId>,
+    flags: LockFlags,
+) -> Result<(), RuntimeError> {
+    if is_lock_flags_permitted(&LOCK_FLAGS_PERMISSIONS, substate_kind, blueprint_id, flags) {
+        Ok(())
+    } else {
+        Err(RuntimeError::SystemError(SystemError::InvalidLockFlags))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The rules the system enforced before they were defined as a table.
+    fn is_permitted_by_original_rules(
+        substate_kind: LockedSubstateKind,
+        blueprint_id: Option<&BlueprintId>,
+        flags: LockFlags,
+    ) -> bool {
+        let is_special =
+            flags.contains(LockFlags::UNMODIFIED_BASE) || flags.contains(LockFlags::FORCE_WRITE);
+        match substate_kind {
+            LockedSubstateKind::ActorField => {
+                !is_special
+                    || blueprint_id
+                        == Some(&BlueprintId::new(
+                            &RESOURCE_PACKAGE,
+                            FUNGIBLE_VAULT_BLUEPRINT,
+                        ))
+            }
+            LockedSubstateKind::ActorKeyValueEntry | LockedSubstateKind::KeyValueStoreEntry => {
+                !is_special
+            }
+        }
+    }
+
+    #[test]
+    fn lock_flags_permissions_match_original_rules_for_all_combinations() {
+        let blueprint_ids = [
+            None,
+            Some(BlueprintId::new(
+                &RESOURCE_PACKAGE,
+                FUNGIBLE_VAULT_BLUEPRINT,
+            )),
+            Some(BlueprintId::new(
+                &RESOURCE_PACKAGE,
+                NON_FUNGIBLE_VAULT_BLUEPRINT,
+            )),
+            Some(BlueprintId::new(&ACCOUNT_PACKAGE, ACCOUNT_BLUEPRINT)),
+        ];
+
+        for substate_kind in LockedSubstateKind::ALL {
+            for blueprint_id in &blueprint_ids {
+                for bits in 0..=LockFlags::all().bits() {
+                    let flags = LockFlags::from_bits(bits).unwrap();
+                    assert_eq!(
+                        is_lock_flags_permitted(
+                            &LOCK_FLAGS_PERMISSIONS,
+                            substate_kind,
+                            blueprint_id.as_ref(),
+                            flags
+                        ),
+                        is_permitted_by_original_rules(substate_kind, blueprint_id.as_ref(), flags),
+                        "{:?} of {:?} opened with {:?}",
+                        substate_kind,
+                        blueprint_id,
+                        flags
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn custom_lock_flags_permissions_can_be_checked() {
+        let permissions = [LockFlagsPermission {
+            substate_kind: LockedSubstateKind::KeyValueStoreEntry,
+            scope: LockFlagsPermissionScope::Any,
+            allowed_flags: LockFlags::MUTABLE.union(LockFlags::FORCE_WRITE),
+        }];
+
+        assert!(is_lock_flags_permitted(
+            &permissions,
+            LockedSubstateKind::KeyValueStoreEntry,
+            None,
+            LockFlags::FORCE_WRITE
+        ));
+        assert!(!is_lock_flags_permitted(
+            &permissions,
+            LockedSubstateKind::KeyValueStoreEntry,
+            None,
+            LockFlags::UNMODIFIED_BASE
+        ));
+        assert!(!is_lock_flags_permitted(
+            &permissions,
+            LockedSubstateKind::ActorField,
+            None,
+            LockFlags::read_only()
+        ));
+    }
+}
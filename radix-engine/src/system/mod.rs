@@ -3,6 +3,7 @@ pub mod bootstrap;
 #[cfg(feature = "db_checker")]
 pub mod checkers;
 pub mod id_allocation;
+pub mod lock_flags_permissions;
 pub mod module;
 pub mod node_init;
 pub mod payload_validation;
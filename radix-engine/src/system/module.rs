@@ -45,6 +45,8 @@ pub trait SystemModuleApi {
     fn current_stack_depth_uncosted(&self) -> usize;
 
     fn current_stack_id_uncosted(&self) -> usize;
+
+    fn current_owned_nodes_uncosted(&self) -> Vec<NodeId>;
 }
 
 impl<'a, V: SystemCallbackObject, K: KernelInternalApi<System = System<V>> + ?Sized> SystemModuleApi
@@ -67,6 +69,10 @@ impl<'a, V: SystemCallbackObject, K: KernelInternalApi<System = System<V>> + ?Si
     fn current_stack_id_uncosted(&self) -> usize {
         self.api.kernel_get_current_stack_id_uncosted()
     }
+
+    fn current_owned_nodes_uncosted(&self) -> Vec<NodeId> {
+        self.api.kernel_get_owned_nodes_uncosted()
+    }
 }
 
 pub trait ResolvableSystemModule {
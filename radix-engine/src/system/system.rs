@@ -8,6 +8,7 @@ use crate::internal_prelude::*;
 use crate::kernel::call_frame::{NodeVisibility, ReferenceOrigin};
 use crate::kernel::kernel_api::*;
 use crate::system::actor::{Actor, FunctionActor, InstanceContext, MethodActor, MethodType};
+use crate::system::lock_flags_permissions::{check_lock_flags, LockedSubstateKind};
 use crate::system::node_init::type_info_partition;
 use crate::system::system_callback::*;
 use crate::system::system_modules::transaction_runtime::Event;
@@ -1101,6 +1102,30 @@ impl<'a, Y: SystemBasedKernelApi> SystemService<'a, Y> {
             _ => Ok(false),
         }
     }
+
+    fn key_value_entry_has_value(
+        &mut self,
+        node_id: &NodeId,
+        key: &SubstateKey,
+    ) -> Result<bool, RuntimeError> {
+        let handle = self.api.kernel_open_substate(
+            node_id,
+            MAIN_BASE_PARTITION,
+            key,
+            LockFlags::read_only(),
+            SystemLockData::KeyValueEntry(KeyValueEntryLockData::Read),
+        )?;
+        let has_value = self.api.kernel_read_substate(handle).and_then(|v| {
+            v.as_typed::<KeyValueEntrySubstate<ScryptoValue>>()
+                .map(|kv_entry| kv_entry.into_value().is_some())
+                .map_err(|e| {
+                    RuntimeError::SystemError(SystemError::InvalidKeyValueEntrySubstate(e))
+                })
+        });
+        self.api.kernel_close_substate(handle)?;
+
+        has_value
+    }
 }
 
 #[cfg_attr(
@@ -1801,9 +1826,7 @@ impl<'a, Y: SystemBasedKernelApi> SystemKeyValueStoreApi<RuntimeError> for Syste
     ) -> Result<KeyValueEntryHandle, RuntimeError> {
         let type_info = TypeInfoBlueprint::get_type(&node_id, self.api)?;
 
-        if flags.contains(LockFlags::UNMODIFIED_BASE) || flags.contains(LockFlags::FORCE_WRITE) {
-            return Err(RuntimeError::SystemError(SystemError::InvalidLockFlags));
-        }
+        check_lock_flags(LockedSubstateKind::KeyValueStoreEntry, None, flags)?;
 
         let info = match type_info {
             TypeInfoSubstate::KeyValueStore(info) => info,
@@ -1860,6 +1883,46 @@ impl<'a, Y: SystemBasedKernelApi> SystemKeyValueStoreApi<RuntimeError> for Syste
         let handle = self.key_value_store_open_entry(node_id, key, LockFlags::MUTABLE)?;
         self.key_value_entry_remove_and_close_substate(handle)
     }
+
+    // Costing through kernel
+    fn key_value_store_scan_keys(
+        &mut self,
+        node_id: &NodeId,
+        cursor: Option<Vec<u8>>,
+        limit: u32,
+    ) -> Result<KeyValueStoreKeys, RuntimeError> {
+        let type_info = TypeInfoBlueprint::get_type(node_id, self.api)?;
+        if !matches!(type_info, TypeInfoSubstate::KeyValueStore(..)) {
+            return Err(RuntimeError::SystemError(SystemError::NotAKeyValueStore));
+        }
+
+        // Removed entries are kept as empty substates, so each scanned entry is opened once to
+        // check whether it holds a value, and the scan continues after the last scanned key.
+        let after = cursor.clone().map(SubstateKey::Map);
+        let scanned_keys = self.api.kernel_scan_keys_after::<MapKey>(
+            node_id,
+            MAIN_BASE_PARTITION,
+            after.as_ref(),
+            limit,
+        )?;
+        let next_cursor = if (scanned_keys.len() as u32) < limit {
+            None
+        } else {
+            scanned_keys
+                .last()
+                .map(|key| key.clone().into_map())
+                .or(cursor)
+        };
+
+        let mut keys = Vec::new();
+        for key in scanned_keys {
+            if self.key_value_entry_has_value(node_id, &key)? {
+                keys.push(key.into_map());
+            }
+        }
+
+        Ok(KeyValueStoreKeys { keys, next_cursor })
+    }
 }
 
 #[cfg_attr(
@@ -2554,15 +2617,11 @@ impl<'a, Y: SystemBasedKernelApi> SystemActorApi<RuntimeError> for SystemService
         let (node_id, blueprint_info, partition_num, transient) =
             self.get_actor_field_info(actor_object_type, field_index)?;
 
-        // TODO: Remove
-        if flags.contains(LockFlags::UNMODIFIED_BASE) || flags.contains(LockFlags::FORCE_WRITE) {
-            if !(blueprint_info.blueprint_id.eq(&BlueprintId::new(
-                &RESOURCE_PACKAGE,
-                FUNGIBLE_VAULT_BLUEPRINT,
-            ))) {
-                return Err(RuntimeError::SystemError(SystemError::InvalidLockFlags));
-            }
-        }
+        check_lock_flags(
+            LockedSubstateKind::ActorField,
+            Some(&blueprint_info.blueprint_id),
+            flags,
+        )?;
 
         let lock_data = if flags.contains(LockFlags::MUTABLE) {
             let target = BlueprintTypeTarget {
@@ -2671,9 +2730,7 @@ impl<'a, Y: SystemBasedKernelApi> SystemActorKeyValueEntryApi<RuntimeError>
         key: &Vec<u8>,
         flags: LockFlags,
     ) -> Result<KeyValueEntryHandle, RuntimeError> {
-        if flags.contains(LockFlags::UNMODIFIED_BASE) || flags.contains(LockFlags::FORCE_WRITE) {
-            return Err(RuntimeError::SystemError(SystemError::InvalidLockFlags));
-        }
+        check_lock_flags(LockedSubstateKind::ActorKeyValueEntry, None, flags)?;
 
         let actor_object_type: ActorStateRef = object_handle.try_into()?;
 
@@ -2754,10 +2811,17 @@ impl<'a, Y: SystemBasedKernelApi> SystemExecutionTraceApi<RuntimeError> for Syst
     // No costing should be applied
     #[trace_resources]
     fn update_instruction_index(&mut self, new_index: usize) -> Result<(), RuntimeError> {
-        self.api
-            .kernel_get_system()
-            .modules
-            .update_instruction_index(new_index);
+        let stack_id = self.api.kernel_get_current_stack_id_uncosted();
+        let system = self.api.kernel_get_system();
+        system.modules.update_instruction_index(stack_id, new_index);
+        Ok(())
+    }
+
+    // No costing should be applied
+    #[trace_resources]
+    fn clear_instruction_index(&mut self) -> Result<(), RuntimeError> {
+        let system = self.api.kernel_get_system();
+        system.modules.clear_instruction_index();
         Ok(())
     }
 }
@@ -2996,6 +3060,17 @@ impl<'a, Y: SystemBasedKernelApi> KernelSubstateApi<SystemLockData> for SystemSe
             .kernel_scan_keys::<K>(node_id, partition_num, limit)
     }
 
+    fn kernel_scan_keys_after<K: SubstateKeyContent>(
+        &mut self,
+        node_id: &NodeId,
+        partition_num: PartitionNumber,
+        after: Option<&SubstateKey>,
+        limit: u32,
+    ) -> Result<Vec<SubstateKey>, RuntimeError> {
+        self.api
+            .kernel_scan_keys_after::<K>(node_id, partition_num, after, limit)
+    }
+
     fn kernel_drain_substates<K: SubstateKeyContent>(
         &mut self,
         node_id: &NodeId,
@@ -3035,4 +3110,8 @@ impl<'a, Y: SystemBasedKernelApi> KernelInternalApi for SystemService<'a, Y> {
         self.api
             .kernel_read_substate_uncosted(node_id, partition_num, substate_key)
     }
+
+    fn kernel_get_owned_nodes_uncosted(&self) -> Vec<NodeId> {
+        self.api.kernel_get_owned_nodes_uncosted()
+    }
 }
@@ -18,15 +18,19 @@ use crate::system::actor::Actor;
 use crate::system::actor::BlueprintHookActor;
 use crate::system::actor::FunctionActor;
 use crate::system::actor::MethodActor;
+use crate::system::actor::MethodType;
 use crate::system::module::InitSystemModule;
 use crate::system::system::SystemService;
 use crate::system::system_callback_api::SystemCallbackObject;
 use crate::system::system_db_reader::SystemDatabaseReader;
 use crate::system::system_modules::auth::AuthModule;
 use crate::system::system_modules::costing::*;
+use crate::system::system_modules::debugger::{DebuggerConfig, DebuggerModule};
+use crate::system::system_modules::execution_audit::{ExecutionAudit, ExecutionAuditModule};
 use crate::system::system_modules::execution_trace::ExecutionTraceModule;
 use crate::system::system_modules::kernel_trace::KernelTraceModule;
-use crate::system::system_modules::limits::LimitsModule;
+use crate::system::system_modules::limits::{LimitsModule, TransactionQuotas};
+use crate::system::system_modules::metrics::{ExecutionMetricsSink, MetricsModule};
 use crate::system::system_modules::transaction_runtime::TransactionRuntimeModule;
 use crate::system::system_modules::{EnabledModules, SystemModuleMixer};
 use crate::system::system_substates::KeyValueEntrySubstate;
@@ -44,6 +48,7 @@ use radix_engine_interface::blueprints::identity::IDENTITY_BLUEPRINT;
 use radix_engine_interface::blueprints::package::*;
 use radix_engine_interface::blueprints::transaction_processor::*;
 use radix_substate_store_interface::interface::*;
+use radix_transactions::manifest::ManifestInstructionSet;
 use radix_transactions::model::*;
 
 #[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
@@ -134,6 +139,10 @@ impl SystemBoot {
         SystemBoot::V2(SystemVersion::V4, parameters)
     }
 
+    pub fn dugong_reentrancy_guard_for_previous_parameters(parameters: SystemParameters) -> Self {
+        SystemBoot::V2(SystemVersion::V5, parameters)
+    }
+
     pub fn bottlenose(network_definition: NetworkDefinition) -> Self {
         SystemBoot::V1(SystemParameters::bottlenose(network_definition))
     }
@@ -149,6 +158,11 @@ impl SystemBoot {
         }
     }
 
+    /// Returns the fee table of the system version.
+    pub fn fee_table(&self) -> FeeTable {
+        FeeTable::new(self.system_version())
+    }
+
     pub fn into_parameters(self) -> SystemParameters {
         match self {
             Self::V1(parameters) => parameters,
@@ -164,11 +178,12 @@ pub enum SystemVersion {
     V2,
     V3,
     V4,
+    V5,
 }
 
 impl SystemVersion {
     pub const fn latest() -> Self {
-        Self::V4
+        Self::V5
     }
 
     fn create_auth_module(
@@ -258,6 +273,10 @@ impl SystemVersion {
     pub fn assert_access_rule_is_noop_when_auth_module_disabled(self) -> bool {
         self >= SystemVersion::V4
     }
+
+    pub fn reject_reentrancy_into_non_reentrant_blueprints(self) -> bool {
+        self >= SystemVersion::V5
+    }
 }
 
 #[derive(Clone)]
@@ -376,6 +395,9 @@ pub struct SystemSelfInit {
     pub enable_cost_breakdown: bool,
     pub execution_trace: Option<usize>,
     pub enable_debug_information: bool,
+    pub enable_execution_audit: bool,
+    pub debugger: Option<DebuggerConfig>,
+    pub metrics_sink: Option<Arc<dyn ExecutionMetricsSink>>,
 
     // Configuration
     pub system_parameters: SystemParameters,
@@ -393,6 +415,9 @@ impl SystemSelfInit {
             enable_kernel_trace: execution_config.enable_kernel_trace,
             enable_cost_breakdown: execution_config.enable_cost_breakdown,
             enable_debug_information: execution_config.enable_debug_information,
+            enable_execution_audit: execution_config.enable_execution_audit,
+            debugger: execution_config.debugger,
+            metrics_sink: execution_config.metrics_sink,
             execution_trace: execution_config.execution_trace,
             system_overrides: execution_config.system_overrides,
             system_logic_version,
@@ -409,6 +434,7 @@ pub struct System<V: SystemCallbackObject> {
     pub auth_cache: NonIterMap<CanonicalBlueprintId, AuthConfig>,
     pub modules: SystemModuleMixer,
     pub finalization: SystemFinalization,
+    pub failure_context: Option<TransactionFailureContext>,
 }
 
 pub trait HasModules {
@@ -449,6 +475,7 @@ impl<V: SystemCallbackObject> System<V> {
             modules,
             finalization,
             versioned_system_logic,
+            failure_context: None,
         }
     }
 
@@ -659,6 +686,7 @@ impl<V: SystemCallbackObject> System<V> {
     ) -> (
         FeeReserveFinalizationSummary,
         IndexMap<NodeId, Decimal>,
+        Vec<LockedFee>,
         Vec<(EventTypeIdentifier, Vec<u8>)>,
         CostingParameters,
         TransactionCostingParameters,
@@ -702,6 +730,7 @@ impl<V: SystemCallbackObject> System<V> {
         let (fee_reserve_finalization, costing_parameters, transaction_costing_parameters) =
             fee_reserve.finalize();
         let mut fee_payments: IndexMap<NodeId, Decimal> = index_map_new();
+        let mut locked_fees = Vec::with_capacity(fee_reserve_finalization.locked_fees.len());
         let mut required = fee_reserve_finalization.total_cost();
         let mut collected_fees = LiquidFungibleResource::new(Decimal::ZERO);
         for (vault_id, mut locked, contingent) in
@@ -720,6 +749,13 @@ impl<V: SystemCallbackObject> System<V> {
             // NOTE: Decimal arithmetic operation safe unwrap.
             // No chance to overflow considering current costing parameters
 
+            locked_fees.push(LockedFee {
+                vault_id,
+                locked: locked.amount(),
+                contingent,
+                paid: amount,
+            });
+
             // Take fees
             collected_fees.put(locked.take_by_amount(amount).unwrap());
             required = required.checked_sub(amount).unwrap();
@@ -894,9 +930,13 @@ impl<V: SystemCallbackObject> System<V> {
             ));
         }
 
+        // Locks were settled in reverse order
+        locked_fees.reverse();
+
         (
             fee_reserve_finalization,
             fee_payments,
+            locked_fees,
             events,
             costing_parameters,
             transaction_costing_parameters,
@@ -1226,6 +1266,7 @@ impl<V: SystemCallbackObject> System<V> {
             transaction_costing_parameters,
             fee_summary,
             result,
+            None,
         )
     }
 
@@ -1263,7 +1304,9 @@ impl<V: SystemCallbackObject> System<V> {
     ) -> TransactionReceipt {
         let print_execution_summary = modules.is_kernel_trace_enabled();
         let execution_trace_enabled = modules.is_execution_trace_enabled();
-        let (costing_module, runtime_module, execution_trace_module) = modules.unpack();
+        let execution_audit_enabled = modules.is_execution_audit_enabled();
+        let (costing_module, runtime_module, execution_trace_module, execution_audit_module) =
+            modules.unpack();
         let (mut fee_reserve, cost_breakdown, detailed_cost_breakdown) =
             costing_module.unpack_for_receipt();
         let is_success = outcome.is_ok();
@@ -1278,6 +1321,7 @@ impl<V: SystemCallbackObject> System<V> {
         let (
             fee_reserve_finalization,
             paying_vaults,
+            locked_fees,
             finalization_events,
             costing_parameters,
             transaction_costing_parameters,
@@ -1341,12 +1385,18 @@ impl<V: SystemCallbackObject> System<V> {
         } else {
             None
         };
+        let execution_audit = if execution_audit_enabled {
+            Some(execution_audit_module.finalize())
+        } else {
+            None
+        };
 
         let fee_summary = fee_reserve_finalization.into();
         let result = TransactionResult::Commit(CommitResult {
             state_updates,
             state_update_summary,
             fee_source: FeeSource { paying_vaults },
+            locked_fees,
             fee_destination,
             outcome: match outcome {
                 Ok(o) => TransactionOutcome::Success(o),
@@ -1367,6 +1417,7 @@ impl<V: SystemCallbackObject> System<V> {
             transaction_costing_parameters,
             fee_summary,
             result,
+            execution_audit,
         )
     }
 
@@ -1378,6 +1429,7 @@ impl<V: SystemCallbackObject> System<V> {
         transaction_costing_parameters: TransactionCostingParameters,
         fee_summary: TransactionFeeSummary,
         result: TransactionResult,
+        execution_audit: Option<ExecutionAudit>,
     ) -> TransactionReceipt {
         let transaction_costing_parameters = TransactionCostingParametersReceiptV2 {
             tip_proportion: transaction_costing_parameters.tip.proportion(),
@@ -1391,6 +1443,7 @@ impl<V: SystemCallbackObject> System<V> {
 
         let debug_information = detailed_cost_breakdown.map(|b| TransactionDebugInformation {
             detailed_execution_cost_breakdown: b.detailed_execution_cost_breakdown,
+            execution_cost_breakdown_by_instruction: b.execution_cost_breakdown_by_instruction,
         });
 
         let receipt = TransactionReceipt {
@@ -1401,6 +1454,8 @@ impl<V: SystemCallbackObject> System<V> {
             result,
             resources_usage: None,
             debug_information,
+            execution_audit,
+            failure_context: None,
         };
 
         // Dump summary
@@ -1412,6 +1467,42 @@ impl<V: SystemCallbackObject> System<V> {
         receipt
     }
 
+    /// Records where the intents failed to execute, while the call frames of the error are still
+    /// on the stack.
+    fn record_failure_context<Y: SystemBasedKernelApi>(
+        api: &mut Y,
+        executable: &ExecutableTransaction,
+    ) {
+        let intent_index = api.kernel_get_current_stack_id_uncosted();
+        let call_path = api
+            .kernel_get_call_frame_data_stack_uncosted()
+            .into_iter()
+            .cloned()
+            .collect();
+        let system = api.kernel_get_system();
+        let instruction_index = system
+            .modules
+            .costing_mut_even_if_disabled()
+            .current_instruction
+            .filter(|(instruction_intent_index, _)| *instruction_intent_index == intent_index)
+            .map(|(_, instruction_index)| instruction_index);
+        let instruction = instruction_index.and_then(|instruction_index| {
+            let intent = executable.all_intents().nth(intent_index)?;
+            // Decode the instructions as the intent processor of the system version did
+            if system.versioned_system_logic <= SystemVersion::V1 {
+                decode_instruction::<InstructionV1>(&intent.encoded_instructions, instruction_index)
+            } else {
+                decode_instruction::<InstructionV2>(&intent.encoded_instructions, instruction_index)
+            }
+        });
+        system.failure_context = Some(TransactionFailureContext {
+            intent_index,
+            instruction_index,
+            instruction,
+            call_path,
+        });
+    }
+
     fn resolve_modules(
         executable: &ExecutableTransaction,
         init_input: SystemSelfInit,
@@ -1432,11 +1523,22 @@ impl<V: SystemCallbackObject> System<V> {
             if init_input.execution_trace.is_some() {
                 enabled_modules |= EnabledModules::EXECUTION_TRACE;
             }
+            if init_input.enable_execution_audit {
+                enabled_modules |= EnabledModules::EXECUTION_AUDIT;
+            }
+            if init_input.debugger.is_some() {
+                enabled_modules |= EnabledModules::DEBUGGER;
+            }
+            if init_input.metrics_sink.is_some() {
+                enabled_modules |= EnabledModules::METRICS;
+            }
 
             enabled_modules
         };
 
         let mut abort_when_loan_repaid = false;
+        let mut transaction_quotas = TransactionQuotas::unlimited();
+        let mut allow_nested_transaction_processor = false;
 
         // Override system configuration
         if let Some(system_overrides) = &init_input.system_overrides {
@@ -1467,10 +1569,19 @@ impl<V: SystemCallbackObject> System<V> {
             if system_overrides.abort_when_loan_repaid {
                 abort_when_loan_repaid = true;
             }
+
+            if let Some(quotas_override) = &system_overrides.transaction_quotas {
+                transaction_quotas = *quotas_override;
+            }
+
+            if system_overrides.allow_nested_transaction_processor {
+                allow_nested_transaction_processor = true;
+            }
         }
 
         let costing_module = CostingModule {
             current_depth: 0,
+            current_instruction: None,
             fee_reserve: SystemLoanFeeReserve::new(
                 system_parameters.costing_parameters,
                 executable.costing_parameters().clone(),
@@ -1503,7 +1614,8 @@ impl<V: SystemCallbackObject> System<V> {
                     print_execution_summary,
                     costing_module.clone(),
                 )
-            })?;
+            })?
+            .with_nested_transaction_processor(allow_nested_transaction_processor);
 
         let module_mixer = SystemModuleMixer::new(
             enabled_modules,
@@ -1513,9 +1625,19 @@ impl<V: SystemCallbackObject> System<V> {
                 *executable.unique_hash(),
             ),
             auth_module,
-            LimitsModule::from_params(system_parameters.limit_parameters),
+            LimitsModule::from_params(system_parameters.limit_parameters)
+                .with_quotas(transaction_quotas),
             costing_module,
             ExecutionTraceModule::new(init_input.execution_trace.unwrap_or(0)),
+            ExecutionAuditModule::default(),
+            init_input
+                .debugger
+                .map(DebuggerModule::new)
+                .unwrap_or_default(),
+            init_input
+                .metrics_sink
+                .map(MetricsModule::new)
+                .unwrap_or_default(),
         );
 
         Ok(module_mixer)
@@ -1735,11 +1857,17 @@ impl<V: SystemCallbackObject> KernelTransactionExecutor for System<V> {
 
         let system_logic_version = system_service.system().versioned_system_logic;
 
-        let output = system_logic_version.execute_transaction(
+        let output = match system_logic_version.execute_transaction(
             api,
             executable,
             global_address_reservations,
-        )?;
+        ) {
+            Ok(output) => output,
+            Err(error) => {
+                Self::record_failure_context(api, executable);
+                return Err(error);
+            }
+        };
 
         Ok(output)
     }
@@ -1838,12 +1966,14 @@ impl<V: SystemCallbackObject> KernelTransactionExecutor for System<V> {
             println!("{:?}", interpretation_result);
         }
 
+        self.modules.publish_metrics();
+
         let result_type = Self::determine_result_type(
             interpretation_result,
             &mut self.modules.costing_mut_even_if_disabled().fee_reserve,
         );
 
-        match result_type {
+        let mut receipt = match result_type {
             TransactionResultType::Reject(reason) => {
                 Self::create_rejection_receipt(reason, self.modules)
             }
@@ -1853,7 +1983,9 @@ impl<V: SystemCallbackObject> KernelTransactionExecutor for System<V> {
             TransactionResultType::Commit(outcome) => {
                 Self::create_commit_receipt(outcome, track, self.modules, self.finalization)
             }
-        }
+        };
+        receipt.failure_context = self.failure_context;
+        receipt
     }
 }
 
@@ -2056,6 +2188,43 @@ impl<V: SystemCallbackObject> KernelCallbackObject for System<V> {
                     }
                 }
 
+                // Reject re-entering a non-reentrant global object
+                if let Actor::Method(MethodActor {
+                    method_type: MethodType::Main,
+                    node_id,
+                    ..
+                }) = actor
+                {
+                    if system
+                        .system()
+                        .versioned_system_logic
+                        .reject_reentrancy_into_non_reentrant_blueprints()
+                        && node_id.is_global()
+                        && definition
+                            .interface
+                            .feature_set
+                            .contains(BLUEPRINT_NON_REENTRANT_FEATURE)
+                    {
+                        let call_frames = system.api().kernel_get_call_frame_data_stack_uncosted();
+                        let is_reentered =
+                            call_frames.iter().rev().skip(1).any(|caller| match caller {
+                                Actor::Method(MethodActor {
+                                    method_type: MethodType::Main,
+                                    node_id: caller_node_id,
+                                    ..
+                                }) => caller_node_id == node_id,
+                                _ => false,
+                            });
+                        if is_reentered {
+                            return Err(RuntimeError::SystemUpstreamError(
+                                SystemUpstreamError::ReentrancyDetected {
+                                    address: GlobalAddress::new_or_panic(node_id.0),
+                                },
+                            ));
+                        }
+                    }
+                }
+
                 // Execute
                 let export = definition
                     .function_exports
@@ -2374,3 +2543,14 @@ impl<V: SystemCallbackObject> KernelCallbackObject for System<V> {
         }
     }
 }
+
+fn decode_instruction<I: ManifestInstructionSet + ManifestDecode + ManifestCategorize>(
+    encoded_instructions: &[u8],
+    instruction_index: usize,
+) -> Option<InstructionV2> {
+    manifest_decode::<Vec<I>>(encoded_instructions)
+        .ok()?
+        .into_iter()
+        .nth(instruction_index)
+        .map(ManifestInstructionSet::into_any)
+}
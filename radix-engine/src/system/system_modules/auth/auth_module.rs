@@ -48,6 +48,9 @@ pub struct AuthModule {
     /// function call and add virtual resources to the transaction processor
     /// call frame
     pub v1_transaction_processor_proofs_for_injection: Option<AuthZoneInit>,
+    /// Whether the root only functions of the transaction processor can be called from any call
+    /// frame, to run nested manifests
+    pub allow_nested_transaction_processor: bool,
 }
 
 pub enum AuthorizationCheckResult {
@@ -74,15 +77,25 @@ impl AuthModule {
     pub fn new() -> Self {
         Self {
             v1_transaction_processor_proofs_for_injection: None,
+            allow_nested_transaction_processor: false,
         }
     }
 
     pub fn new_with_transaction_processor_auth_zone(auth_zone_init: AuthZoneInit) -> Self {
         Self {
             v1_transaction_processor_proofs_for_injection: Some(auth_zone_init),
+            allow_nested_transaction_processor: false,
         }
     }
 
+    pub fn with_nested_transaction_processor(
+        mut self,
+        allow_nested_transaction_processor: bool,
+    ) -> Self {
+        self.allow_nested_transaction_processor = allow_nested_transaction_processor;
+        self
+    }
+
     // In SystemV1, the transaction processor is initiated via a call_function, and we
     // used this to inject the signature proofs and resource simulation.
     //
@@ -125,6 +125,10 @@ pub struct CostBreakdown {
 pub struct DetailedCostBreakdown {
     /// A more detailed cost breakdown with information on the depth.
     pub detailed_execution_cost_breakdown: Vec<DetailedExecutionCostBreakdownEntry>,
+    /// The execution cost units consumed while processing each manifest instruction, keyed by
+    /// intent index and instruction index. Costs applied outside of an instruction (e.g. on boot,
+    /// when switching intents or when dropping the worktop) are excluded.
+    pub execution_cost_breakdown_by_instruction: IndexMap<(usize, usize), u32>,
 }
 
 #[derive(Debug, Clone)]
@@ -141,6 +145,9 @@ pub struct CostingModule {
 
     /// This keeps track of the current kernel depth.
     pub current_depth: usize,
+    /// The intent index and instruction index of the instruction currently being processed, if
+    /// any.
+    pub current_instruction: Option<(usize, usize)>,
 }
 
 impl CostingModule {
@@ -176,6 +183,13 @@ impl CostingModule {
                         cost_units,
                     },
                 });
+            if let Some(instruction) = self.current_instruction {
+                detailed_cost_breakdown
+                    .execution_cost_breakdown_by_instruction
+                    .entry(instruction)
+                    .or_default()
+                    .add_assign(cost_units);
+            }
         }
 
         Ok(())
@@ -328,6 +342,14 @@ impl CostingModule {
         Ok(())
     }
 
+    pub fn update_instruction_index(&mut self, intent_index: usize, new_index: usize) {
+        self.current_instruction = Some((intent_index, new_index));
+    }
+
+    pub fn clear_instruction_index(&mut self) {
+        self.current_instruction = None;
+    }
+
     pub fn lock_fee(
         &mut self,
         vault_id: NodeId,
@@ -64,6 +64,13 @@ lazy_static! {
 /// - Execution time for 100,000,000 cost units' worth of computation: <= 1 second
 /// - Baseline: 1 microsecond = 100 cost units
 ///
+/// ## Versioning
+/// The fee table is versioned by the [`SystemVersion`] of the network, which is stored in the
+/// `SystemBoot` substate, alongside the per-network [`CostingParameters`] (e.g. the cost unit
+/// prices and limits). Both are updated by protocol updates, so a change to the fee table must
+/// be keyed on a new [`SystemVersion`], to keep the costing of past transactions unchanged.
+///
+/// [`CostingParameters`]: crate::transaction::CostingParameters
 #[derive(Debug, Clone, ScryptoSbor)]
 pub struct FeeTable {
     wasm_execution_units_divider: u32,
@@ -0,0 +1,302 @@
+use crate::errors::*;
+use crate::internal_prelude::*;
+use crate::kernel::kernel_api::*;
+use crate::kernel::kernel_callback_api::*;
+use crate::system::actor::*;
+use crate::system::module::*;
+use crate::system::system_callback::*;
+use radix_engine_interface::api::field_api::LockFlags;
+use sbor::rust::fmt;
+use sbor::rust::sync::Arc;
+
+//===================================================================================
+// Note: Debugger must not produce any error or transactional side effect!
+//===================================================================================
+
+/// A condition upon which the [`DebuggerModule`] pauses execution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// Breaks before a function or method of the package is invoked, optionally narrowed down to
+    /// a blueprint and to a function or method name.
+    Invocation {
+        package_address: PackageAddress,
+        blueprint_name: Option<String>,
+        ident: Option<String>,
+    },
+    /// Breaks before a substate of the node is opened, optionally narrowed down to a partition.
+    SubstateAccess {
+        node_id: NodeId,
+        partition_num: Option<PartitionNumber>,
+    },
+}
+
+impl Breakpoint {
+    fn matches(&self, event: &DebuggerEvent) -> bool {
+        match (self, event) {
+            (
+                Breakpoint::Invocation {
+                    package_address,
+                    blueprint_name,
+                    ident,
+                },
+                DebuggerEvent::Invocation { callee, .. },
+            ) => {
+                let Some(blueprint_id) = callee.blueprint_id() else {
+                    return false;
+                };
+                let callee_ident = match callee.as_ref() {
+                    Actor::Method(MethodActor { ident, .. })
+                    | Actor::Function(FunctionActor { ident, .. }) => Some(ident),
+                    Actor::BlueprintHook(..) | Actor::Root => None,
+                };
+                blueprint_id.package_address == *package_address
+                    && blueprint_name
+                        .as_ref()
+                        .map_or(true, |name| *name == blueprint_id.blueprint_name)
+                    && ident
+                        .as_ref()
+                        .map_or(true, |ident| Some(ident) == callee_ident)
+            }
+            (
+                Breakpoint::SubstateAccess {
+                    node_id,
+                    partition_num,
+                },
+                DebuggerEvent::SubstateAccess {
+                    node_id: accessed_node_id,
+                    partition_num: accessed_partition_num,
+                    ..
+                },
+            ) => {
+                node_id == accessed_node_id
+                    && partition_num.map_or(true, |partition_num| {
+                        partition_num == *accessed_partition_num
+                    })
+            }
+            _ => false,
+        }
+    }
+}
+
+/// The kernel call at which execution was paused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DebuggerEvent {
+    /// A function or method is about to be invoked.
+    Invocation {
+        callee: Box<Actor>,
+        args: IndexedScryptoValue,
+    },
+    /// A substate is about to be opened.
+    SubstateAccess {
+        node_id: NodeId,
+        partition_num: PartitionNumber,
+        substate_key: SubstateKey,
+        flags: LockFlags,
+    },
+}
+
+/// The state of execution at a hit breakpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DebuggerBreak {
+    /// The index of the hit breakpoint within [`DebuggerConfig::breakpoints`].
+    pub breakpoint_index: usize,
+    pub event: DebuggerEvent,
+    /// The actor of the current call frame (i.e. the caller, for an invocation).
+    pub actor: Actor,
+    /// The depth of the current call frame.
+    pub depth: usize,
+    /// The nodes owned by the current call frame.
+    pub owned_nodes: Vec<NodeId>,
+}
+
+pub type DebuggerCallback = Arc<dyn Fn(&DebuggerBreak) + Send + Sync>;
+
+/// The breakpoints to pause execution at, and the callback to invoke when one is hit.
+///
+/// The callback is invoked synchronously, so execution stays paused until it returns. For example,
+/// a debugger frontend can send each break over a channel, and wait for the user to continue.
+#[derive(Clone)]
+pub struct DebuggerConfig {
+    pub breakpoints: Vec<Breakpoint>,
+    pub callback: DebuggerCallback,
+}
+
+impl DebuggerConfig {
+    pub fn new(callback: impl Fn(&DebuggerBreak) + Send + Sync + 'static) -> Self {
+        Self {
+            breakpoints: Vec::new(),
+            callback: Arc::new(callback),
+        }
+    }
+
+    pub fn with_breakpoint(mut self, breakpoint: Breakpoint) -> Self {
+        self.breakpoints.push(breakpoint);
+        self
+    }
+}
+
+impl fmt::Debug for DebuggerConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DebuggerConfig")
+            .field("breakpoints", &self.breakpoints)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DebuggerModule {
+    config: Option<DebuggerConfig>,
+}
+
+impl DebuggerModule {
+    pub fn new(config: DebuggerConfig) -> Self {
+        Self {
+            config: Some(config),
+        }
+    }
+
+    fn on_event<M: SystemModuleApiFor<Self>>(api: &mut M, event: DebuggerEvent) {
+        let Some(config) = &api.module().config else {
+            return;
+        };
+        let Some(breakpoint_index) = config
+            .breakpoints
+            .iter()
+            .position(|breakpoint| breakpoint.matches(&event))
+        else {
+            return;
+        };
+        let callback = config.callback.clone();
+
+        let debugger_break = DebuggerBreak {
+            breakpoint_index,
+            event,
+            actor: api.system_state().current_call_frame.clone(),
+            depth: api.current_stack_depth_uncosted(),
+            owned_nodes: api.current_owned_nodes_uncosted(),
+        };
+        callback(&debugger_break);
+    }
+}
+
+impl InitSystemModule for DebuggerModule {}
+impl ResolvableSystemModule for DebuggerModule {
+    #[inline]
+    fn resolve_from_system(system: &mut impl HasModules) -> &mut Self {
+        &mut system.modules_mut().debugger
+    }
+}
+impl PrivilegedSystemModule for DebuggerModule {}
+
+impl<ModuleApi: SystemModuleApiFor<Self>> SystemModule<ModuleApi> for DebuggerModule {
+    fn before_invoke(
+        api: &mut ModuleApi,
+        invocation: &KernelInvocation<Actor>,
+    ) -> Result<(), RuntimeError> {
+        Self::on_event(
+            api,
+            DebuggerEvent::Invocation {
+                callee: Box::new(invocation.call_frame_data.clone()),
+                args: invocation.args.clone(),
+            },
+        );
+        Ok(())
+    }
+
+    fn on_open_substate(
+        api: &mut ModuleApi,
+        event: &OpenSubstateEvent,
+    ) -> Result<(), RuntimeError> {
+        if let OpenSubstateEvent::Start {
+            node_id,
+            partition_num,
+            substate_key,
+            flags,
+        } = event
+        {
+            Self::on_event(
+                api,
+                DebuggerEvent::SubstateAccess {
+                    node_id: **node_id,
+                    partition_num: **partition_num,
+                    substate_key: (*substate_key).clone(),
+                    flags: **flags,
+                },
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn function_actor(package_address: PackageAddress, blueprint_name: &str, ident: &str) -> Actor {
+        Actor::Function(FunctionActor {
+            blueprint_id: BlueprintId::new(&package_address, blueprint_name),
+            ident: ident.to_string(),
+            auth_zone: NodeId([0; NodeId::LENGTH]),
+        })
+    }
+
+    fn invocation(callee: Actor) -> DebuggerEvent {
+        DebuggerEvent::Invocation {
+            callee: Box::new(callee),
+            args: IndexedScryptoValue::unit(),
+        }
+    }
+
+    #[test]
+    fn invocation_breakpoint_is_narrowed_down_by_blueprint_and_ident() {
+        let event = invocation(function_actor(ACCOUNT_PACKAGE, "Account", "create"));
+        let breakpoint =
+            |blueprint_name: Option<&str>, ident: Option<&str>| Breakpoint::Invocation {
+                package_address: ACCOUNT_PACKAGE,
+                blueprint_name: blueprint_name.map(str::to_string),
+                ident: ident.map(str::to_string),
+            };
+
+        assert!(breakpoint(None, None).matches(&event));
+        assert!(breakpoint(Some("Account"), None).matches(&event));
+        assert!(breakpoint(Some("Account"), Some("create")).matches(&event));
+        assert!(!breakpoint(Some("Identity"), None).matches(&event));
+        assert!(!breakpoint(None, Some("create_advanced")).matches(&event));
+        assert!(!Breakpoint::Invocation {
+            package_address: IDENTITY_PACKAGE,
+            blueprint_name: None,
+            ident: None,
+        }
+        .matches(&event));
+        assert!(!breakpoint(None, None).matches(&invocation(Actor::Root)));
+    }
+
+    #[test]
+    fn substate_access_breakpoint_is_narrowed_down_by_partition() {
+        let event = DebuggerEvent::SubstateAccess {
+            node_id: FAUCET.into_node_id(),
+            partition_num: MAIN_BASE_PARTITION,
+            substate_key: SubstateKey::Field(0u8),
+            flags: LockFlags::read_only(),
+        };
+        let breakpoint =
+            |node_id: NodeId, partition_num: Option<PartitionNumber>| Breakpoint::SubstateAccess {
+                node_id,
+                partition_num,
+            };
+
+        assert!(breakpoint(FAUCET.into_node_id(), None).matches(&event));
+        assert!(breakpoint(FAUCET.into_node_id(), Some(MAIN_BASE_PARTITION)).matches(&event));
+        assert!(
+            !breakpoint(FAUCET.into_node_id(), Some(TYPE_INFO_FIELD_PARTITION)).matches(&event)
+        );
+        assert!(!breakpoint(CONSENSUS_MANAGER.into_node_id(), None).matches(&event));
+        assert!(
+            !breakpoint(FAUCET.into_node_id(), None).matches(&invocation(function_actor(
+                FAUCET_PACKAGE,
+                "Faucet",
+                "new"
+            )))
+        );
+    }
+}
@@ -0,0 +1,255 @@
+use crate::errors::*;
+use crate::internal_prelude::*;
+use crate::kernel::kernel_api::*;
+use crate::kernel::kernel_callback_api::*;
+use crate::system::actor::Actor;
+use crate::system::module::*;
+use crate::system::system_callback::*;
+use crate::track::interface::NodeSubstates;
+
+//===================================================================================
+// Note: ExecutionAudit must not produce any error or transactional side effect!
+//===================================================================================
+
+/// The kind of kernel call recorded in an [`ExecutionAuditEntry`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionAuditEventType {
+    Invoke,
+    InvokeReturn,
+    AllocateNodeId,
+    CreateNode,
+    DropNode,
+    OpenSubstate,
+    ReadSubstate,
+    WriteSubstate,
+    SetSubstate,
+    RemoveSubstate,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutionAuditEntry {
+    pub event_type: ExecutionAuditEventType,
+    /// The call frame depth at which the kernel call was made.
+    pub depth: usize,
+    /// The hash of the canonical encoding of the kernel call's input (or output, for
+    /// [`ExecutionAuditEventType::InvokeReturn`] and [`ExecutionAuditEventType::ReadSubstate`]).
+    pub hash: Hash,
+}
+
+/// The kernel calls made during a transaction's execution, in order.
+///
+/// Executing the same transaction against the same state must always produce the same audit, so
+/// comparing the audits of two runs pinpoints the first kernel call at which they diverged.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecutionAudit {
+    pub entries: Vec<ExecutionAuditEntry>,
+}
+
+impl ExecutionAudit {
+    /// Returns the index of the first entry which differs between this and the other audit (which
+    /// may be the length of the shorter one), or `None` if they are identical.
+    pub fn first_difference(&self, other: &ExecutionAudit) -> Option<usize> {
+        (0..self.entries.len().max(other.entries.len()))
+            .find(|index| self.entries.get(*index) != other.entries.get(*index))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionAuditModule {
+    audit: ExecutionAudit,
+}
+
+impl ExecutionAuditModule {
+    pub fn finalize(self) -> ExecutionAudit {
+        self.audit
+    }
+
+    fn record<M: SystemModuleApiFor<Self>>(
+        api: &mut M,
+        event_type: ExecutionAuditEventType,
+        encoded: &[u8],
+    ) {
+        let depth = api.current_stack_depth_uncosted();
+        api.module().audit.entries.push(ExecutionAuditEntry {
+            event_type,
+            depth,
+            hash: hash(encoded),
+        });
+    }
+}
+
+fn encode_substate_location(
+    node_id: &NodeId,
+    partition_num: &PartitionNumber,
+    substate_key: &SubstateKey,
+) -> Vec<u8> {
+    let mut encoded = node_id.as_bytes().to_vec();
+    encoded.push(partition_num.0);
+    encoded.extend(scrypto_encode(substate_key).unwrap());
+    encoded
+}
+
+fn encode_node(node_id: &NodeId, node_substates: &NodeSubstates) -> Vec<u8> {
+    let mut encoded = node_id.as_bytes().to_vec();
+    for (partition_num, substates) in node_substates {
+        for (substate_key, value) in substates {
+            encoded.push(partition_num.0);
+            encoded.extend(scrypto_encode(substate_key).unwrap());
+            encoded.extend(value.as_slice());
+        }
+    }
+    encoded
+}
+
+impl InitSystemModule for ExecutionAuditModule {}
+impl ResolvableSystemModule for ExecutionAuditModule {
+    #[inline]
+    fn resolve_from_system(system: &mut impl HasModules) -> &mut Self {
+        &mut system.modules_mut().execution_audit
+    }
+}
+impl PrivilegedSystemModule for ExecutionAuditModule {}
+
+impl<ModuleApi: SystemModuleApiFor<Self>> SystemModule<ModuleApi> for ExecutionAuditModule {
+    fn before_invoke(
+        api: &mut ModuleApi,
+        invocation: &KernelInvocation<Actor>,
+    ) -> Result<(), RuntimeError> {
+        let mut encoded = scrypto_encode(&invocation.call_frame_data).unwrap();
+        encoded.extend(invocation.args.as_slice());
+        Self::record(api, ExecutionAuditEventType::Invoke, &encoded);
+        Ok(())
+    }
+
+    fn after_invoke(api: &mut ModuleApi, output: &IndexedScryptoValue) -> Result<(), RuntimeError> {
+        Self::record(
+            api,
+            ExecutionAuditEventType::InvokeReturn,
+            output.as_slice(),
+        );
+        Ok(())
+    }
+
+    fn on_allocate_node_id(
+        api: &mut ModuleApi,
+        entity_type: EntityType,
+    ) -> Result<(), RuntimeError> {
+        Self::record(
+            api,
+            ExecutionAuditEventType::AllocateNodeId,
+            &[entity_type as u8],
+        );
+        Ok(())
+    }
+
+    fn on_create_node(api: &mut ModuleApi, event: &CreateNodeEvent) -> Result<(), RuntimeError> {
+        if let CreateNodeEvent::Start(node_id, node_substates) = event {
+            let encoded = encode_node(node_id, node_substates);
+            Self::record(api, ExecutionAuditEventType::CreateNode, &encoded);
+        }
+        Ok(())
+    }
+
+    fn on_drop_node(api: &mut ModuleApi, event: &DropNodeEvent) -> Result<(), RuntimeError> {
+        if let DropNodeEvent::End(node_id, node_substates) = event {
+            let encoded = encode_node(node_id, node_substates);
+            Self::record(api, ExecutionAuditEventType::DropNode, &encoded);
+        }
+        Ok(())
+    }
+
+    fn on_open_substate(
+        api: &mut ModuleApi,
+        event: &OpenSubstateEvent,
+    ) -> Result<(), RuntimeError> {
+        if let OpenSubstateEvent::Start {
+            node_id,
+            partition_num,
+            substate_key,
+            flags,
+        } = event
+        {
+            let mut encoded = encode_substate_location(node_id, partition_num, substate_key);
+            encoded.extend(flags.bits().to_le_bytes());
+            Self::record(api, ExecutionAuditEventType::OpenSubstate, &encoded);
+        }
+        Ok(())
+    }
+
+    fn on_read_substate(
+        api: &mut ModuleApi,
+        event: &ReadSubstateEvent,
+    ) -> Result<(), RuntimeError> {
+        if let ReadSubstateEvent::OnRead { value, .. } = event {
+            Self::record(api, ExecutionAuditEventType::ReadSubstate, value.as_slice());
+        }
+        Ok(())
+    }
+
+    fn on_write_substate(
+        api: &mut ModuleApi,
+        event: &WriteSubstateEvent,
+    ) -> Result<(), RuntimeError> {
+        if let WriteSubstateEvent::Start { value, .. } = event {
+            Self::record(
+                api,
+                ExecutionAuditEventType::WriteSubstate,
+                value.as_slice(),
+            );
+        }
+        Ok(())
+    }
+
+    fn on_set_substate(api: &mut ModuleApi, event: &SetSubstateEvent) -> Result<(), RuntimeError> {
+        if let SetSubstateEvent::Start(node_id, partition_num, substate_key, value) = event {
+            let mut encoded = encode_substate_location(node_id, partition_num, substate_key);
+            encoded.extend(value.as_slice());
+            Self::record(api, ExecutionAuditEventType::SetSubstate, &encoded);
+        }
+        Ok(())
+    }
+
+    fn on_remove_substate(
+        api: &mut ModuleApi,
+        event: &RemoveSubstateEvent,
+    ) -> Result<(), RuntimeError> {
+        if let RemoveSubstateEvent::Start(node_id, partition_num, substate_key) = event {
+            let encoded = encode_substate_location(node_id, partition_num, substate_key);
+            Self::record(api, ExecutionAuditEventType::RemoveSubstate, &encoded);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(event_type: ExecutionAuditEventType, depth: usize, seed: u8) -> ExecutionAuditEntry {
+        ExecutionAuditEntry {
+            event_type,
+            depth,
+            hash: hash([seed]),
+        }
+    }
+
+    #[test]
+    fn first_difference_points_at_first_differing_entry() {
+        let audit = ExecutionAudit {
+            entries: vec![
+                entry(ExecutionAuditEventType::Invoke, 0, 1),
+                entry(ExecutionAuditEventType::ReadSubstate, 1, 2),
+                entry(ExecutionAuditEventType::InvokeReturn, 0, 3),
+            ],
+        };
+
+        let mut diverged = audit.clone();
+        diverged.entries[1].hash = hash([7]);
+        let mut truncated = audit.clone();
+        truncated.entries.pop();
+
+        assert_eq!(audit.first_difference(&audit.clone()), None);
+        assert_eq!(audit.first_difference(&diverged), Some(1));
+        assert_eq!(audit.first_difference(&truncated), Some(2));
+    }
+}
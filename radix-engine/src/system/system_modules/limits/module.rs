@@ -21,6 +21,9 @@ pub enum TransactionLimitsError {
     PanicMessageSizeTooLarge { actual: usize, max: usize },
     TooManyLogs,
     TooManyEvents,
+    TooManySyscalls { max: usize },
+    TooManySubstateWrites { max: usize },
+    TooManyNewNodes { max: usize },
 }
 
 pub struct TransactionLimitsConfig {
@@ -37,14 +40,49 @@ pub struct TransactionLimitsConfig {
     pub max_number_of_events: usize,
 }
 
+/// Quotas on the number of kernel operations of a transaction.
+///
+/// Unlike the [`LimitParameters`], the quotas are not part of the protocol: they are unlimited by
+/// default, and can be set by node operators through the `SystemOverrides`, to bound the
+/// worst-case resource usage of the transactions they execute beyond cost units.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TransactionQuotas {
+    /// The maximum number of invocations, and of node and substate operations.
+    pub max_number_of_syscalls: usize,
+    /// The maximum number of substates written, set or removed.
+    pub max_number_of_substate_writes: usize,
+    /// The maximum number of nodes created.
+    pub max_number_of_new_nodes: usize,
+}
+
+impl TransactionQuotas {
+    pub const fn unlimited() -> Self {
+        Self {
+            max_number_of_syscalls: usize::MAX,
+            max_number_of_substate_writes: usize::MAX,
+            max_number_of_new_nodes: usize::MAX,
+        }
+    }
+}
+
+impl Default for TransactionQuotas {
+    fn default() -> Self {
+        Self::unlimited()
+    }
+}
+
 /// Tracks and verifies transaction limits during transaction execution,
 /// if exceeded breaks execution with appropriate error.
 /// Default limits values are defined in radix-common/constants.
 /// Stores boundary values of the limits and returns them in transaction receipt.
 pub struct LimitsModule {
     config: TransactionLimitsConfig,
+    quotas: TransactionQuotas,
     heap_substate_total_bytes: usize,
     track_substate_total_bytes: usize,
+    number_of_syscalls: usize,
+    number_of_substate_writes: usize,
+    number_of_new_nodes: usize,
 }
 
 impl LimitsModule {
@@ -55,8 +93,12 @@ impl LimitsModule {
     pub fn new(limits_config: TransactionLimitsConfig) -> Self {
         LimitsModule {
             config: limits_config,
+            quotas: TransactionQuotas::unlimited(),
             heap_substate_total_bytes: 0,
             track_substate_total_bytes: 0,
+            number_of_syscalls: 0,
+            number_of_substate_writes: 0,
+            number_of_new_nodes: 0,
         }
     }
 
@@ -75,17 +117,69 @@ impl LimitsModule {
             max_panic_message_size: limit_parameters.max_panic_message_size,
         };
 
-        LimitsModule {
-            config,
-            heap_substate_total_bytes: 0,
-            track_substate_total_bytes: 0,
-        }
+        Self::new(config)
+    }
+
+    pub fn with_quotas(mut self, quotas: TransactionQuotas) -> Self {
+        self.quotas = quotas;
+        self
     }
 
     pub fn config(&self) -> &TransactionLimitsConfig {
         &self.config
     }
 
+    pub fn quotas(&self) -> &TransactionQuotas {
+        &self.quotas
+    }
+
+    pub fn process_syscall(&mut self) -> Result<(), RuntimeError> {
+        self.number_of_syscalls += 1;
+        if self.number_of_syscalls > self.quotas.max_number_of_syscalls {
+            return Err(RuntimeError::SystemModuleError(
+                SystemModuleError::TransactionLimitsError(
+                    TransactionLimitsError::TooManySyscalls {
+                        max: self.quotas.max_number_of_syscalls,
+                    },
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn process_substate_write(&mut self) -> Result<(), RuntimeError> {
+        self.process_syscall()?;
+        self.number_of_substate_writes += 1;
+        if self.number_of_substate_writes > self.quotas.max_number_of_substate_writes {
+            return Err(RuntimeError::SystemModuleError(
+                SystemModuleError::TransactionLimitsError(
+                    TransactionLimitsError::TooManySubstateWrites {
+                        max: self.quotas.max_number_of_substate_writes,
+                    },
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn process_new_node(&mut self) -> Result<(), RuntimeError> {
+        self.process_syscall()?;
+        self.number_of_new_nodes += 1;
+        if self.number_of_new_nodes > self.quotas.max_number_of_new_nodes {
+            return Err(RuntimeError::SystemModuleError(
+                SystemModuleError::TransactionLimitsError(
+                    TransactionLimitsError::TooManyNewNodes {
+                        max: self.quotas.max_number_of_new_nodes,
+                    },
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
     pub fn process_substate_key(&self, substate_key: &SubstateKey) -> Result<(), RuntimeError> {
         let len = match substate_key {
             SubstateKey::Map(map_key) => map_key.len(),
@@ -192,6 +286,8 @@ impl<ModuleApi: SystemModuleApiFor<Self>> SystemModule<ModuleApi> for LimitsModu
         api: &mut ModuleApi,
         invocation: &KernelInvocation<Actor>,
     ) -> Result<(), RuntimeError> {
+        api.module().process_syscall()?;
+
         // Check depth
         let current_depth = api.current_stack_depth_uncosted();
         if current_depth == api.module().config.max_call_depth {
@@ -220,6 +316,7 @@ impl<ModuleApi: SystemModuleApiFor<Self>> SystemModule<ModuleApi> for LimitsModu
 
         match event {
             CreateNodeEvent::Start(_node_id, node_substates) => {
+                limits.process_new_node()?;
                 for partitions in node_substates.values() {
                     for (key, value) in partitions {
                         limits.process_substate_key(key)?;
@@ -241,7 +338,10 @@ impl<ModuleApi: SystemModuleApiFor<Self>> SystemModule<ModuleApi> for LimitsModu
             DropNodeEvent::IOAccess(io_access) => {
                 api.module().process_io_access(io_access)?;
             }
-            DropNodeEvent::Start(..) | DropNodeEvent::End(..) => {}
+            DropNodeEvent::Start(..) => {
+                api.module().process_syscall()?;
+            }
+            DropNodeEvent::End(..) => {}
         }
 
         Ok(())
@@ -263,6 +363,7 @@ impl<ModuleApi: SystemModuleApiFor<Self>> SystemModule<ModuleApi> for LimitsModu
     ) -> Result<(), RuntimeError> {
         match event {
             OpenSubstateEvent::Start { substate_key, .. } => {
+                api.module().process_syscall()?;
                 api.module().process_substate_key(substate_key)?;
             }
             OpenSubstateEvent::IOAccess(io_access) => {
@@ -282,7 +383,9 @@ impl<ModuleApi: SystemModuleApiFor<Self>> SystemModule<ModuleApi> for LimitsModu
             ReadSubstateEvent::IOAccess(io_access) => {
                 api.module().process_io_access(io_access)?;
             }
-            ReadSubstateEvent::OnRead { .. } => {}
+            ReadSubstateEvent::OnRead { .. } => {
+                api.module().process_syscall()?;
+            }
         }
 
         Ok(())
@@ -294,6 +397,7 @@ impl<ModuleApi: SystemModuleApiFor<Self>> SystemModule<ModuleApi> for LimitsModu
     ) -> Result<(), RuntimeError> {
         match event {
             WriteSubstateEvent::Start { value, .. } => {
+                api.module().process_substate_write()?;
                 api.module().process_substate_value(value)?;
             }
             WriteSubstateEvent::IOAccess(io_access) => {
@@ -307,6 +411,7 @@ impl<ModuleApi: SystemModuleApiFor<Self>> SystemModule<ModuleApi> for LimitsModu
     fn on_set_substate(api: &mut ModuleApi, event: &SetSubstateEvent) -> Result<(), RuntimeError> {
         match event {
             SetSubstateEvent::Start(_node_id, _partition_num, substate_key, substate_value) => {
+                api.module().process_substate_write()?;
                 api.module().process_substate_key(substate_key)?;
                 api.module().process_substate_value(substate_value)?;
             }
@@ -318,12 +423,26 @@ impl<ModuleApi: SystemModuleApiFor<Self>> SystemModule<ModuleApi> for LimitsModu
         Ok(())
     }
 
+    fn on_close_substate(
+        api: &mut ModuleApi,
+        event: &CloseSubstateEvent,
+    ) -> Result<(), RuntimeError> {
+        match event {
+            CloseSubstateEvent::Start(..) => {
+                api.module().process_syscall()?;
+            }
+        }
+
+        Ok(())
+    }
+
     fn on_remove_substate(
         api: &mut ModuleApi,
         event: &RemoveSubstateEvent,
     ) -> Result<(), RuntimeError> {
         match event {
             RemoveSubstateEvent::Start(_node_id, _partition_num, substate_key) => {
+                api.module().process_substate_write()?;
                 api.module().process_substate_key(substate_key)?;
             }
             RemoveSubstateEvent::IOAccess(io_access) => {
@@ -336,7 +455,9 @@ impl<ModuleApi: SystemModuleApiFor<Self>> SystemModule<ModuleApi> for LimitsModu
 
     fn on_scan_keys(api: &mut ModuleApi, event: &ScanKeysEvent) -> Result<(), RuntimeError> {
         match event {
-            ScanKeysEvent::Start => {}
+            ScanKeysEvent::Start => {
+                api.module().process_syscall()?;
+            }
             ScanKeysEvent::IOAccess(io_access) => {
                 api.module().process_io_access(io_access)?;
             }
@@ -350,7 +471,9 @@ impl<ModuleApi: SystemModuleApiFor<Self>> SystemModule<ModuleApi> for LimitsModu
         event: &DrainSubstatesEvent,
     ) -> Result<(), RuntimeError> {
         match event {
-            DrainSubstatesEvent::Start(_) => {}
+            DrainSubstatesEvent::Start(_) => {
+                api.module().process_syscall()?;
+            }
             DrainSubstatesEvent::IOAccess(io_access) => {
                 api.module().process_io_access(io_access)?;
             }
@@ -364,7 +487,9 @@ impl<ModuleApi: SystemModuleApiFor<Self>> SystemModule<ModuleApi> for LimitsModu
         event: &ScanSortedSubstatesEvent,
     ) -> Result<(), RuntimeError> {
         match event {
-            ScanSortedSubstatesEvent::Start => {}
+            ScanSortedSubstatesEvent::Start => {
+                api.module().process_syscall()?;
+            }
             ScanSortedSubstatesEvent::IOAccess(io_access) => {
                 api.module().process_io_access(io_access)?;
             }
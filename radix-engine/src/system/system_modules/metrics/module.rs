@@ -0,0 +1,202 @@
+use crate::errors::*;
+use crate::internal_prelude::*;
+use crate::kernel::kernel_callback_api::*;
+use crate::system::actor::Actor;
+use crate::system::module::*;
+use crate::system::system_callback::*;
+use sbor::rust::fmt;
+use sbor::rust::sync::Arc;
+
+//===================================================================================
+// Note: Metrics must not produce any error or transactional side effect!
+//===================================================================================
+
+/// The number of kernel calls of each type made during a transaction's execution, along with
+/// the number of WASM instances created.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecutionMetrics {
+    pub invocations: u64,
+    pub wasm_instantiations: u64,
+    pub node_id_allocations: u64,
+    pub node_creations: u64,
+    pub node_drops: u64,
+    /// The number of substates opened, each of which acquires a lock until it is closed.
+    pub lock_acquisitions: u64,
+    pub substate_reads: u64,
+    /// The number of substates written through a handle, set, or removed.
+    pub substate_writes: u64,
+    pub substate_scans: u64,
+    pub substate_drains: u64,
+    pub stack_switches: u64,
+}
+
+/// A receiver of the [`ExecutionMetrics`] of each executed transaction, e.g. to export them to a
+/// monitoring system.
+///
+/// The metrics are pushed once, when the receipt is created, whatever the transaction's outcome.
+/// Transactions rejected before execution starts produce no metrics.
+pub trait ExecutionMetricsSink: Send + Sync {
+    fn on_transaction_end(&self, metrics: &ExecutionMetrics);
+}
+
+impl<F: Fn(&ExecutionMetrics) + Send + Sync> ExecutionMetricsSink for F {
+    fn on_transaction_end(&self, metrics: &ExecutionMetrics) {
+        self(metrics)
+    }
+}
+
+impl fmt::Debug for dyn ExecutionMetricsSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ExecutionMetricsSink")
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MetricsModule {
+    sink: Option<Arc<dyn ExecutionMetricsSink>>,
+    metrics: ExecutionMetrics,
+}
+
+impl MetricsModule {
+    pub fn new(sink: Arc<dyn ExecutionMetricsSink>) -> Self {
+        Self {
+            sink: Some(sink),
+            metrics: ExecutionMetrics::default(),
+        }
+    }
+
+    pub fn on_wasm_instantiation(&mut self) {
+        self.metrics.wasm_instantiations += 1;
+    }
+
+    /// Pushes the metrics collected so far to the sink.
+    pub fn publish(&self) {
+        if let Some(sink) = &self.sink {
+            sink.on_transaction_end(&self.metrics);
+        }
+    }
+
+    fn metrics<M: SystemModuleApiFor<Self>>(api: &mut M) -> &mut ExecutionMetrics {
+        &mut api.module().metrics
+    }
+}
+
+impl InitSystemModule for MetricsModule {}
+impl ResolvableSystemModule for MetricsModule {
+    #[inline]
+    fn resolve_from_system(system: &mut impl HasModules) -> &mut Self {
+        &mut system.modules_mut().metrics
+    }
+}
+impl PrivilegedSystemModule for MetricsModule {}
+
+impl<ModuleApi: SystemModuleApiFor<Self>> SystemModule<ModuleApi> for MetricsModule {
+    fn before_invoke(
+        api: &mut ModuleApi,
+        _invocation: &KernelInvocation<Actor>,
+    ) -> Result<(), RuntimeError> {
+        Self::metrics(api).invocations += 1;
+        Ok(())
+    }
+
+    fn on_allocate_node_id(
+        api: &mut ModuleApi,
+        _entity_type: EntityType,
+    ) -> Result<(), RuntimeError> {
+        Self::metrics(api).node_id_allocations += 1;
+        Ok(())
+    }
+
+    fn on_create_node(api: &mut ModuleApi, event: &CreateNodeEvent) -> Result<(), RuntimeError> {
+        if let CreateNodeEvent::Start(..) = event {
+            Self::metrics(api).node_creations += 1;
+        }
+        Ok(())
+    }
+
+    fn on_drop_node(api: &mut ModuleApi, event: &DropNodeEvent) -> Result<(), RuntimeError> {
+        if let DropNodeEvent::Start(..) = event {
+            Self::metrics(api).node_drops += 1;
+        }
+        Ok(())
+    }
+
+    fn on_open_substate(
+        api: &mut ModuleApi,
+        event: &OpenSubstateEvent,
+    ) -> Result<(), RuntimeError> {
+        if let OpenSubstateEvent::Start { .. } = event {
+            Self::metrics(api).lock_acquisitions += 1;
+        }
+        Ok(())
+    }
+
+    fn on_read_substate(
+        api: &mut ModuleApi,
+        event: &ReadSubstateEvent,
+    ) -> Result<(), RuntimeError> {
+        if let ReadSubstateEvent::OnRead { .. } = event {
+            Self::metrics(api).substate_reads += 1;
+        }
+        Ok(())
+    }
+
+    fn on_write_substate(
+        api: &mut ModuleApi,
+        event: &WriteSubstateEvent,
+    ) -> Result<(), RuntimeError> {
+        if let WriteSubstateEvent::Start { .. } = event {
+            Self::metrics(api).substate_writes += 1;
+        }
+        Ok(())
+    }
+
+    fn on_set_substate(api: &mut ModuleApi, event: &SetSubstateEvent) -> Result<(), RuntimeError> {
+        if let SetSubstateEvent::Start(..) = event {
+            Self::metrics(api).substate_writes += 1;
+        }
+        Ok(())
+    }
+
+    fn on_remove_substate(
+        api: &mut ModuleApi,
+        event: &RemoveSubstateEvent,
+    ) -> Result<(), RuntimeError> {
+        if let RemoveSubstateEvent::Start(..) = event {
+            Self::metrics(api).substate_writes += 1;
+        }
+        Ok(())
+    }
+
+    fn on_scan_keys(api: &mut ModuleApi, event: &ScanKeysEvent) -> Result<(), RuntimeError> {
+        if let ScanKeysEvent::Start = event {
+            Self::metrics(api).substate_scans += 1;
+        }
+        Ok(())
+    }
+
+    fn on_scan_sorted_substates(
+        api: &mut ModuleApi,
+        event: &ScanSortedSubstatesEvent,
+    ) -> Result<(), RuntimeError> {
+        if let ScanSortedSubstatesEvent::Start = event {
+            Self::metrics(api).substate_scans += 1;
+        }
+        Ok(())
+    }
+
+    fn on_drain_substates(
+        api: &mut ModuleApi,
+        event: &DrainSubstatesEvent,
+    ) -> Result<(), RuntimeError> {
+        if let DrainSubstatesEvent::Start(..) = event {
+            Self::metrics(api).substate_drains += 1;
+        }
+        Ok(())
+    }
+
+    fn on_switch_stack(api: &mut ModuleApi) -> Result<(), RuntimeError> {
+        Self::metrics(api).stack_switches += 1;
+        Ok(())
+    }
+}
@@ -1,8 +1,11 @@
 pub mod auth;
 pub mod costing;
+pub mod debugger;
+pub mod execution_audit;
 pub mod execution_trace;
 pub mod kernel_trace;
 pub mod limits;
+pub mod metrics;
 pub mod transaction_runtime;
 
 mod module_mixer;
@@ -13,9 +13,12 @@ use crate::system::system_callback::*;
 use crate::system::system_modules::auth::AuthModule;
 use crate::system::system_modules::costing::CostingModule;
 use crate::system::system_modules::costing::SystemLoanFeeReserve;
+use crate::system::system_modules::debugger::DebuggerModule;
+use crate::system::system_modules::execution_audit::ExecutionAuditModule;
 use crate::system::system_modules::execution_trace::ExecutionTraceModule;
 use crate::system::system_modules::kernel_trace::KernelTraceModule;
 use crate::system::system_modules::limits::LimitsModule;
+use crate::system::system_modules::metrics::MetricsModule;
 use crate::system::system_modules::transaction_runtime::{Event, TransactionRuntimeModule};
 use bitflags::bitflags;
 use paste::paste;
@@ -36,6 +39,15 @@ bitflags! {
 
         // Execution trace, for preview only
         const EXECUTION_TRACE = 0x01 << 6;
+
+        // Execution audit, for debugging nondeterminism only
+        const EXECUTION_AUDIT = 0x01 << 7;
+
+        // Debugger, for debugging only
+        const DEBUGGER = 0x01 << 8;
+
+        // Metrics, for monitoring only
+        const METRICS = 0x01 << 9;
     }
 }
 
@@ -82,6 +94,9 @@ pub struct SystemModuleMixer {
     pub(super) auth: AuthModule,
     pub(crate) transaction_runtime: TransactionRuntimeModule,
     pub(super) execution_trace: ExecutionTraceModule,
+    pub(super) execution_audit: ExecutionAuditModule,
+    pub(super) debugger: DebuggerModule,
+    pub(super) metrics: MetricsModule,
 }
 
 // Macro generates default modules dispatches call based on passed function name and arguments.
@@ -117,6 +132,18 @@ macro_rules! internal_call_dispatch {
                 ExecutionTraceModule::[< $fn >]($($param, )*)?;
                 $(ExecutionTraceModule::[< $privileged_fn >]($($privileged_fn_param, )*)?;)?
             }
+            if modules.contains(EnabledModules::EXECUTION_AUDIT) {
+                ExecutionAuditModule::[< $fn >]($($param, )*)?;
+                $(ExecutionAuditModule::[< $privileged_fn >]($($privileged_fn_param, )*)?;)?
+            }
+            if modules.contains(EnabledModules::DEBUGGER) {
+                DebuggerModule::[< $fn >]($($param, )*)?;
+                $(DebuggerModule::[< $privileged_fn >]($($privileged_fn_param, )*)?;)?
+            }
+            if modules.contains(EnabledModules::METRICS) {
+                MetricsModule::[< $fn >]($($param, )*)?;
+                $(MetricsModule::[< $privileged_fn >]($($privileged_fn_param, )*)?;)?
+            }
             Ok(())
         }}
     };
@@ -131,6 +158,9 @@ impl SystemModuleMixer {
         limits: LimitsModule,
         costing: CostingModule,
         execution_trace: ExecutionTraceModule,
+        execution_audit: ExecutionAuditModule,
+        debugger: DebuggerModule,
+        metrics: MetricsModule,
     ) -> Self {
         Self {
             enabled_modules,
@@ -140,6 +170,9 @@ impl SystemModuleMixer {
             costing,
             limits,
             execution_trace,
+            execution_audit,
+            debugger,
+            metrics,
         }
     }
 
@@ -154,11 +187,22 @@ impl SystemModuleMixer {
             .contains(EnabledModules::EXECUTION_TRACE)
     }
 
+    #[inline]
+    pub fn is_execution_audit_enabled(&self) -> bool {
+        self.enabled_modules
+            .contains(EnabledModules::EXECUTION_AUDIT)
+    }
+
     #[inline]
     pub fn is_auth_enabled(&self) -> bool {
         self.enabled_modules.contains(EnabledModules::AUTH)
     }
 
+    #[inline]
+    pub fn is_nested_transaction_processor_allowed(&self) -> bool {
+        self.auth.allow_nested_transaction_processor
+    }
+
     pub fn unpack_costing(self) -> CostingModule {
         self.costing
     }
@@ -169,8 +213,14 @@ impl SystemModuleMixer {
         CostingModule,
         TransactionRuntimeModule,
         ExecutionTraceModule,
+        ExecutionAuditModule,
     ) {
-        (self.costing, self.transaction_runtime, self.execution_trace)
+        (
+            self.costing,
+            self.transaction_runtime,
+            self.execution_trace,
+            self.execution_audit,
+        )
     }
 }
 
@@ -184,6 +234,21 @@ impl InitSystemModule for SystemModuleMixer {
     fn init(&mut self) -> Result<(), BootloadingError> {
         let modules: EnabledModules = self.enabled_modules;
 
+        // Enable metrics
+        if modules.contains(EnabledModules::METRICS) {
+            self.metrics.init()?;
+        }
+
+        // Enable debugger
+        if modules.contains(EnabledModules::DEBUGGER) {
+            self.debugger.init()?;
+        }
+
+        // Enable execution audit
+        if modules.contains(EnabledModules::EXECUTION_AUDIT) {
+            self.execution_audit.init()?;
+        }
+
         // Enable execution trace
         if modules.contains(EnabledModules::EXECUTION_TRACE) {
             self.execution_trace.init()?;
@@ -238,6 +303,15 @@ impl InitSystemModule for SystemModuleMixer {
         if modules.contains(EnabledModules::EXECUTION_TRACE) {
             self.execution_trace.on_teardown()?;
         }
+        if modules.contains(EnabledModules::EXECUTION_AUDIT) {
+            self.execution_audit.on_teardown()?;
+        }
+        if modules.contains(EnabledModules::DEBUGGER) {
+            self.debugger.on_teardown()?;
+        }
+        if modules.contains(EnabledModules::METRICS) {
+            self.metrics.on_teardown()?;
+        }
 
         Ok(())
     }
@@ -679,6 +753,18 @@ impl SystemModuleMixer {
         }
     }
 
+    pub fn on_wasm_instantiation(&mut self) {
+        if self.enabled_modules.contains(EnabledModules::METRICS) {
+            self.metrics.on_wasm_instantiation();
+        }
+    }
+
+    pub fn publish_metrics(&self) {
+        if self.enabled_modules.contains(EnabledModules::METRICS) {
+            self.metrics.publish();
+        }
+    }
+
     pub fn fee_reserve(&mut self) -> Option<&SystemLoanFeeReserve> {
         if self.enabled_modules.contains(EnabledModules::COSTING) {
             Some(&self.costing.fee_reserve)
@@ -748,13 +834,20 @@ impl SystemModuleMixer {
         }
     }
 
-    pub fn update_instruction_index(&mut self, new_index: usize) {
+    pub fn update_instruction_index(&mut self, intent_index: usize, new_index: usize) {
         if self
             .enabled_modules
             .contains(EnabledModules::EXECUTION_TRACE)
         {
             self.execution_trace.update_instruction_index(new_index)
         }
+        // Tracked even if costing is disabled, as it's also recorded in the failure context
+        self.costing
+            .update_instruction_index(intent_index, new_index)
+    }
+
+    pub fn clear_instruction_index(&mut self) {
+        self.costing.clear_instruction_index()
     }
 
     pub fn apply_execution_cost(
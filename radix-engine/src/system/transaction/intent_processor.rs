@@ -127,6 +127,7 @@ impl<'a, I: TxnInstruction + ManifestDecode + ManifestCategorize> IntentProcesso
             api.update_instruction_index(self.instruction_index)?;
             let (output, yield_instruction) =
                 instruction.execute(&mut self.worktop, &mut self.objects, api)?;
+            api.clear_instruction_index()?;
             self.outputs.push(output);
             self.instruction_index += 1;
 
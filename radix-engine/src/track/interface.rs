@@ -1,5 +1,6 @@
 use crate::errors::RuntimeError;
 use crate::internal_prelude::*;
+use crate::track::state_updates::TrackSnapshot;
 use radix_engine_interface::types::*;
 use radix_substate_store_interface::db_key_mapper::SubstateKeyContent;
 
@@ -162,6 +163,25 @@ pub trait CommitableSubstateStore {
         on_io_access: &mut F,
     ) -> Result<Vec<SubstateKey>, E>;
 
+    /// Returns Substate Keys of maximum count for a given partition, in database sort key order
+    /// and starting after the given key, or from the start of the partition if it's `None`.
+    ///
+    /// Unlike `scan_keys`, the keys of tracked and persisted substates are merged in a single
+    /// order, so that a scan can be continued from the last returned key.
+    ///
+    /// Clients must ensure that the SubstateKeyContent which the partition is
+    /// associated with is passed in. The returned SubstateKeys are guaranteed to be of
+    /// this type.
+    /// Otherwise, behavior is undefined.
+    fn scan_keys_after<K: SubstateKeyContent, E, F: FnMut(IOAccess) -> Result<(), E>>(
+        &mut self,
+        node_id: &NodeId,
+        partition_num: PartitionNumber,
+        after: Option<&SubstateKey>,
+        count: u32,
+        on_io_access: &mut F,
+    ) -> Result<Vec<SubstateKey>, E>;
+
     /// Removes substates of maximum count for a given partition.
     ///
     /// Clients must ensure that the SubstateKeyContent which the partition is
@@ -192,6 +212,43 @@ pub trait CommitableSubstateStore {
 
     /// Return the commit info
     fn get_commit_info(&mut self) -> StoreCommitInfo;
+
+    /// Captures all tracked substates, partition deletions and transient markers
+    fn snapshot(&self) -> TrackSnapshot;
+
+    /// Replaces all tracked state with a previously captured snapshot, discarding all checkpoints
+    fn restore(&mut self, snapshot: TrackSnapshot);
+
+    /// Creates a savepoint which the changes made after it can be rolled back to.
+    ///
+    /// Checkpoints nest, with later checkpoints being discarded along with earlier ones.
+    fn create_checkpoint(&mut self) -> TrackCheckpoint;
+
+    /// Reverts all changes made since the given checkpoint was created, and discards it along with
+    /// all checkpoints created after it.
+    ///
+    /// Fails, keeping all changes and checkpoints, if a node was created or a substate was force
+    /// written since the checkpoint. Upper layers aren't notified of the substates which are no
+    /// longer tracked, and clients must ensure that none of them is still in use.
+    fn rollback_to(&mut self, checkpoint: TrackCheckpoint) -> Result<(), TrackCheckpointError>;
+
+    /// Discards the given checkpoint along with all checkpoints created after it, keeping changes.
+    fn release(&mut self, checkpoint: TrackCheckpoint) -> Result<(), TrackCheckpointError>;
+}
+
+/// A savepoint of a [`CommitableSubstateStore`], unique within the store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ScryptoSbor)]
+pub struct TrackCheckpoint(pub u32);
+
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub enum TrackCheckpointError {
+    /// The checkpoint has already been rolled back to or released, or was never created.
+    CheckpointNotFound(TrackCheckpoint),
+    /// A node was created after the checkpoint, and may still be referenced by the call frames.
+    NodeCreatedAfterCheckpoint(NodeId),
+    /// A substate was force written after the checkpoint, e.g. a vault which a fee was locked
+    /// from, which the fee reserve has already been credited with.
+    ForceWriteAfterCheckpoint(NodeId),
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -204,7 +204,7 @@ impl TrackedSubstateValue {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct TrackedPartition {
     pub substates: BTreeMap<DbSortKey, TrackedSubstate>,
     pub range_read: u32,
@@ -232,7 +232,7 @@ impl TrackedPartition {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct TrackedNode {
     pub tracked_partitions: IndexMap<PartitionNumber, TrackedPartition>,
     // If true, then all SubstateUpdates under this NodeUpdate must be inserts
@@ -256,6 +256,179 @@ impl TrackedNode {
     }
 }
 
+/// A serializable capture of the substates tracked by a [`Track`](super::Track), which can
+/// be restored into a fresh track over the same database.
+#[derive(Debug, Clone, PartialEq, Eq, Default, ScryptoSbor)]
+pub struct TrackSnapshot {
+    pub tracked_nodes: IndexMap<NodeId, TrackedNodeSnapshot>,
+    pub force_write_tracked_nodes: IndexMap<NodeId, TrackedNodeSnapshot>,
+    pub deleted_partitions: IndexSet<(NodeId, PartitionNumber)>,
+    pub transient_substates: BTreeMap<NodeId, BTreeSet<(PartitionNumber, SubstateKey)>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct TrackedNodeSnapshot {
+    pub tracked_partitions: IndexMap<PartitionNumber, TrackedPartitionSnapshot>,
+    pub is_new: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct TrackedPartitionSnapshot {
+    pub substates: BTreeMap<DbSortKey, (SubstateKey, TrackedSubstateValueSnapshot)>,
+    pub range_read: u32,
+}
+
+/// A serializable [`TrackedSubstateValue`], where a `None` write is a deletion.
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub enum TrackedSubstateValueSnapshot {
+    New(ScryptoValue),
+    ReadOnlyNonExistent,
+    ReadOnlyExistent(ScryptoValue),
+    ReadExistAndWrite(ScryptoValue, Option<ScryptoValue>),
+    ReadNonExistAndWrite(ScryptoValue),
+    WriteOnly(Option<ScryptoValue>),
+    Garbage,
+}
+
+impl Write {
+    fn to_snapshot(&self) -> Option<ScryptoValue> {
+        match self {
+            Write::Update(substate) => Some(substate.value.to_scrypto_value()),
+            Write::Delete => None,
+        }
+    }
+
+    fn from_snapshot(value: Option<ScryptoValue>) -> Self {
+        match value {
+            Some(value) => Write::Update(RuntimeSubstate::new(
+                IndexedScryptoValue::from_scrypto_value(value),
+            )),
+            None => Write::Delete,
+        }
+    }
+}
+
+impl From<&TrackedSubstateValue> for TrackedSubstateValueSnapshot {
+    fn from(value: &TrackedSubstateValue) -> Self {
+        match value {
+            TrackedSubstateValue::New(substate) => Self::New(substate.value.to_scrypto_value()),
+            TrackedSubstateValue::ReadOnly(ReadOnly::NonExistent) => Self::ReadOnlyNonExistent,
+            TrackedSubstateValue::ReadOnly(ReadOnly::Existent(substate)) => {
+                Self::ReadOnlyExistent(substate.value.to_scrypto_value())
+            }
+            TrackedSubstateValue::ReadExistAndWrite(read, write) => {
+                Self::ReadExistAndWrite(read.to_scrypto_value(), write.to_snapshot())
+            }
+            TrackedSubstateValue::ReadNonExistAndWrite(substate) => {
+                Self::ReadNonExistAndWrite(substate.value.to_scrypto_value())
+            }
+            TrackedSubstateValue::WriteOnly(write) => Self::WriteOnly(write.to_snapshot()),
+            TrackedSubstateValue::Garbage => Self::Garbage,
+        }
+    }
+}
+
+impl From<TrackedSubstateValueSnapshot> for TrackedSubstateValue {
+    fn from(value: TrackedSubstateValueSnapshot) -> Self {
+        let runtime_substate =
+            |value| RuntimeSubstate::new(IndexedScryptoValue::from_scrypto_value(value));
+        match value {
+            TrackedSubstateValueSnapshot::New(value) => Self::New(runtime_substate(value)),
+            TrackedSubstateValueSnapshot::ReadOnlyNonExistent => {
+                Self::ReadOnly(ReadOnly::NonExistent)
+            }
+            TrackedSubstateValueSnapshot::ReadOnlyExistent(value) => {
+                Self::ReadOnly(ReadOnly::Existent(runtime_substate(value)))
+            }
+            TrackedSubstateValueSnapshot::ReadExistAndWrite(read, write) => {
+                Self::ReadExistAndWrite(
+                    IndexedScryptoValue::from_scrypto_value(read),
+                    Write::from_snapshot(write),
+                )
+            }
+            TrackedSubstateValueSnapshot::ReadNonExistAndWrite(value) => {
+                Self::ReadNonExistAndWrite(runtime_substate(value))
+            }
+            TrackedSubstateValueSnapshot::WriteOnly(write) => {
+                Self::WriteOnly(Write::from_snapshot(write))
+            }
+            TrackedSubstateValueSnapshot::Garbage => Self::Garbage,
+        }
+    }
+}
+
+impl From<&TrackedNode> for TrackedNodeSnapshot {
+    fn from(tracked_node: &TrackedNode) -> Self {
+        let tracked_partitions = tracked_node
+            .tracked_partitions
+            .iter()
+            .map(|(partition_num, tracked_partition)| {
+                let substates = tracked_partition
+                    .substates
+                    .iter()
+                    .map(|(db_sort_key, tracked)| {
+                        (
+                            db_sort_key.clone(),
+                            (
+                                tracked.substate_key.clone(),
+                                (&tracked.substate_value).into(),
+                            ),
+                        )
+                    })
+                    .collect();
+                (
+                    *partition_num,
+                    TrackedPartitionSnapshot {
+                        substates,
+                        range_read: tracked_partition.range_read,
+                    },
+                )
+            })
+            .collect();
+
+        Self {
+            tracked_partitions,
+            is_new: tracked_node.is_new,
+        }
+    }
+}
+
+impl From<TrackedNodeSnapshot> for TrackedNode {
+    fn from(snapshot: TrackedNodeSnapshot) -> Self {
+        let tracked_partitions = snapshot
+            .tracked_partitions
+            .into_iter()
+            .map(|(partition_num, partition)| {
+                let substates = partition
+                    .substates
+                    .into_iter()
+                    .map(|(db_sort_key, (substate_key, substate_value))| {
+                        (
+                            db_sort_key,
+                            TrackedSubstate {
+                                substate_key,
+                                substate_value: substate_value.into(),
+                            },
+                        )
+                    })
+                    .collect();
+                (
+                    partition_num,
+                    TrackedPartition {
+                        substates,
+                        range_read: partition.range_read,
+                    },
+                )
+            })
+            .collect();
+
+        Self {
+            tracked_partitions,
+            is_new: snapshot.is_new,
+        }
+    }
+}
+
 pub struct IterationCountedIter<'a, E> {
     pub iter:
         Box<dyn Iterator<Item = Result<(DbSortKey, (SubstateKey, IndexedScryptoValue)), E>> + 'a>,
@@ -1,7 +1,8 @@
 use crate::internal_prelude::*;
 use crate::kernel::call_frame::TransientSubstates;
 use crate::track::interface::{
-    CommitableSubstateStore, IOAccess, NodeSubstates, TrackedSubstateInfo,
+    CommitableSubstateStore, IOAccess, NodeSubstates, TrackCheckpoint, TrackCheckpointError,
+    TrackedSubstateInfo,
 };
 use crate::track::state_updates::*;
 use radix_engine_interface::types::*;
@@ -14,6 +15,7 @@ use radix_substate_store_interface::{
 use sbor::rust::collections::btree_map::Entry;
 use sbor::rust::iter::empty;
 use sbor::rust::mem;
+use sbor::rust::ops::Bound;
 
 use super::interface::{CanonicalPartition, CanonicalSubstateKey, StoreCommit, StoreCommitInfo};
 
@@ -36,9 +38,43 @@ pub struct MappedTrack<'s, S: SubstateDatabase, M: DatabaseKeyMapper + 'static>
 
     transient_substates: TransientSubstates,
 
+    /// Checkpoints from the earliest to the latest, with the changes made since each was created
+    /// and before the next one was
+    checkpoints: Vec<(TrackCheckpoint, TrackCheckpointUndoLog)>,
+    next_checkpoint_id: u32,
+
     phantom_data: PhantomData<M>,
 }
 
+/// The changes made to a [`MappedTrack`] after a checkpoint, recorded as the prior state of
+/// whatever they touched so that they can be undone.
+#[derive(Default)]
+struct TrackCheckpointUndoLog {
+    /// The tracked state of each changed node before its first change, or `None` if untracked
+    tracked_nodes: IndexMap<NodeId, Option<TrackedNode>>,
+    /// The partitions which were newly marked as deleted
+    deleted_partitions: Vec<(NodeId, PartitionNumber)>,
+    /// The substates which were newly marked as transient
+    transient_substates: Vec<(NodeId, PartitionNumber, SubstateKey)>,
+    /// The first node created, which can't be rolled back as the call frames may reference it
+    created_node: Option<NodeId>,
+    /// The first node force written, which can't be rolled back as it may have locked a fee
+    force_written_node: Option<NodeId>,
+}
+
+impl TrackCheckpointUndoLog {
+    /// Merges the changes made after this log, so that undoing this log undoes them too.
+    fn merge(&mut self, later: TrackCheckpointUndoLog) {
+        for (node_id, tracked_node) in later.tracked_nodes {
+            self.tracked_nodes.entry(node_id).or_insert(tracked_node);
+        }
+        self.deleted_partitions.extend(later.deleted_partitions);
+        self.transient_substates.extend(later.transient_substates);
+        self.created_node = self.created_node.or(later.created_node);
+        self.force_written_node = self.force_written_node.or(later.force_written_node);
+    }
+}
+
 /// Records all the substates that have been read or written into, and all the partitions to delete.
 ///
 /// `NodeId` in this struct isn't always valid.
@@ -111,6 +147,8 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper> MappedTrack<'s, S, M> {
             tracked_nodes: index_map_new(),
             deleted_partitions: index_set_new(),
             transient_substates: TransientSubstates::new(),
+            checkpoints: Vec::new(),
+            next_checkpoint_id: 0,
             phantom_data: PhantomData::default(),
         }
     }
@@ -143,6 +181,7 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper> MappedTrack<'s, S, M> {
     >(
         substate_db: &'x S,
         partition_key: &DbPartitionKey,
+        from_sort_key: Option<&DbSortKey>,
         on_io_access: &'x mut F,
         canonical_partition: CanonicalPartition,
     ) -> Box<dyn Iterator<Item = Result<(DbSortKey, (SubstateKey, IndexedScryptoValue)), E>> + 'x>
@@ -201,7 +240,7 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper> MappedTrack<'s, S, M> {
         }
 
         Box::new(TracedIterator {
-            iterator: substate_db.list_raw_values_from_db_key(partition_key, None),
+            iterator: substate_db.list_raw_values_from_db_key(partition_key, from_sort_key),
             on_io_access,
             canonical_partition,
             errored_out: false,
@@ -274,11 +313,33 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper> MappedTrack<'s, S, M> {
         ))
     }
 
+    fn checkpoint_position(
+        &self,
+        checkpoint: TrackCheckpoint,
+    ) -> Result<usize, TrackCheckpointError> {
+        self.checkpoints
+            .iter()
+            .position(|(id, _)| *id == checkpoint)
+            .ok_or(TrackCheckpointError::CheckpointNotFound(checkpoint))
+    }
+
+    /// Records the tracked state of a node before it's changed, if there's a checkpoint to undo to.
+    fn record_node_change(&mut self, node_id: &NodeId) {
+        if let Some((_, undo_log)) = self.checkpoints.last_mut() {
+            if !undo_log.tracked_nodes.contains_key(node_id) {
+                undo_log
+                    .tracked_nodes
+                    .insert(*node_id, self.tracked_nodes.get(node_id).cloned());
+            }
+        }
+    }
+
     fn get_tracked_partition(
         &mut self,
         node_id: &NodeId,
         partition_num: PartitionNumber,
     ) -> &mut TrackedPartition {
+        self.record_node_change(node_id);
         self.tracked_nodes
             .entry(*node_id)
             .or_insert(TrackedNode::new(false))
@@ -294,6 +355,7 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper> MappedTrack<'s, S, M> {
         substate_key: SubstateKey,
         on_io_access: &mut F,
     ) -> Result<&mut TrackedSubstateValue, E> {
+        self.record_node_change(node_id);
         let db_sort_key = M::to_db_sort_key(&substate_key);
         let partition = &mut self
             .tracked_nodes
@@ -388,6 +450,16 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper> CommitableSubstateStore
         partition_num: PartitionNumber,
         substate_key: SubstateKey,
     ) {
+        if let Some((_, undo_log)) = self.checkpoints.last_mut() {
+            if !self
+                .transient_substates
+                .is_transient(&node_id, partition_num, &substate_key)
+            {
+                undo_log
+                    .transient_substates
+                    .push((node_id, partition_num, substate_key.clone()));
+            }
+        }
         self.transient_substates
             .mark_as_transient(node_id, partition_num, substate_key);
     }
@@ -428,6 +500,10 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper> CommitableSubstateStore
             tracked_partitions.insert(partition_number, tracked_partition);
         }
 
+        self.record_node_change(&node_id);
+        if let Some((_, undo_log)) = self.checkpoints.last_mut() {
+            undo_log.created_node.get_or_insert(node_id);
+        }
         self.tracked_nodes.insert(
             node_id,
             TrackedNode {
@@ -489,6 +565,7 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper> CommitableSubstateStore
         substate_value: IndexedScryptoValue,
         on_io_access: &mut F,
     ) -> Result<(), E> {
+        self.record_node_change(&node_id);
         let tracked_partition = self
             .tracked_nodes
             .entry(node_id)
@@ -559,6 +636,9 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper> CommitableSubstateStore
             )
             .expect("Should not need to go into store on close substate.");
         let cloned_track = tracked.clone();
+        if let Some((_, undo_log)) = self.checkpoints.last_mut() {
+            undo_log.force_written_node.get_or_insert(*node_id);
+        }
 
         self.force_write_tracked_nodes
             .entry(*node_id)
@@ -651,6 +731,7 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper> CommitableSubstateStore
         let mut tracked_iter = IterationCountedIter::new(Self::list_entries_from_db::<E, F, K>(
             self.substate_db,
             &db_partition_key,
+            None,
             on_io_access,
             CanonicalPartition {
                 node_id: *node_id,
@@ -685,6 +766,78 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper> CommitableSubstateStore
         Ok(items)
     }
 
+    fn scan_keys_after<K: SubstateKeyContent, E, F: FnMut(IOAccess) -> Result<(), E>>(
+        &mut self,
+        node_id: &NodeId,
+        partition_number: PartitionNumber,
+        after: Option<&SubstateKey>,
+        limit: u32,
+        on_io_access: &mut F,
+    ) -> Result<Vec<SubstateKey>, E> {
+        let limit: usize = limit.try_into().unwrap();
+        let after = after.map(|substate_key| M::to_db_sort_key(substate_key));
+
+        let node_updates = self.tracked_nodes.get(node_id);
+        let is_new = node_updates
+            .map(|tracked_node| tracked_node.is_new)
+            .unwrap_or(false);
+        let tracked_partition =
+            node_updates.and_then(|n| n.tracked_partitions.get(&partition_number));
+
+        // Optimization, no need to go into database if the node is just created
+        let raw_db_entries = (!is_new)
+            .then(|| {
+                let partition_key = M::to_db_partition_key(node_id, partition_number);
+                Self::list_entries_from_db::<E, F, K>(
+                    self.substate_db,
+                    &partition_key,
+                    after.as_ref(),
+                    on_io_access,
+                    CanonicalPartition {
+                        node_id: *node_id,
+                        partition_number,
+                    },
+                )
+            })
+            .into_iter()
+            .flatten();
+        // The database lists entries from a sort key inclusively, so the cursor entry is skipped
+        let db_read_entries = raw_db_entries
+            .filter(|result| match (result, &after) {
+                (Ok((db_sort_key, _)), Some(after)) => db_sort_key != after,
+                _ => true,
+            })
+            .map(|result| {
+                result.map(|(db_sort_key, (substate_key, _))| (db_sort_key, substate_key))
+            });
+
+        let tracked_range = (
+            after.clone().map_or(Bound::Unbounded, Bound::Excluded),
+            Bound::Unbounded,
+        );
+        let tracked_entry_changes = tracked_partition
+            .into_iter()
+            .flat_map(|tracked_partition| tracked_partition.substates.range(tracked_range.clone()))
+            .map(|(db_sort_key, tracked_substate)| {
+                // TODO: Check that substate is not write locked, before use outside of native blueprints
+                let substate_key = tracked_substate
+                    .substate_value
+                    .get()
+                    .map(|_| tracked_substate.substate_key.clone());
+                (db_sort_key.clone(), substate_key)
+            });
+
+        let mut items = Vec::new();
+        for result in
+            OverlayingResultIterator::new(db_read_entries, tracked_entry_changes).take(limit)
+        {
+            let (_db_sort_key, substate_key) = result?;
+            items.push(substate_key);
+        }
+
+        Ok(items)
+    }
+
     fn drain_substates<K: SubstateKeyContent, E, F: FnMut(IOAccess) -> Result<(), E>>(
         &mut self,
         node_id: &NodeId,
@@ -692,6 +845,7 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper> CommitableSubstateStore
         limit: u32,
         on_io_access: &mut F,
     ) -> Result<Vec<(SubstateKey, IndexedScryptoValue)>, E> {
+        self.record_node_change(node_id);
         let limit: usize = limit.try_into().unwrap();
         let mut items = Vec::new();
 
@@ -742,6 +896,7 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper> CommitableSubstateStore
                 IterationCountedIter::new(Self::list_entries_from_db::<E, F, K>(
                     self.substate_db,
                     &db_partition_key,
+                    None,
                     on_io_access,
                     CanonicalPartition {
                         node_id: *node_id,
@@ -821,6 +976,7 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper> CommitableSubstateStore
     ) -> Result<Vec<(SortedKey, IndexedScryptoValue)>, E> {
         // TODO: ensure we abort if any substates are write locked.
         let limit: usize = limit.try_into().unwrap();
+        self.record_node_change(node_id);
 
         // initialize the track partition, since we will definitely need it: either to read values from it OR to update the `range_read` on it
         let tracked_node = self
@@ -843,6 +999,7 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper> CommitableSubstateStore
             Box::new(Self::list_entries_from_db::<E, F, SortedKey>(
                 self.substate_db,
                 &partition_key,
+                None,
                 on_io_access,
                 CanonicalPartition {
                     node_id: *node_id,
@@ -895,7 +1052,11 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper> CommitableSubstateStore
     fn delete_partition(&mut self, node_id: &NodeId, partition_num: PartitionNumber) {
         // This is used for transaction tracker only, for which we don't account for store access.
 
-        self.deleted_partitions.insert((*node_id, partition_num));
+        if self.deleted_partitions.insert((*node_id, partition_num)) {
+            if let Some((_, undo_log)) = self.checkpoints.last_mut() {
+                undo_log.deleted_partitions.push((*node_id, partition_num));
+            }
+        }
     }
 
     fn get_commit_info(&mut self) -> StoreCommitInfo {
@@ -993,4 +1154,100 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper> CommitableSubstateStore
 
         store_commit
     }
+
+    fn snapshot(&self) -> TrackSnapshot {
+        let snapshot_nodes = |nodes: &IndexMap<NodeId, TrackedNode>| {
+            nodes
+                .iter()
+                .map(|(node_id, tracked_node)| (*node_id, tracked_node.into()))
+                .collect()
+        };
+
+        TrackSnapshot {
+            tracked_nodes: snapshot_nodes(&self.tracked_nodes),
+            force_write_tracked_nodes: snapshot_nodes(&self.force_write_tracked_nodes),
+            deleted_partitions: self.deleted_partitions.clone(),
+            transient_substates: self.transient_substates.transient_substates.clone(),
+        }
+    }
+
+    fn restore(&mut self, snapshot: TrackSnapshot) {
+        let restore_nodes = |nodes: IndexMap<NodeId, TrackedNodeSnapshot>| {
+            nodes
+                .into_iter()
+                .map(|(node_id, tracked_node)| (node_id, tracked_node.into()))
+                .collect()
+        };
+
+        self.tracked_nodes = restore_nodes(snapshot.tracked_nodes);
+        self.force_write_tracked_nodes = restore_nodes(snapshot.force_write_tracked_nodes);
+        self.deleted_partitions = snapshot.deleted_partitions;
+        self.transient_substates.transient_substates = snapshot.transient_substates;
+        self.checkpoints.clear();
+    }
+
+    fn create_checkpoint(&mut self) -> TrackCheckpoint {
+        let checkpoint = TrackCheckpoint(self.next_checkpoint_id);
+        self.next_checkpoint_id += 1;
+        self.checkpoints
+            .push((checkpoint, TrackCheckpointUndoLog::default()));
+        checkpoint
+    }
+
+    fn rollback_to(&mut self, checkpoint: TrackCheckpoint) -> Result<(), TrackCheckpointError> {
+        let position = self.checkpoint_position(checkpoint)?;
+        for (_, undo_log) in &self.checkpoints[position..] {
+            if let Some(node_id) = undo_log.created_node {
+                return Err(TrackCheckpointError::NodeCreatedAfterCheckpoint(node_id));
+            }
+            if let Some(node_id) = undo_log.force_written_node {
+                return Err(TrackCheckpointError::ForceWriteAfterCheckpoint(node_id));
+            }
+        }
+
+        let undo_logs = self.checkpoints.split_off(position);
+        for (_, undo_log) in undo_logs.into_iter().rev() {
+            for (node_id, tracked_node) in undo_log.tracked_nodes {
+                match tracked_node {
+                    Some(tracked_node) => {
+                        self.tracked_nodes.insert(node_id, tracked_node);
+                    }
+                    None => {
+                        self.tracked_nodes.shift_remove(&node_id);
+                    }
+                }
+            }
+            for deleted_partition in undo_log.deleted_partitions {
+                self.deleted_partitions.shift_remove(&deleted_partition);
+            }
+            for (node_id, partition_num, substate_key) in undo_log.transient_substates {
+                if let Some(transient_substates) = self
+                    .transient_substates
+                    .transient_substates
+                    .get_mut(&node_id)
+                {
+                    transient_substates.remove(&(partition_num, substate_key));
+                    if transient_substates.is_empty() {
+                        self.transient_substates
+                            .transient_substates
+                            .remove(&node_id);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn release(&mut self, checkpoint: TrackCheckpoint) -> Result<(), TrackCheckpointError> {
+        let position = self.checkpoint_position(checkpoint)?;
+        let undo_logs = self.checkpoints.split_off(position);
+        if let Some((_, undo_log)) = self.checkpoints.last_mut() {
+            for (_, later_undo_log) in undo_logs {
+                undo_log.merge(later_undo_log);
+            }
+        }
+
+        Ok(())
+    }
 }
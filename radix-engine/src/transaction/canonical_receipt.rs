@@ -0,0 +1,493 @@
+use super::*;
+use crate::internal_prelude::*;
+use crate::system::system_modules::costing::RoyaltyRecipient;
+use radix_transactions::model::TransactionCostingParametersReceiptV2;
+#[cfg(all(feature = "std", feature = "serde"))]
+use sbor::representations::*;
+
+define_single_versioned! {
+    #[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+    pub VersionedCanonicalTransactionReceipt(CanonicalTransactionReceiptVersions) => CanonicalTransactionReceipt = CanonicalTransactionReceiptV1
+}
+
+/// The parts of a [`TransactionReceipt`] which are determined by the transaction, the state it was
+/// executed against and the execution config, in a form which can be stored and hashed.
+///
+/// The hardware resources usage, the debug information and the execution audit are left out, as
+/// they either depend on the machine or aren't encodable.
+///
+/// The receipt level types are copies of the live receipt types as of V1, so adding a field to
+/// e.g. [`CommitResult`] doesn't change the encoding of V1 receipts. The state updates, outcome,
+/// execution trace and rejection reason are still the live engine types though, so a change to
+/// any of those (e.g. a new [`RuntimeError`] variant) changes the encoding of receipts which
+/// include them. The canonical hash is therefore only comparable between receipts produced by
+/// the same engine version.
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct CanonicalTransactionReceiptV1 {
+    pub costing_parameters: CostingParameters,
+    pub transaction_costing_parameters: TransactionCostingParametersReceiptV2,
+    pub fee_summary: CanonicalTransactionFeeSummaryV1,
+    pub fee_details: Option<CanonicalTransactionFeeDetailsV1>,
+    pub result: CanonicalTransactionResultV1,
+}
+
+/// The V1 canonical form of a [`TransactionFeeSummary`].
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct CanonicalTransactionFeeSummaryV1 {
+    pub total_execution_cost_units_consumed: u32,
+    pub total_finalization_cost_units_consumed: u32,
+    pub total_execution_cost_in_xrd: Decimal,
+    pub total_finalization_cost_in_xrd: Decimal,
+    pub total_tipping_cost_in_xrd: Decimal,
+    pub total_storage_cost_in_xrd: Decimal,
+    pub total_royalty_cost_in_xrd: Decimal,
+}
+
+/// The V1 canonical form of a [`TransactionFeeDetails`].
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct CanonicalTransactionFeeDetailsV1 {
+    pub execution_cost_breakdown: BTreeMap<String, u32>,
+    pub finalization_cost_breakdown: BTreeMap<String, u32>,
+}
+
+/// The V1 canonical form of a [`TransactionResult`].
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub enum CanonicalTransactionResultV1 {
+    Commit(CanonicalCommitResultV1),
+    Reject(CanonicalRejectResultV1),
+    Abort(CanonicalAbortResultV1),
+}
+
+/// The V1 canonical form of a [`CommitResult`].
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct CanonicalCommitResultV1 {
+    pub state_updates: StateUpdates,
+    pub state_update_summary: StateUpdateSummary,
+    pub fee_source: CanonicalFeeSourceV1,
+    pub locked_fees: Vec<CanonicalLockedFeeV1>,
+    pub fee_destination: CanonicalFeeDestinationV1,
+    pub outcome: TransactionOutcome,
+    pub application_events: Vec<(EventTypeIdentifier, Vec<u8>)>,
+    pub application_logs: Vec<(Level, String)>,
+    pub system_structure: SystemStructure,
+    pub execution_trace: Option<TransactionExecutionTrace>,
+    pub performed_nullifications: Vec<Nullification>,
+}
+
+/// The V1 canonical form of a [`FeeSource`].
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct CanonicalFeeSourceV1 {
+    pub paying_vaults: IndexMap<NodeId, Decimal>,
+}
+
+/// The V1 canonical form of a [`LockedFee`].
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct CanonicalLockedFeeV1 {
+    pub vault_id: NodeId,
+    pub locked: Decimal,
+    pub contingent: bool,
+    pub paid: Decimal,
+}
+
+/// The V1 canonical form of a [`FeeDestination`].
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct CanonicalFeeDestinationV1 {
+    pub to_proposer: Decimal,
+    pub to_validator_set: Decimal,
+    pub to_burn: Decimal,
+    pub to_royalty_recipients: IndexMap<RoyaltyRecipient, Decimal>,
+}
+
+/// The V1 canonical form of a [`RejectResult`].
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct CanonicalRejectResultV1 {
+    pub reason: RejectionReason,
+}
+
+/// The V1 canonical form of an [`AbortResult`].
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct CanonicalAbortResultV1 {
+    pub reason: AbortReason,
+}
+
+impl From<&TransactionFeeSummary> for CanonicalTransactionFeeSummaryV1 {
+    fn from(fee_summary: &TransactionFeeSummary) -> Self {
+        Self {
+            total_execution_cost_units_consumed: fee_summary.total_execution_cost_units_consumed,
+            total_finalization_cost_units_consumed: fee_summary
+                .total_finalization_cost_units_consumed,
+            total_execution_cost_in_xrd: fee_summary.total_execution_cost_in_xrd,
+            total_finalization_cost_in_xrd: fee_summary.total_finalization_cost_in_xrd,
+            total_tipping_cost_in_xrd: fee_summary.total_tipping_cost_in_xrd,
+            total_storage_cost_in_xrd: fee_summary.total_storage_cost_in_xrd,
+            total_royalty_cost_in_xrd: fee_summary.total_royalty_cost_in_xrd,
+        }
+    }
+}
+
+impl From<&TransactionFeeDetails> for CanonicalTransactionFeeDetailsV1 {
+    fn from(fee_details: &TransactionFeeDetails) -> Self {
+        Self {
+            execution_cost_breakdown: fee_details.execution_cost_breakdown.clone(),
+            finalization_cost_breakdown: fee_details.finalization_cost_breakdown.clone(),
+        }
+    }
+}
+
+impl From<&TransactionResult> for CanonicalTransactionResultV1 {
+    fn from(result: &TransactionResult) -> Self {
+        match result {
+            TransactionResult::Commit(commit) => Self::Commit(commit.into()),
+            TransactionResult::Reject(reject) => Self::Reject(CanonicalRejectResultV1 {
+                reason: reject.reason.clone(),
+            }),
+            TransactionResult::Abort(abort) => Self::Abort(CanonicalAbortResultV1 {
+                reason: abort.reason.clone(),
+            }),
+        }
+    }
+}
+
+impl From<&CommitResult> for CanonicalCommitResultV1 {
+    fn from(commit: &CommitResult) -> Self {
+        Self {
+            state_updates: commit.state_updates.clone(),
+            state_update_summary: commit.state_update_summary.clone(),
+            fee_source: CanonicalFeeSourceV1 {
+                paying_vaults: commit.fee_source.paying_vaults.clone(),
+            },
+            locked_fees: commit
+                .locked_fees
+                .iter()
+                .map(|locked_fee| CanonicalLockedFeeV1 {
+                    vault_id: locked_fee.vault_id,
+                    locked: locked_fee.locked,
+                    contingent: locked_fee.contingent,
+                    paid: locked_fee.paid,
+                })
+                .collect(),
+            fee_destination: CanonicalFeeDestinationV1 {
+                to_proposer: commit.fee_destination.to_proposer,
+                to_validator_set: commit.fee_destination.to_validator_set,
+                to_burn: commit.fee_destination.to_burn,
+                to_royalty_recipients: commit.fee_destination.to_royalty_recipients.clone(),
+            },
+            outcome: commit.outcome.clone(),
+            application_events: commit.application_events.clone(),
+            application_logs: commit.application_logs.clone(),
+            system_structure: commit.system_structure.clone(),
+            execution_trace: commit.execution_trace.clone(),
+            performed_nullifications: commit.performed_nullifications.clone(),
+        }
+    }
+}
+
+impl VersionedCanonicalTransactionReceipt {
+    /// The schema of the canonical encoding, which annotates its JSON representation.
+    pub fn schema() -> SingleTypeSchema<ScryptoCustomSchema> {
+        SingleTypeSchema::for_type::<Self>()
+    }
+
+    /// Converts the [canonical JSON](TransactionReceipt::to_canonical_json) of a receipt back to
+    /// its canonical SBOR encoding, e.g. to check a stored receipt against its canonical hash.
+    #[cfg(all(feature = "std", feature = "serde"))]
+    pub fn canonical_sbor_from_json(
+        json: &str,
+        network_definition: &NetworkDefinition,
+    ) -> Result<Vec<u8>, CanonicalJsonError> {
+        let json: serde_json::Value = serde_json::from_str(json)
+            .map_err(|error| CanonicalJsonError::InvalidJson(error.to_string()))?;
+        let decoder = AddressBech32Decoder::new(network_definition);
+        let value = scrypto_value_from_json(&json, &decoder)?;
+        let payload = scrypto_encode(&value).map_err(CanonicalJsonError::EncodeError)?;
+        // Only accept values which are canonical receipts
+        scrypto_decode::<Self>(&payload).map_err(CanonicalJsonError::DecodeError)?;
+        Ok(payload)
+    }
+}
+
+#[cfg(all(feature = "std", feature = "serde"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CanonicalJsonError {
+    InvalidJson(String),
+    InvalidValue(String),
+    EncodeError(EncodeError),
+    DecodeError(DecodeError),
+}
+
+impl TransactionReceipt {
+    pub fn to_canonical(&self) -> VersionedCanonicalTransactionReceipt {
+        CanonicalTransactionReceiptV1 {
+            costing_parameters: self.costing_parameters,
+            transaction_costing_parameters: self.transaction_costing_parameters.clone(),
+            fee_summary: (&self.fee_summary).into(),
+            fee_details: self.fee_details.as_ref().map(Into::into),
+            result: (&self.result).into(),
+        }
+        .into()
+    }
+
+    /// Encodes the [canonical](Self::to_canonical) parts of the receipt with SBOR.
+    ///
+    /// Struct fields are encoded in declaration order and maps in insertion order, which the
+    /// engine keeps deterministic, so identical executions have identical encodings.
+    pub fn to_canonical_sbor(&self) -> Vec<u8> {
+        scrypto_encode(&self.to_canonical()).expect("Canonical receipt should be encodable")
+    }
+
+    /// The hash of the [canonical SBOR encoding](Self::to_canonical_sbor) of the receipt.
+    pub fn canonical_hash(&self) -> Hash {
+        hash(self.to_canonical_sbor())
+    }
+
+    /// Serializes the [canonical](Self::to_canonical) parts of the receipt to JSON, in the
+    /// programmatic SBOR representation annotated with the names from
+    /// [the schema](VersionedCanonicalTransactionReceipt::schema).
+    ///
+    /// The JSON can be converted back to the canonical SBOR encoding, which is what should be
+    /// hashed when verifying a stored receipt, with
+    /// [`VersionedCanonicalTransactionReceipt::canonical_sbor_from_json`].
+    #[cfg(all(feature = "std", feature = "serde"))]
+    pub fn to_canonical_json(&self, network_definition: &NetworkDefinition) -> String {
+        let SingleTypeSchema { schema, type_id } = VersionedCanonicalTransactionReceipt::schema();
+        let schema = schema.fully_update_and_into_latest_version();
+        let encoder = AddressBech32Encoder::new(network_definition);
+        let payload = self.to_canonical_sbor();
+        let raw_payload = ScryptoRawPayload::new_from_valid_slice(&payload);
+        let serializable = raw_payload.serializable(SerializationParameters::WithSchema {
+            mode: SerializationMode::Programmatic,
+            custom_context: ScryptoValueDisplayContext::with_optional_bech32(Some(&encoder)),
+            schema: &schema,
+            type_id,
+            depth_limit: SCRYPTO_SBOR_V1_MAX_DEPTH,
+        });
+
+        serde_json::to_string_pretty(&serializable).expect("Canonical receipt should serialize")
+    }
+}
+
+/// Parses a value in the programmatic JSON representation, as produced by
+/// [`TransactionReceipt::to_canonical_json`]. Type and field names are ignored.
+#[cfg(all(feature = "std", feature = "serde"))]
+fn scrypto_value_from_json(
+    json: &serde_json::Value,
+    decoder: &AddressBech32Decoder,
+) -> Result<ScryptoValue, CanonicalJsonError> {
+    let kind = json_str(json, "kind")?;
+    if kind == "Bytes" {
+        let bytes =
+            hex::decode(json_str(json, "hex")?).map_err(|_| invalid_json_value(json, "hex"))?;
+        return Ok(ScryptoValue::Array {
+            element_value_kind: ValueKind::U8,
+            elements: bytes
+                .into_iter()
+                .map(|value| ScryptoValue::U8 { value })
+                .collect(),
+        });
+    }
+
+    let value = match value_kind_from_name(kind).ok_or_else(|| invalid_json_value(json, "kind"))? {
+        ValueKind::Bool => ScryptoValue::Bool {
+            value: json_field(json, "value")?
+                .as_bool()
+                .ok_or_else(|| invalid_json_value(json, "value"))?,
+        },
+        ValueKind::I8 => ScryptoValue::I8 {
+            value: json_parse(json, "value")?,
+        },
+        ValueKind::I16 => ScryptoValue::I16 {
+            value: json_parse(json, "value")?,
+        },
+        ValueKind::I32 => ScryptoValue::I32 {
+            value: json_parse(json, "value")?,
+        },
+        ValueKind::I64 => ScryptoValue::I64 {
+            value: json_parse(json, "value")?,
+        },
+        ValueKind::I128 => ScryptoValue::I128 {
+            value: json_parse(json, "value")?,
+        },
+        ValueKind::U8 => ScryptoValue::U8 {
+            value: json_parse(json, "value")?,
+        },
+        ValueKind::U16 => ScryptoValue::U16 {
+            value: json_parse(json, "value")?,
+        },
+        ValueKind::U32 => ScryptoValue::U32 {
+            value: json_parse(json, "value")?,
+        },
+        ValueKind::U64 => ScryptoValue::U64 {
+            value: json_parse(json, "value")?,
+        },
+        ValueKind::U128 => ScryptoValue::U128 {
+            value: json_parse(json, "value")?,
+        },
+        ValueKind::String => ScryptoValue::String {
+            value: json_str(json, "value")?.to_owned(),
+        },
+        ValueKind::Tuple => ScryptoValue::Tuple {
+            fields: json_values(json, "fields", decoder)?,
+        },
+        ValueKind::Enum => ScryptoValue::Enum {
+            discriminator: json_parse(json, "variant_id")?,
+            fields: json_values(json, "fields", decoder)?,
+        },
+        ValueKind::Array => ScryptoValue::Array {
+            element_value_kind: json_value_kind(json, "element_kind")?,
+            elements: json_values(json, "elements", decoder)?,
+        },
+        ValueKind::Map => ScryptoValue::Map {
+            key_value_kind: json_value_kind(json, "key_kind")?,
+            value_value_kind: json_value_kind(json, "value_kind")?,
+            entries: json_field(json, "entries")?
+                .as_array()
+                .ok_or_else(|| invalid_json_value(json, "entries"))?
+                .iter()
+                .map(|entry| {
+                    Ok((
+                        scrypto_value_from_json(json_field(entry, "key")?, decoder)?,
+                        scrypto_value_from_json(json_field(entry, "value")?, decoder)?,
+                    ))
+                })
+                .collect::<Result<_, _>>()?,
+        },
+        ValueKind::Custom(custom_value_kind) => {
+            let value = json_str(json, "value")?;
+            let custom_value = match custom_value_kind {
+                ScryptoCustomValueKind::Reference => {
+                    ScryptoCustomValue::Reference(Reference(node_id_from_json(value, decoder)?))
+                }
+                ScryptoCustomValueKind::Own => {
+                    ScryptoCustomValue::Own(Own(node_id_from_json(value, decoder)?))
+                }
+                ScryptoCustomValueKind::Decimal => ScryptoCustomValue::Decimal(
+                    value
+                        .parse()
+                        .map_err(|_| invalid_json_value(json, "value"))?,
+                ),
+                ScryptoCustomValueKind::PreciseDecimal => ScryptoCustomValue::PreciseDecimal(
+                    value
+                        .parse()
+                        .map_err(|_| invalid_json_value(json, "value"))?,
+                ),
+                ScryptoCustomValueKind::NonFungibleLocalId => {
+                    ScryptoCustomValue::NonFungibleLocalId(
+                        value
+                            .parse()
+                            .map_err(|_| invalid_json_value(json, "value"))?,
+                    )
+                }
+            };
+            ScryptoValue::Custom {
+                value: custom_value,
+            }
+        }
+    };
+
+    Ok(value)
+}
+
+#[cfg(all(feature = "std", feature = "serde"))]
+fn value_kind_from_name(name: &str) -> Option<ScryptoValueKind> {
+    let value_kind = match name {
+        "Bool" => ValueKind::Bool,
+        "I8" => ValueKind::I8,
+        "I16" => ValueKind::I16,
+        "I32" => ValueKind::I32,
+        "I64" => ValueKind::I64,
+        "I128" => ValueKind::I128,
+        "U8" => ValueKind::U8,
+        "U16" => ValueKind::U16,
+        "U32" => ValueKind::U32,
+        "U64" => ValueKind::U64,
+        "U128" => ValueKind::U128,
+        "String" => ValueKind::String,
+        "Tuple" => ValueKind::Tuple,
+        "Enum" => ValueKind::Enum,
+        "Array" => ValueKind::Array,
+        "Map" => ValueKind::Map,
+        "Reference" => ValueKind::Custom(ScryptoCustomValueKind::Reference),
+        "Own" => ValueKind::Custom(ScryptoCustomValueKind::Own),
+        "Decimal" => ValueKind::Custom(ScryptoCustomValueKind::Decimal),
+        "PreciseDecimal" => ValueKind::Custom(ScryptoCustomValueKind::PreciseDecimal),
+        "NonFungibleLocalId" => ValueKind::Custom(ScryptoCustomValueKind::NonFungibleLocalId),
+        _ => return None,
+    };
+    Some(value_kind)
+}
+
+/// Node ids are serialized as addresses, or as `NodeId(<hex>)` if they don't have an address.
+#[cfg(all(feature = "std", feature = "serde"))]
+fn node_id_from_json(
+    value: &str,
+    decoder: &AddressBech32Decoder,
+) -> Result<NodeId, CanonicalJsonError> {
+    let bytes = match value
+        .strip_prefix("NodeId(")
+        .and_then(|value| value.strip_suffix(')'))
+    {
+        Some(hex) => hex::decode(hex).ok(),
+        None => decoder
+            .validate_and_decode(value)
+            .ok()
+            .map(|(_, bytes)| bytes),
+    };
+    bytes
+        .and_then(|bytes| <[u8; NodeId::LENGTH]>::try_from(bytes).ok())
+        .map(NodeId::from)
+        .ok_or_else(|| CanonicalJsonError::InvalidValue(format!("Invalid node id: {value}")))
+}
+
+#[cfg(all(feature = "std", feature = "serde"))]
+fn json_field<'j>(
+    json: &'j serde_json::Value,
+    field: &str,
+) -> Result<&'j serde_json::Value, CanonicalJsonError> {
+    json.get(field)
+        .ok_or_else(|| CanonicalJsonError::InvalidValue(format!("Missing `{field}` in {json}")))
+}
+
+#[cfg(all(feature = "std", feature = "serde"))]
+fn json_str<'j>(json: &'j serde_json::Value, field: &str) -> Result<&'j str, CanonicalJsonError> {
+    json_field(json, field)?
+        .as_str()
+        .ok_or_else(|| invalid_json_value(json, field))
+}
+
+/// Integers are serialized as numbers, or as strings if they may not fit in a JSON number.
+#[cfg(all(feature = "std", feature = "serde"))]
+fn json_parse<T: FromStr>(json: &serde_json::Value, field: &str) -> Result<T, CanonicalJsonError> {
+    let parsed = match json_field(json, field)? {
+        serde_json::Value::String(value) => value.parse().ok(),
+        serde_json::Value::Number(value) => value.to_string().parse().ok(),
+        _ => None,
+    };
+    parsed.ok_or_else(|| invalid_json_value(json, field))
+}
+
+#[cfg(all(feature = "std", feature = "serde"))]
+fn json_value_kind(
+    json: &serde_json::Value,
+    field: &str,
+) -> Result<ScryptoValueKind, CanonicalJsonError> {
+    value_kind_from_name(json_str(json, field)?).ok_or_else(|| invalid_json_value(json, field))
+}
+
+#[cfg(all(feature = "std", feature = "serde"))]
+fn json_values(
+    json: &serde_json::Value,
+    field: &str,
+    decoder: &AddressBech32Decoder,
+) -> Result<Vec<ScryptoValue>, CanonicalJsonError> {
+    json_field(json, field)?
+        .as_array()
+        .ok_or_else(|| invalid_json_value(json, field))?
+        .iter()
+        .map(|value| scrypto_value_from_json(value, decoder))
+        .collect()
+}
+
+#[cfg(all(feature = "std", feature = "serde"))]
+fn invalid_json_value(json: &serde_json::Value, field: &str) -> CanonicalJsonError {
+    CanonicalJsonError::InvalidValue(format!("Invalid `{field}` in {json}"))
+}
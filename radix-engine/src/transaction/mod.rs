@@ -1,13 +1,21 @@
+mod canonical_receipt;
+#[cfg(feature = "std")]
+mod parallel_executor;
 mod preview_executor;
 mod state_update_summary;
 mod system_structure;
 mod transaction_executor;
 mod transaction_receipt;
 mod transaction_reconciler;
+mod transaction_replay;
 
+pub use canonical_receipt::*;
+#[cfg(feature = "std")]
+pub use parallel_executor::*;
 pub use preview_executor::*;
 pub use state_update_summary::*;
 pub use system_structure::*;
 pub use transaction_executor::*;
 pub use transaction_receipt::*;
 pub use transaction_reconciler::*;
+pub use transaction_replay::*;
@@ -0,0 +1,402 @@
+use crate::blueprints::consensus_manager::*;
+use crate::blueprints::resource::*;
+use crate::internal_prelude::*;
+use crate::transaction::*;
+use crate::vm::*;
+use radix_substate_store_interface::db_key_mapper::*;
+use radix_substate_store_interface::interface::*;
+use radix_transactions::model::*;
+use sbor::rust::cell::RefCell;
+
+/// How a transaction in a batch passed to [`execute_and_commit_transactions_in_parallel`] ended
+/// up being executed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ParallelExecutionMode {
+    /// Executed in parallel against the state from before the batch, and none of the substates it
+    /// read were changed by an earlier transaction of the batch.
+    Parallel,
+    /// Executed in order, because it references a global address also referenced by an earlier
+    /// transaction of the batch.
+    StaticConflict,
+    /// Executed in parallel, but re-executed in order, because it read a substate changed by an
+    /// earlier transaction of the batch.
+    RuntimeConflict,
+}
+
+#[derive(Debug, Clone)]
+pub struct ParallelExecutedTransaction {
+    pub receipt: TransactionReceipt,
+    pub mode: ParallelExecutionMode,
+}
+
+/// Executes the given transactions on up to `thread_count` threads, and commits their state
+/// updates in order, with the same results as executing and committing them one after another.
+///
+/// Conflicts are first detected statically: a transaction which references a global address
+/// referenced by an earlier transaction of the batch, other than the always visible ones, is not
+/// executed in parallel. The remaining
+/// transactions are executed in parallel against the state from before the batch, recording the
+/// substates they read. On commit, a transaction which read a substate changed by an earlier
+/// transaction of the batch is re-executed against the committed state.
+///
+/// Fee payments add to the validator rewards of the consensus manager, which every transaction
+/// reads and writes. These are not treated as conflicts for transactions which don't reference
+/// the consensus manager. Instead, the fees paid by a transaction executed in parallel are added
+/// to the committed validator rewards.
+pub fn execute_and_commit_transactions_in_parallel<V: VmInitialize + Sync>(
+    substate_db: &mut (impl SubstateDatabase + CommittableSubstateDatabase + Sync),
+    vm_modules: &V,
+    execution_config: &ExecutionConfig,
+    executables: &[ExecutableTransaction],
+    thread_count: usize,
+) -> Vec<ParallelExecutedTransaction> {
+    let parallel_indices = find_statically_independent_transactions(executables);
+    let fee_sinks = FeeSinks::read(&*substate_db);
+
+    let mut parallel_results = {
+        let substate_db = &*substate_db;
+        let chunk_size = parallel_indices.len().div_ceil(thread_count.max(1)).max(1);
+        std::thread::scope(|scope| {
+            let threads = parallel_indices
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|index| {
+                                let recording_db = ReadRecordingDatabase::new(substate_db);
+                                let receipt = execute_transaction(
+                                    &recording_db,
+                                    vm_modules,
+                                    execution_config,
+                                    &executables[*index],
+                                );
+                                (*index, (receipt, recording_db.into_reads()))
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+            threads
+                .into_iter()
+                .flat_map(|thread| thread.join().expect("Execution thread panicked"))
+                .collect::<IndexMap<_, _>>()
+        })
+    };
+
+    let mut changes = ChangedSubstates::default();
+    let mut executed = Vec::with_capacity(executables.len());
+    for (index, executable) in executables.iter().enumerate() {
+        let (mut receipt, mode) = match parallel_results.swap_remove(&index) {
+            Some((receipt, reads)) => {
+                let ignored_reads = match &fee_sinks {
+                    Some(fee_sinks)
+                        if !executable.all_references().iter().any(|reference| {
+                            reference.as_node_id() == CONSENSUS_MANAGER.as_node_id()
+                        }) =>
+                    {
+                        fee_sinks.db_keys()
+                    }
+                    _ => vec![],
+                };
+                if changes.conflict_with(&reads, &ignored_reads) {
+                    (
+                        execute_transaction(substate_db, vm_modules, execution_config, executable),
+                        ParallelExecutionMode::RuntimeConflict,
+                    )
+                } else {
+                    (receipt, ParallelExecutionMode::Parallel)
+                }
+            }
+            None => (
+                execute_transaction(substate_db, vm_modules, execution_config, executable),
+                ParallelExecutionMode::StaticConflict,
+            ),
+        };
+        if let TransactionResult::Commit(commit) = &mut receipt.result {
+            if let (ParallelExecutionMode::Parallel, Some(fee_sinks)) = (mode, &fee_sinks) {
+                fee_sinks.merge_fees(&*substate_db, &mut commit.state_updates);
+            }
+            let database_updates = commit.state_updates.create_database_updates();
+            changes.record(&*substate_db, &database_updates);
+            substate_db.commit(&database_updates);
+        }
+        executed.push(ParallelExecutedTransaction { receipt, mode });
+    }
+    executed
+}
+
+/// Returns the indices of the transactions which reference no global address referenced by an
+/// earlier transaction.
+///
+/// The always visible global nodes (e.g. the signature resources) are ignored, as any transaction
+/// can access them without referencing them.
+fn find_statically_independent_transactions(executables: &[ExecutableTransaction]) -> Vec<usize> {
+    let always_visible_global_nodes =
+        always_visible_global_nodes(AlwaysVisibleGlobalNodesVersion::latest());
+    let mut referenced_addresses = index_set_new();
+    let mut independent_indices = Vec::new();
+    for (index, executable) in executables.iter().enumerate() {
+        let addresses = executable
+            .all_references()
+            .into_iter()
+            .filter(|reference| {
+                reference.as_node_id().is_global()
+                    && !always_visible_global_nodes.contains(reference.as_node_id())
+            })
+            .collect::<IndexSet<_>>();
+        if addresses.is_disjoint(&referenced_addresses) {
+            independent_indices.push(index);
+        }
+        referenced_addresses.extend(addresses);
+    }
+    independent_indices
+}
+
+/// The substates which the fees of every transaction are paid into, as of before the batch.
+struct FeeSinks {
+    validator_rewards: ValidatorRewardsSubstate,
+    rewards_vault_balance: LiquidFungibleResource,
+}
+
+impl FeeSinks {
+    fn read(substate_db: &impl SubstateDatabase) -> Option<Self> {
+        let validator_rewards = substate_db
+            .get_substate::<FieldSubstate<ConsensusManagerValidatorRewardsFieldPayload>>(
+                CONSENSUS_MANAGER,
+                MAIN_BASE_PARTITION,
+                ConsensusManagerField::ValidatorRewards.field_index(),
+            )?
+            .into_payload()
+            .into_unique_version();
+        let rewards_vault_balance = substate_db
+            .get_substate::<FungibleVaultBalanceFieldSubstate>(
+                validator_rewards.rewards_vault.0 .0,
+                MAIN_BASE_PARTITION,
+                FungibleVaultField::Balance.field_index(),
+            )?
+            .into_payload()
+            .into_unique_version();
+        Some(Self {
+            validator_rewards,
+            rewards_vault_balance,
+        })
+    }
+
+    fn rewards_vault_id(&self) -> NodeId {
+        self.validator_rewards.rewards_vault.0 .0
+    }
+
+    fn db_keys(&self) -> Vec<(DbPartitionKey, DbSortKey)> {
+        vec![
+            (
+                SpreadPrefixKeyMapper::to_db_partition_key(
+                    CONSENSUS_MANAGER.as_node_id(),
+                    MAIN_BASE_PARTITION,
+                ),
+                SpreadPrefixKeyMapper::to_db_sort_key(
+                    &ConsensusManagerField::ValidatorRewards.into(),
+                ),
+            ),
+            (
+                SpreadPrefixKeyMapper::to_db_partition_key(
+                    &self.rewards_vault_id(),
+                    MAIN_BASE_PARTITION,
+                ),
+                SpreadPrefixKeyMapper::to_db_sort_key(&FungibleVaultField::Balance.into()),
+            ),
+        ]
+    }
+
+    /// Rebases the fee sink updates of a transaction executed against the state from before the
+    /// batch onto the committed state, by adding the fees it paid to the committed values.
+    fn merge_fees(&self, substate_db: &impl SubstateDatabase, state_updates: &mut StateUpdates) {
+        if let Some(value) = written_value_mut(
+            state_updates,
+            CONSENSUS_MANAGER.as_node_id(),
+            &ConsensusManagerField::ValidatorRewards.into(),
+        ) {
+            let written = scrypto_decode::<
+                FieldSubstate<ConsensusManagerValidatorRewardsFieldPayload>,
+            >(value)
+            .expect("Validator rewards should decode")
+            .into_payload()
+            .into_unique_version();
+            let mut merged = Self::read(substate_db)
+                .expect("Validator rewards should exist")
+                .validator_rewards;
+            for (validator_index, written_amount) in written.proposer_rewards {
+                let base_amount = self
+                    .validator_rewards
+                    .proposer_rewards
+                    .get(&validator_index)
+                    .cloned()
+                    .unwrap_or_default();
+                let entry = merged.proposer_rewards.entry(validator_index).or_default();
+                *entry = entry
+                    .checked_add(written_amount.checked_sub(base_amount).unwrap())
+                    .unwrap();
+            }
+            *value = scrypto_encode(&FieldSubstate::new_unlocked_field(
+                ConsensusManagerValidatorRewardsFieldPayload::from_content_source(merged),
+            ))
+            .unwrap();
+        }
+
+        if let Some(value) = written_value_mut(
+            state_updates,
+            &self.rewards_vault_id(),
+            &FungibleVaultField::Balance.into(),
+        ) {
+            let written = scrypto_decode::<FungibleVaultBalanceFieldSubstate>(value)
+                .expect("Rewards vault balance should decode")
+                .into_payload()
+                .into_unique_version();
+            let committed = Self::read(substate_db)
+                .expect("Rewards vault should exist")
+                .rewards_vault_balance;
+            let merged = LiquidFungibleResource::new(
+                committed
+                    .amount()
+                    .checked_add(written.amount())
+                    .unwrap()
+                    .checked_sub(self.rewards_vault_balance.amount())
+                    .unwrap(),
+            );
+            *value = scrypto_encode(
+                &FungibleVaultBalanceFieldPayload::from_content_source(merged)
+                    .into_unlocked_substate(),
+            )
+            .unwrap();
+        }
+    }
+}
+
+/// Returns the value set by the given state updates for a substate of the main partition.
+fn written_value_mut<'a>(
+    state_updates: &'a mut StateUpdates,
+    node_id: &NodeId,
+    substate_key: &SubstateKey,
+) -> Option<&'a mut DbSubstateValue> {
+    let NodeStateUpdates::Delta { by_partition } = state_updates.by_node.get_mut(node_id)?;
+    match by_partition.get_mut(&MAIN_BASE_PARTITION)? {
+        PartitionStateUpdates::Delta { by_substate } => match by_substate.get_mut(substate_key)? {
+            DatabaseUpdate::Set(value) => Some(value),
+            DatabaseUpdate::Delete => None,
+        },
+        PartitionStateUpdates::Batch(..) => None,
+    }
+}
+
+/// The substates changed by the transactions of the batch committed so far.
+#[derive(Default)]
+struct ChangedSubstates {
+    substates: IndexSet<(DbPartitionKey, DbSortKey)>,
+    /// The partitions with any substate changed, or which were reset.
+    partitions: IndexSet<DbPartitionKey>,
+    reset_partitions: IndexSet<DbPartitionKey>,
+}
+
+impl ChangedSubstates {
+    /// Records the substates which the given updates change, before they are committed.
+    fn record(&mut self, substate_db: &impl SubstateDatabase, database_updates: &DatabaseUpdates) {
+        for (node_key, node_updates) in &database_updates.node_updates {
+            for (partition_num, partition_updates) in &node_updates.partition_updates {
+                let partition_key = DbPartitionKey {
+                    node_key: node_key.clone(),
+                    partition_num: *partition_num,
+                };
+                match partition_updates {
+                    PartitionDatabaseUpdates::Delta { substate_updates } => {
+                        for (sort_key, update) in substate_updates {
+                            let current_value =
+                                substate_db.get_raw_substate_by_db_key(&partition_key, sort_key);
+                            let is_changed = match update {
+                                DatabaseUpdate::Set(value) => current_value.as_ref() != Some(value),
+                                DatabaseUpdate::Delete => current_value.is_some(),
+                            };
+                            if is_changed {
+                                self.substates
+                                    .insert((partition_key.clone(), sort_key.clone()));
+                                self.partitions.insert(partition_key.clone());
+                            }
+                        }
+                    }
+                    PartitionDatabaseUpdates::Reset { .. } => {
+                        self.partitions.insert(partition_key.clone());
+                        self.reset_partitions.insert(partition_key);
+                    }
+                }
+            }
+        }
+    }
+
+    fn conflict_with(
+        &self,
+        reads: &RecordedReads,
+        ignored_substates: &[(DbPartitionKey, DbSortKey)],
+    ) -> bool {
+        reads.substates.iter().any(|substate| {
+            !ignored_substates.contains(substate)
+                && (self.substates.contains(substate)
+                    || self.reset_partitions.contains(&substate.0))
+        }) || reads
+            .partitions
+            .iter()
+            .any(|partition_key| self.partitions.contains(partition_key))
+    }
+}
+
+#[derive(Default)]
+struct RecordedReads {
+    substates: IndexSet<(DbPartitionKey, DbSortKey)>,
+    /// The partitions listed, rather than read one substate at a time.
+    partitions: IndexSet<DbPartitionKey>,
+}
+
+/// Records the substates and partitions read from the wrapped database.
+struct ReadRecordingDatabase<'s, S: SubstateDatabase> {
+    substate_db: &'s S,
+    reads: RefCell<RecordedReads>,
+}
+
+impl<'s, S: SubstateDatabase> ReadRecordingDatabase<'s, S> {
+    fn new(substate_db: &'s S) -> Self {
+        Self {
+            substate_db,
+            reads: RefCell::new(RecordedReads::default()),
+        }
+    }
+
+    fn into_reads(self) -> RecordedReads {
+        self.reads.into_inner()
+    }
+}
+
+impl<'s, S: SubstateDatabase> SubstateDatabase for ReadRecordingDatabase<'s, S> {
+    fn get_raw_substate_by_db_key(
+        &self,
+        partition_key: &DbPartitionKey,
+        sort_key: &DbSortKey,
+    ) -> Option<DbSubstateValue> {
+        self.reads
+            .borrow_mut()
+            .substates
+            .insert((partition_key.clone(), sort_key.clone()));
+        self.substate_db
+            .get_raw_substate_by_db_key(partition_key, sort_key)
+    }
+
+    fn list_raw_values_from_db_key(
+        &self,
+        partition_key: &DbPartitionKey,
+        from_sort_key: Option<&DbSortKey>,
+    ) -> Box<dyn Iterator<Item = PartitionEntry> + '_> {
+        self.reads
+            .borrow_mut()
+            .partitions
+            .insert(partition_key.clone());
+        self.substate_db
+            .list_raw_values_from_db_key(partition_key, from_sort_key)
+    }
+}
@@ -4,7 +4,7 @@ use crate::vm::VmInitialize;
 use radix_common::network::NetworkDefinition;
 use radix_substate_store_interface::interface::*;
 use radix_transactions::errors::TransactionValidationError;
-use radix_transactions::model::PreviewIntentV1;
+use radix_transactions::model::{PreviewFlags, PreviewIntentV1, PreviewTransactionV2};
 use radix_transactions::validation::*;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -27,12 +27,8 @@ pub fn execute_preview(
 ) -> Result<TransactionReceipt, PreviewError> {
     let validator = TransactionValidator::new(substate_db, network);
 
-    let mut execution_config = if preview_intent.flags.disable_auth {
-        ExecutionConfig::for_preview_no_auth(network.clone())
-    } else {
-        ExecutionConfig::for_preview(network.clone())
-    };
-    execution_config = execution_config.with_kernel_trace(with_kernel_trace);
+    let execution_config = preview_execution_config(network.clone(), &preview_intent.flags)
+        .with_kernel_trace(with_kernel_trace);
 
     let validated = validator.validate_preview_intent_v1(preview_intent)?;
 
@@ -43,3 +39,34 @@ pub fn execute_preview(
         validated.create_executable(),
     ))
 }
+
+/// Executes a V2 preview transaction, validated by the given validator, with the given execution
+/// config, which is usually created with [`preview_execution_config`].
+pub fn execute_preview_v2(
+    substate_db: &impl SubstateDatabase,
+    vm_modules: &impl VmInitialize,
+    validator: &TransactionValidator,
+    preview_transaction: PreviewTransactionV2,
+    flags: PreviewFlags,
+    execution_config: &ExecutionConfig,
+) -> Result<TransactionReceipt, PreviewError> {
+    let validated = preview_transaction.prepare_and_validate(validator)?;
+
+    Ok(execute_transaction(
+        substate_db,
+        vm_modules,
+        execution_config,
+        validated.create_executable(flags),
+    ))
+}
+
+pub fn preview_execution_config(
+    network_definition: NetworkDefinition,
+    flags: &PreviewFlags,
+) -> ExecutionConfig {
+    if flags.disable_auth {
+        ExecutionConfig::for_preview_no_auth(network_definition)
+    } else {
+        ExecutionConfig::for_preview(network_definition)
+    }
+}
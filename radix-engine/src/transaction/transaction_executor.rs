@@ -2,6 +2,9 @@ use crate::errors::*;
 use crate::internal_prelude::*;
 use crate::kernel::kernel::KernelInit;
 use crate::system::system_callback::*;
+use crate::system::system_modules::debugger::DebuggerConfig;
+use crate::system::system_modules::limits::TransactionQuotas;
+use crate::system::system_modules::metrics::ExecutionMetricsSink;
 use crate::transaction::*;
 use crate::vm::*;
 use radix_common::constants::*;
@@ -135,6 +138,17 @@ pub struct SystemOverrides {
     pub network_definition: Option<NetworkDefinition>,
     pub costing_parameters: Option<CostingParameters>,
     pub limit_parameters: Option<LimitParameters>,
+    /// Node-local quotas on the kernel operations of the transaction. Other nodes may execute
+    /// the same transaction without them, so they're only for previews and mempool rejection
+    /// checks, and must never be set for a transaction which is committed. They can only be set
+    /// with [`ExecutionConfig::for_preview_with_transaction_quotas`] or
+    /// [`ExecutionConfig::for_notarized_transaction_rejection_check_with_transaction_quotas`].
+    pub(crate) transaction_quotas: Option<TransactionQuotas>,
+    /// Whether blueprints can call the transaction processor to run manifests of their own, each
+    /// with its own worktop and auth zone. Otherwise, only the root call frame can call it.
+    /// This isn't enacted by any protocol version, so it's only for previews and tests, and must
+    /// never be set for a transaction which is committed.
+    pub allow_nested_transaction_processor: bool,
 }
 
 impl SystemOverrides {
@@ -147,6 +161,8 @@ impl SystemOverrides {
             network_definition,
             costing_parameters: None,
             limit_parameters: None,
+            transaction_quotas: None,
+            allow_nested_transaction_processor: false,
         }
     }
 
@@ -166,10 +182,20 @@ impl SystemOverrides {
         self
     }
 
+    pub const fn set_limit_parameters(mut self, limit_parameters: Option<LimitParameters>) -> Self {
+        self.limit_parameters = limit_parameters;
+        self
+    }
+
     pub fn set_abort_when_loan_repaid(mut self) -> Self {
         self.abort_when_loan_repaid = true;
         self
     }
+
+    pub fn set_allow_nested_transaction_processor(mut self) -> Self {
+        self.allow_nested_transaction_processor = true;
+        self
+    }
 }
 
 impl Default for SystemOverrides {
@@ -185,6 +211,9 @@ pub struct ExecutionConfig {
     pub enable_cost_breakdown: bool,
     pub execution_trace: Option<usize>,
     pub enable_debug_information: bool,
+    pub enable_execution_audit: bool,
+    pub debugger: Option<DebuggerConfig>,
+    pub metrics_sink: Option<Arc<dyn ExecutionMetricsSink>>,
 
     pub system_overrides: Option<SystemOverrides>,
 }
@@ -199,6 +228,9 @@ impl ExecutionConfig {
             execution_trace: None,
             system_overrides: None,
             enable_debug_information: false,
+            enable_execution_audit: false,
+            debugger: None,
+            metrics_sink: None,
         }
     }
 
@@ -259,6 +291,14 @@ impl ExecutionConfig {
             .update_system_overrides(|overrides| overrides.set_abort_when_loan_repaid())
     }
 
+    pub fn for_notarized_transaction_rejection_check_with_transaction_quotas(
+        network_definition: NetworkDefinition,
+        transaction_quotas: TransactionQuotas,
+    ) -> Self {
+        Self::for_notarized_transaction_rejection_check(network_definition)
+            .with_transaction_quotas(transaction_quotas)
+    }
+
     pub fn update_system_overrides(
         mut self,
         update: impl FnOnce(SystemOverrides) -> SystemOverrides,
@@ -289,6 +329,13 @@ impl ExecutionConfig {
         }
     }
 
+    pub fn for_preview_with_transaction_quotas(
+        network_definition: NetworkDefinition,
+        transaction_quotas: TransactionQuotas,
+    ) -> Self {
+        Self::for_preview(network_definition).with_transaction_quotas(transaction_quotas)
+    }
+
     pub fn for_preview_no_auth(network_definition: NetworkDefinition) -> Self {
         Self {
             enable_cost_breakdown: true,
@@ -302,6 +349,25 @@ impl ExecutionConfig {
         }
     }
 
+    fn with_transaction_quotas(self, transaction_quotas: TransactionQuotas) -> Self {
+        self.update_system_overrides(|mut overrides| {
+            overrides.transaction_quotas = Some(transaction_quotas);
+            overrides
+        })
+    }
+
+    pub fn has_transaction_quotas(&self) -> bool {
+        self.system_overrides
+            .as_ref()
+            .is_some_and(|overrides| overrides.transaction_quotas.is_some())
+    }
+
+    pub fn allows_nested_transaction_processor(&self) -> bool {
+        self.system_overrides
+            .as_ref()
+            .is_some_and(|overrides| overrides.allow_nested_transaction_processor)
+    }
+
     pub fn with_kernel_trace(mut self, enabled: bool) -> Self {
         self.enable_kernel_trace = enabled;
         self
@@ -316,6 +382,28 @@ impl ExecutionConfig {
         self.enable_cost_breakdown = enabled;
         self
     }
+
+    /// Records a hash of every kernel call into the receipt's
+    /// [`execution_audit`](TransactionReceipt::execution_audit), so that two runs of the same
+    /// transaction can be compared for nondeterminism.
+    pub fn with_execution_audit(mut self, enabled: bool) -> Self {
+        self.enable_execution_audit = enabled;
+        self
+    }
+
+    /// Pauses execution at the given breakpoints, invoking the debugger callback with the state
+    /// of the current call frame.
+    pub fn with_debugger(mut self, debugger: DebuggerConfig) -> Self {
+        self.debugger = Some(debugger);
+        self
+    }
+
+    /// Counts the kernel calls and WASM instantiations of the transaction, pushing them to the
+    /// sink when its receipt is created.
+    pub fn with_metrics_sink(mut self, sink: Arc<dyn ExecutionMetricsSink>) -> Self {
+        self.metrics_sink = Some(sink);
+        self
+    }
 }
 
 pub fn execute_transaction<'v, V: VmInitialize>(
@@ -335,6 +423,14 @@ pub fn execute_and_commit_transaction<'s, V: VmInitialize>(
     execution_config: &ExecutionConfig,
     executable: impl AsRef<ExecutableTransaction>,
 ) -> TransactionReceipt {
+    assert!(
+        !execution_config.has_transaction_quotas(),
+        "Transaction quotas are node-local and must not be set for committed transactions"
+    );
+    assert!(
+        !execution_config.allows_nested_transaction_processor(),
+        "Nested transaction processors are only for previews and tests, and must not be allowed for committed transactions"
+    );
     let receipt = execute_transaction(substate_db, vm_modules, execution_config, executable);
     if let TransactionResult::Commit(commit) = &receipt.result {
         substate_db.commit(&commit.state_updates.create_database_updates());
@@ -1,10 +1,16 @@
 use super::*;
 use crate::blueprints::consensus_manager::EpochChangeEvent;
+use crate::blueprints::resource::{
+    BurnFungibleResourceEvent, BurnNonFungibleResourceEvent, MintFungibleResourceEvent,
+    MintNonFungibleResourceEvent,
+};
 use crate::errors::*;
 use crate::internal_prelude::*;
 use crate::kernel::kernel_callback_api::ExecutionReceipt;
+use crate::system::actor::Actor;
 use crate::system::system_db_reader::SystemDatabaseReader;
 use crate::system::system_modules::costing::*;
+use crate::system::system_modules::execution_audit::*;
 use crate::system::system_modules::execution_trace::*;
 use crate::system::system_substate_schemas::*;
 use crate::transaction::SystemStructure;
@@ -34,6 +40,12 @@ pub struct TransactionReceipt {
     /// This field contains debug information about the transaction which is extracted during the
     /// transaction execution.
     pub debug_information: Option<TransactionDebugInformation>,
+    /// The kernel calls made while executing the transaction, for detecting nondeterminism.
+    /// Available for committed transactions if `ExecutionConfig::enable_execution_audit` is enabled
+    pub execution_audit: Option<ExecutionAudit>,
+    /// Where the execution of the intents failed.
+    /// Available if the transaction failed or was rejected while executing its instructions
+    pub failure_context: Option<TransactionFailureContext>,
 }
 
 // Type for backwards compatibility to avoid integrator compile errors
@@ -232,6 +244,8 @@ pub struct CommitResult {
     pub state_update_summary: StateUpdateSummary,
     /// The source of transaction fee
     pub fee_source: FeeSource,
+    /// The fees locked during the transaction, in the order of locking
+    pub locked_fees: Vec<LockedFee>,
     /// The destination of transaction fee
     pub fee_destination: FeeDestination,
     /// Transaction execution outcome
@@ -252,9 +266,28 @@ pub struct CommitResult {
 
 #[derive(Debug, Clone, Default, ScryptoSbor, PartialEq, Eq)]
 pub struct FeeSource {
+    /// The amount of RORK taken from each vault which locked a fee.
+    ///
+    /// Locked fees are used in the reverse order of locking (the most recently locked fee is used
+    /// first), and any unused locked amount is refunded to its vault.
     pub paying_vaults: IndexMap<NodeId, Decimal>,
 }
 
+/// A fee locked by a vault, e.g. by a `lock_fee` call on an account. A transaction can lock fees
+/// from any number of vaults at any point, each lock topping up the fee reserve.
+///
+/// If the transaction fails, non-contingent locks still pay for the cost of the transaction, while
+/// contingent locks are refunded in full.
+#[derive(Debug, Clone, ScryptoSbor, PartialEq, Eq)]
+pub struct LockedFee {
+    pub vault_id: NodeId,
+    pub locked: Decimal,
+    /// Whether the fee can only be used if the transaction succeeds.
+    pub contingent: bool,
+    /// The amount of the lock used to pay for the transaction. The rest is refunded to the vault.
+    pub paid: Decimal,
+}
+
 #[derive(Debug, Clone, Default, ScryptoSbor, PartialEq, Eq)]
 pub struct FeeDestination {
     pub to_proposer: Decimal,
@@ -374,6 +407,41 @@ pub struct TransactionDebugInformation {
     /* Costing Breakdown */
     /// A detailed trace of where execution cost units were consumed.
     pub detailed_execution_cost_breakdown: Vec<DetailedExecutionCostBreakdownEntry>,
+    /// The execution cost units consumed by each manifest instruction, keyed by intent index and
+    /// instruction index, where intent `0` is the transaction intent and intent `i + 1` is the
+    /// subintent at index `i`.
+    pub execution_cost_breakdown_by_instruction: IndexMap<(usize, usize), u32>,
+}
+
+/// The location of the error of a transaction which failed while executing its intents.
+///
+/// Like [`TransactionDebugInformation`], this is intentionally not SBOR codable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionFailureContext {
+    /// The index of the intent which failed, where `0` is the transaction intent and `i + 1` is
+    /// the subintent at index `i`.
+    pub intent_index: usize,
+    /// The index of the last instruction of the intent to start executing. If the intent failed
+    /// after its instructions completed, this is its last instruction.
+    pub instruction_index: Option<usize>,
+    /// The instruction at `instruction_index`.
+    pub instruction: Option<InstructionV2>,
+    /// The actors of the call frames of the intent at the time of the error, from its root frame
+    /// to the frame in which the error occurred.
+    pub call_path: Vec<Actor>,
+}
+
+/// The execution cost of a manifest instruction, as returned by
+/// [`TransactionReceipt::execution_cost_breakdown_by_instruction`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstructionExecutionCost {
+    /// The index of the intent of the instruction, where `0` is the transaction intent and
+    /// `i + 1` is the subintent at index `i`.
+    pub intent_index: usize,
+    pub instruction_index: usize,
+    pub cost_units: u32,
+    /// The cost of the execution cost units in RORK, excluding tip.
+    pub cost_in_xrd: Decimal,
 }
 
 impl TransactionExecutionTrace {
@@ -384,8 +452,30 @@ impl TransactionExecutionTrace {
         }
         aggregator
     }
+
+    /// Returns the net resource changes of all instructions, by the node of the actor which moved
+    /// the resources in or out of its vaults and then by resource. Net zero changes are omitted.
+    pub fn resource_changes_by_component(&self) -> ResourceChanges {
+        let mut resource_changes = ResourceChanges::new();
+        for resource_change in self.resource_changes.values().flatten() {
+            let delta = resource_changes
+                .entry(resource_change.node_id)
+                .or_default()
+                .entry(resource_change.resource_address)
+                .or_default();
+            *delta = delta.checked_add(resource_change.amount).unwrap();
+        }
+        for (_, deltas) in resource_changes.iter_mut() {
+            deltas.retain(|_, delta| !delta.is_zero());
+        }
+        resource_changes.retain(|_, deltas| !deltas.is_empty());
+        resource_changes
+    }
 }
 
+/// Net resource changes, by the node of a component and then by resource.
+pub type ResourceChanges = IndexMap<NodeId, IndexMap<ResourceAddress, Decimal>>;
+
 impl TransactionResult {
     pub fn is_commit_success(&self) -> bool {
         match self {
@@ -401,6 +491,7 @@ impl CommitResult {
             state_updates: Default::default(),
             state_update_summary: Default::default(),
             fee_source: Default::default(),
+            locked_fees: Default::default(),
             fee_destination: Default::default(),
             outcome,
             application_events: Default::default(),
@@ -437,6 +528,38 @@ impl CommitResult {
         None
     }
 
+    /// Returns the decoded events of the given type, alongside their emitter, in the order
+    /// they were emitted.
+    ///
+    /// Events are matched on their name, and events which share the name but fail to decode
+    /// as `T` are skipped, so callers should still check the emitter where names are ambiguous.
+    pub fn application_events_of<T: ScryptoEvent>(&self) -> Vec<(&Emitter, T)> {
+        self.application_events
+            .iter()
+            .filter(|(EventTypeIdentifier(_, event_name), _)| event_name == T::EVENT_NAME)
+            .filter_map(|(EventTypeIdentifier(emitter, _), event_data)| {
+                scrypto_decode::<T>(event_data)
+                    .ok()
+                    .map(|event| (emitter, event))
+            })
+            .collect()
+    }
+
+    /// Returns the only decoded event of the given type, alongside its emitter.
+    ///
+    /// Panics unless exactly one such event was emitted.
+    pub fn expect_single_application_event_of<T: ScryptoEvent>(&self) -> (&Emitter, T) {
+        let mut events = self.application_events_of::<T>();
+        if events.len() != 1 {
+            panic!(
+                "Expected a single {} event but found {}",
+                T::EVENT_NAME,
+                events.len()
+            );
+        }
+        events.remove(0)
+    }
+
     pub fn new_package_addresses(&self) -> &IndexSet<PackageAddress> {
         &self.state_update_summary.new_packages
     }
@@ -457,6 +580,70 @@ impl CommitResult {
         &self.state_update_summary.vault_balance_changes
     }
 
+    /// Returns the net change of the total supply of each resource which was minted or burned, as
+    /// reported by the mint and burn events of its resource manager.
+    ///
+    /// Resources whose mints and burns cancel each other out are omitted.
+    pub fn resource_supply_changes(&self) -> IndexMap<ResourceAddress, BalanceChange> {
+        let mut supply_changes: IndexMap<ResourceAddress, BalanceChange> = index_map_new();
+        for (EventTypeIdentifier(emitter, event_name), event_data) in &self.application_events {
+            let Emitter::Method(node_id, ModuleId::Main) = emitter else {
+                continue;
+            };
+            if !node_id.is_global_resource_manager() {
+                continue;
+            }
+            let supply_change = match event_name.as_str() {
+                MintFungibleResourceEvent::EVENT_NAME => {
+                    scrypto_decode::<MintFungibleResourceEvent>(event_data)
+                        .ok()
+                        .map(|event| BalanceChange::Fungible(event.amount))
+                }
+                BurnFungibleResourceEvent::EVENT_NAME => {
+                    scrypto_decode::<BurnFungibleResourceEvent>(event_data)
+                        .ok()
+                        .map(|event| BalanceChange::Fungible(-event.amount))
+                }
+                MintNonFungibleResourceEvent::EVENT_NAME => {
+                    scrypto_decode::<MintNonFungibleResourceEvent>(event_data)
+                        .ok()
+                        .map(|event| BalanceChange::NonFungible {
+                            added: event.ids.into_iter().collect(),
+                            removed: BTreeSet::new(),
+                        })
+                }
+                BurnNonFungibleResourceEvent::EVENT_NAME => {
+                    scrypto_decode::<BurnNonFungibleResourceEvent>(event_data)
+                        .ok()
+                        .map(|event| BalanceChange::NonFungible {
+                            added: BTreeSet::new(),
+                            removed: event.ids.into_iter().collect(),
+                        })
+                }
+                _ => None,
+            };
+            let Some(supply_change) = supply_change else {
+                continue;
+            };
+            let resource_address = ResourceAddress::new_or_panic(node_id.0);
+            match supply_changes.get_mut(&resource_address) {
+                Some(existing_supply_change) => *existing_supply_change += supply_change,
+                None => {
+                    supply_changes.insert(resource_address, supply_change);
+                }
+            }
+        }
+        supply_changes.retain(|_, supply_change| !supply_change.prune_and_check_if_zero());
+        supply_changes
+    }
+
+    /// Returns the net resource changes of each component, if the execution trace is enabled.
+    pub fn resource_changes_by_component(&self) -> Option<ResourceChanges> {
+        self.execution_trace
+            .as_ref()
+            .map(|execution_trace| execution_trace.resource_changes_by_component())
+    }
+
     pub fn output<T: ScryptoDecode>(&self, nth: usize) -> T {
         match &self.outcome {
             TransactionOutcome::Success(o) => match o.get(nth) {
@@ -511,6 +698,8 @@ impl TransactionReceipt {
             result: TransactionResult::Commit(commit_result),
             resources_usage: Default::default(),
             debug_information: Default::default(),
+            execution_audit: Default::default(),
+            failure_context: Default::default(),
         }
     }
 
@@ -520,6 +709,35 @@ impl TransactionReceipt {
         ))
     }
 
+    /// Returns the execution cost units consumed by each manifest instruction, along with their
+    /// cost in RORK (excluding tip), ordered by intent and then by instruction.
+    ///
+    /// Costs incurred outside of an instruction, such as when switching between intents or
+    /// dropping the worktop, are not attributed to any instruction.
+    ///
+    /// This is only available if the transaction was executed with debug information enabled.
+    pub fn execution_cost_breakdown_by_instruction(&self) -> Option<Vec<InstructionExecutionCost>> {
+        let debug_information = self.debug_information.as_ref()?;
+        let mut breakdown = debug_information
+            .execution_cost_breakdown_by_instruction
+            .iter()
+            .map(
+                |(&(intent_index, instruction_index), &cost_units)| InstructionExecutionCost {
+                    intent_index,
+                    instruction_index,
+                    cost_units,
+                    cost_in_xrd: self
+                        .costing_parameters
+                        .execution_cost_unit_price
+                        .checked_mul(Decimal::from(cost_units))
+                        .unwrap(),
+                },
+            )
+            .collect::<Vec<_>>();
+        breakdown.sort_by_key(|cost| (cost.intent_index, cost.instruction_index));
+        Some(breakdown)
+    }
+
     pub fn is_commit_success(&self) -> bool {
         matches!(
             self.result,
@@ -1075,6 +1293,34 @@ impl<'a> ContextualDisplay<TransactionReceiptDisplayContext<'a>> for Transaction
                 )?;
             }
 
+            if let Some(resource_changes) = c.resource_changes_by_component() {
+                let resource_changes: Vec<_> = resource_changes
+                    .iter()
+                    .flat_map(|(node_id, deltas)| {
+                        deltas
+                            .iter()
+                            .map(move |(resource, delta)| (node_id, resource, delta))
+                    })
+                    .collect();
+                context.format_top_level_title_with_detail(
+                    f,
+                    "Resource Changes",
+                    resource_changes.len(),
+                )?;
+                for (i, (node_id, resource, delta)) in resource_changes.iter().enumerate() {
+                    write!(
+                        f,
+                        // NB - we use Entity instead of Component to protect people who read new components as
+                        //      `Component: ` from the receipts
+                        "\n{} Entity: {}\n   ResAddr: {}\n   Change: {}",
+                        prefix!(i, resource_changes),
+                        node_id.display(address_display_context),
+                        resource.display(address_display_context),
+                        delta
+                    )?;
+                }
+            }
+
             context.format_top_level_title_with_detail(
                 f,
                 "New Entities",
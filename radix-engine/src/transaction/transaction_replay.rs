@@ -0,0 +1,229 @@
+use crate::internal_prelude::*;
+use crate::transaction::*;
+use crate::vm::*;
+use radix_substate_store_interface::interface::*;
+use radix_transactions::model::*;
+
+/// A previously committed transaction, together with the result it had when it was committed.
+#[derive(Debug, Clone)]
+pub struct CommittedTransactionRecord {
+    pub executable: ExecutableTransaction,
+    pub result: TransactionResult,
+}
+
+/// The first point at which a replayed transaction behaved differently than when it was
+/// originally committed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayDivergence {
+    /// The index of the diverging transaction within the replayed list.
+    pub transaction_index: usize,
+    pub kind: ReplayDivergenceKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayDivergenceKind {
+    /// The transaction was committed on one run, but rejected or aborted on the other, or the
+    /// results differ in some way not covered by the other variants (e.g. the fee source).
+    Result {
+        expected: Box<TransactionResult>,
+        actual: Box<TransactionResult>,
+    },
+    /// The transaction was committed on both runs, but with different outcomes.
+    Outcome {
+        expected: TransactionOutcome,
+        actual: TransactionOutcome,
+    },
+    /// A partition was written differently, in a way which cannot be pinned down to a single
+    /// substate (e.g. it was reset on one run, and only updated on the other).
+    PartitionWrite {
+        partition_key: DbPartitionKey,
+        expected: Option<PartitionDatabaseUpdates>,
+        actual: Option<PartitionDatabaseUpdates>,
+    },
+    /// A substate was written differently.
+    SubstateWrite {
+        partition_key: DbPartitionKey,
+        sort_key: DbSortKey,
+        expected: Option<DatabaseUpdate>,
+        actual: Option<DatabaseUpdate>,
+    },
+    /// The emitted events differ, starting at the given index.
+    Event {
+        event_index: usize,
+        expected: Option<(EventTypeIdentifier, Vec<u8>)>,
+        actual: Option<(EventTypeIdentifier, Vec<u8>)>,
+    },
+    /// The emitted logs differ, starting at the given index.
+    Log {
+        log_index: usize,
+        expected: Option<(Level, String)>,
+        actual: Option<(Level, String)>,
+    },
+}
+
+/// Re-executes the given previously committed transactions on top of the given database, and
+/// reports the first one whose result differs from the recorded one.
+///
+/// This is intended for debugging nondeterminism (e.g. after engine changes): the database should
+/// hold the state from before the first of the transactions. Each replayed transaction's state
+/// updates are committed before the next one is executed, and the replay stops at the first
+/// divergence, leaving the diverging transaction uncommitted.
+///
+/// Returns the number of replayed transactions if none diverged.
+pub fn replay_committed_transactions<'a, V: VmInitialize>(
+    substate_db: &mut (impl SubstateDatabase + CommittableSubstateDatabase),
+    vm_modules: &V,
+    execution_config: &ExecutionConfig,
+    transactions: impl IntoIterator<Item = &'a CommittedTransactionRecord>,
+) -> Result<usize, ReplayDivergence> {
+    let mut replayed_count = 0;
+    for (transaction_index, transaction) in transactions.into_iter().enumerate() {
+        let receipt = execute_transaction(
+            substate_db,
+            vm_modules,
+            execution_config,
+            &transaction.executable,
+        );
+        if let Some(kind) = find_result_divergence(&transaction.result, &receipt.result) {
+            return Err(ReplayDivergence {
+                transaction_index,
+                kind,
+            });
+        }
+        if let TransactionResult::Commit(commit) = &receipt.result {
+            substate_db.commit(&commit.state_updates.create_database_updates());
+        }
+        replayed_count += 1;
+    }
+    Ok(replayed_count)
+}
+
+/// Compares the results of two executions of the same transaction, and returns the first
+/// difference (if any).
+pub fn find_result_divergence(
+    expected: &TransactionResult,
+    actual: &TransactionResult,
+) -> Option<ReplayDivergenceKind> {
+    if expected == actual {
+        return None;
+    }
+    if let (TransactionResult::Commit(expected_commit), TransactionResult::Commit(actual_commit)) =
+        (expected, actual)
+    {
+        if expected_commit.outcome != actual_commit.outcome {
+            return Some(ReplayDivergenceKind::Outcome {
+                expected: expected_commit.outcome.clone(),
+                actual: actual_commit.outcome.clone(),
+            });
+        }
+        if let Some(divergence) = find_database_updates_divergence(
+            &expected_commit.state_updates.create_database_updates(),
+            &actual_commit.state_updates.create_database_updates(),
+        ) {
+            return Some(divergence);
+        }
+        if let Some(event_index) = first_difference_index(
+            &expected_commit.application_events,
+            &actual_commit.application_events,
+        ) {
+            return Some(ReplayDivergenceKind::Event {
+                event_index,
+                expected: expected_commit.application_events.get(event_index).cloned(),
+                actual: actual_commit.application_events.get(event_index).cloned(),
+            });
+        }
+        if let Some(log_index) = first_difference_index(
+            &expected_commit.application_logs,
+            &actual_commit.application_logs,
+        ) {
+            return Some(ReplayDivergenceKind::Log {
+                log_index,
+                expected: expected_commit.application_logs.get(log_index).cloned(),
+                actual: actual_commit.application_logs.get(log_index).cloned(),
+            });
+        }
+    }
+    Some(ReplayDivergenceKind::Result {
+        expected: Box::new(expected.clone()),
+        actual: Box::new(actual.clone()),
+    })
+}
+
+fn find_database_updates_divergence(
+    expected: &DatabaseUpdates,
+    actual: &DatabaseUpdates,
+) -> Option<ReplayDivergenceKind> {
+    let partition_updates = |database_updates: &DatabaseUpdates| {
+        database_updates
+            .node_updates
+            .iter()
+            .flat_map(|(node_key, node_updates)| {
+                node_updates
+                    .partition_updates
+                    .iter()
+                    .map(|(partition_num, partition_updates)| {
+                        let partition_key = DbPartitionKey {
+                            node_key: node_key.clone(),
+                            partition_num: *partition_num,
+                        };
+                        (partition_key, partition_updates.clone())
+                    })
+            })
+            .collect::<IndexMap<_, _>>()
+    };
+    let expected = partition_updates(expected);
+    let actual = partition_updates(actual);
+
+    let partition_keys = expected
+        .keys()
+        .chain(actual.keys())
+        .cloned()
+        .collect::<IndexSet<_>>();
+    // A partition missing on one side is the same as one without any substate updates
+    let no_updates = PartitionDatabaseUpdates::Delta {
+        substate_updates: index_map_new(),
+    };
+    for partition_key in partition_keys {
+        let expected_updates = expected.get(&partition_key).unwrap_or(&no_updates);
+        let actual_updates = actual.get(&partition_key).unwrap_or(&no_updates);
+        if expected_updates == actual_updates {
+            continue;
+        }
+        if let (
+            PartitionDatabaseUpdates::Delta {
+                substate_updates: expected_substate_updates,
+            },
+            PartitionDatabaseUpdates::Delta {
+                substate_updates: actual_substate_updates,
+            },
+        ) = (expected_updates, actual_updates)
+        {
+            if let Some(sort_key) = expected_substate_updates
+                .keys()
+                .chain(actual_substate_updates.keys())
+                .find(|sort_key| {
+                    expected_substate_updates.get(*sort_key)
+                        != actual_substate_updates.get(*sort_key)
+                })
+            {
+                return Some(ReplayDivergenceKind::SubstateWrite {
+                    partition_key,
+                    sort_key: sort_key.clone(),
+                    expected: expected_substate_updates.get(sort_key).cloned(),
+                    actual: actual_substate_updates.get(sort_key).cloned(),
+                });
+            }
+            continue;
+        }
+        return Some(ReplayDivergenceKind::PartitionWrite {
+            expected: expected.get(&partition_key).cloned(),
+            actual: actual.get(&partition_key).cloned(),
+            partition_key,
+        });
+    }
+    None
+}
+
+fn first_difference_index<T: PartialEq>(expected: &[T], actual: &[T]) -> Option<usize> {
+    (0..expected.len().max(actual.len())).find(|index| expected.get(*index) != actual.get(*index))
+}
@@ -206,6 +206,164 @@ impl BabylonSettings {
         self.consensus_manager_config = consensus_manager_config;
         self
     }
+
+    pub fn with_initial_time_ms(mut self, initial_time_ms: i64) -> Self {
+        self.initial_time_ms = initial_time_ms;
+        self
+    }
+}
+
+/// A builder of [`BabylonSettings`] with custom initial validators, stakes, resources and balances,
+/// e.g. for private networks and testnets.
+///
+/// The genesis data is ingested in a fixed order (validators, stakes, resources, resource balances
+/// and then XRD balances), each in the order it was added, so the same builder calls always
+/// produce the same genesis transactions and addresses.
+#[derive(Debug, Clone)]
+pub struct GenesisBuilder {
+    settings: BabylonSettings,
+    validators: Vec<GenesisValidator>,
+    stakes: IndexMap<Secp256k1PublicKey, Vec<(ComponentAddress, Decimal)>>,
+    resources: Vec<GenesisResource>,
+    resource_balances: IndexMap<ResourceAddress, Vec<(ComponentAddress, Decimal)>>,
+    xrd_balances: Vec<(ComponentAddress, Decimal)>,
+}
+
+impl Default for GenesisBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GenesisBuilder {
+    /// Starts from [`BabylonSettings::test_minimal`], which has no genesis data.
+    pub fn new() -> Self {
+        Self::from_settings(BabylonSettings::test_minimal())
+    }
+
+    /// Starts from the given settings, e.g. with a custom genesis epoch. Any genesis data chunks of
+    /// the settings are ingested before the built ones.
+    pub fn from_settings(settings: BabylonSettings) -> Self {
+        Self {
+            settings,
+            validators: vec![],
+            stakes: index_map_new(),
+            resources: vec![],
+            resource_balances: index_map_new(),
+            xrd_balances: vec![],
+        }
+    }
+
+    /// Returns the address reserved for a genesis resource created from the given seed.
+    pub fn resource_address_from_seed(seed: &[u8]) -> ResourceAddress {
+        ResourceAddress::new_or_panic(
+            NodeId::new(
+                EntityType::GlobalFungibleResourceManager as u8,
+                &hash(seed).lower_bytes(),
+            )
+            .0,
+        )
+    }
+
+    pub fn with_validator(mut self, validator: impl Into<GenesisValidator>) -> Self {
+        self.validators.push(validator.into());
+        self
+    }
+
+    /// Stakes XRD to a validator, crediting the stake units to the staker account.
+    pub fn with_stake(
+        mut self,
+        validator_key: Secp256k1PublicKey,
+        staker_account: ComponentAddress,
+        xrd_amount: Decimal,
+    ) -> Self {
+        self.stakes
+            .entry(validator_key)
+            .or_default()
+            .push((staker_account, xrd_amount));
+        self
+    }
+
+    pub fn with_resource(mut self, resource: GenesisResource) -> Self {
+        self.resources.push(resource);
+        self
+    }
+
+    pub fn with_resource_balance(
+        mut self,
+        resource_address: ResourceAddress,
+        account: ComponentAddress,
+        amount: Decimal,
+    ) -> Self {
+        self.resource_balances
+            .entry(resource_address)
+            .or_default()
+            .push((account, amount));
+        self
+    }
+
+    pub fn with_xrd_balance(mut self, account: ComponentAddress, amount: Decimal) -> Self {
+        self.xrd_balances.push((account, amount));
+        self
+    }
+
+    pub fn build(self) -> BabylonSettings {
+        let mut settings = self.settings;
+        let genesis_data_chunks = &mut settings.genesis_data_chunks;
+        if !self.validators.is_empty() {
+            genesis_data_chunks.push(GenesisDataChunk::Validators(self.validators));
+        }
+        if !self.stakes.is_empty() {
+            let mut accounts = index_set_new();
+            let allocations = self
+                .stakes
+                .into_iter()
+                .map(|(validator_key, stakes)| {
+                    let stake_allocations = stakes
+                        .into_iter()
+                        .map(|(account, xrd_amount)| GenesisStakeAllocation {
+                            account_index: accounts.insert_full(account).0 as u32,
+                            xrd_amount,
+                        })
+                        .collect();
+                    (validator_key, stake_allocations)
+                })
+                .collect();
+            genesis_data_chunks.push(GenesisDataChunk::Stakes {
+                accounts: accounts.into_iter().collect(),
+                allocations,
+            });
+        }
+        if !self.resources.is_empty() {
+            genesis_data_chunks.push(GenesisDataChunk::Resources(self.resources));
+        }
+        if !self.resource_balances.is_empty() {
+            let mut accounts = index_set_new();
+            let allocations = self
+                .resource_balances
+                .into_iter()
+                .map(|(resource_address, balances)| {
+                    let resource_allocations = balances
+                        .into_iter()
+                        .map(|(account, amount)| GenesisResourceAllocation {
+                            account_index: accounts.insert_full(account).0 as u32,
+                            amount,
+                        })
+                        .collect();
+                    (resource_address, resource_allocations)
+                })
+                .collect();
+            genesis_data_chunks.push(GenesisDataChunk::ResourceBalances {
+                accounts: accounts.into_iter().collect(),
+                allocations,
+            });
+        }
+        if !self.xrd_balances.is_empty() {
+            genesis_data_chunks.push(GenesisDataChunk::XrdBalances(self.xrd_balances));
+        }
+
+        settings
+    }
 }
 
 impl UpdateSettings for BabylonSettings {
@@ -5,6 +5,10 @@ use crate::{internal_prelude::*, system::system_callback::SystemBoot};
 pub struct DugongSettings {
     pub native_entity_metadata_updates: UpdateSetting<NoSettings>,
     pub system_logic_updates: UpdateSetting<NoSettings>,
+    /// Enables the `kv_store_scan_keys` host function for Scrypto blueprints
+    pub vm_boot_to_enable_kv_store_scan_keys: UpdateSetting<NoSettings>,
+    /// Enables the reentrancy guard of blueprints declaring the `non_reentrant` feature
+    pub system_logic_to_enable_reentrancy_guard: UpdateSetting<NoSettings>,
 }
 
 impl UpdateSettings for DugongSettings {
@@ -18,6 +22,12 @@ impl UpdateSettings for DugongSettings {
         Self {
             native_entity_metadata_updates: UpdateSetting::enabled_as_default_for_network(network),
             system_logic_updates: UpdateSetting::enabled_as_default_for_network(network),
+            vm_boot_to_enable_kv_store_scan_keys: UpdateSetting::enabled_as_default_for_network(
+                network,
+            ),
+            system_logic_to_enable_reentrancy_guard: UpdateSetting::enabled_as_default_for_network(
+                network,
+            ),
         }
     }
 
@@ -25,6 +35,8 @@ impl UpdateSettings for DugongSettings {
         Self {
             native_entity_metadata_updates: UpdateSetting::Disabled,
             system_logic_updates: UpdateSetting::Disabled,
+            vm_boot_to_enable_kv_store_scan_keys: UpdateSetting::Disabled,
+            system_logic_to_enable_reentrancy_guard: UpdateSetting::Disabled,
         }
     }
 
@@ -55,6 +67,8 @@ fn generate_main_batch(
     DugongSettings {
         native_entity_metadata_updates,
         system_logic_updates,
+        vm_boot_to_enable_kv_store_scan_keys,
+        system_logic_to_enable_reentrancy_guard,
     }: &DugongSettings,
 ) -> ProtocolUpdateBatch {
     let mut batch = ProtocolUpdateBatch::empty();
@@ -73,6 +87,20 @@ fn generate_main_batch(
         );
     }
 
+    if let UpdateSetting::Enabled(NoSettings) = &vm_boot_to_enable_kv_store_scan_keys {
+        batch.mut_add_flash(
+            "dugong-vm-boot-to-enable-kv-store-scan-keys",
+            generate_vm_boot_to_enable_kv_store_scan_keys(),
+        );
+    }
+
+    if let UpdateSetting::Enabled(NoSettings) = &system_logic_to_enable_reentrancy_guard {
+        batch.mut_add_flash(
+            "dugong-system-logic-to-enable-reentrancy-guard",
+            generate_system_logic_to_enable_reentrancy_guard(store),
+        );
+    }
+
     batch
 }
 
@@ -123,3 +151,31 @@ fn generate_system_logic_v4_updates(store: &dyn SubstateDatabase) -> StateUpdate
         SystemBoot::dugong_for_previous_parameters(existing_system_boot.into_parameters()),
     )
 }
+
+fn generate_vm_boot_to_enable_kv_store_scan_keys() -> StateUpdates {
+    StateUpdates::empty().set_substate(
+        TRANSACTION_TRACKER,
+        BOOT_LOADER_PARTITION,
+        BootLoaderField::VmBoot,
+        VmBoot::V1 {
+            scrypto_version: ScryptoVmVersion::kv_store_scan_keys().into(),
+        },
+    )
+}
+
+fn generate_system_logic_to_enable_reentrancy_guard(store: &dyn SubstateDatabase) -> StateUpdates {
+    let existing_system_boot: SystemBoot = store.get_existing_substate(
+        TRANSACTION_TRACKER,
+        BOOT_LOADER_PARTITION,
+        BootLoaderField::SystemBoot,
+    );
+
+    StateUpdates::empty().set_substate(
+        TRANSACTION_TRACKER,
+        BOOT_LOADER_PARTITION,
+        BootLoaderField::SystemBoot,
+        SystemBoot::dugong_reentrancy_guard_for_previous_parameters(
+            existing_system_boot.into_parameters(),
+        ),
+    )
+}
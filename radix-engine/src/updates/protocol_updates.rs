@@ -13,6 +13,14 @@ define_single_versioned! {
 }
 
 impl ProtocolUpdateStatusSummarySubstate {
+    /// Loads the protocol version of the given database, along with the status of any
+    /// in-progress protocol update.
+    ///
+    /// Databases created before the status substate existed are versioned by inspecting their
+    /// boot loader substates. Together with [`ProtocolBuilder::from_current_to_latest`], this
+    /// allows an existing ledger to be migrated across protocol versions in place.
+    ///
+    /// [`ProtocolBuilder::from_current_to_latest`]: super::ProtocolBuilder::from_current_to_latest
     pub fn load(database: &impl SubstateDatabase) -> Self {
         let substate = database.get_substate(
             TRANSACTION_TRACKER,
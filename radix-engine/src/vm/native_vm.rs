@@ -131,6 +131,7 @@ impl<I: VmInvoke> VmInvoke for NativeVmInstance<I> {
                         export_name,
                         input,
                         PackageV1MinorVersion::Zero,
+                        api.kernel_get_system().versioned_system_logic,
                         api,
                         vm_api,
                     ),
@@ -138,6 +139,7 @@ impl<I: VmInvoke> VmInvoke for NativeVmInstance<I> {
                         export_name,
                         input,
                         PackageV1MinorVersion::One,
+                        api.kernel_get_system().versioned_system_logic,
                         api,
                         vm_api,
                     ),
@@ -6,11 +6,12 @@ pub enum ScryptoVmVersion {
     V1_0,
     V1_1,
     V1_2,
+    V1_3,
 }
 
 impl ScryptoVmVersion {
     pub const fn latest() -> ScryptoVmVersion {
-        Self::cuttlefish()
+        Self::dugong()
     }
 
     pub const fn babylon_genesis() -> ScryptoVmVersion {
@@ -25,6 +26,10 @@ impl ScryptoVmVersion {
         Self::V1_2
     }
 
+    pub const fn dugong() -> ScryptoVmVersion {
+        Self::V1_3
+    }
+
     pub const fn crypto_utils_v1() -> ScryptoVmVersion {
         Self::V1_1
     }
@@ -32,6 +37,10 @@ impl ScryptoVmVersion {
     pub const fn crypto_utils_v2() -> ScryptoVmVersion {
         Self::V1_2
     }
+
+    pub const fn kv_store_scan_keys() -> ScryptoVmVersion {
+        Self::V1_3
+    }
 }
 
 impl From<ScryptoVmVersion> for u64 {
@@ -48,6 +57,7 @@ impl TryFrom<u64> for ScryptoVmVersion {
             0 => Ok(Self::V1_0),
             1 => Ok(Self::V1_1),
             2 => Ok(Self::V1_2),
+            3 => Ok(Self::V1_3),
             v => Err(Self::Error::FromIntError(v)),
         }
     }
@@ -65,7 +75,7 @@ mod test {
     #[test]
     fn test_scrypto_vm_version() {
         let v = ScryptoVmVersion::latest();
-        assert_eq!(v, ScryptoVmVersion::V1_2);
+        assert_eq!(v, ScryptoVmVersion::V1_3);
         assert_eq!(ScryptoVmVersion::crypto_utils_v1(), ScryptoVmVersion::V1_1);
     }
 
@@ -77,9 +87,9 @@ mod test {
         let v: ScryptoVmVersion = 1u64.try_into().unwrap();
         assert_eq!(v, ScryptoVmVersion::V1_1);
 
-        let e = ScryptoVmVersion::try_from(3u64).unwrap_err();
+        let e = ScryptoVmVersion::try_from(4u64).unwrap_err();
 
-        assert_eq!(e, ScryptoVmVersionError::FromIntError(3u64));
+        assert_eq!(e, ScryptoVmVersionError::FromIntError(4u64));
     }
 
     #[test]
@@ -87,5 +97,6 @@ mod test {
         assert!(ScryptoVmVersion::crypto_utils_v1() == ScryptoVmVersion::V1_1);
         assert!(ScryptoVmVersion::crypto_utils_v1() > ScryptoVmVersion::V1_0);
         assert!(ScryptoVmVersion::crypto_utils_v1() < ScryptoVmVersion::crypto_utils_v2());
+        assert!(ScryptoVmVersion::crypto_utils_v2() < ScryptoVmVersion::kv_store_scan_keys());
     }
 }
@@ -269,6 +269,7 @@ impl<'g, W: WasmEngine + 'g, E: NativeVmExtension> SystemCallbackObject for Vm<'
                         &instrumented_code.instrumented_code,
                     )
                 };
+                api.kernel_get_system().modules.on_wasm_instantiation();
 
                 api.consume_cost_units(ClientCostingEntry::PrepareWasmCode {
                     size: instrumented_code.instrumented_code.len(),
@@ -36,6 +36,7 @@ pub const ACTOR_EMIT_EVENT_FUNCTION_NAME: &str = "actor_emit_event";
 pub const KEY_VALUE_STORE_NEW_FUNCTION_NAME: &str = "kv_store_new";
 pub const KEY_VALUE_STORE_OPEN_ENTRY_FUNCTION_NAME: &str = "kv_store_open_entry";
 pub const KEY_VALUE_STORE_REMOVE_ENTRY_FUNCTION_NAME: &str = "kv_store_remove_entry";
+pub const KEY_VALUE_STORE_SCAN_KEYS_FUNCTION_NAME: &str = "kv_store_scan_keys";
 
 //=================
 // KV Entry
@@ -174,6 +174,8 @@ pub enum WasmRuntimeError {
 
     InvalidHash(ParseHashError),
     Secp256k1KeyRecoveryError,
+
+    InvalidKeyValueStoreCursor(DecodeError),
 }
 
 impl SelfError for WasmRuntimeError {
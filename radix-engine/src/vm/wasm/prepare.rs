@@ -289,6 +289,38 @@ impl WasmModule {
                             ));
                         }
                     }
+                    KEY_VALUE_STORE_SCAN_KEYS_FUNCTION_NAME => {
+                        if version < ScryptoVmVersion::kv_store_scan_keys() {
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::ProtocolVersionMismatch {
+                                    name: entry.name.to_string(),
+                                    current_version: version.into(),
+                                    expected_version: ScryptoVmVersion::kv_store_scan_keys().into(),
+                                },
+                            ));
+                        }
+
+                        if let TypeRef::Func(type_index) = entry.ty {
+                            if Self::function_type_matches(
+                                &self.module,
+                                type_index,
+                                vec![
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                ],
+                                vec![ValType::I64],
+                            ) {
+                                continue;
+                            }
+
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::InvalidFunctionType(entry.name.to_string()),
+                            ));
+                        }
+                    }
                     ACTOR_OPEN_FIELD_FUNCTION_NAME => {
                         if let TypeRef::Func(type_index) = entry.ty {
                             if Self::function_type_matches(
@@ -1585,6 +1617,11 @@ mod tests {
                     CRYPTO_UTILS_SECP256K1_ECDSA_VERIFY_AND_KEY_RECOVER_UNCOMPRESSED_FUNCTION_NAME,
                 ],
             ),
+            (
+                ScryptoVmVersion::V1_2,
+                ScryptoVmVersion::kv_store_scan_keys(),
+                vec![KEY_VALUE_STORE_SCAN_KEYS_FUNCTION_NAME],
+            ),
         ] {
             for name in names {
                 assert_invalid_wasm!(
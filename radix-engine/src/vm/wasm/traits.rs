@@ -105,6 +105,13 @@ pub trait WasmRuntime {
         key: Vec<u8>,
     ) -> Result<Buffer, InvokeError<WasmRuntimeError>>;
 
+    fn key_value_store_scan_keys(
+        &mut self,
+        node_id: Vec<u8>,
+        cursor: Vec<u8>,
+        limit: u32,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>>;
+
     fn instance_of(
         &mut self,
         object_id: Vec<u8>,
@@ -281,6 +288,14 @@ pub trait WasmInstance {
 }
 
 /// A Scrypto WASM engine validates, instruments and runs Scrypto modules.
+///
+/// Alternative backends can be plugged into the [`ScryptoVm`] by implementing this trait.
+/// Any backend must execute the instrumented code deterministically, including the metering
+/// injected at instrumentation time, as the results are part of consensus. Implementations
+/// are expected to cache compiled modules by `code_hash`, as the [`WasmiEngine`] does.
+///
+/// [`ScryptoVm`]: crate::vm::ScryptoVm
+/// [`WasmiEngine`]: crate::vm::wasm::WasmiEngine
 pub trait WasmEngine {
     type WasmInstance: WasmInstance;
 
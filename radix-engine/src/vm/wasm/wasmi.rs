@@ -9,6 +9,7 @@ use crate::vm::wasm::WasmEngine;
 use radix_engine_interface::api::actor_api::EventFlags;
 use radix_engine_interface::blueprints::package::CodeHash;
 use sbor::rust::mem::MaybeUninit;
+use sbor::rust::num::NonZeroUsize;
 #[cfg(not(feature = "fuzzing"))]
 use sbor::rust::sync::Arc;
 use wasmi::core::HostError;
@@ -492,6 +493,24 @@ fn key_value_store_remove(
         .map(|buffer| buffer.0)
 }
 
+fn key_value_store_scan_keys(
+    mut caller: Caller<'_, HostState>,
+    node_id_ptr: u32,
+    node_id_len: u32,
+    cursor_ptr: u32,
+    cursor_len: u32,
+    limit: u32,
+) -> Result<u64, InvokeError<WasmRuntimeError>> {
+    let runtime = grab_runtime!(caller);
+    let memory = grab_memory!(caller);
+    let node_id = read_memory(caller.as_context_mut(), memory, node_id_ptr, node_id_len)?;
+    let cursor = read_memory(caller.as_context_mut(), memory, cursor_ptr, cursor_len)?;
+
+    runtime
+        .key_value_store_scan_keys(node_id, cursor, limit)
+        .map(|buffer| buffer.0)
+}
+
 fn lock_field(
     caller: Caller<'_, HostState>,
     object_handle: u32,
@@ -1332,6 +1351,27 @@ impl WasmiModule {
             },
         );
 
+        let host_key_value_store_scan_keys = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             node_id_ptr: u32,
+             node_id_len: u32,
+             cursor_ptr: u32,
+             cursor_len: u32,
+             limit: u32|
+             -> Result<u64, Error> {
+                key_value_store_scan_keys(
+                    caller,
+                    node_id_ptr,
+                    node_id_len,
+                    cursor_ptr,
+                    cursor_len,
+                    limit,
+                )
+                .map_err(Error::host)
+            },
+        );
+
         let host_lock_field = Func::wrap(
             store.as_context_mut(),
             |caller: Caller<'_, HostState>,
@@ -1746,6 +1786,11 @@ impl WasmiModule {
             KEY_VALUE_STORE_REMOVE_ENTRY_FUNCTION_NAME,
             host_key_value_store_remove
         );
+        linker_define!(
+            linker,
+            KEY_VALUE_STORE_SCAN_KEYS_FUNCTION_NAME,
+            host_key_value_store_scan_keys
+        );
 
         linker_define!(linker, FIELD_ENTRY_READ_FUNCTION_NAME, host_field_lock_read);
         linker_define!(
@@ -2051,7 +2096,24 @@ impl WasmInstance for WasmiInstance {
 
 #[derive(Debug, Clone)]
 pub struct WasmiEngineOptions {
-    max_cache_size: usize,
+    max_cache_size: NonZeroUsize,
+}
+
+impl Default for WasmiEngineOptions {
+    fn default() -> Self {
+        Self {
+            max_cache_size: NonZeroUsize::new(WASM_ENGINE_CACHE_SIZE)
+                .expect("WASM engine cache size should be non-zero"),
+        }
+    }
+}
+
+impl WasmiEngineOptions {
+    /// Sets the maximum number of compiled modules to keep cached, keyed by code hash.
+    pub fn with_max_cache_size(mut self, max_cache_size: NonZeroUsize) -> Self {
+        self.max_cache_size = max_cache_size;
+        self
+    }
 }
 
 pub struct WasmiEngine {
@@ -2067,28 +2129,24 @@ pub struct WasmiEngine {
 
 impl Default for WasmiEngine {
     fn default() -> Self {
-        Self::new(WasmiEngineOptions {
-            max_cache_size: WASM_ENGINE_CACHE_SIZE,
-        })
+        Self::new(WasmiEngineOptions::default())
     }
 }
 
 impl WasmiEngine {
     pub fn new(options: WasmiEngineOptions) -> Self {
         #[cfg(all(not(feature = "fuzzing"), not(feature = "moka")))]
-        let modules_cache = RefCell::new(lru::LruCache::new(
-            sbor::rust::num::NonZeroUsize::new(options.max_cache_size).unwrap(),
-        ));
+        let modules_cache = RefCell::new(lru::LruCache::new(options.max_cache_size));
         #[cfg(all(not(feature = "fuzzing"), feature = "moka"))]
         let modules_cache = moka::sync::Cache::builder()
             .weigher(|_key: &CodeHash, _value: &Arc<WasmiModule>| -> u32 {
                 // No sophisticated weighing mechanism, just keep a fixed size cache
                 1u32
             })
-            .max_capacity(options.max_cache_size as u64)
+            .max_capacity(options.max_cache_size.get() as u64)
             .build();
         #[cfg(feature = "fuzzing")]
-        let modules_cache = options.max_cache_size;
+        let modules_cache = options.max_cache_size.get();
 
         Self { modules_cache }
     }
@@ -2259,4 +2317,18 @@ mod tests {
         };
         assert_eq!(val, 11100);
     }
+
+    #[test]
+    #[cfg(not(feature = "fuzzing"))]
+    fn test_wasmi_engine_modules_cache_has_configured_max_size() {
+        let max_cache_size = NonZeroUsize::new(7).unwrap();
+        let engine =
+            WasmiEngine::new(WasmiEngineOptions::default().with_max_cache_size(max_cache_size));
+
+        #[cfg(not(feature = "moka"))]
+        let capacity = usize::from(engine.modules_cache.borrow().cap()) as u64;
+        #[cfg(feature = "moka")]
+        let capacity = engine.modules_cache.policy().max_capacity().unwrap();
+        assert_eq!(capacity, max_cache_size.get() as u64);
+    }
 }
@@ -99,7 +99,7 @@ impl<'y, Y: SystemApi<RuntimeError>> WasmRuntime for ScryptoRuntime<'y, Y> {
             // Practically speaking, there is little gain of keeping multiple buffers open before
             // [multi-value](https://github.com/WebAssembly/multi-value/blob/master/proposals/multi-value/Overview.md) is supported and used.
             // We reduce it to `4` so that the amount of memory that a transaction can consume is reduced, which is beneficial for parallel execution.
-            ScryptoVmVersion::V1_2 => 4,
+            ScryptoVmVersion::V1_2 | ScryptoVmVersion::V1_3 => 4,
         };
         if self.buffers.len() >= max_number_of_buffers {
             return Err(InvokeError::SelfError(WasmRuntimeError::TooManyBuffers));
@@ -348,6 +348,24 @@ impl<'y, Y: SystemApi<RuntimeError>> WasmRuntime for ScryptoRuntime<'y, Y> {
         self.allocate_buffer(rtn)
     }
 
+    fn key_value_store_scan_keys(
+        &mut self,
+        node_id: Vec<u8>,
+        cursor: Vec<u8>,
+        limit: u32,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        let node_id = NodeId(
+            TryInto::<[u8; NodeId::LENGTH]>::try_into(node_id.as_ref())
+                .map_err(|_| WasmRuntimeError::InvalidNodeId)?,
+        );
+        let cursor = scrypto_decode::<Option<Vec<u8>>>(&cursor)
+            .map_err(WasmRuntimeError::InvalidKeyValueStoreCursor)?;
+        let keys = self
+            .api
+            .key_value_store_scan_keys(&node_id, cursor, limit)?;
+        self.allocate_buffer(scrypto_encode(&keys).expect("Failed to encode keys"))
+    }
+
     fn actor_open_field(
         &mut self,
         object_handle: u32,
@@ -11,3 +11,4 @@ pub mod consensus_manager;
 pub mod modules;
 pub mod resource;
 pub mod runtime;
+pub mod transaction_processor;
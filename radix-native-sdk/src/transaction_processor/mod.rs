@@ -0,0 +1,50 @@
+use radix_common::constants::TRANSACTION_PROCESSOR_PACKAGE;
+use radix_common::crypto::Hash;
+use radix_common::data::scrypto::model::Reference;
+use radix_common::data::scrypto::{scrypto_decode, scrypto_encode};
+use radix_engine_interface::api::*;
+use radix_engine_interface::blueprints::transaction_processor::*;
+use sbor::rust::prelude::*;
+use sbor::DecodeError;
+
+#[derive(Debug)]
+pub enum TransactionProcessorRunError<E> {
+    /// The transaction processor call failed.
+    ApiError(E),
+    /// The transaction processor returned outputs which couldn't be decoded.
+    OutputDecodeError(DecodeError),
+}
+
+#[derive(Debug)]
+pub struct TransactionProcessor;
+
+impl TransactionProcessor {
+    /// Runs the given manifest encoded instructions in a nested transaction processor, with its
+    /// own worktop and auth zone.
+    ///
+    /// The transaction processor can only be called from the root call frame, unless nested
+    /// transaction processors are allowed by the system.
+    pub fn run<Y: SystemApi<E>, E: SystemApiError>(
+        manifest_encoded_instructions: Vec<u8>,
+        references: Vec<Reference>,
+        blobs: IndexMap<Hash, Vec<u8>>,
+        api: &mut Y,
+    ) -> Result<Vec<InstructionOutput>, TransactionProcessorRunError<E>> {
+        let rtn = api
+            .call_function(
+                TRANSACTION_PROCESSOR_PACKAGE,
+                TRANSACTION_PROCESSOR_BLUEPRINT,
+                TRANSACTION_PROCESSOR_RUN_IDENT,
+                scrypto_encode(&TransactionProcessorRunInput {
+                    manifest_encoded_instructions,
+                    global_address_reservations: Vec::new(),
+                    references,
+                    blobs,
+                })
+                .unwrap(),
+            )
+            .map_err(TransactionProcessorRunError::ApiError)?;
+
+        scrypto_decode(&rtn).map_err(TransactionProcessorRunError::OutputDecodeError)
+    }
+}
@@ -6,6 +6,7 @@ compile_error!("Either feature `std` or `alloc` must be enabled for this crate."
 compile_error!("Feature `std` and `alloc` can't be enabled at the same time.");
 
 pub mod memory_db;
+pub mod read_through_store;
 #[cfg(feature = "rocksdb")]
 pub mod rocks_db;
 #[cfg(feature = "rocksdb")]
@@ -1,6 +1,11 @@
+use crate::substate_database_overlay::*;
 use radix_common::prelude::*;
 use radix_substate_store_interface::interface::*;
 
+/// A fork of an [`InMemorySubstateDatabase`], see [`InMemorySubstateDatabase::fork`].
+pub type ForkedInMemorySubstateDatabase =
+    SubstateDatabaseOverlay<Rc<InMemorySubstateDatabase>, InMemorySubstateDatabase>;
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct InMemorySubstateDatabase {
     partitions: BTreeMap<DbPartitionKey, BTreeMap<DbSortKey, DbSubstateValue>>,
@@ -12,6 +17,15 @@ impl InMemorySubstateDatabase {
             partitions: BTreeMap::new(),
         }
     }
+
+    /// Creates a copy-on-write fork of this database, which reads through to this database and
+    /// keeps its own commits in an overlay.
+    ///
+    /// The database is shared rather than copied, so any number of forks can be created cheaply
+    /// from the same state, e.g. to execute alternative transactions against it.
+    pub fn fork(self: &Rc<Self>) -> ForkedInMemorySubstateDatabase {
+        SubstateDatabaseOverlay::new(self.clone())
+    }
 }
 
 impl SubstateDatabase for InMemorySubstateDatabase {
@@ -91,3 +105,98 @@ impl ListableSubstateDatabase for InMemorySubstateDatabase {
         Box::new(partition_iter)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sbor::rust::num::NonZeroUsize;
+
+    #[test]
+    fn exported_substates_can_be_imported_into_an_empty_database() {
+        let mut db = InMemorySubstateDatabase::standard();
+        db.commit(&DatabaseUpdates {
+            node_updates: indexmap! {
+                vec![0] => NodeDatabaseUpdates {
+                    partition_updates: indexmap! {
+                        0 => PartitionDatabaseUpdates::Reset {
+                            new_substate_values: indexmap! {
+                                DbSortKey(vec![1]) => vec![2],
+                                DbSortKey(vec![3]) => vec![4],
+                            }
+                        },
+                        1 => PartitionDatabaseUpdates::Reset {
+                            new_substate_values: indexmap! {
+                                DbSortKey(vec![5]) => vec![6],
+                            }
+                        },
+                    }
+                },
+                vec![1] => NodeDatabaseUpdates {
+                    partition_updates: indexmap! {
+                        0 => PartitionDatabaseUpdates::Reset {
+                            new_substate_values: indexmap! {
+                                DbSortKey(vec![7]) => vec![8],
+                            }
+                        },
+                    }
+                },
+            },
+        });
+
+        // One substate per update, so that the first partition is exported in two updates.
+        let archive = db
+            .export_all_substates(NonZeroUsize::new(1).unwrap())
+            .map(|database_updates| scrypto_encode(&database_updates).unwrap())
+            .collect::<Vec<_>>();
+        let mut imported_db = InMemorySubstateDatabase::standard();
+        for database_updates in &archive {
+            imported_db.commit(&scrypto_decode::<DatabaseUpdates>(database_updates).unwrap());
+        }
+
+        assert_eq!(archive.len(), 4);
+        assert_eq!(imported_db, db);
+    }
+
+    #[test]
+    fn forks_of_the_same_database_are_isolated() {
+        let set = |node_key: u8, value: u8| DatabaseUpdates {
+            node_updates: indexmap! {
+                vec![node_key] => NodeDatabaseUpdates {
+                    partition_updates: indexmap! {
+                        0 => PartitionDatabaseUpdates::Delta {
+                            substate_updates: indexmap! {
+                                DbSortKey(vec![0]) => DatabaseUpdate::Set(vec![value]),
+                            }
+                        },
+                    }
+                },
+            },
+        };
+        let partition_key = |node_key: u8| DbPartitionKey {
+            node_key: vec![node_key],
+            partition_num: 0,
+        };
+        let mut db = InMemorySubstateDatabase::standard();
+        db.commit(&set(0, 1));
+        let db = Rc::new(db);
+
+        let mut fork = db.fork();
+        fork.commit(&set(0, 2));
+        fork.commit(&set(1, 3));
+        let other_fork = db.fork();
+
+        let read = |db: &dyn SubstateDatabase, node_key: u8| {
+            db.get_raw_substate_by_db_key(&partition_key(node_key), &DbSortKey(vec![0]))
+        };
+        assert_eq!(read(&fork, 0), Some(vec![2]));
+        assert_eq!(read(&fork, 1), Some(vec![3]));
+        assert_eq!(
+            fork.list_partition_keys().collect::<Vec<_>>(),
+            vec![partition_key(0), partition_key(1)]
+        );
+        assert_eq!(read(&other_fork, 0), Some(vec![1]));
+        assert_eq!(read(&other_fork, 1), None);
+        assert_eq!(read(db.as_ref(), 0), Some(vec![1]));
+        assert_eq!(read(db.as_ref(), 1), None);
+    }
+}
@@ -0,0 +1,359 @@
+use radix_common::prelude::*;
+use radix_substate_store_interface::interface::*;
+use sbor::rust::cell::RefCell;
+use sbor::rust::collections::VecDeque;
+use sbor::rust::num::NonZeroUsize;
+
+/// The default number of substates fetched per page when listing a partition.
+pub const DEFAULT_PARTITION_PAGE_SIZE: usize = 100;
+
+/// A source of substates for a [`ReadThroughSubstateStore`], e.g. a client of a gateway API.
+///
+/// The engine reads substates synchronously, so an asynchronous source must block until its
+/// response is available. There is no way to report a failed fetch to the engine, and reporting
+/// a missing substate instead would silently change the outcome of the execution, so a fetcher
+/// should panic if it can't reach its source.
+pub trait SubstateFetcher {
+    /// Fetches a single substate, or `None` if it doesn't exist.
+    fn fetch_substate(
+        &self,
+        partition_key: &DbPartitionKey,
+        sort_key: &DbSortKey,
+    ) -> Option<DbSubstateValue>;
+
+    /// Fetches up to `limit` substates of a partition, in ascending order of their sort keys,
+    /// starting from `from_sort_key` (inclusive) or from the start of the partition.
+    ///
+    /// Fewer than `limit` substates must only be returned at the end of the partition.
+    fn fetch_partition_page(
+        &self,
+        partition_key: &DbPartitionKey,
+        from_sort_key: Option<&DbSortKey>,
+        limit: usize,
+    ) -> Vec<PartitionEntry>;
+}
+
+/// How much of a partition was listed from its start.
+#[derive(Debug, Default, Clone)]
+enum ListedRange {
+    #[default]
+    None,
+    /// All substates up to and including the sort key were fetched.
+    UpTo(DbSortKey),
+    All,
+}
+
+impl ListedRange {
+    fn contains(&self, sort_key: &DbSortKey) -> bool {
+        match self {
+            ListedRange::None => false,
+            ListedRange::UpTo(last_sort_key) => sort_key <= last_sort_key,
+            ListedRange::All => true,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct CachedPartition {
+    /// The fetched substates, where `None` records that a substate doesn't exist.
+    substates: BTreeMap<DbSortKey, Option<DbSubstateValue>>,
+    listed_range: ListedRange,
+}
+
+/// A [`SubstateDatabase`] which fetches substates from a [`SubstateFetcher`] when they are first
+/// read, and caches them so that each is fetched at most once.
+///
+/// Partitions are listed lazily, one page at a time, so listing the first few substates of a
+/// large partition only fetches its first page. The pages listed from the start of a partition
+/// are cached, and later reads and listings of that range are served from the cache.
+///
+/// This allows executing transactions against the state of a remote ledger, without a copy of
+/// it. The store is read-only; to commit the results of transactions, wrap it in a
+/// [`SubstateDatabaseOverlay`](crate::substate_database_overlay::SubstateDatabaseOverlay).
+pub struct ReadThroughSubstateStore<F: SubstateFetcher> {
+    fetcher: F,
+    partition_page_size: NonZeroUsize,
+    cache: RefCell<BTreeMap<DbPartitionKey, CachedPartition>>,
+}
+
+impl<F: SubstateFetcher> ReadThroughSubstateStore<F> {
+    pub fn new(fetcher: F) -> Self {
+        Self {
+            fetcher,
+            partition_page_size: NonZeroUsize::new(DEFAULT_PARTITION_PAGE_SIZE)
+                .expect("Default partition page size should be non-zero"),
+            cache: RefCell::new(BTreeMap::new()),
+        }
+    }
+
+    /// Sets the number of substates fetched per page when listing a partition.
+    pub fn with_partition_page_size(mut self, partition_page_size: NonZeroUsize) -> Self {
+        self.partition_page_size = partition_page_size;
+        self
+    }
+
+    /// Returns the next substates of a partition listing, starting from `from_sort_key`, and the
+    /// sort key to continue the listing from, or `None` at the end of the partition.
+    ///
+    /// The substates are served from the cache if their range was listed before, or else fetched
+    /// as a page.
+    fn list_page(
+        &self,
+        partition_key: &DbPartitionKey,
+        from_sort_key: Option<&DbSortKey>,
+    ) -> (Vec<PartitionEntry>, Option<DbSortKey>) {
+        let mut cache = self.cache.borrow_mut();
+        let partition = cache.entry(partition_key.clone()).or_default();
+
+        let is_cached = match from_sort_key {
+            Some(from_sort_key) => partition.listed_range.contains(from_sort_key),
+            None => !matches!(partition.listed_range, ListedRange::None),
+        };
+        if is_cached {
+            let range = match from_sort_key {
+                Some(from_sort_key) => partition.substates.range(from_sort_key.clone()..),
+                None => partition.substates.range(..),
+            };
+            let entries = range
+                .take_while(|(sort_key, _)| partition.listed_range.contains(sort_key))
+                .filter_map(|(sort_key, value)| Some((sort_key.clone(), value.clone()?)))
+                .collect();
+            let next_from_sort_key = match &partition.listed_range {
+                ListedRange::UpTo(last_sort_key) => Some(next_sort_key(last_sort_key)),
+                ListedRange::None | ListedRange::All => None,
+            };
+            return (entries, next_from_sort_key);
+        }
+
+        let limit = self.partition_page_size.get();
+        let entries = self
+            .fetcher
+            .fetch_partition_page(partition_key, from_sort_key, limit);
+        let next_from_sort_key = match entries.last() {
+            Some((last_sort_key, _)) if entries.len() == limit => {
+                Some(next_sort_key(last_sort_key))
+            }
+            _ => None,
+        };
+
+        // The page extends the listed range if it starts right after it.
+        let extends_listed_range = match (&partition.listed_range, from_sort_key) {
+            (ListedRange::None, None) => true,
+            (ListedRange::UpTo(last_sort_key), Some(from_sort_key)) => {
+                *from_sort_key == next_sort_key(last_sort_key)
+            }
+            _ => false,
+        };
+        if extends_listed_range {
+            partition.listed_range = match (&next_from_sort_key, entries.last()) {
+                (Some(_), Some((last_sort_key, _))) => ListedRange::UpTo(last_sort_key.clone()),
+                _ => ListedRange::All,
+            };
+            // Any cached substate in the range of the page but not in it doesn't exist.
+            for (sort_key, value) in partition.substates.iter_mut() {
+                if from_sort_key.map_or(true, |from_sort_key| sort_key >= from_sort_key)
+                    && partition.listed_range.contains(sort_key)
+                {
+                    *value = None;
+                }
+            }
+        }
+        for (sort_key, value) in &entries {
+            partition
+                .substates
+                .insert(sort_key.clone(), Some(value.clone()));
+        }
+
+        (entries, next_from_sort_key)
+    }
+}
+
+/// The smallest sort key after the given one, as sort keys are ordered lexicographically.
+fn next_sort_key(sort_key: &DbSortKey) -> DbSortKey {
+    let mut bytes = sort_key.0.clone();
+    bytes.push(0);
+    DbSortKey(bytes)
+}
+
+impl<F: SubstateFetcher> SubstateDatabase for ReadThroughSubstateStore<F> {
+    fn get_raw_substate_by_db_key(
+        &self,
+        partition_key: &DbPartitionKey,
+        sort_key: &DbSortKey,
+    ) -> Option<DbSubstateValue> {
+        let mut cache = self.cache.borrow_mut();
+        let partition = cache.entry(partition_key.clone()).or_default();
+        if let Some(value) = partition.substates.get(sort_key) {
+            return value.clone();
+        }
+        if partition.listed_range.contains(sort_key) {
+            return None;
+        }
+        let value = self.fetcher.fetch_substate(partition_key, sort_key);
+        partition.substates.insert(sort_key.clone(), value.clone());
+        value
+    }
+
+    fn list_raw_values_from_db_key(
+        &self,
+        partition_key: &DbPartitionKey,
+        from_sort_key: Option<&DbSortKey>,
+    ) -> Box<dyn Iterator<Item = PartitionEntry> + '_> {
+        let partition_key = partition_key.clone();
+        // The position of the next page, where `Some(None)` is the start of the partition.
+        let mut next_page_from_sort_key = Some(from_sort_key.cloned());
+        let mut page = VecDeque::new();
+        let iterator = core::iter::from_fn(move || loop {
+            if let Some(entry) = page.pop_front() {
+                return Some(entry);
+            }
+            let from_sort_key = next_page_from_sort_key.take()?;
+            let (entries, next_from_sort_key) =
+                self.list_page(&partition_key, from_sort_key.as_ref());
+            next_page_from_sort_key = next_from_sort_key.map(Some);
+            page.extend(entries);
+        });
+        Box::new(iterator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory_db::InMemorySubstateDatabase;
+
+    struct CountingFetcher {
+        database: InMemorySubstateDatabase,
+        fetches: RefCell<usize>,
+    }
+
+    impl SubstateFetcher for &CountingFetcher {
+        fn fetch_substate(
+            &self,
+            partition_key: &DbPartitionKey,
+            sort_key: &DbSortKey,
+        ) -> Option<DbSubstateValue> {
+            *self.fetches.borrow_mut() += 1;
+            self.database
+                .get_raw_substate_by_db_key(partition_key, sort_key)
+        }
+
+        fn fetch_partition_page(
+            &self,
+            partition_key: &DbPartitionKey,
+            from_sort_key: Option<&DbSortKey>,
+            limit: usize,
+        ) -> Vec<PartitionEntry> {
+            *self.fetches.borrow_mut() += 1;
+            self.database
+                .list_raw_values_from_db_key(partition_key, from_sort_key)
+                .take(limit)
+                .collect()
+        }
+    }
+
+    fn fetcher() -> CountingFetcher {
+        let mut database = InMemorySubstateDatabase::standard();
+        database.commit(&DatabaseUpdates {
+            node_updates: indexmap! {
+                vec![0] => NodeDatabaseUpdates {
+                    partition_updates: indexmap! {
+                        0 => PartitionDatabaseUpdates::Reset {
+                            new_substate_values: indexmap! {
+                                DbSortKey(vec![1]) => vec![2],
+                                DbSortKey(vec![3]) => vec![4],
+                            }
+                        },
+                    }
+                },
+            },
+        });
+        CountingFetcher {
+            database,
+            fetches: RefCell::new(0),
+        }
+    }
+
+    fn partition_key() -> DbPartitionKey {
+        DbPartitionKey {
+            node_key: vec![0],
+            partition_num: 0,
+        }
+    }
+
+    #[test]
+    fn substates_are_fetched_once() {
+        let fetcher = fetcher();
+        let store = ReadThroughSubstateStore::new(&fetcher);
+
+        for _ in 0..2 {
+            assert_eq!(
+                store.get_raw_substate_by_db_key(&partition_key(), &DbSortKey(vec![1])),
+                Some(vec![2])
+            );
+            assert_eq!(
+                store.get_raw_substate_by_db_key(&partition_key(), &DbSortKey(vec![2])),
+                None
+            );
+        }
+
+        assert_eq!(*fetcher.fetches.borrow(), 2);
+    }
+
+    #[test]
+    fn listed_partitions_are_fetched_once() {
+        let fetcher = fetcher();
+        let store = ReadThroughSubstateStore::new(&fetcher);
+        store.get_raw_substate_by_db_key(&partition_key(), &DbSortKey(vec![1]));
+
+        assert_eq!(
+            store
+                .list_raw_values_from_db_key(&partition_key(), None)
+                .collect::<Vec<_>>(),
+            vec![(DbSortKey(vec![1]), vec![2]), (DbSortKey(vec![3]), vec![4])]
+        );
+        assert_eq!(
+            store
+                .list_raw_values_from_db_key(&partition_key(), Some(&DbSortKey(vec![2])))
+                .collect::<Vec<_>>(),
+            vec![(DbSortKey(vec![3]), vec![4])]
+        );
+        assert_eq!(
+            store.get_raw_substate_by_db_key(&partition_key(), &DbSortKey(vec![5])),
+            None
+        );
+
+        assert_eq!(*fetcher.fetches.borrow(), 2);
+    }
+
+    #[test]
+    fn partitions_are_listed_one_page_at_a_time() {
+        let fetcher = fetcher();
+        let store = ReadThroughSubstateStore::new(&fetcher)
+            .with_partition_page_size(NonZeroUsize::new(1).unwrap());
+
+        assert_eq!(
+            store
+                .list_raw_values_from_db_key(&partition_key(), None)
+                .next(),
+            Some((DbSortKey(vec![1]), vec![2]))
+        );
+        assert_eq!(*fetcher.fetches.borrow(), 1);
+
+        // The first page is cached, and the rest of the partition takes two more pages, the last
+        // of which is empty.
+        for _ in 0..2 {
+            assert_eq!(
+                store
+                    .list_raw_values_from_db_key(&partition_key(), None)
+                    .collect::<Vec<_>>(),
+                vec![(DbSortKey(vec![1]), vec![2]), (DbSortKey(vec![3]), vec![4])]
+            );
+        }
+        assert_eq!(
+            store.get_raw_substate_by_db_key(&partition_key(), &DbSortKey(vec![2])),
+            None
+        );
+        assert_eq!(*fetcher.fetches.borrow(), 3);
+    }
+}
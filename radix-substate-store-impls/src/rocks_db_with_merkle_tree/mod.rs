@@ -238,41 +238,71 @@ impl CommittableSubstateDatabase for RocksDBWithMerkleTreeSubstateStore {
 
         if self.pruning_enabled {
             for part in state_tree_diff.stale_tree_parts.take() {
-                match part {
-                    StaleTreePart::Node(node_key) => {
+                self.delete_stale_tree_part(part);
+            }
+        }
+    }
+}
+
+impl PrunableSubstateDatabase for RocksDBWithMerkleTreeSubstateStore {
+    /// Deletes the Merkle tree nodes which became stale at or before the given state version.
+    ///
+    /// This has no effect if pruning is enabled, as stale nodes are then deleted on commit.
+    fn prune_before(&mut self, state_version: u64) {
+        let stale_tree_parts = self
+            .db
+            .iterator_cf(self.cf(STALE_MERKLE_TREE_PARTS_CF), IteratorMode::Start)
+            .map(|kv| {
+                let (key_bytes, value_bytes) = kv.unwrap();
+                let stale_at_version = u64::from_be_bytes(key_bytes.as_ref().try_into().unwrap());
+                (key_bytes, stale_at_version, value_bytes)
+            })
+            .take_while(|(_, stale_at_version, _)| *stale_at_version <= state_version)
+            .collect::<Vec<_>>();
+
+        for (key_bytes, _, value_bytes) in stale_tree_parts {
+            for part in scrypto_decode::<Vec<StaleTreePart>>(&value_bytes).unwrap() {
+                self.delete_stale_tree_part(part);
+            }
+            self.db
+                .delete_cf(self.cf(STALE_MERKLE_TREE_PARTS_CF), key_bytes)
+                .unwrap();
+        }
+    }
+}
+
+impl RocksDBWithMerkleTreeSubstateStore {
+    fn delete_stale_tree_part(&self, part: StaleTreePart) {
+        match part {
+            StaleTreePart::Node(node_key) => {
+                self.db
+                    .delete_cf(self.cf(MERKLE_NODES_CF), encode_key(&node_key))
+                    .unwrap();
+            }
+            StaleTreePart::Subtree(node_key) => {
+                let mut queue = VecDeque::new();
+                queue.push_back(node_key);
+
+                while let Some(node_key) = queue.pop_front() {
+                    if let Some(bytes) = self
+                        .db
+                        .get_cf(self.cf(MERKLE_NODES_CF), encode_key(&node_key))
+                        .unwrap()
+                    {
                         self.db
                             .delete_cf(self.cf(MERKLE_NODES_CF), encode_key(&node_key))
                             .unwrap();
-                    }
-                    StaleTreePart::Subtree(node_key) => {
-                        let mut queue = VecDeque::new();
-                        queue.push_back(node_key);
-
-                        while let Some(node_key) = queue.pop_front() {
-                            if let Some(bytes) = self
-                                .db
-                                .get_cf(self.cf(MERKLE_NODES_CF), encode_key(&node_key))
-                                .unwrap()
-                            {
-                                self.db
-                                    .delete_cf(self.cf(MERKLE_NODES_CF), encode_key(&node_key))
-                                    .unwrap();
-                                let value: VersionedTreeNode = scrypto_decode(&bytes).unwrap();
-                                match value.fully_update_and_into_latest_version() {
-                                    TreeNodeV1::Internal(x) => {
-                                        for child in x.children {
-                                            queue.push_back(
-                                                node_key.gen_child_node_key(
-                                                    child.version,
-                                                    child.nibble,
-                                                ),
-                                            )
-                                        }
-                                    }
-                                    TreeNodeV1::Leaf(_) => {}
-                                    TreeNodeV1::Null => {}
+                        let value: VersionedTreeNode = scrypto_decode(&bytes).unwrap();
+                        match value.fully_update_and_into_latest_version() {
+                            TreeNodeV1::Internal(x) => {
+                                for child in x.children {
+                                    queue.push_back(
+                                        node_key.gen_child_node_key(child.version, child.nibble),
+                                    )
                                 }
                             }
+                            TreeNodeV1::Leaf(_) => {}
+                            TreeNodeV1::Null => {}
                         }
                     }
                 }
@@ -368,4 +398,50 @@ mod tests {
         });
         assert_eq!(db.list_partition_keys().count(), 8);
     }
+
+    #[cfg(not(feature = "alloc"))]
+    #[test]
+    fn test_prune_before_deletes_only_stale_tree_nodes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut options = Options::default();
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+        let mut db =
+            RocksDBWithMerkleTreeSubstateStore::with_options(&options, temp_dir.into_path(), false);
+
+        for value in 0..3u8 {
+            db.commit(&DatabaseUpdates {
+                node_updates: indexmap! {
+                    vec![0] => NodeDatabaseUpdates {
+                        partition_updates: indexmap! {
+                            0 => PartitionDatabaseUpdates::Reset {
+                                new_substate_values: indexmap! {
+                                    DbSortKey(vec![1]) => vec![value]
+                                }
+                            }
+                        }
+                    }
+                },
+            });
+        }
+        let root_key = |version| StoredTreeNodeKey::new(version, NibblePath::new_even(vec![]));
+        assert!(db.get_node(&root_key(1)).is_some());
+        assert!(db.get_node(&root_key(2)).is_some());
+
+        db.prune_before(2);
+
+        assert!(db.get_node(&root_key(1)).is_none());
+        assert!(db.get_node(&root_key(2)).is_some());
+        assert!(db.get_node(&root_key(3)).is_some());
+        assert_eq!(
+            db.get_raw_substate_by_db_key(
+                &DbPartitionKey {
+                    node_key: vec![0],
+                    partition_num: 0
+                },
+                &DbSortKey(vec![1])
+            ),
+            Some(vec![2])
+        );
+    }
 }
@@ -7,15 +7,20 @@ pub mod substate_tier;
 pub mod tier_framework;
 
 use entity_tier::EntityTier;
-use radix_common::crypto::Hash;
+use partition_tier::PartitionTier;
+use radix_common::crypto::{hash, Hash};
+use radix_common::prelude::{DbSubstateValue, ScryptoSbor};
 use radix_rust::prelude::*;
 use radix_substate_store_interface::interface::*;
+use substate_tier::SubstateTier;
+use tier_framework::*;
 use tree_store::*;
 use types::*;
 
-// The sources copied from Aptos (the `jellyfish` and `types` modules) contain support for
-// generating proofs, which we plan to use in near future. Hence, we do not delete that code, but
-// suppress warnings.
+pub use types::{LeafKey, SparseMerkleLeafNode, SparseMerkleProof};
+
+// The sources copied from Aptos (the `jellyfish` and `types` modules) contain more than we use
+// (e.g. range and non-inclusion proofs). We do not delete that code, but suppress warnings.
 
 #[allow(dead_code)]
 mod jellyfish;
@@ -48,6 +53,115 @@ pub fn put_at_next_version<S: TreeStore>(
         .unwrap_or(SPARSE_MERKLE_PLACEHOLDER_HASH)
 }
 
+/// Returns a proof of the given substate's inclusion in the "3-Tier JMT" at the given version, or
+/// `None` if the substate does not exist at that version.
+///
+/// # Panics
+/// Panics if a root node for `root_state_version` does not exist.
+pub fn get_substate_inclusion_proof<S: ReadableTreeStore>(
+    tree_store: &S,
+    root_state_version: Version,
+    partition_key: &DbPartitionKey,
+    sort_key: &DbSortKey,
+) -> Option<SubstateInclusionProof> {
+    let (entity_proof, partition_tier_version) = get_tier_leaf_proof(
+        &EntityTier::new(tree_store, Some(root_state_version)),
+        &partition_key.node_key,
+    )?;
+    let (partition_proof, substate_tier_version) = get_tier_leaf_proof(
+        &PartitionTier::new(
+            tree_store,
+            Some(partition_tier_version),
+            partition_key.node_key.clone(),
+        ),
+        &partition_key.partition_num,
+    )?;
+    let (substate_proof, _) = get_tier_leaf_proof(
+        &SubstateTier::new(
+            tree_store,
+            Some(substate_tier_version),
+            partition_key.node_key.clone(),
+            partition_key.partition_num,
+        ),
+        sort_key,
+    )?;
+    Some(SubstateInclusionProof {
+        entity_proof,
+        partition_proof,
+        substate_proof,
+    })
+}
+
+fn get_tier_leaf_proof<T: ReadableTier<Payload = Version>>(
+    tier: &T,
+    key: &T::TypedLeafKey,
+) -> Option<(SparseMerkleProof, Version)> {
+    let root_version = tier.root_version()?;
+    let (leaf, proof) = tier
+        .jmt()
+        .get_with_proof(&T::to_leaf_key(key), root_version)
+        .unwrap();
+    leaf.map(|(_hash, payload, _version)| (proof, payload))
+}
+
+/// A proof that a substate is included in the "3-Tier JMT" with a specific root hash.
+///
+/// It consists of a regular JMT inclusion proof for each tier: of the entity's leaf within the
+/// ReNode-Tier, of the partition's leaf within that entity's Partition-Tier and of the substate's
+/// leaf within that partition's Substate-Tier. Since each tier's leaf holds the root hash of the
+/// tier below it, the proofs can be verified bottom-up, starting from the substate value's hash.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Eq, PartialEq, ScryptoSbor)]
+pub struct SubstateInclusionProof {
+    entity_proof: SparseMerkleProof,
+    partition_proof: SparseMerkleProof,
+    substate_proof: SparseMerkleProof,
+}
+
+impl SubstateInclusionProof {
+    /// Returns the proof of the entity's leaf within the ReNode-Tier.
+    pub fn entity_proof(&self) -> &SparseMerkleProof {
+        &self.entity_proof
+    }
+
+    /// Returns the proof of the partition's leaf within the entity's Partition-Tier.
+    pub fn partition_proof(&self) -> &SparseMerkleProof {
+        &self.partition_proof
+    }
+
+    /// Returns the proof of the substate's leaf within the partition's Substate-Tier.
+    pub fn substate_proof(&self) -> &SparseMerkleProof {
+        &self.substate_proof
+    }
+
+    /// Checks whether this proof shows the given substate value to be stored under the given key
+    /// in a state tree with the given root hash.
+    pub fn verify(
+        &self,
+        root_hash: &Hash,
+        partition_key: &DbPartitionKey,
+        sort_key: &DbSortKey,
+        value: &DbSubstateValue,
+    ) -> bool {
+        let Some(substate_tier_hash) = self.substate_proof.compute_inclusion_root_hash(
+            &SubstateTier::<TypedInMemoryTreeStore>::to_leaf_key(sort_key),
+            &hash(value),
+        ) else {
+            return false;
+        };
+        let Some(partition_tier_hash) = self.partition_proof.compute_inclusion_root_hash(
+            &PartitionTier::<TypedInMemoryTreeStore>::to_leaf_key(&partition_key.partition_num),
+            &substate_tier_hash,
+        ) else {
+            return false;
+        };
+        self.entity_proof.compute_inclusion_root_hash(
+            &EntityTier::<TypedInMemoryTreeStore>::to_leaf_key(&partition_key.node_key),
+            &partition_tier_hash,
+        ) == Some(*root_hash)
+    }
+}
+
 pub fn list_substate_hashes_at_version<S: ReadableTreeStore>(
     tree_store: &S,
     root_state_version: Version,
@@ -2,6 +2,7 @@ use super::jellyfish::JellyfishMerkleTree;
 use super::tier_framework::{StateTreeTier, TIER_SEPARATOR};
 use super::tree_store::*;
 use super::types::*;
+use super::{get_substate_inclusion_proof, SubstateInclusionProof};
 use crate::state_tree::entity_tier::EntityTier;
 use crate::state_tree::substate_tier::SubstateSummary;
 use itertools::Itertools;
@@ -864,6 +865,92 @@ fn lists_historical_substate_summaries() {
     );
 }
 
+#[test]
+fn substate_inclusion_proof_verifies_against_root_hash() {
+    let mut tester = StateTreeTester::new_empty();
+    tester.put_substate_changes(vec![
+        change(1, 9, 1, Some(196)),
+        change(3, 2, 6, Some(16)),
+        change(3, 2, 7, Some(27)),
+    ]);
+    let root_hash = tester
+        .put_substate_changes(vec![
+            change(3, 2, 6, Some(36)),
+            change(3, 4, 7, Some(237)),
+            change(4, 2, 6, Some(46)),
+        ])
+        .unwrap();
+
+    let partition_key = partition_key(from_seed(3), 2);
+    let sort_key = DbSortKey(from_seed(6));
+    let proof = get_substate_inclusion_proof(
+        &tester.tree_store,
+        tester.current_version.unwrap(),
+        &partition_key,
+        &sort_key,
+    )
+    .unwrap();
+
+    assert!(proof.verify(&root_hash, &partition_key, &sort_key, &from_seed(36)));
+    // the value from before the update is not proven
+    assert!(!proof.verify(&root_hash, &partition_key, &sort_key, &from_seed(16)));
+    // the proof is bound to the substate's key
+    let other_sort_key = DbSortKey(from_seed(7));
+    assert!(!proof.verify(&root_hash, &partition_key, &other_sort_key, &from_seed(36)));
+    // the proof is bound to the root hash
+    assert!(!proof.verify(
+        &Hash([7; Hash::LENGTH]),
+        &partition_key,
+        &sort_key,
+        &from_seed(36)
+    ));
+}
+
+#[test]
+fn substate_inclusion_proof_verifies_after_encoding_round_trip() {
+    let mut tester = StateTreeTester::new_empty();
+    let root_hash = tester
+        .put_substate_changes(vec![
+            change(3, 2, 6, Some(36)),
+            change(3, 2, 7, Some(37)),
+            change(4, 2, 6, Some(46)),
+        ])
+        .unwrap();
+    let partition_key = partition_key(from_seed(3), 2);
+    let sort_key = DbSortKey(from_seed(6));
+    let proof = get_substate_inclusion_proof(
+        &tester.tree_store,
+        tester.current_version.unwrap(),
+        &partition_key,
+        &sort_key,
+    )
+    .unwrap();
+
+    let decoded =
+        scrypto_decode::<SubstateInclusionProof>(&scrypto_encode(&proof).unwrap()).unwrap();
+
+    assert_eq!(decoded, proof);
+    assert!(decoded.verify(&root_hash, &partition_key, &sort_key, &from_seed(36)));
+}
+
+#[test]
+fn substate_inclusion_proof_not_returned_for_missing_substate() {
+    let mut tester = StateTreeTester::new_empty();
+    tester.put_substate_changes(vec![change(3, 2, 6, Some(36))]);
+    let version = tester.current_version.unwrap();
+
+    for (partition_key, sort_key) in [
+        (partition_key(from_seed(3), 2), DbSortKey(from_seed(7))),
+        (partition_key(from_seed(3), 4), DbSortKey(from_seed(6))),
+        (partition_key(from_seed(5), 2), DbSortKey(from_seed(6))),
+    ] {
+        assert_eq!(
+            get_substate_inclusion_proof(&tester.tree_store, version, &partition_key, &sort_key),
+            None
+        );
+    }
+}
+
 type SingleSubstateChange = (DbSubstateKey, DatabaseUpdate);
 
 fn change(
@@ -81,7 +81,7 @@
 
 use itertools::Itertools;
 use radix_common::crypto::{hash, Hash};
-use radix_common::Sbor;
+use radix_common::{Sbor, ScryptoSbor};
 use sbor::rust::collections::hash_map::HashMap;
 use sbor::rust::ops::Range;
 use sbor::rust::string::String;
@@ -146,11 +146,37 @@ impl SparseMerkleProof {
     pub fn siblings(&self) -> &[Hash] {
         &self.siblings
     }
+
+    // INITIAL-MODIFICATION: instead of Aptos' `verify()` against a known root, we compute the root
+    // (so that it can be chained across the tiers of our "3-Tier JMT")
+    /// Computes the root hash of a tree in which this proof shows `key` to map to `value_hash`.
+    /// Returns `None` if this is not an inclusion proof of exactly that leaf.
+    pub fn compute_inclusion_root_hash(&self, key: &LeafKey, value_hash: &Hash) -> Option<Hash> {
+        let leaf = self
+            .leaf
+            .as_ref()
+            .filter(|leaf| &leaf.key == key && &leaf.value_hash == value_hash)?;
+        let key_bits = key.iter_bits();
+        let skipped_bits = key_bits.len().checked_sub(self.siblings.len())?;
+        let root_hash = self
+            .siblings
+            .iter()
+            .zip(key_bits.rev().skip(skipped_bits))
+            .fold(leaf.hash(), |hash, (sibling_hash, bit)| {
+                if bit {
+                    SparseMerkleInternalNode::new(*sibling_hash, hash).hash()
+                } else {
+                    SparseMerkleInternalNode::new(hash, *sibling_hash).hash()
+                }
+            });
+        Some(root_hash)
+    }
 }
 
 /// A proof that can be used to authenticate an element in a Sparse Merkle Tree given trusted root
 /// hash. For example, `TransactionInfoToAccountProof` can be constructed on top of this structure.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Eq, PartialEq, ScryptoSbor)]
 pub struct SparseMerkleProof {
     /// This proof can be used to authenticate whether a given leaf exists in the tree or not.
     ///     - If this is `Some(leaf_node)`
@@ -239,7 +265,8 @@ impl SparseMerkleRangeProof {
 }
 
 // SOURCE: https://github.com/aptos-labs/aptos-core/blob/1.0.4/types/src/proof/mod.rs#L97
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Eq, PartialEq, ScryptoSbor)]
 pub struct SparseMerkleLeafNode {
     key: LeafKey,
     value_hash: Hash,
@@ -672,13 +699,15 @@ impl<'a> NibbleIterator<'a> {
 
 // INITIAL-MODIFICATION: We will use this type (instead of `Hash`) to allow for arbitrary key length
 /// A leaf key (i.e. a complete nibble path).
-#[derive(Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd, ScryptoSbor)]
 pub struct LeafKey {
     /// The underlying bytes.
     /// All leaf keys of the same tree must be of the same length - otherwise the tree's behavior
     /// becomes unspecified.
     /// All leaf keys must be evenly distributed across their space - otherwise the tree's
     /// performance degrades.
+    #[cfg_attr(feature = "serde", serde(with = "hex::serde"))]
     pub bytes: Vec<u8>,
 }
 
@@ -1,8 +1,18 @@
 use crate::state_tree::tree_store::{TypedInMemoryTreeStore, Version};
-use crate::state_tree::{list_substate_hashes_at_version, put_at_next_version};
+use crate::state_tree::{
+    get_substate_inclusion_proof, list_substate_hashes_at_version, put_at_next_version,
+    SubstateInclusionProof,
+};
 use radix_common::prelude::*;
 use radix_substate_store_interface::interface::*;
 
+/// A wrapper around a [`SubstateDatabase`] which maintains a Merkle state tree (a 3-tier JMT)
+/// over the committed substates.
+///
+/// Every commit (e.g. of a transaction receipt's database updates) creates a new tree version,
+/// whose root hash commits to the full substate state and can be read with
+/// [`Self::get_current_root_hash`]. Substates are keyed by their database keys, which gives the
+/// tree a stable ordering independent of the order of updates.
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct StateTreeUpdatingDatabase<D> {
     underlying: D,
@@ -33,6 +43,27 @@ impl<D> StateTreeUpdatingDatabase<D> {
         list_substate_hashes_at_version(&self.tree_store, self.current_version)
     }
 
+    /// Returns a proof that the given substate is included in the state tree with the
+    /// [current root hash](Self::get_current_root_hash), or `None` if there is no such substate.
+    ///
+    /// The proof can be checked with [`SubstateInclusionProof::verify`], without access to the
+    /// database.
+    pub fn get_substate_inclusion_proof(
+        &self,
+        partition_key: &DbPartitionKey,
+        sort_key: &DbSortKey,
+    ) -> Option<SubstateInclusionProof> {
+        if self.current_version == 0 {
+            return None;
+        }
+        get_substate_inclusion_proof(
+            &self.tree_store,
+            self.current_version,
+            partition_key,
+            sort_key,
+        )
+    }
+
     fn update_with(&mut self, db_updates: &DatabaseUpdates) {
         self.current_hash = put_at_next_version(
             &mut self.tree_store,
@@ -63,6 +94,20 @@ impl<D: SubstateDatabase> SubstateDatabase for StateTreeUpdatingDatabase<D> {
     }
 }
 
+impl<D: SubstateDatabase> StateTreeUpdatingDatabase<D> {
+    /// Returns the given substate's value together with a proof of its inclusion in the state tree
+    /// with the [current root hash](Self::get_current_root_hash).
+    pub fn get_substate_with_proof(
+        &self,
+        partition_key: &DbPartitionKey,
+        sort_key: &DbSortKey,
+    ) -> Option<(DbSubstateValue, SubstateInclusionProof)> {
+        let value = self.get_raw_substate_by_db_key(partition_key, sort_key)?;
+        let proof = self.get_substate_inclusion_proof(partition_key, sort_key)?;
+        Some((value, proof))
+    }
+}
+
 impl<D: ListableSubstateDatabase> ListableSubstateDatabase for StateTreeUpdatingDatabase<D> {
     fn list_partition_keys(&self) -> Box<dyn Iterator<Item = DbPartitionKey> + '_> {
         self.underlying.list_partition_keys()
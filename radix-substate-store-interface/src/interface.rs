@@ -1,5 +1,6 @@
 use crate::db_key_mapper::*;
 use radix_common::prelude::*;
+use sbor::rust::num::NonZeroUsize;
 
 pub type DbNodeKey = Vec<u8>;
 
@@ -732,4 +733,67 @@ pub trait ListableSubstateDatabaseExtensions: ListableSubstateDatabase {
             .map(|key| SpreadPrefixKeyMapper::from_db_partition_key(&key));
         Box::new(iterator)
     }
+
+    /// Exports all current substates, as a stream of database updates which recreate them.
+    ///
+    /// Each update holds at most `max_substates_per_update` substates of a single partition, so
+    /// that the state is never loaded into memory at once. The first update of a partition resets
+    /// it, and any further ones set the rest of its substates. The updates can be encoded to
+    /// archive the state, and committed in order to an empty database to import it again.
+    fn export_all_substates(
+        &self,
+        max_substates_per_update: NonZeroUsize,
+    ) -> Box<dyn Iterator<Item = DatabaseUpdates> + '_>
+    where
+        Self: SubstateDatabase,
+    {
+        let iterator = self.list_partition_keys().flat_map(move |partition_key| {
+            let mut substate_values = self
+                .list_raw_values_from_db_key(&partition_key, None)
+                .peekable();
+            let mut is_first_update = true;
+            core::iter::from_fn(move || {
+                if !is_first_update && substate_values.peek().is_none() {
+                    return None;
+                }
+                let new_substate_values = substate_values
+                    .by_ref()
+                    .take(max_substates_per_update.get())
+                    .collect::<IndexMap<_, _>>();
+                let partition_updates = if is_first_update {
+                    is_first_update = false;
+                    PartitionDatabaseUpdates::Reset {
+                        new_substate_values,
+                    }
+                } else {
+                    PartitionDatabaseUpdates::Delta {
+                        substate_updates: new_substate_values
+                            .into_iter()
+                            .map(|(sort_key, value)| (sort_key, DatabaseUpdate::Set(value)))
+                            .collect(),
+                    }
+                };
+                Some(DatabaseUpdates {
+                    node_updates: indexmap! {
+                        partition_key.node_key.clone() => NodeDatabaseUpdates {
+                            partition_updates: indexmap! {
+                                partition_key.partition_num => partition_updates,
+                            },
+                        },
+                    },
+                })
+            })
+        });
+        Box::new(iterator)
+    }
+}
+
+/// A database which keeps the history needed to read past state versions.
+pub trait PrunableSubstateDatabase {
+    /// Permanently deletes the history which is only needed to read state versions before the
+    /// given one.
+    ///
+    /// The current substates are never pruned, and any state version from the given one onwards
+    /// stays readable.
+    fn prune_before(&mut self, state_version: u64);
 }
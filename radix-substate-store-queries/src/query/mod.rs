@@ -1,7 +1,9 @@
 mod accounter;
+mod state_tree_exporter;
 mod traverse;
 mod vault_finder;
 
 pub use accounter::*;
+pub use state_tree_exporter::*;
 pub use traverse::*;
 pub use vault_finder::*;
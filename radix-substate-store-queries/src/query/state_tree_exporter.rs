@@ -0,0 +1,206 @@
+use super::StateTreeVisitor;
+use radix_common::prelude::*;
+use radix_engine_interface::blueprints::resource::{
+    LiquidFungibleResource, LiquidNonFungibleVault,
+};
+use sbor::rust::vec::Vec;
+
+/// A node in an exported state tree, along with the nodes it owns.
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct StateTreeNode {
+    pub node_id: NodeId,
+    pub depth: u32,
+    /// The contents of the node, if it is a vault.
+    pub vault: Option<StateTreeVault>,
+    pub children: Vec<StateTreeNode>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub enum StateTreeVault {
+    Fungible {
+        resource_address: ResourceAddress,
+        amount: Decimal,
+    },
+    NonFungible {
+        resource_address: ResourceAddress,
+        amount: Decimal,
+        ids: Vec<NonFungibleLocalId>,
+    },
+}
+
+/// A [`StateTreeVisitor`] which collects the traversed nodes into serializable trees, e.g. for
+/// exporting the full state owned by a component.
+///
+/// Each node visited at depth 0 (i.e. each traversed subtree) starts a new tree.
+#[derive(Default)]
+pub struct StateTreeExporter {
+    /// The trees which were completely traversed.
+    roots: Vec<StateTreeNode>,
+    /// The path from the root to the most recently visited node.
+    stack: Vec<StateTreeNode>,
+}
+
+impl StateTreeExporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the traversed trees, in the order of their roots.
+    pub fn into_trees(mut self) -> Vec<StateTreeNode> {
+        self.complete_to_depth(0);
+        self.roots
+    }
+
+    /// Completes every node at `depth` or deeper, attaching it to its parent, or to the roots.
+    fn complete_to_depth(&mut self, depth: u32) {
+        while self.stack.last().is_some_and(|node| node.depth >= depth) {
+            let node = self.stack.pop().unwrap();
+            match self.stack.last_mut() {
+                Some(parent) => parent.children.push(node),
+                None => self.roots.push(node),
+            }
+        }
+    }
+}
+
+impl StateTreeVisitor for StateTreeExporter {
+    fn visit_fungible_vault(
+        &mut self,
+        _vault_id: NodeId,
+        address: &ResourceAddress,
+        resource: &LiquidFungibleResource,
+    ) {
+        if let Some(node) = self.stack.last_mut() {
+            node.vault = Some(StateTreeVault::Fungible {
+                resource_address: *address,
+                amount: resource.amount(),
+            });
+        }
+    }
+
+    fn visit_non_fungible_vault(
+        &mut self,
+        _vault_id: NodeId,
+        address: &ResourceAddress,
+        resource: &LiquidNonFungibleVault,
+    ) {
+        if let Some(node) = self.stack.last_mut() {
+            node.vault = Some(StateTreeVault::NonFungible {
+                resource_address: *address,
+                amount: resource.amount,
+                ids: Vec::new(),
+            });
+        }
+    }
+
+    fn visit_non_fungible(
+        &mut self,
+        _vault_id: NodeId,
+        _address: &ResourceAddress,
+        id: &NonFungibleLocalId,
+    ) {
+        if let Some(StateTreeNode {
+            vault: Some(StateTreeVault::NonFungible { ids, .. }),
+            ..
+        }) = self.stack.last_mut()
+        {
+            ids.push(id.clone());
+        }
+    }
+
+    fn visit_node_id(
+        &mut self,
+        _parent_id: Option<&(NodeId, PartitionNumber, SubstateKey)>,
+        node_id: &NodeId,
+        depth: u32,
+    ) {
+        // Nodes are visited depth-first, so any node at the same depth or deeper is complete
+        self.complete_to_depth(depth);
+        self.stack.push(StateTreeNode {
+            node_id: *node_id,
+            depth,
+            vault: None,
+            children: Vec::new(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::StateTreeTraverser;
+    use radix_engine::updates::ProtocolBuilder;
+    use radix_engine_interface::prelude::dec;
+    use radix_substate_store_impls::memory_db::InMemorySubstateDatabase;
+
+    #[test]
+    fn visited_nodes_are_nested_by_depth() {
+        let ids: Vec<NodeId> = (0u8..4).map(|i| NodeId([i; NodeId::LENGTH])).collect();
+        let mut exporter = StateTreeExporter::new();
+        exporter.visit_node_id(None, &ids[0], 0);
+        exporter.visit_node_id(None, &ids[1], 1);
+        exporter.visit_node_id(None, &ids[2], 2);
+        exporter.visit_fungible_vault(ids[2], &RORK, &LiquidFungibleResource::new(dec!(5)));
+        exporter.visit_node_id(None, &ids[3], 1);
+
+        let leaf = |node_id: NodeId, depth: u32, vault: Option<StateTreeVault>| StateTreeNode {
+            node_id,
+            depth,
+            vault,
+            children: vec![],
+        };
+        let vault = StateTreeVault::Fungible {
+            resource_address: RORK,
+            amount: dec!(5),
+        };
+        assert_eq!(
+            exporter.into_trees(),
+            vec![StateTreeNode {
+                children: vec![
+                    StateTreeNode {
+                        children: vec![leaf(ids[2], 2, Some(vault))],
+                        ..leaf(ids[1], 1, None)
+                    },
+                    leaf(ids[3], 1, None),
+                ],
+                ..leaf(ids[0], 0, None)
+            }]
+        );
+    }
+
+    #[test]
+    fn each_traversed_subtree_is_exported_as_a_tree() {
+        let mut substate_db = InMemorySubstateDatabase::standard();
+        ProtocolBuilder::for_simulator()
+            .from_bootstrap_to_latest()
+            .commit_each_protocol_update(&mut substate_db);
+
+        let mut exporter = StateTreeExporter::new();
+        let mut traverser = StateTreeTraverser::new(&substate_db, &mut exporter, 100);
+        traverser.traverse_subtree(None, FAUCET.into_node_id());
+        traverser.traverse_subtree(None, CONSENSUS_MANAGER.into_node_id());
+        let trees = exporter.into_trees();
+
+        fn has_vault_of(node: &StateTreeNode, resource: ResourceAddress) -> bool {
+            matches!(
+                node.vault,
+                Some(StateTreeVault::Fungible { resource_address, .. }) if resource_address == resource
+            ) || node
+                .children
+                .iter()
+                .any(|child| has_vault_of(child, resource))
+        }
+        // The faucet owns its vault and a key-value store, and the consensus manager its vault
+        assert_eq!(
+            trees
+                .iter()
+                .map(|tree| (tree.node_id, tree.children.len()))
+                .collect::<Vec<_>>(),
+            vec![
+                (FAUCET.into_node_id(), 2),
+                (CONSENSUS_MANAGER.into_node_id(), 1)
+            ]
+        );
+        assert!(trees.iter().all(|tree| has_vault_of(tree, RORK)));
+    }
+}
@@ -478,7 +478,8 @@ where
         self.add_instruction(instruction.into())
     }
 
-    /// Takes resource from worktop.
+    /// Takes all of a resource from worktop, so it can be forwarded without knowing its amount
+    /// or non-fungible ids upfront.
     pub fn take_all_from_worktop(
         self,
         resource_address: impl ResolvableStaticManifestResourceAddress,
@@ -889,6 +890,9 @@ where
         self.add_v1_instruction(instruction)
     }
 
+    /// Updates a single mutable field of a non-fungible's data, leaving its other fields as they
+    /// are. The field must be marked as mutable in the resource's non-fungible data schema, and
+    /// the call must satisfy the resource's `non_fungible_data_updater` role.
     pub fn update_non_fungible_data(
         self,
         resource_address: impl ReferencedManifestResourceAddress,
@@ -1011,6 +1015,49 @@ where
         self.call_method(address, VALIDATOR_CLAIM_RORK_IDENT, (bucket,))
     }
 
+    pub fn update_validator_key(
+        self,
+        validator_address: impl ReferencedManifestComponentAddress,
+        key: Secp256k1PublicKey,
+    ) -> Self {
+        let address = validator_address.resolve_referenced(&self.registrar);
+        self.call_method(
+            address,
+            VALIDATOR_UPDATE_KEY_IDENT,
+            ValidatorUpdateKeyManifestInput { key },
+        )
+    }
+
+    pub fn update_validator_fee(
+        self,
+        validator_address: impl ReferencedManifestComponentAddress,
+        new_fee_factor: impl Resolve<Decimal>,
+    ) -> Self {
+        let address = validator_address.resolve_referenced(&self.registrar);
+        self.call_method(
+            address,
+            VALIDATOR_UPDATE_FEE_IDENT,
+            ValidatorUpdateFeeManifestInput {
+                new_fee_factor: new_fee_factor.resolve(),
+            },
+        )
+    }
+
+    pub fn update_validator_accept_delegated_stake(
+        self,
+        validator_address: impl ReferencedManifestComponentAddress,
+        accept_delegated_stake: bool,
+    ) -> Self {
+        let address = validator_address.resolve_referenced(&self.registrar);
+        self.call_method(
+            address,
+            VALIDATOR_UPDATE_ACCEPT_DELEGATED_STAKE_IDENT,
+            ValidatorUpdateAcceptDelegatedStakeManifestInput {
+                accept_delegated_stake,
+            },
+        )
+    }
+
     /// Calls a scrypto function where the arguments should be one of:
     /// * A tuple, such as `()`, `(x,)` or `(x, y, z)`
     ///   * IMPORTANT: If calling with a single argument, you must include a trailing comma
@@ -1754,64 +1801,46 @@ where
         })
     }
 
-    pub fn freeze_withdraw(self, vault_id: InternalAddress) -> Self {
+    /// Freezes the given operations on a vault, e.g. `VaultFreezeFlags::WITHDRAW | VaultFreezeFlags::DEPOSIT`.
+    pub fn freeze_vault(self, vault_id: InternalAddress, to_freeze: VaultFreezeFlags) -> Self {
         self.add_v1_instruction(CallDirectVaultMethod {
             address: vault_id,
             method_name: VAULT_FREEZE_IDENT.to_string(),
-            args: to_manifest_value_and_unwrap!(&VaultFreezeInput {
-                to_freeze: VaultFreezeFlags::WITHDRAW,
-            }),
+            args: to_manifest_value_and_unwrap!(&VaultFreezeInput { to_freeze }),
         })
     }
 
-    pub fn unfreeze_withdraw(self, vault_id: InternalAddress) -> Self {
+    /// Unfreezes the given operations on a vault.
+    pub fn unfreeze_vault(self, vault_id: InternalAddress, to_unfreeze: VaultFreezeFlags) -> Self {
         self.add_v1_instruction(CallDirectVaultMethod {
             address: vault_id,
             method_name: VAULT_UNFREEZE_IDENT.to_string(),
-            args: to_manifest_value_and_unwrap!(&VaultUnfreezeInput {
-                to_unfreeze: VaultFreezeFlags::WITHDRAW,
-            }),
+            args: to_manifest_value_and_unwrap!(&VaultUnfreezeInput { to_unfreeze }),
         })
     }
 
+    pub fn freeze_withdraw(self, vault_id: InternalAddress) -> Self {
+        self.freeze_vault(vault_id, VaultFreezeFlags::WITHDRAW)
+    }
+
+    pub fn unfreeze_withdraw(self, vault_id: InternalAddress) -> Self {
+        self.unfreeze_vault(vault_id, VaultFreezeFlags::WITHDRAW)
+    }
+
     pub fn freeze_deposit(self, vault_id: InternalAddress) -> Self {
-        self.add_v1_instruction(CallDirectVaultMethod {
-            address: vault_id,
-            method_name: VAULT_FREEZE_IDENT.to_string(),
-            args: to_manifest_value_and_unwrap!(&VaultFreezeInput {
-                to_freeze: VaultFreezeFlags::DEPOSIT,
-            }),
-        })
+        self.freeze_vault(vault_id, VaultFreezeFlags::DEPOSIT)
     }
 
     pub fn unfreeze_deposit(self, vault_id: InternalAddress) -> Self {
-        self.add_v1_instruction(CallDirectVaultMethod {
-            address: vault_id,
-            method_name: VAULT_UNFREEZE_IDENT.to_string(),
-            args: to_manifest_value_and_unwrap!(&VaultUnfreezeInput {
-                to_unfreeze: VaultFreezeFlags::DEPOSIT,
-            }),
-        })
+        self.unfreeze_vault(vault_id, VaultFreezeFlags::DEPOSIT)
     }
 
     pub fn freeze_burn(self, vault_id: InternalAddress) -> Self {
-        self.add_v1_instruction(CallDirectVaultMethod {
-            address: vault_id,
-            method_name: VAULT_FREEZE_IDENT.to_string(),
-            args: to_manifest_value_and_unwrap!(&VaultFreezeInput {
-                to_freeze: VaultFreezeFlags::BURN,
-            }),
-        })
+        self.freeze_vault(vault_id, VaultFreezeFlags::BURN)
     }
 
     pub fn unfreeze_burn(self, vault_id: InternalAddress) -> Self {
-        self.add_v1_instruction(CallDirectVaultMethod {
-            address: vault_id,
-            method_name: VAULT_UNFREEZE_IDENT.to_string(),
-            args: to_manifest_value_and_unwrap!(&VaultUnfreezeInput {
-                to_unfreeze: VaultFreezeFlags::BURN,
-            }),
-        })
+        self.unfreeze_vault(vault_id, VaultFreezeFlags::BURN)
     }
 
     /// Creates an account.
@@ -2291,6 +2320,122 @@ where
             ),
         )
     }
+
+    /// Proposes a new rule set on an access controller, authorized by its primary role.
+    pub fn initiate_recovery_as_primary(
+        self,
+        access_controller_address: impl ReferencedManifestComponentAddress,
+        rule_set: RuleSet,
+        timed_recovery_delay_in_minutes: Option<u32>,
+    ) -> Self {
+        let address = access_controller_address.resolve_referenced(&self.registrar);
+        self.call_method(
+            address,
+            ACCESS_CONTROLLER_INITIATE_RECOVERY_AS_PRIMARY_IDENT,
+            AccessControllerInitiateRecoveryAsPrimaryManifestInput {
+                rule_set,
+                timed_recovery_delay_in_minutes,
+            },
+        )
+    }
+
+    /// Proposes a new rule set on an access controller, authorized by its recovery role.
+    pub fn initiate_recovery_as_recovery(
+        self,
+        access_controller_address: impl ReferencedManifestComponentAddress,
+        rule_set: RuleSet,
+        timed_recovery_delay_in_minutes: Option<u32>,
+    ) -> Self {
+        let address = access_controller_address.resolve_referenced(&self.registrar);
+        self.call_method(
+            address,
+            ACCESS_CONTROLLER_INITIATE_RECOVERY_AS_RECOVERY_IDENT,
+            AccessControllerInitiateRecoveryAsRecoveryManifestInput {
+                rule_set,
+                timed_recovery_delay_in_minutes,
+            },
+        )
+    }
+
+    /// Confirms the recovery proposal made by the primary role. The proposal must be repeated
+    /// exactly for the confirmation to succeed.
+    pub fn quick_confirm_primary_role_recovery_proposal(
+        self,
+        access_controller_address: impl ReferencedManifestComponentAddress,
+        rule_set: RuleSet,
+        timed_recovery_delay_in_minutes: Option<u32>,
+    ) -> Self {
+        let address = access_controller_address.resolve_referenced(&self.registrar);
+        self.call_method(
+            address,
+            ACCESS_CONTROLLER_QUICK_CONFIRM_PRIMARY_ROLE_RECOVERY_PROPOSAL_IDENT,
+            AccessControllerQuickConfirmPrimaryRoleRecoveryProposalManifestInput {
+                rule_set,
+                timed_recovery_delay_in_minutes,
+            },
+        )
+    }
+
+    /// Confirms the recovery proposal made by the recovery role. The proposal must be repeated
+    /// exactly for the confirmation to succeed.
+    pub fn quick_confirm_recovery_role_recovery_proposal(
+        self,
+        access_controller_address: impl ReferencedManifestComponentAddress,
+        rule_set: RuleSet,
+        timed_recovery_delay_in_minutes: Option<u32>,
+    ) -> Self {
+        let address = access_controller_address.resolve_referenced(&self.registrar);
+        self.call_method(
+            address,
+            ACCESS_CONTROLLER_QUICK_CONFIRM_RECOVERY_ROLE_RECOVERY_PROPOSAL_IDENT,
+            AccessControllerQuickConfirmRecoveryRoleRecoveryProposalManifestInput {
+                rule_set,
+                timed_recovery_delay_in_minutes,
+            },
+        )
+    }
+
+    /// Enacts the recovery role's proposal once its timed recovery delay has elapsed.
+    pub fn timed_confirm_recovery(
+        self,
+        access_controller_address: impl ReferencedManifestComponentAddress,
+        rule_set: RuleSet,
+        timed_recovery_delay_in_minutes: Option<u32>,
+    ) -> Self {
+        let address = access_controller_address.resolve_referenced(&self.registrar);
+        self.call_method(
+            address,
+            ACCESS_CONTROLLER_TIMED_CONFIRM_RECOVERY_IDENT,
+            AccessControllerTimedConfirmRecoveryManifestInput {
+                rule_set,
+                timed_recovery_delay_in_minutes,
+            },
+        )
+    }
+
+    pub fn cancel_primary_role_recovery_proposal(
+        self,
+        access_controller_address: impl ReferencedManifestComponentAddress,
+    ) -> Self {
+        let address = access_controller_address.resolve_referenced(&self.registrar);
+        self.call_method(
+            address,
+            ACCESS_CONTROLLER_CANCEL_PRIMARY_ROLE_RECOVERY_PROPOSAL_IDENT,
+            AccessControllerCancelPrimaryRoleRecoveryProposalInput,
+        )
+    }
+
+    pub fn cancel_recovery_role_recovery_proposal(
+        self,
+        access_controller_address: impl ReferencedManifestComponentAddress,
+    ) -> Self {
+        let address = access_controller_address.resolve_referenced(&self.registrar);
+        self.call_method(
+            address,
+            ACCESS_CONTROLLER_CANCEL_RECOVERY_ROLE_RECOVERY_PROPOSAL_IDENT,
+            AccessControllerCancelRecoveryRoleRecoveryProposalInput,
+        )
+    }
 }
 
 //===========================
@@ -2307,6 +2452,11 @@ where
         self.add_instruction(instruction.into())
     }
 
+    /// Asserts that the worktop holds no resources, so that nothing is left behind for later
+    /// instructions (or the end of the transaction) to deposit or drop.
+    ///
+    /// This is written as `ASSERT_WORKTOP_IS_EMPTY` in the manifest syntax, which is an alias of
+    /// `ASSERT_WORKTOP_RESOURCES_ONLY` with no constraints.
     pub fn assert_worktop_is_empty(self) -> Self {
         self.add_v2_instruction(AssertWorktopResourcesOnly {
             constraints: Default::default(),
@@ -1048,7 +1048,8 @@ mod tests {
 
     use super::*;
     use crate::builder::*;
-    use crate::internal_prelude::Secp256k1PrivateKey;
+    use crate::internal_prelude::{Ed25519PrivateKey, Secp256k1PrivateKey};
+    use radix_engine_interface::blueprints::package::PackageDefinition;
 
     #[test]
     #[allow(deprecated)]
@@ -1082,4 +1083,92 @@ mod tests {
             true
         );
     }
+
+    #[test]
+    #[allow(deprecated)]
+    fn built_transaction_carries_blobs_and_signatures() {
+        let notary = Ed25519PrivateKey::from_u64(1).unwrap();
+        let signer = Secp256k1PrivateKey::from_u64(2).unwrap();
+        let code = vec![0u8, 1, 2, 3];
+
+        let transaction = TransactionBuilder::new()
+            .header(TransactionHeaderV1 {
+                network_id: NetworkDefinition::simulator().id,
+                start_epoch_inclusive: Epoch::zero(),
+                end_epoch_exclusive: Epoch::of(100),
+                nonce: 5,
+                notary_public_key: notary.public_key().into(),
+                notary_is_signatory: false,
+                tip_percentage: 0,
+            })
+            .manifest(
+                ManifestBuilder::new()
+                    .publish_package(code.clone(), PackageDefinition::default())
+                    .build(),
+            )
+            .sign(&signer)
+            .notarize(&notary)
+            .build();
+
+        let prepared = transaction
+            .prepare(PreparationSettings::latest_ref())
+            .unwrap();
+        assert_eq!(
+            prepared
+                .signed_intent
+                .intent
+                .blobs
+                .blobs_by_hash
+                .get(&hash(&code)),
+            Some(&code)
+        );
+        assert_eq!(
+            transaction.signed_intent.intent_signatures.signatures,
+            vec![IntentSignatureV1(
+                signer.sign_with_public_key(&prepared.transaction_intent_hash())
+            )]
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn multi_signed_transaction_is_notarized_over_the_signed_intent() {
+        let notary = Ed25519PrivateKey::from_u64(1).unwrap();
+        let signers = [
+            Secp256k1PrivateKey::from_u64(2).unwrap(),
+            Secp256k1PrivateKey::from_u64(3).unwrap(),
+            Secp256k1PrivateKey::from_u64(4).unwrap(),
+        ];
+
+        let transaction = TransactionBuilder::new()
+            .header(TransactionHeaderV1 {
+                network_id: NetworkDefinition::simulator().id,
+                start_epoch_inclusive: Epoch::zero(),
+                end_epoch_exclusive: Epoch::of(100),
+                nonce: 5,
+                notary_public_key: notary.public_key().into(),
+                notary_is_signatory: false,
+                tip_percentage: 0,
+            })
+            .manifest(ManifestBuilder::new().drop_auth_zone_proofs().build())
+            .multi_sign(&signers)
+            .notarize(&notary)
+            .build();
+
+        let validated = transaction
+            .prepare_and_validate(&TransactionValidator::new_for_latest_simulator())
+            .unwrap();
+        assert_eq!(
+            validated.signer_keys,
+            signers
+                .iter()
+                .map(|signer| signer.public_key().into())
+                .collect::<IndexSet<PublicKey>>()
+        );
+        assert!(verify(
+            &validated.signed_transaction_intent_hash().0,
+            &notary.public_key().into(),
+            &transaction.notary_signature.0,
+        ));
+    }
 }
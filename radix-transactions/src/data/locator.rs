@@ -0,0 +1,168 @@
+use crate::internal_prelude::*;
+use radix_common::data::manifest::model::{
+    ManifestBlobRef, ManifestBucket, ManifestExpression, ManifestProof,
+};
+use radix_common::data::manifest::{ManifestCustomValue, ManifestValue};
+use sbor::rust::mem;
+use sbor::rust::vec::Vec;
+
+/// The locations of the custom values of a [`ManifestValue`] which are resolved by the
+/// transaction processor, in the order they are encountered by [`transform`].
+///
+/// Each value is paired with its [`SborPath`], which can be passed to
+/// [`replace_manifest_value_at`] to rewrite it.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ManifestValueLocations {
+    pub buckets: Vec<(SborPath, ManifestBucket)>,
+    pub proofs: Vec<(SborPath, ManifestProof)>,
+    pub address_reservations: Vec<(SborPath, ManifestAddressReservation)>,
+    pub named_addresses: Vec<(SborPath, ManifestNamedAddress)>,
+    pub expressions: Vec<(SborPath, ManifestExpression)>,
+    pub blobs: Vec<(SborPath, ManifestBlobRef)>,
+}
+
+impl ManifestValueLocations {
+    pub fn locate(value: &ManifestValue) -> Self {
+        let mut locations = Self::default();
+        locations.visit(&mut SborPathBuf::new(), value);
+        locations
+    }
+
+    fn visit(&mut self, path: &mut SborPathBuf, value: &ManifestValue) {
+        match value {
+            Value::Tuple { fields: values }
+            | Value::Enum { fields: values, .. }
+            | Value::Array {
+                elements: values, ..
+            } => {
+                for (i, value) in values.iter().enumerate() {
+                    path.push(i);
+                    self.visit(path, value);
+                    path.pop();
+                }
+            }
+            Value::Map { entries, .. } => {
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    path.push(i);
+
+                    path.push(0);
+                    self.visit(path, key);
+                    path.pop();
+
+                    path.push(1);
+                    self.visit(path, value);
+                    path.pop();
+
+                    path.pop();
+                }
+            }
+            Value::Custom { value } => {
+                let located = path.clone().into();
+                match value {
+                    ManifestCustomValue::Bucket(b) => self.buckets.push((located, *b)),
+                    ManifestCustomValue::Proof(p) => self.proofs.push((located, *p)),
+                    ManifestCustomValue::AddressReservation(r) => {
+                        self.address_reservations.push((located, *r))
+                    }
+                    ManifestCustomValue::Address(ManifestAddress::Named(a)) => {
+                        self.named_addresses.push((located, *a))
+                    }
+                    ManifestCustomValue::Expression(e) => self.expressions.push((located, *e)),
+                    ManifestCustomValue::Blob(b) => self.blobs.push((located, b.clone())),
+                    ManifestCustomValue::Address(ManifestAddress::Static(_))
+                    | ManifestCustomValue::Decimal(_)
+                    | ManifestCustomValue::PreciseDecimal(_)
+                    | ManifestCustomValue::NonFungibleLocalId(_) => {}
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Replaces the value at the given path, returning the previous value, or `None` if the path
+/// doesn't exist in the value.
+///
+/// The replacement isn't checked against the kinds of its siblings, so replacing an array element
+/// or map entry with a value of a different kind results in a value which can't be encoded.
+pub fn replace_manifest_value_at(
+    value: &mut ManifestValue,
+    path: &SborPath,
+    replacement: ManifestValue,
+) -> Option<ManifestValue> {
+    path.get_from_value_mut(value)
+        .map(|target| mem::replace(target, replacement))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_custom_values_in_nested_value() {
+        let value = manifest_decode::<ManifestValue>(
+            &manifest_encode(&(
+                ManifestBucket(1),
+                vec![ManifestProof(2), ManifestProof(3)],
+                indexmap!(ManifestExpression::EntireWorktop => ManifestBlobRef([7; 32])),
+                ManifestAddress::named(4),
+            ))
+            .unwrap(),
+        )
+        .unwrap();
+
+        let locations = ManifestValueLocations::locate(&value);
+
+        assert_eq!(
+            locations,
+            ManifestValueLocations {
+                buckets: vec![(SborPath::new(vec![0]), ManifestBucket(1))],
+                proofs: vec![
+                    (SborPath::new(vec![1, 0]), ManifestProof(2)),
+                    (SborPath::new(vec![1, 1]), ManifestProof(3)),
+                ],
+                address_reservations: vec![],
+                named_addresses: vec![(SborPath::new(vec![3]), ManifestNamedAddress(4))],
+                expressions: vec![(
+                    SborPath::new(vec![2, 0, 0]),
+                    ManifestExpression::EntireWorktop
+                )],
+                blobs: vec![(SborPath::new(vec![2, 0, 1]), ManifestBlobRef([7; 32]))],
+            }
+        );
+    }
+
+    #[test]
+    fn replaces_value_at_located_path() {
+        let mut value = manifest_decode::<ManifestValue>(
+            &manifest_encode(&(1u8, (ManifestExpression::EntireAuthZone,))).unwrap(),
+        )
+        .unwrap();
+        let (path, _) = ManifestValueLocations::locate(&value).expressions[0].clone();
+
+        let replaced = replace_manifest_value_at(
+            &mut value,
+            &path,
+            ManifestValue::Custom {
+                value: ManifestCustomValue::Bucket(ManifestBucket(5)),
+            },
+        );
+
+        assert_eq!(
+            replaced,
+            Some(ManifestValue::Custom {
+                value: ManifestCustomValue::Expression(ManifestExpression::EntireAuthZone)
+            })
+        );
+        assert_eq!(
+            ManifestValueLocations::locate(&value).buckets,
+            vec![(SborPath::new(vec![1, 0]), ManifestBucket(5))]
+        );
+        let unchanged = value.clone();
+        assert_eq!(
+            replace_manifest_value_at(&mut value, &SborPath::new(vec![2]), ManifestValue::unit()),
+            None
+        );
+        assert_eq!(value, unchanged);
+    }
+}
@@ -1,7 +1,9 @@
 mod converter;
 mod formatter;
+mod locator;
 mod transformer;
 
 pub use converter::*;
 pub use formatter::*;
+pub use locator::*;
 pub use transformer::*;
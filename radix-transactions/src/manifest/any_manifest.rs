@@ -396,4 +396,26 @@ mod tests {
         let cuttlefish_decoded = TransactionManifestV2::from_raw(&cuttlefish_raw).unwrap();
         assert_eq!(manifest, cuttlefish_decoded);
     }
+
+    #[test]
+    pub fn text_and_binary_manifests_decompile_identically() {
+        let network = NetworkDefinition::simulator();
+        let manifest: AnyManifest = ManifestBuilder::new_v2()
+            .lock_fee_from_faucet()
+            .take_all_from_worktop(RORK, "my_bucket")
+            .return_to_worktop("my_bucket")
+            .build()
+            .into();
+        let text = decompile_any(&manifest, &network).unwrap();
+
+        let compiled =
+            compile_any_manifest(&text, ManifestKind::V2, &network, BlobProvider::new()).unwrap();
+        let raw = compiled.to_raw().unwrap();
+        let decoded = AnyManifest::from_raw(&raw).unwrap();
+
+        assert_eq!(decoded, manifest);
+        assert_eq!(decompile_any(&decoded, &network).unwrap(), text);
+        // The encoding is canonical, so re-encoding yields the same bytes
+        assert_eq!(decoded.to_raw().unwrap(), raw);
+    }
 }
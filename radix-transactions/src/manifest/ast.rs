@@ -7,6 +7,14 @@ use super::generator::*;
 #[derive(Debug, Clone, PartialEq, Eq, EnumDiscriminants, EnumCount)]
 #[strum_discriminants(derive(FromRepr))]
 pub enum Instruction {
+    //========================================
+    // VARIABLE BINDINGS (RESOLVED BY THE GENERATOR)
+    //========================================
+    Let {
+        variable: ValueWithSpan,
+        value: ValueWithSpan,
+    },
+
     //========================================
     // PSEUDO-INSTRUCTIONS AT THE START
     //========================================
@@ -220,6 +228,16 @@ pub enum Instruction {
     CreateNonFungibleResourceWithInitialSupply {
         args: Vec<ValueWithSpan>,
     },
+    CreateSimpleFungibleResource {
+        divisibility: ValueWithSpan,
+        metadata: ValueWithSpan,
+        initial_supply: ValueWithSpan,
+    },
+    CreateSimpleNonFungibleResource {
+        id_type: ValueWithSpan,
+        metadata: ValueWithSpan,
+        initial_supply: ValueWithSpan,
+    },
     CreateAccessController {
         args: Vec<ValueWithSpan>,
     },
@@ -251,6 +269,10 @@ pub enum Instruction {
         address: ValueWithSpan,
         args: Vec<ValueWithSpan>,
     },
+    GetMetadata {
+        address: ValueWithSpan,
+        args: Vec<ValueWithSpan>,
+    },
     SetComponentRoyalty {
         address: ValueWithSpan,
         args: Vec<ValueWithSpan>,
@@ -354,6 +376,7 @@ pub enum ValueKind {
     // ==============
     Intent,
     NamedIntent,
+    Variable,
 }
 
 impl ValueKind {
@@ -474,6 +497,11 @@ pub enum Value {
     AddressReservation(Box<ValueWithSpan>),
     Intent(Box<ValueWithSpan>),
     NamedIntent(Box<ValueWithSpan>),
+
+    // ==============
+    // Variables
+    // ==============
+    Variable(String),
 }
 
 impl Value {
@@ -524,6 +552,11 @@ impl Value {
             Value::AddressReservation(_) => ValueKind::AddressReservation,
             Value::Intent(_) => ValueKind::Intent,
             Value::NamedIntent(_) => ValueKind::NamedIntent,
+
+            // ==============
+            // Variables
+            // ==============
+            Value::Variable(_) => ValueKind::Variable,
         }
     }
 }
@@ -598,6 +631,12 @@ impl ValueKindWithSpan {
                     error_kind: GeneratorErrorKind::IntentCannotBeUsedAsValueKind,
                 })
             }
+            ValueKind::Variable => {
+                return Err(GeneratorError {
+                    span: self.span,
+                    error_kind: GeneratorErrorKind::VariableCannotBeUsedAsValueKind,
+                })
+            }
         };
         Ok(value_kind)
     }
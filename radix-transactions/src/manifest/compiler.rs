@@ -7,6 +7,18 @@ pub enum CompileError {
     GeneratorError(generator::GeneratorError),
 }
 
+impl CompileError {
+    /// The span of the manifest source which caused the error, e.g. for reporting its line and
+    /// column in an editor.
+    pub fn span(&self) -> token::Span {
+        match self {
+            CompileError::LexerError(err) => err.span,
+            CompileError::ParserError(err) => err.span,
+            CompileError::GeneratorError(err) => err.span,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum CompileErrorDiagnosticsStyle {
     PlainText,
@@ -147,6 +147,109 @@ pub fn decompile(
     Ok(buf)
 }
 
+/// Human-readable names for addresses, which [`decompile_with_address_names`] lists alongside a
+/// decompiled manifest to make it easier to review.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AddressNames {
+    names: IndexMap<GlobalAddress, String>,
+}
+
+macro_rules! address_names {
+    ($($address:ident),* $(,)?) => {
+        AddressNames::new()$(.with_name($address, stringify!($address)))*
+    };
+}
+
+impl AddressNames {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The names of the well-known addresses which exist from genesis (e.g. `RORK` or `FAUCET`),
+    /// as declared in [`radix_common::constants`].
+    pub fn well_known() -> Self {
+        address_names!(
+            RORK,
+            SECP256K1_SIGNATURE_RESOURCE,
+            ED25519_SIGNATURE_RESOURCE,
+            PACKAGE_OF_DIRECT_CALLER_RESOURCE,
+            GLOBAL_CALLER_RESOURCE,
+            SYSTEM_EXECUTION_RESOURCE,
+            PACKAGE_OWNER_BADGE,
+            VALIDATOR_OWNER_BADGE,
+            ACCOUNT_OWNER_BADGE,
+            IDENTITY_OWNER_BADGE,
+            PACKAGE_PACKAGE,
+            RESOURCE_PACKAGE,
+            ACCOUNT_PACKAGE,
+            IDENTITY_PACKAGE,
+            CONSENSUS_MANAGER_PACKAGE,
+            ACCESS_CONTROLLER_PACKAGE,
+            POOL_PACKAGE,
+            TRANSACTION_PROCESSOR_PACKAGE,
+            METADATA_MODULE_PACKAGE,
+            ROYALTY_MODULE_PACKAGE,
+            ROLE_ASSIGNMENT_MODULE_PACKAGE,
+            TEST_UTILS_PACKAGE,
+            GENESIS_HELPER_PACKAGE,
+            FAUCET_PACKAGE,
+            TRANSACTION_TRACKER_PACKAGE,
+            LOCKER_PACKAGE,
+            CONSENSUS_MANAGER,
+            GENESIS_HELPER,
+            FAUCET,
+            TRANSACTION_TRACKER,
+        )
+    }
+
+    /// Names the given address, replacing any previous name it had.
+    pub fn with_name(mut self, address: impl Into<GlobalAddress>, name: impl Into<String>) -> Self {
+        self.names.insert(address.into(), name.into());
+        self
+    }
+
+    pub fn get(&self, address: &GlobalAddress) -> Option<&str> {
+        self.names.get(address).map(|name| name.as_str())
+    }
+}
+
+/// Decompiles the manifest like [`decompile`], but prefixes it with a comment naming each of the
+/// given addresses which the manifest references, e.g. `# RORK = resource_sim1...`.
+///
+/// Being a comment, the header does not affect the compilation of the output.
+pub fn decompile_with_address_names(
+    manifest: &impl TypedReadableManifest,
+    network: &NetworkDefinition,
+    address_names: &AddressNames,
+) -> Result<String, DecompileError> {
+    let decompiled = decompile(manifest, network)?;
+    let instructions = manifest
+        .get_typed_instructions()
+        .iter()
+        .map(|instruction| instruction.clone().into_any())
+        .collect::<Vec<_>>();
+    let references = extract_references(
+        &manifest_encode(&instructions)?,
+        traversal::ExpectedStart::PayloadPrefix(MANIFEST_SBOR_V1_PAYLOAD_PREFIX),
+    );
+    let address_bech32_encoder = AddressBech32Encoder::new(network);
+    let mut buf = String::new();
+    for (address, name) in address_names.names.iter() {
+        if !references.contains(&Reference(*address.as_node_id())) {
+            continue;
+        }
+        let Ok(bech32) = address_bech32_encoder.encode(address.as_node_id().as_bytes()) else {
+            continue;
+        };
+        buf.push_str(&format!("# {} = {}\n", name, bech32));
+    }
+    if !buf.is_empty() {
+        buf.push('\n');
+    }
+    buf.push_str(&decompiled);
+    Ok(buf)
+}
+
 pub struct DecompiledInstruction {
     command: &'static str,
     fields: Vec<DecompiledInstructionField>,
@@ -228,3 +331,73 @@ pub fn output_instruction<F: fmt::Write>(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::*;
+
+    #[test]
+    fn decompiling_with_address_names_lists_referenced_addresses() {
+        let network = NetworkDefinition::simulator();
+        let account = ComponentAddress::preallocated_account_from_public_key(
+            &Secp256k1PrivateKey::from_u64(1).unwrap().public_key(),
+        );
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .get_free_xrd_from_faucet()
+            .take_all_from_worktop(RORK, "rork")
+            .try_deposit_or_abort(account, None, "rork")
+            .build();
+        let address_names = AddressNames::well_known().with_name(account, "my_account");
+
+        let decompiled = decompile_with_address_names(&manifest, &network, &address_names).unwrap();
+
+        let encoder = AddressBech32Encoder::new(&network);
+        let header = [
+            format!("# RORK = {}", encoder.encode(RORK.as_bytes()).unwrap()),
+            format!("# FAUCET = {}", encoder.encode(FAUCET.as_bytes()).unwrap()),
+            format!(
+                "# my_account = {}",
+                encoder.encode(account.as_bytes()).unwrap()
+            ),
+        ];
+        assert_eq!(
+            decompiled,
+            format!(
+                "{}\n\n{}",
+                header.join("\n"),
+                decompile(&manifest, &network).unwrap()
+            )
+        );
+        let recompiled: TransactionManifestV1 =
+            compile_manifest(&decompiled, &network, BlobProvider::new()).unwrap();
+        assert_eq!(recompiled.instructions, manifest.instructions);
+    }
+
+    #[test]
+    fn decompiling_with_address_names_ignores_addresses_only_mentioned_in_strings() {
+        let network = NetworkDefinition::simulator();
+        let encoder = AddressBech32Encoder::new(&network);
+        let faucet = encoder.encode(FAUCET.as_bytes()).unwrap();
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                CONSENSUS_MANAGER,
+                "get_current_epoch",
+                (format!("Address(\"{}\")", faucet),),
+            )
+            .build();
+
+        let decompiled =
+            decompile_with_address_names(&manifest, &network, &AddressNames::well_known()).unwrap();
+
+        assert_eq!(
+            decompiled,
+            format!(
+                "# CONSENSUS_MANAGER = {}\n\n{}",
+                encoder.encode(CONSENSUS_MANAGER.as_bytes()).unwrap(),
+                decompile(&manifest, &network).unwrap()
+            )
+        );
+    }
+}
@@ -380,9 +380,8 @@ SET_COMPONENT_ROYALTY
     "my_method"
     Enum<0u8>()
 ;
-CALL_METADATA_METHOD
+GET_METADATA
     Address("${component_address}")
-    "get"
     "HelloWorld"
 ;
 CALL_ROLE_ASSIGNMENT_METHOD
@@ -725,6 +724,10 @@ REMOVE_METADATA
     Address("${resource_address}")
     "field_name"
 ;
+GET_METADATA
+    Address("${resource_address}")
+    "field_name"
+;
 "##,
             ),
         );
@@ -1629,6 +1632,86 @@ CALL_METHOD
         );
     }
 
+    #[test]
+    fn test_compile_errors_report_line_and_column() {
+        let compile_error_position = |manifest: &str| {
+            let err = compile_manifest_v1(
+                &apply_address_replacements(manifest),
+                &NetworkDefinition::simulator(),
+                BlobProvider::default(),
+            )
+            .unwrap_err();
+            let span = err.span();
+            (err, span.start.line_number(), span.start.column_number())
+        };
+
+        let (err, line, column) = compile_error_position(
+            "CALL_METHOD\n    Address(\"${account_address}\")\n    \"lock_fee\"\n    Decimal(\"abc\");",
+        );
+        assert!(matches!(
+            err,
+            CompileError::GeneratorError(generator::GeneratorError {
+                error_kind: generator::GeneratorErrorKind::InvalidDecimal { .. },
+                ..
+            })
+        ));
+        assert_eq!((line, column), (4, 13));
+
+        let (err, line, column) =
+            compile_error_position("CALL_METHOD\n    Address(\"a\", \"b\")\n    \"lock_fee\";");
+        assert!(matches!(
+            err,
+            CompileError::ParserError(parser::ParserError {
+                error_kind: parser::ParserErrorKind::InvalidNumberOfValues {
+                    expected: 1,
+                    actual: 2
+                },
+                ..
+            })
+        ));
+        assert_eq!((line, column), (2, 13));
+    }
+
+    #[test]
+    fn test_blobs_referenced_by_multiple_instructions_are_validated_once() {
+        let blob = vec![7u8; 32];
+        let manifest = apply_address_replacements(format!(
+            r##"
+CALL_METHOD Address("${{account_address}}") "deposit" Blob("{blob_hash}");
+CALL_METHOD Address("${{account_address}}") "deposit" Blob("{blob_hash}");
+"##,
+            blob_hash = hash(&blob)
+        ));
+        let mut manifest = compile_manifest_v1(
+            &manifest,
+            &NetworkDefinition::simulator(),
+            BlobProvider::new_with_blobs(vec![blob.clone(), blob.clone()]),
+        )
+        .unwrap();
+
+        assert_eq!(manifest.blobs.len(), 1);
+        assert_eq!(manifest.validate(ValidationRuleset::all()), Ok(()));
+        assert_eq!(
+            manifest.validate(ValidationRuleset {
+                max_blob_size: Some(31),
+                ..ValidationRuleset::all()
+            }),
+            Err(ManifestValidationError::BlobTooLarge {
+                blob_ref: ManifestBlobRef(hash(&blob).0),
+                size: 32,
+                limit: 31,
+            })
+        );
+
+        manifest.blobs = indexmap!(hash(&blob) => vec![8u8; 32]);
+        assert_eq!(
+            manifest.validate(ValidationRuleset::all()),
+            Err(ManifestValidationError::BlobHashMismatch(ManifestBlobRef(
+                hash(&blob).0
+            )))
+        );
+    }
+
     fn compile_and_decompile_with_inversion_test_v1(
         name: &str,
         manifest: impl AsRef<str>,
@@ -84,6 +84,10 @@ pub enum GeneratorErrorKind {
     IntentCannotBeUsedAsValueKind,
     NamedIntentCannotBeUsedInValue,
     NamedIntentCannotBeUsedAsValueKind,
+    UndefinedVariable(String),
+    VariableAlreadyDefined(String),
+    VariableCannotBeUsedAsValueKind,
+    LetInstructionMustBeResolved,
     ArgumentCouldNotBeReadAsExpectedType {
         type_name: String,
         error_message: String,
@@ -318,6 +322,8 @@ pub fn generate_manifest<B, M: BuildableManifest>(
 where
     B: IsBlobProvider,
 {
+    let instructions = resolve_variables(instructions)?;
+
     let mut id_validator = BasicManifestValidator::new();
     let mut name_resolver = NameResolver::new();
 
@@ -356,6 +362,336 @@ where
     Ok(manifest)
 }
 
+/// Resolves `LET` bindings, substituting every `$name` reference with the value bound to it.
+///
+/// Variables must be bound before they are referenced, and can't be re-bound. The `LET`
+/// instructions themselves are removed from the returned instructions.
+pub fn resolve_variables(
+    instructions: &[ast::InstructionWithSpan],
+) -> Result<Vec<ast::InstructionWithSpan>, GeneratorError> {
+    let mut variables = IndexMap::<String, ast::Value>::new();
+    let mut resolved_instructions = Vec::with_capacity(instructions.len());
+
+    for instruction in instructions {
+        let mut instruction = instruction.clone();
+        for value in instruction_values_mut(&mut instruction.instruction) {
+            resolve_variables_in_value(value, &variables)?;
+        }
+
+        if let Instruction::Let { variable, value } = instruction.instruction {
+            let ast::Value::Variable(name) = variable.value else {
+                return Err(GeneratorError {
+                    span: variable.span,
+                    error_kind: GeneratorErrorKind::InvalidAstValue {
+                        expected_value_kinds: vec![ast::ValueKind::Variable],
+                        actual: variable.value,
+                    },
+                });
+            };
+            if variables.contains_key(&name) {
+                return Err(GeneratorError {
+                    span: variable.span,
+                    error_kind: GeneratorErrorKind::VariableAlreadyDefined(name),
+                });
+            }
+            variables.insert(name, value.value);
+        } else {
+            resolved_instructions.push(instruction);
+        }
+    }
+
+    Ok(resolved_instructions)
+}
+
+fn resolve_variables_in_value(
+    value: &mut ast::ValueWithSpan,
+    variables: &IndexMap<String, ast::Value>,
+) -> Result<(), GeneratorError> {
+    match &mut value.value {
+        ast::Value::Variable(name) => {
+            // The bound value has already been resolved, and keeps the span of the reference
+            value.value = variables
+                .get(name.as_str())
+                .ok_or_else(|| GeneratorError {
+                    span: value.span,
+                    error_kind: GeneratorErrorKind::UndefinedVariable(name.clone()),
+                })?
+                .clone();
+        }
+        ast::Value::Enum(_, fields) | ast::Value::Array(_, fields) | ast::Value::Tuple(fields) => {
+            for field in fields {
+                resolve_variables_in_value(field, variables)?;
+            }
+        }
+        ast::Value::Map(_, _, entries) => {
+            for (key, value) in entries {
+                resolve_variables_in_value(key, variables)?;
+                resolve_variables_in_value(value, variables)?;
+            }
+        }
+        ast::Value::Some(inner)
+        | ast::Value::Ok(inner)
+        | ast::Value::Err(inner)
+        | ast::Value::Bytes(inner)
+        | ast::Value::NonFungibleGlobalId(inner)
+        | ast::Value::Address(inner)
+        | ast::Value::NamedAddress(inner)
+        | ast::Value::Bucket(inner)
+        | ast::Value::Proof(inner)
+        | ast::Value::Expression(inner)
+        | ast::Value::Blob(inner)
+        | ast::Value::Decimal(inner)
+        | ast::Value::PreciseDecimal(inner)
+        | ast::Value::NonFungibleLocalId(inner)
+        | ast::Value::AddressReservation(inner)
+        | ast::Value::Intent(inner)
+        | ast::Value::NamedIntent(inner) => {
+            resolve_variables_in_value(inner, variables)?;
+        }
+        ast::Value::Bool(_)
+        | ast::Value::I8(_)
+        | ast::Value::I16(_)
+        | ast::Value::I32(_)
+        | ast::Value::I64(_)
+        | ast::Value::I128(_)
+        | ast::Value::U8(_)
+        | ast::Value::U16(_)
+        | ast::Value::U32(_)
+        | ast::Value::U64(_)
+        | ast::Value::U128(_)
+        | ast::Value::String(_)
+        | ast::Value::None => {}
+    }
+    Ok(())
+}
+
+pub(crate) fn instruction_values_mut(
+    instruction: &mut Instruction,
+) -> Vec<&mut ast::ValueWithSpan> {
+    match instruction {
+        Instruction::Let { value, .. } => vec![value],
+        Instruction::UsePreallocatedAddress {
+            package_address,
+            blueprint_name,
+            address_reservation,
+            preallocated_address,
+        } => {
+            vec![
+                package_address,
+                blueprint_name,
+                address_reservation,
+                preallocated_address,
+            ]
+        }
+        Instruction::UseChild {
+            named_intent,
+            subintent_hash,
+        } => {
+            vec![named_intent, subintent_hash]
+        }
+        Instruction::TakeFromWorktop {
+            resource_address,
+            amount,
+            new_bucket,
+        } => {
+            vec![resource_address, amount, new_bucket]
+        }
+        Instruction::TakeNonFungiblesFromWorktop {
+            ids,
+            resource_address,
+            new_bucket,
+        } => {
+            vec![ids, resource_address, new_bucket]
+        }
+        Instruction::TakeAllFromWorktop {
+            resource_address,
+            new_bucket,
+        } => {
+            vec![resource_address, new_bucket]
+        }
+        Instruction::ReturnToWorktop { bucket } | Instruction::BurnResource { bucket } => {
+            vec![bucket]
+        }
+        Instruction::AssertWorktopContains {
+            resource_address,
+            amount,
+        } => {
+            vec![resource_address, amount]
+        }
+        Instruction::AssertWorktopContainsNonFungibles {
+            resource_address,
+            ids,
+        } => {
+            vec![resource_address, ids]
+        }
+        Instruction::AssertWorktopContainsAny { resource_address } => {
+            vec![resource_address]
+        }
+        Instruction::AssertWorktopIsEmpty
+        | Instruction::DropAuthZoneSignatureProofs
+        | Instruction::DropAuthZoneRegularProofs
+        | Instruction::DropAuthZoneProofs
+        | Instruction::DropNamedProofs
+        | Instruction::DropAllProofs => vec![],
+        Instruction::AssertWorktopResourcesOnly { constraints }
+        | Instruction::AssertWorktopResourcesInclude { constraints }
+        | Instruction::AssertNextCallReturnsOnly { constraints }
+        | Instruction::AssertNextCallReturnsInclude { constraints } => {
+            vec![constraints]
+        }
+        Instruction::AssertBucketContents { bucket, constraint } => {
+            vec![bucket, constraint]
+        }
+        Instruction::CreateProofFromBucketOfAmount {
+            bucket,
+            amount,
+            new_proof,
+        } => {
+            vec![bucket, amount, new_proof]
+        }
+        Instruction::CreateProofFromBucketOfNonFungibles {
+            bucket,
+            ids,
+            new_proof,
+        } => {
+            vec![bucket, ids, new_proof]
+        }
+        Instruction::CreateProofFromBucketOfAll { bucket, new_proof } => {
+            vec![bucket, new_proof]
+        }
+        Instruction::CreateProofFromAuthZoneOfAmount {
+            resource_address,
+            amount,
+            new_proof,
+        } => {
+            vec![resource_address, amount, new_proof]
+        }
+        Instruction::CreateProofFromAuthZoneOfNonFungibles {
+            resource_address,
+            ids,
+            new_proof,
+        } => {
+            vec![resource_address, ids, new_proof]
+        }
+        Instruction::CreateProofFromAuthZoneOfAll {
+            resource_address,
+            new_proof,
+        } => {
+            vec![resource_address, new_proof]
+        }
+        Instruction::CloneProof { proof, new_proof } => {
+            vec![proof, new_proof]
+        }
+        Instruction::DropProof { proof } | Instruction::PushToAuthZone { proof } => {
+            vec![proof]
+        }
+        Instruction::PopFromAuthZone { new_proof } => {
+            vec![new_proof]
+        }
+        Instruction::CallFunction {
+            package_address,
+            blueprint_name,
+            function_name,
+            args,
+        } => vec![package_address, blueprint_name, function_name]
+            .into_iter()
+            .chain(args.iter_mut())
+            .collect(),
+        Instruction::CallMethod {
+            address,
+            method_name,
+            args,
+        }
+        | Instruction::CallRoyaltyMethod {
+            address,
+            method_name,
+            args,
+        }
+        | Instruction::CallMetadataMethod {
+            address,
+            method_name,
+            args,
+        }
+        | Instruction::CallRoleAssignmentMethod {
+            address,
+            method_name,
+            args,
+        }
+        | Instruction::CallDirectVaultMethod {
+            address,
+            method_name,
+            args,
+        } => vec![address, method_name]
+            .into_iter()
+            .chain(args.iter_mut())
+            .collect(),
+        Instruction::AllocateGlobalAddress {
+            package_address,
+            blueprint_name,
+            address_reservation,
+            named_address,
+        } => {
+            vec![
+                package_address,
+                blueprint_name,
+                address_reservation,
+                named_address,
+            ]
+        }
+        Instruction::YieldToParent { args }
+        | Instruction::PublishPackage { args }
+        | Instruction::PublishPackageAdvanced { args }
+        | Instruction::CreateFungibleResource { args }
+        | Instruction::CreateFungibleResourceWithInitialSupply { args }
+        | Instruction::CreateNonFungibleResource { args }
+        | Instruction::CreateNonFungibleResourceWithInitialSupply { args }
+        | Instruction::CreateAccessController { args }
+        | Instruction::CreateIdentity { args }
+        | Instruction::CreateIdentityAdvanced { args }
+        | Instruction::CreateAccount { args }
+        | Instruction::CreateAccountAdvanced { args }
+        | Instruction::CreateValidator { args } => args.iter_mut().collect(),
+        Instruction::CreateSimpleFungibleResource {
+            divisibility,
+            metadata,
+            initial_supply,
+        } => vec![divisibility, metadata, initial_supply],
+        Instruction::CreateSimpleNonFungibleResource {
+            id_type,
+            metadata,
+            initial_supply,
+        } => vec![id_type, metadata, initial_supply],
+        Instruction::YieldToChild { child, args } => {
+            vec![child].into_iter().chain(args.iter_mut()).collect()
+        }
+        Instruction::VerifyParent { access_rule } => {
+            vec![access_rule]
+        }
+        Instruction::RecallFromVault { vault_id, args }
+        | Instruction::FreezeVault { vault_id, args }
+        | Instruction::UnfreezeVault { vault_id, args }
+        | Instruction::RecallNonFungiblesFromVault { vault_id, args } => {
+            vec![vault_id].into_iter().chain(args.iter_mut()).collect()
+        }
+        Instruction::SetMetadata { address, args }
+        | Instruction::RemoveMetadata { address, args }
+        | Instruction::LockMetadata { address, args }
+        | Instruction::GetMetadata { address, args }
+        | Instruction::SetComponentRoyalty { address, args }
+        | Instruction::SetOwnerRole { address, args }
+        | Instruction::LockOwnerRole { address, args }
+        | Instruction::SetRole { address, args }
+        | Instruction::LockComponentRoyalty { address, args }
+        | Instruction::ClaimComponentRoyalties { address, args }
+        | Instruction::ClaimPackageRoyalties { address, args }
+        | Instruction::MintFungible { address, args }
+        | Instruction::MintNonFungible { address, args }
+        | Instruction::MintRuidNonFungible { address, args } => {
+            vec![address].into_iter().chain(args.iter_mut()).collect()
+        }
+    }
+}
+
 fn generate_pseudo_instructions(
     manifest: &mut impl BuildableManifest,
     instructions_iter: &mut Peekable<core::slice::Iter<ast::InstructionWithSpan>>,
@@ -490,6 +826,15 @@ where
     B: IsBlobProvider,
 {
     Ok(match &instruction.instruction {
+        // ==============
+        // Variable bindings
+        // ==============
+        ast::Instruction::Let { .. } => {
+            return Err(GeneratorError {
+                span: instruction.span,
+                error_kind: GeneratorErrorKind::LetInstructionMustBeResolved,
+            })
+        }
         // ==============
         // Pseudo-instructions
         // ==============
@@ -1031,6 +1376,84 @@ where
             args: generate_args(args, resolver, address_bech32_decoder, blobs)?,
         }
         .into(),
+        ast::Instruction::CreateSimpleFungibleResource {
+            divisibility,
+            metadata,
+            initial_supply,
+        } => CallFunction {
+            package_address: RESOURCE_PACKAGE.into(),
+            blueprint_name: FUNGIBLE_RESOURCE_MANAGER_BLUEPRINT.to_string(),
+            function_name: FUNGIBLE_RESOURCE_MANAGER_CREATE_WITH_INITIAL_SUPPLY_IDENT.to_string(),
+            args: to_manifest_value_and_unwrap!(
+                &FungibleResourceManagerCreateWithInitialSupplyManifestInput {
+                    owner_role: OwnerRole::None,
+                    track_total_supply: true,
+                    divisibility: generate_typed_value(
+                        divisibility,
+                        resolver,
+                        address_bech32_decoder,
+                        blobs,
+                    )?,
+                    initial_supply: generate_typed_value(
+                        initial_supply,
+                        resolver,
+                        address_bech32_decoder,
+                        blobs,
+                    )?,
+                    resource_roles: FungibleResourceRoles::default(),
+                    metadata: generate_simple_resource_metadata(
+                        metadata,
+                        resolver,
+                        address_bech32_decoder,
+                        blobs,
+                    )?,
+                    address_reservation: None,
+                }
+            ),
+        }
+        .into(),
+        ast::Instruction::CreateSimpleNonFungibleResource {
+            id_type,
+            metadata,
+            initial_supply,
+        } => {
+            let ids: Vec<NonFungibleLocalId> =
+                generate_typed_value(initial_supply, resolver, address_bech32_decoder, blobs)?;
+            CallFunction {
+                package_address: RESOURCE_PACKAGE.into(),
+                blueprint_name: NON_FUNGIBLE_RESOURCE_MANAGER_BLUEPRINT.to_string(),
+                function_name: NON_FUNGIBLE_RESOURCE_MANAGER_CREATE_WITH_INITIAL_SUPPLY_IDENT
+                    .to_string(),
+                args: to_manifest_value_and_unwrap!(
+                    &NonFungibleResourceManagerCreateWithInitialSupplyManifestInput {
+                        owner_role: OwnerRole::None,
+                        id_type: generate_typed_value(
+                            id_type,
+                            resolver,
+                            address_bech32_decoder,
+                            blobs,
+                        )?,
+                        track_total_supply: true,
+                        non_fungible_schema:
+                            NonFungibleDataSchema::new_local_without_self_package_replacement::<()>(
+                            ),
+                        entries: ids
+                            .into_iter()
+                            .map(|id| (id, (to_manifest_value_and_unwrap!(&()),)))
+                            .collect(),
+                        resource_roles: NonFungibleResourceRoles::default(),
+                        metadata: generate_simple_resource_metadata(
+                            metadata,
+                            resolver,
+                            address_bech32_decoder,
+                            blobs,
+                        )?,
+                        address_reservation: None,
+                    }
+                ),
+            }
+            .into()
+        }
         ast::Instruction::CreateAccessController { args } => CallFunction {
             package_address: ACCESS_CONTROLLER_PACKAGE.into(),
             blueprint_name: ACCESS_CONTROLLER_BLUEPRINT.to_string(),
@@ -1088,6 +1511,12 @@ where
             args: generate_args(args, resolver, address_bech32_decoder, blobs)?,
         }
         .into(),
+        ast::Instruction::GetMetadata { address, args } => CallMetadataMethod {
+            address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)?,
+            method_name: METADATA_GET_IDENT.to_string(),
+            args: generate_args(args, resolver, address_bech32_decoder, blobs)?,
+        }
+        .into(),
         ast::Instruction::SetComponentRoyalty { address, args } => CallRoyaltyMethod {
             address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)?,
             method_name: COMPONENT_ROYALTY_SET_ROYALTY_IDENT.to_string(),
@@ -1771,6 +2200,29 @@ fn generate_subintent_hash(
     }
 }
 
+/// Generates the metadata of a resource created by a `CREATE_SIMPLE_*_RESOURCE` instruction, from
+/// a `Map<String, Enum>` of metadata values. The metadata is left unlocked.
+fn generate_simple_resource_metadata<B>(
+    value: &ast::ValueWithSpan,
+    resolver: &mut NameResolver,
+    address_bech32_decoder: &AddressBech32Decoder,
+    blobs: &B,
+) -> Result<ModuleConfig<MetadataInit>, GeneratorError>
+where
+    B: IsBlobProvider,
+{
+    let entries: IndexMap<String, MetadataValue> =
+        generate_typed_value(value, resolver, address_bech32_decoder, blobs)?;
+    let mut init = MetadataInit::new();
+    for (key, value) in entries {
+        init.set(key, value);
+    }
+    Ok(ModuleConfig {
+        init,
+        roles: RoleAssignmentInit::new(),
+    })
+}
+
 pub fn generate_typed_value<T: ManifestDecode + ScryptoDescribe, B>(
     value_with_span: &ast::ValueWithSpan,
     resolver: &mut NameResolver,
@@ -2014,6 +2466,11 @@ where
                 span: value_with_span.span,
             });
         }
+        // Variables are substituted by `resolve_variables` before generation
+        ast::Value::Variable(name) => Err(GeneratorError {
+            error_kind: GeneratorErrorKind::UndefinedVariable(name.clone()),
+            span: value_with_span.span,
+        }),
     }
 }
 
@@ -2293,6 +2750,23 @@ pub fn generator_error_diagnostics(
             let title = format!("a NamedIntent cannot be used as a value kind");
             (title, "cannot be used as a value kind".to_string())
         }
+        GeneratorErrorKind::UndefinedVariable(string) => {
+            let title = format!("undefined variable '${}'", string);
+            (title, "undefined variable".to_string())
+        }
+        GeneratorErrorKind::VariableAlreadyDefined(string) => {
+            let title = format!("variable already defined '${}'", string);
+            (title, "variable already defined".to_string())
+        }
+        GeneratorErrorKind::VariableCannotBeUsedAsValueKind => {
+            let title = "a variable cannot be used as a value kind".to_string();
+            (title, "cannot be used as a value kind".to_string())
+        }
+        GeneratorErrorKind::LetInstructionMustBeResolved => {
+            let title =
+                "a LET instruction must be resolved before generating instructions".to_string();
+            (title, "unresolved LET instruction".to_string())
+        }
         GeneratorErrorKind::ArgumentCouldNotBeReadAsExpectedType {
             type_name,
             error_message,
@@ -2615,6 +3089,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_let_bindings_are_substituted() {
+        use crate::manifest::*;
+
+        let with_variables = r#"
+            LET faucet = Address("component_sim1cptxxxxxxxxxfaucetxxxxxxxxx000527798379xxxxxxxxxhkrefh");
+            LET fee = Decimal("500");
+            LET fees = Tuple($fee, $fee);
+            CALL_METHOD $faucet "lock_fee" $fee;
+            CALL_METHOD $faucet "free" $fees;
+        "#;
+        let without_variables = r#"
+            CALL_METHOD Address("component_sim1cptxxxxxxxxxfaucetxxxxxxxxx000527798379xxxxxxxxxhkrefh") "lock_fee" Decimal("500");
+            CALL_METHOD Address("component_sim1cptxxxxxxxxxfaucetxxxxxxxxx000527798379xxxxxxxxxhkrefh") "free" Tuple(Decimal("500"), Decimal("500"));
+        "#;
+
+        let compile = |manifest: &str| {
+            compile_manifest_v1(
+                manifest,
+                &NetworkDefinition::simulator(),
+                BlobProvider::default(),
+            )
+            .unwrap()
+        };
+        assert_eq!(compile(with_variables), compile(without_variables));
+    }
+
     #[test]
     fn test_publish_instruction() {
         generate_instruction_ok!(
@@ -2911,6 +3412,104 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_create_simple_fungible_resource_instruction() {
+        generate_instruction_ok!(
+            r#"CREATE_SIMPLE_FUNGIBLE_RESOURCE
+                18u8
+                Map<String, Enum>("name" => Enum<Metadata::String>("Token"))
+                Decimal("500")
+            ;"#,
+            CallFunction {
+                package_address: RESOURCE_PACKAGE.into(),
+                blueprint_name: FUNGIBLE_RESOURCE_MANAGER_BLUEPRINT.to_string(),
+                function_name: FUNGIBLE_RESOURCE_MANAGER_CREATE_WITH_INITIAL_SUPPLY_IDENT
+                    .to_string(),
+                args: to_manifest_value_and_unwrap!(
+                    &FungibleResourceManagerCreateWithInitialSupplyManifestInput {
+                        owner_role: OwnerRole::None,
+                        track_total_supply: true,
+                        divisibility: 18,
+                        initial_supply: "500".parse().unwrap(),
+                        resource_roles: FungibleResourceRoles::default(),
+                        metadata: metadata! {
+                            init {
+                                "name" => "Token".to_owned(), updatable;
+                            }
+                        },
+                        address_reservation: None,
+                    }
+                )
+            },
+        );
+    }
+
+    #[test]
+    fn test_create_simple_non_fungible_resource_instruction() {
+        generate_instruction_ok!(
+            r##"CREATE_SIMPLE_NON_FUNGIBLE_RESOURCE
+                Enum<NonFungibleIdType::Integer>()
+                Map<String, Enum>("name" => Enum<Metadata::String>("Token"))
+                Array<NonFungibleLocalId>(NonFungibleLocalId("#1#"), NonFungibleLocalId("#2#"))
+            ;"##,
+            CallFunction {
+                package_address: RESOURCE_PACKAGE.into(),
+                blueprint_name: NON_FUNGIBLE_RESOURCE_MANAGER_BLUEPRINT.to_string(),
+                function_name: NON_FUNGIBLE_RESOURCE_MANAGER_CREATE_WITH_INITIAL_SUPPLY_IDENT
+                    .to_string(),
+                args: to_manifest_value_and_unwrap!(
+                    &NonFungibleResourceManagerCreateWithInitialSupplyManifestInput {
+                        owner_role: OwnerRole::None,
+                        id_type: NonFungibleIdType::Integer,
+                        track_total_supply: true,
+                        non_fungible_schema:
+                            NonFungibleDataSchema::new_local_without_self_package_replacement::<()>(
+                            ),
+                        entries: indexmap!(
+                            NonFungibleLocalId::integer(1) => (to_manifest_value_and_unwrap!(&()),),
+                            NonFungibleLocalId::integer(2) => (to_manifest_value_and_unwrap!(&()),),
+                        ),
+                        resource_roles: NonFungibleResourceRoles::default(),
+                        metadata: metadata! {
+                            init {
+                                "name" => "Token".to_owned(), updatable;
+                            }
+                        },
+                        address_reservation: None,
+                    }
+                )
+            },
+        );
+    }
+
+    #[test]
+    fn test_create_simple_fungible_resource_with_invalid_divisibility_fails() {
+        let instruction = Parser::new(
+            tokenize(r#"CREATE_SIMPLE_FUNGIBLE_RESOURCE "18" Map<String, Enum>() Decimal("1");"#)
+                .unwrap(),
+            PARSER_MAX_DEPTH,
+        )
+        .unwrap()
+        .parse_instruction()
+        .unwrap();
+
+        let result = generate_instruction(
+            &instruction,
+            &mut BasicManifestValidator::new(),
+            &mut NameResolver::new(),
+            &AddressBech32Decoder::new(&NetworkDefinition::simulator()),
+            &MockBlobProvider::default(),
+        );
+
+        assert!(matches!(
+            result,
+            Err(GeneratorError {
+                error_kind: GeneratorErrorKind::ArgumentCouldNotBeReadAsExpectedType { .. },
+                ..
+            })
+        ));
+    }
+
     #[test]
     fn test_mint_non_fungible_instruction() {
         let address_bech32_decoder = AddressBech32Decoder::new(&NetworkDefinition::simulator());
@@ -8,6 +8,7 @@ pub enum ExpectedChar {
     Exact(char),
     OneOf(Vec<char>),
     HexDigit,
+    Letter,
     DigitLetterQuotePunctuation,
 }
 
@@ -68,6 +69,47 @@ pub fn tokenize(s: &str) -> Result<Vec<TokenWithSpan>, LexerError> {
     Ok(tokens)
 }
 
+/// Tokenizes the text, recovering from errors by resuming after the invalid characters, so that
+/// all lexer errors of the text are reported.
+pub fn tokenize_with_recovery(s: &str) -> (Vec<TokenWithSpan>, Vec<LexerError>) {
+    recover_tokens(Lexer::new(s))
+}
+
+/// As [`tokenize_with_recovery`], but starts at the given position of the text, which must be
+/// outside of any token, e.g. to tokenize only the edited part of a text.
+pub fn tokenize_with_recovery_from(
+    s: &str,
+    start: Position,
+) -> (Vec<TokenWithSpan>, Vec<LexerError>) {
+    let mut lexer = Lexer::new(s);
+    lexer.current = start;
+    recover_tokens(lexer)
+}
+
+fn recover_tokens(mut lexer: Lexer) -> (Vec<TokenWithSpan>, Vec<LexerError>) {
+    let mut tokens = Vec::new();
+    let mut errors = Vec::new();
+    loop {
+        let start = lexer.current;
+        match lexer.next_token() {
+            Ok(Some(token)) => tokens.push(token),
+            Ok(None) => break,
+            Err(error) => {
+                let is_eof = error.error_kind == LexerErrorKind::UnexpectedEof;
+                errors.push(error);
+                if is_eof {
+                    break;
+                }
+                // Always make progress, even if the invalid character wasn't consumed.
+                if lexer.current == start && lexer.advance().is_err() {
+                    break;
+                }
+            }
+        }
+    }
+    (tokens, errors)
+}
+
 impl Lexer {
     pub fn new(text: &str) -> Self {
         Self {
@@ -104,10 +146,6 @@ impl Lexer {
         Ok(c)
     }
 
-    fn advance_expected(&mut self, expected: char) -> Result<char, LexerError> {
-        self.advance_matching(|c| c == expected, ExpectedChar::Exact(expected))
-    }
-
     fn advance_matching(
         &mut self,
         matcher: impl Fn(char) -> bool,
@@ -161,6 +199,7 @@ impl Lexer {
             '-' | '0'..='9' => self.tokenize_number(),
             '"' => self.tokenize_string(),
             'a'..='z' | 'A'..='Z' => self.tokenize_identifier(),
+            '$' => self.tokenize_variable(),
             '{' | '}' | '(' | ')' | '<' | '>' | ',' | ';' | '&' | '=' => {
                 self.tokenize_punctuation()
             }
@@ -376,6 +415,23 @@ impl Lexer {
         Ok(self.new_token(token, start, self.current))
     }
 
+    fn tokenize_variable(&mut self) -> Result<TokenWithSpan, LexerError> {
+        let start = self.current;
+        assert_eq!(self.advance()?, '$');
+
+        let mut name =
+            String::from(self.advance_matching(|c| c.is_ascii_alphabetic(), ExpectedChar::Letter)?);
+        while !self.is_eof() {
+            let next_char = self.peek()?;
+            if !(next_char.is_ascii_alphanumeric() || next_char == '_') {
+                break;
+            }
+            name.push(self.advance()?);
+        }
+
+        Ok(self.new_token(Token::Variable(name), start, self.current))
+    }
+
     fn tokenize_punctuation(&mut self) -> Result<TokenWithSpan, LexerError> {
         let token_start = self.current;
 
@@ -387,8 +443,12 @@ impl Lexer {
             ',' => Token::Comma,
             ';' => Token::Semicolon,
             '=' => {
-                self.advance_expected('>')?;
-                Token::FatArrow
+                if !self.is_eof() && self.peek()? == '>' {
+                    self.advance()?;
+                    Token::FatArrow
+                } else {
+                    Token::Equals
+                }
             }
             c => {
                 return Err(LexerError::unexpected_char(
@@ -433,6 +493,7 @@ pub fn lexer_error_diagnostics(
                     }
                 }
                 ExpectedChar::HexDigit => "hex digit".to_string(),
+                ExpectedChar::Letter => "letter".to_string(),
                 ExpectedChar::DigitLetterQuotePunctuation => "digit, letter, quotation mark or one of punctuation characters '(', ')', '<', '>', ',', ';', '='".to_string(),
             };
             (
@@ -677,14 +738,31 @@ mod tests {
         );
 
         lex_error!(
-            "x=7",
+            "$1u8",
             LexerError {
-                error_kind: LexerErrorKind::UnexpectedChar('7', ExpectedChar::Exact('>')),
-                span: span!(start = (2, 0, 2), end = (3, 0, 3))
+                error_kind: LexerErrorKind::UnexpectedChar('1', ExpectedChar::Letter),
+                span: span!(start = (1, 0, 1), end = (2, 0, 2))
             }
         );
     }
 
+    #[test]
+    fn test_variable() {
+        lex_ok!(
+            "LET xrd = $xrd_address; $a1=>$b",
+            vec![
+                Token::Ident("LET".to_string()),
+                Token::Ident("xrd".to_string()),
+                Token::Equals,
+                Token::Variable("xrd_address".to_string()),
+                Token::Semicolon,
+                Token::Variable("a1".to_string()),
+                Token::FatArrow,
+                Token::Variable("b".to_string()),
+            ]
+        );
+    }
+
     #[test]
     fn test_unicode() {
         lex_ok!(r#""\u2764""#, vec![Token::StringLiteral("❤".to_string())]);
@@ -0,0 +1,378 @@
+//! A linting pass over parsed manifest instructions, which reports common mistakes that still
+//! compile (e.g. a bucket which is never used).
+//!
+//! Each [`LintDiagnostic`] carries the [`Span`] of the offending value or instruction, so it can be
+//! shown in an editor.
+
+use crate::internal_prelude::*;
+use crate::manifest::ast::{Instruction, InstructionWithSpan, Value, ValueWithSpan};
+use crate::manifest::generator::{instruction_values_mut, resolve_variables, GeneratorError};
+use crate::manifest::token::Span;
+use radix_engine_interface::blueprints::account::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LintRule {
+    /// Resources are taken from the worktop before anything could have been put on it.
+    TakeFromEmptyWorktop,
+    /// A named proof is never dropped, pushed to the auth zone, or passed to an invocation.
+    ProofNeverDropped,
+    /// Fees are locked after other instructions, which run on the limited fee loan.
+    LateLockFee,
+    /// A named bucket is never used.
+    UnusedBucket,
+}
+
+impl LintRule {
+    pub const ALL: [LintRule; 4] = [
+        LintRule::TakeFromEmptyWorktop,
+        LintRule::ProofNeverDropped,
+        LintRule::LateLockFee,
+        LintRule::UnusedBucket,
+    ];
+}
+
+/// The rules to check. All rules are enabled by default.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintConfig {
+    disabled_rules: IndexSet<LintRule>,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self {
+            disabled_rules: index_set_new(),
+        }
+    }
+}
+
+impl LintConfig {
+    pub fn without_rule(mut self, rule: LintRule) -> Self {
+        self.disabled_rules.insert(rule);
+        self
+    }
+
+    pub fn is_enabled(&self, rule: LintRule) -> bool {
+        !self.disabled_rules.contains(&rule)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintDiagnostic {
+    pub rule: LintRule,
+    pub span: Span,
+    pub message: String,
+}
+
+/// The methods which lock a fee, from the account or faucet.
+const LOCK_FEE_METHODS: [&str; 3] = [
+    ACCOUNT_LOCK_FEE_IDENT,
+    ACCOUNT_LOCK_FEE_AND_WITHDRAW_IDENT,
+    ACCOUNT_LOCK_FEE_AND_WITHDRAW_NON_FUNGIBLES_IDENT,
+];
+
+/// Lexes and parses the manifest, and lints its instructions.
+pub fn lint_manifest_string(
+    s: &str,
+    config: &LintConfig,
+) -> Result<Vec<LintDiagnostic>, Box<CompileError>> {
+    let tokens = lexer::tokenize(s).map_err(|error| Box::new(CompileError::LexerError(error)))?;
+    let instructions = parser::Parser::new(tokens, parser::PARSER_MAX_DEPTH)
+        .and_then(|mut parser| parser.parse_manifest())
+        .map_err(|error| Box::new(CompileError::ParserError(error)))?;
+    lint_manifest(&instructions, config)
+        .map_err(|error| Box::new(CompileError::GeneratorError(error)))
+}
+
+/// Lints the parsed instructions of a manifest, returning the diagnostics ordered by rule and then
+/// by position.
+///
+/// Only buckets and proofs referenced by name are tracked.
+pub fn lint_manifest(
+    instructions: &[InstructionWithSpan],
+    config: &LintConfig,
+) -> Result<Vec<LintDiagnostic>, GeneratorError> {
+    let mut instructions = resolve_variables(instructions)?;
+
+    let mut diagnostics = Vec::new();
+    let mut worktop_may_have_resources = false;
+    let mut has_non_fee_instructions = false;
+    let mut unused_buckets = IndexMap::<String, Span>::new();
+    let mut undropped_proofs = IndexMap::<String, Span>::new();
+
+    for InstructionWithSpan { instruction, span } in instructions.iter_mut() {
+        let span = *span;
+        match instruction {
+            Instruction::UsePreallocatedAddress { .. } | Instruction::UseChild { .. } => continue,
+            Instruction::TakeFromWorktop { .. }
+            | Instruction::TakeNonFungiblesFromWorktop { .. }
+            | Instruction::TakeAllFromWorktop { .. }
+                if !worktop_may_have_resources =>
+            {
+                diagnostics.push(LintDiagnostic {
+                    rule: LintRule::TakeFromEmptyWorktop,
+                    span,
+                    message: "resources are taken from the worktop before any were put on it"
+                        .to_owned(),
+                });
+            }
+            _ => {}
+        }
+
+        match lock_fee_method_name(instruction) {
+            Some(method_name) if has_non_fee_instructions => {
+                diagnostics.push(LintDiagnostic {
+                    rule: LintRule::LateLockFee,
+                    span,
+                    message: format!(
+                        "`{method_name}` is called after other instructions, which run on the fee loan"
+                    ),
+                });
+            }
+            Some(_) => {}
+            None => has_non_fee_instructions = true,
+        }
+
+        worktop_may_have_resources |= may_put_resources_on_worktop(instruction);
+        track_named_values(instruction, &mut unused_buckets, &mut undropped_proofs);
+    }
+
+    for (name, span) in unused_buckets {
+        diagnostics.push(LintDiagnostic {
+            rule: LintRule::UnusedBucket,
+            span,
+            message: format!("bucket `{name}` is never used"),
+        });
+    }
+    for (name, span) in undropped_proofs {
+        diagnostics.push(LintDiagnostic {
+            rule: LintRule::ProofNeverDropped,
+            span,
+            message: format!("proof `{name}` is never dropped"),
+        });
+    }
+
+    diagnostics.retain(|diagnostic| config.is_enabled(diagnostic.rule));
+    diagnostics.sort_by_key(|diagnostic| {
+        (
+            LintRule::ALL
+                .iter()
+                .position(|rule| *rule == diagnostic.rule),
+            diagnostic.span.start.full_index,
+        )
+    });
+    Ok(diagnostics)
+}
+
+/// The name of the method, if the instruction locks a fee.
+fn lock_fee_method_name(instruction: &Instruction) -> Option<&str> {
+    match instruction {
+        Instruction::CallMethod {
+            method_name:
+                ValueWithSpan {
+                    value: Value::String(method_name),
+                    ..
+                },
+            ..
+        } if LOCK_FEE_METHODS.contains(&method_name.as_str()) => Some(method_name),
+        _ => None,
+    }
+}
+
+/// Whether the instruction may put resources on the worktop. Invocations (and yields) may return
+/// buckets, which are put on the worktop.
+fn may_put_resources_on_worktop(instruction: &Instruction) -> bool {
+    !matches!(
+        instruction,
+        Instruction::Let { .. }
+            | Instruction::UsePreallocatedAddress { .. }
+            | Instruction::UseChild { .. }
+            | Instruction::TakeFromWorktop { .. }
+            | Instruction::TakeNonFungiblesFromWorktop { .. }
+            | Instruction::TakeAllFromWorktop { .. }
+            | Instruction::BurnResource { .. }
+            | Instruction::AssertWorktopContains { .. }
+            | Instruction::AssertWorktopContainsNonFungibles { .. }
+            | Instruction::AssertWorktopContainsAny { .. }
+            | Instruction::AssertWorktopIsEmpty
+            | Instruction::AssertWorktopResourcesOnly { .. }
+            | Instruction::AssertWorktopResourcesInclude { .. }
+            | Instruction::AssertNextCallReturnsOnly { .. }
+            | Instruction::AssertNextCallReturnsInclude { .. }
+            | Instruction::AssertBucketContents { .. }
+            | Instruction::CreateProofFromBucketOfAmount { .. }
+            | Instruction::CreateProofFromBucketOfNonFungibles { .. }
+            | Instruction::CreateProofFromBucketOfAll { .. }
+            | Instruction::CreateProofFromAuthZoneOfAmount { .. }
+            | Instruction::CreateProofFromAuthZoneOfNonFungibles { .. }
+            | Instruction::CreateProofFromAuthZoneOfAll { .. }
+            | Instruction::CloneProof { .. }
+            | Instruction::DropProof { .. }
+            | Instruction::PushToAuthZone { .. }
+            | Instruction::PopFromAuthZone { .. }
+            | Instruction::DropAuthZoneSignatureProofs
+            | Instruction::DropAuthZoneRegularProofs
+            | Instruction::DropAuthZoneProofs
+            | Instruction::DropNamedProofs
+            | Instruction::DropAllProofs
+            | Instruction::AllocateGlobalAddress { .. }
+            | Instruction::VerifyParent { .. }
+    )
+}
+
+/// Records the bucket or proof declared by the instruction, and removes the ones it uses.
+fn track_named_values(
+    instruction: &mut Instruction,
+    unused_buckets: &mut IndexMap<String, Span>,
+    undropped_proofs: &mut IndexMap<String, Span>,
+) {
+    if matches!(
+        instruction,
+        Instruction::DropNamedProofs | Instruction::DropAllProofs
+    ) {
+        undropped_proofs.clear();
+    }
+    // Cloning a proof doesn't consume it, while any other use of a proof does.
+    let consumes_proofs = !matches!(instruction, Instruction::CloneProof { .. });
+
+    let declared_span = match instruction {
+        Instruction::TakeFromWorktop { new_bucket, .. }
+        | Instruction::TakeNonFungiblesFromWorktop { new_bucket, .. }
+        | Instruction::TakeAllFromWorktop { new_bucket, .. } => {
+            if let Some(name) = referenced_name(new_bucket) {
+                unused_buckets.insert(name, new_bucket.span);
+            }
+            Some(new_bucket.span)
+        }
+        Instruction::CreateProofFromBucketOfAmount { new_proof, .. }
+        | Instruction::CreateProofFromBucketOfNonFungibles { new_proof, .. }
+        | Instruction::CreateProofFromBucketOfAll { new_proof, .. }
+        | Instruction::CreateProofFromAuthZoneOfAmount { new_proof, .. }
+        | Instruction::CreateProofFromAuthZoneOfNonFungibles { new_proof, .. }
+        | Instruction::CreateProofFromAuthZoneOfAll { new_proof, .. }
+        | Instruction::CloneProof { new_proof, .. }
+        | Instruction::PopFromAuthZone { new_proof } => {
+            if let Some(name) = referenced_name(new_proof) {
+                undropped_proofs.insert(name, new_proof.span);
+            }
+            Some(new_proof.span)
+        }
+        _ => None,
+    };
+
+    for value in instruction_values_mut(instruction) {
+        if Some(value.span) == declared_span {
+            continue;
+        }
+        visit_references(value, &mut |value| match &value.value {
+            Value::Bucket(_) => {
+                if let Some(name) = referenced_name(value) {
+                    unused_buckets.swap_remove(&name);
+                }
+            }
+            Value::Proof(_) if consumes_proofs => {
+                if let Some(name) = referenced_name(value) {
+                    undropped_proofs.swap_remove(&name);
+                }
+            }
+            _ => {}
+        });
+    }
+}
+
+/// The name of a bucket or proof referenced by name, e.g. `Bucket("name")`.
+fn referenced_name(value: &ValueWithSpan) -> Option<String> {
+    match &value.value {
+        Value::Bucket(inner) | Value::Proof(inner) => match &inner.value {
+            Value::String(name) => Some(name.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Visits the buckets and proofs within the value.
+fn visit_references(value: &ValueWithSpan, visitor: &mut impl FnMut(&ValueWithSpan)) {
+    match &value.value {
+        Value::Bucket(_) | Value::Proof(_) => visitor(value),
+        Value::Enum(_, fields) | Value::Array(_, fields) | Value::Tuple(fields) => {
+            for field in fields {
+                visit_references(field, visitor);
+            }
+        }
+        Value::Map(_, _, entries) => {
+            for (key, value) in entries {
+                visit_references(key, visitor);
+                visit_references(value, visitor);
+            }
+        }
+        Value::Some(inner) | Value::Ok(inner) | Value::Err(inner) => {
+            visit_references(inner, visitor)
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lint(s: &str) -> Vec<(LintRule, usize)> {
+        lint_manifest_string(s, &LintConfig::default())
+            .unwrap()
+            .into_iter()
+            .map(|diagnostic| (diagnostic.rule, diagnostic.span.start.line_number()))
+            .collect()
+    }
+
+    #[test]
+    fn well_formed_manifest_has_no_diagnostics() {
+        let manifest = r#"
+            CALL_METHOD Address("account_sim1c956qr3kxlgypxwst89j9yf24tjc7zxd4up38x37zr6q4jxdx9rhma") "lock_fee" Decimal("10");
+            CALL_METHOD Address("account_sim1c956qr3kxlgypxwst89j9yf24tjc7zxd4up38x37zr6q4jxdx9rhma") "withdraw" Address("resource_sim1tknxxxxxxxxxradxrdxxxxxxxxx009923554798xxxxxxxxxakj8n3") Decimal("1");
+            TAKE_ALL_FROM_WORKTOP Address("resource_sim1tknxxxxxxxxxradxrdxxxxxxxxx009923554798xxxxxxxxxakj8n3") Bucket("bucket");
+            CREATE_PROOF_FROM_BUCKET_OF_ALL Bucket("bucket") Proof("proof");
+            CLONE_PROOF Proof("proof") Proof("proof2");
+            DROP_PROOF Proof("proof");
+            PUSH_TO_AUTH_ZONE Proof("proof2");
+            CALL_METHOD Address("account_sim1c956qr3kxlgypxwst89j9yf24tjc7zxd4up38x37zr6q4jxdx9rhma") "deposit" Bucket("bucket");
+        "#;
+
+        assert_eq!(lint(manifest), vec![]);
+    }
+
+    #[test]
+    fn common_mistakes_are_reported() {
+        let manifest = r#"
+            TAKE_ALL_FROM_WORKTOP Address("resource_sim1tknxxxxxxxxxradxrdxxxxxxxxx009923554798xxxxxxxxxakj8n3") Bucket("bucket1");
+            CALL_METHOD Address("account_sim1c956qr3kxlgypxwst89j9yf24tjc7zxd4up38x37zr6q4jxdx9rhma") "lock_fee" Decimal("10");
+            CREATE_PROOF_FROM_AUTH_ZONE_OF_ALL Address("resource_sim1tknxxxxxxxxxradxrdxxxxxxxxx009923554798xxxxxxxxxakj8n3") Proof("proof1");
+            CREATE_PROOF_FROM_AUTH_ZONE_OF_ALL Address("resource_sim1tknxxxxxxxxxradxrdxxxxxxxxx009923554798xxxxxxxxxakj8n3") Proof("proof2");
+            CALL_METHOD Address("account_sim1c956qr3kxlgypxwst89j9yf24tjc7zxd4up38x37zr6q4jxdx9rhma") "withdraw" Address("resource_sim1tknxxxxxxxxxradxrdxxxxxxxxx009923554798xxxxxxxxxakj8n3") Decimal("1");
+            TAKE_ALL_FROM_WORKTOP Address("resource_sim1tknxxxxxxxxxradxrdxxxxxxxxx009923554798xxxxxxxxxakj8n3") Bucket("bucket2");
+            CALL_METHOD Address("account_sim1c956qr3kxlgypxwst89j9yf24tjc7zxd4up38x37zr6q4jxdx9rhma") "deposit_batch" Array<Bucket>(Bucket("bucket2")) Proof("proof2");
+        "#;
+
+        assert_eq!(
+            lint(manifest),
+            vec![
+                (LintRule::TakeFromEmptyWorktop, 2),
+                (LintRule::ProofNeverDropped, 4),
+                (LintRule::LateLockFee, 3),
+                (LintRule::UnusedBucket, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn disabled_rules_are_not_reported() {
+        let manifest = r#"
+            TAKE_ALL_FROM_WORKTOP Address("resource_sim1tknxxxxxxxxxradxrdxxxxxxxxx009923554798xxxxxxxxxakj8n3") Bucket("bucket");
+        "#;
+        let config = LintConfig::default().without_rule(LintRule::UnusedBucket);
+
+        let diagnostics = lint_manifest_string(manifest, &config).unwrap();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, LintRule::TakeFromEmptyWorktop);
+    }
+}
@@ -1125,6 +1125,9 @@ impl CallMetadataMethod {
             METADATA_LOCK_IDENT => {
                 return DecompiledInstruction::new("LOCK_METADATA").add_argument(&self.address);
             }
+            METADATA_GET_IDENT => {
+                return DecompiledInstruction::new("GET_METADATA").add_argument(self.address);
+            }
             _ => {}
         }
         DecompiledInstruction::new(Self::IDENT)
@@ -0,0 +1,241 @@
+use crate::internal_prelude::*;
+use crate::manifest::static_resource_movements::*;
+use core::ops::ControlFlow;
+use radix_engine_interface::blueprints::account::*;
+use radix_engine_interface::blueprints::package::*;
+
+/// A static summary of the notable effects of a manifest, which doesn't require executing it.
+///
+/// The account withdraws and deposits are resolved by a [`StaticResourceMovementsVisitor`], which
+/// the summary visitor drives alongside its own checks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ManifestSummary {
+    /// The accounts which are called to lock a fee, in order of first appearance.
+    pub fee_payers: IndexSet<ManifestGlobalAddress>,
+    /// The accounts which are called to create a proof, in order of first appearance.
+    pub proof_creators: IndexSet<ManifestGlobalAddress>,
+    /// The number of proofs created by the manifest, from buckets, the auth zone or accounts.
+    ///
+    /// Popping a proof from the auth zone moves an existing proof, so isn't counted.
+    pub proofs_created: usize,
+    /// The indices of the instructions which publish a package.
+    pub package_publish_instructions: Vec<usize>,
+    /// The resources withdrawn from each account.
+    pub account_withdraws: IndexMap<ComponentAddress, NetWithdraws>,
+    /// The resources deposited into each account.
+    pub account_deposits: IndexMap<ComponentAddress, NetDeposits>,
+}
+
+/// A [`ManifestInterpretationVisitor`] which builds a [`ManifestSummary`].
+pub struct ManifestSummaryVisitor {
+    summary: ManifestSummary,
+    named_accounts: IndexSet<ManifestNamedAddress>,
+    resource_movements: StaticResourceMovementsVisitor,
+}
+
+impl ManifestSummaryVisitor {
+    pub fn new(initial_worktop_state_is_unknown: bool) -> Self {
+        Self {
+            summary: Default::default(),
+            named_accounts: Default::default(),
+            resource_movements: StaticResourceMovementsVisitor::new(
+                initial_worktop_state_is_unknown,
+            ),
+        }
+    }
+
+    pub fn output(self) -> ManifestSummary {
+        self.summary
+    }
+
+    fn is_account(&self, address: &ManifestGlobalAddress) -> bool {
+        match address {
+            ManifestGlobalAddress::Static(address) => address.as_node_id().is_global_account(),
+            ManifestGlobalAddress::Named(named_address) => {
+                self.named_accounts.contains(named_address)
+            }
+        }
+    }
+}
+
+impl ManifestInterpretationVisitor for ManifestSummaryVisitor {
+    type Output = StaticResourceMovementsError;
+
+    fn on_start_instruction(&mut self, details: OnStartInstruction) -> ControlFlow<Self::Output> {
+        self.resource_movements.on_start_instruction(details)
+    }
+
+    fn on_end_instruction(&mut self, details: OnEndInstruction) -> ControlFlow<Self::Output> {
+        if let ManifestInstructionEffect::Invocation { kind, .. } = details.effect {
+            match kind {
+                InvocationKind::Method {
+                    address,
+                    module_id: ModuleId::Main,
+                    method,
+                } if self.is_account(address) => match method {
+                    ACCOUNT_LOCK_FEE_IDENT
+                    | ACCOUNT_LOCK_CONTINGENT_FEE_IDENT
+                    | ACCOUNT_LOCK_FEE_AND_WITHDRAW_IDENT
+                    | ACCOUNT_LOCK_FEE_AND_WITHDRAW_NON_FUNGIBLES_IDENT => {
+                        self.summary.fee_payers.insert(*address);
+                    }
+                    ACCOUNT_CREATE_PROOF_OF_AMOUNT_IDENT
+                    | ACCOUNT_CREATE_PROOF_OF_NON_FUNGIBLES_IDENT => {
+                        self.summary.proof_creators.insert(*address);
+                        self.summary.proofs_created += 1;
+                    }
+                    _ => {}
+                },
+                InvocationKind::Function {
+                    address: ManifestPackageAddress::Static(package_address),
+                    blueprint: PACKAGE_BLUEPRINT,
+                    function: PACKAGE_PUBLISH_WASM_IDENT | PACKAGE_PUBLISH_WASM_ADVANCED_IDENT,
+                } if *package_address == PACKAGE_PACKAGE => {
+                    self.summary
+                        .package_publish_instructions
+                        .push(details.index);
+                }
+                _ => {}
+            }
+        }
+        self.resource_movements.on_end_instruction(details)
+    }
+
+    fn on_new_bucket(&mut self, details: OnNewBucket) -> ControlFlow<Self::Output> {
+        self.resource_movements.on_new_bucket(details)
+    }
+
+    fn on_consume_bucket(&mut self, details: OnConsumeBucket) -> ControlFlow<Self::Output> {
+        self.resource_movements.on_consume_bucket(details)
+    }
+
+    fn on_new_proof(&mut self, details: OnNewProof) -> ControlFlow<Self::Output> {
+        if details.state.source_amount != ProofSourceAmount::AuthZonePopLastAddedProof {
+            self.summary.proofs_created += 1;
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn on_new_named_address(&mut self, details: OnNewNamedAddress) -> ControlFlow<Self::Output> {
+        if *details.package_address == ACCOUNT_PACKAGE
+            && details.blueprint_name == ACCOUNT_BLUEPRINT
+        {
+            self.named_accounts.insert(details.named_address);
+        }
+        self.resource_movements.on_new_named_address(details)
+    }
+
+    fn on_pass_expression(&mut self, details: OnPassExpression) -> ControlFlow<Self::Output> {
+        self.resource_movements.on_pass_expression(details)
+    }
+
+    fn on_resource_assertion(&mut self, details: OnResourceAssertion) -> ControlFlow<Self::Output> {
+        self.resource_movements.on_resource_assertion(details)
+    }
+
+    fn on_finish(&mut self, details: OnFinish) -> ControlFlow<Self::Output> {
+        self.resource_movements.on_finish(details)?;
+        let resource_movements = core::mem::replace(
+            &mut self.resource_movements,
+            StaticResourceMovementsVisitor::new(false),
+        );
+        match resource_movements.output().resolve_account_changes() {
+            Ok((account_withdraws, account_deposits)) => {
+                self.summary.account_withdraws = account_withdraws;
+                self.summary.account_deposits = account_deposits;
+                ControlFlow::Continue(())
+            }
+            Err(error) => ControlFlow::Break(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::*;
+
+    fn summarize(manifest: &TransactionManifestV1) -> ManifestSummary {
+        let mut visitor = ManifestSummaryVisitor::new(false);
+        StaticManifestInterpreter::new(ValidationRuleset::all(), manifest)
+            .validate_and_apply_visitor(&mut visitor)
+            .unwrap();
+        visitor.output()
+    }
+
+    fn account(key: u64) -> ComponentAddress {
+        ComponentAddress::preallocated_account_from_public_key(
+            &Secp256k1PrivateKey::from_u64(key).unwrap().public_key(),
+        )
+    }
+
+    #[test]
+    fn summary_reports_fee_payers_proofs_and_publishes() {
+        let account = account(1);
+        let manifest = ManifestBuilder::new()
+            .lock_fee(account, 10)
+            .create_proof_from_account_of_amount(account, RORK, 1)
+            .pop_from_auth_zone("proof")
+            .drop_proof("proof")
+            .publish_package_advanced(
+                None,
+                vec![],
+                PackageDefinition::default(),
+                MetadataInit::default(),
+                OwnerRole::None,
+            )
+            .build();
+
+        let summary = summarize(&manifest);
+
+        let account = ManifestGlobalAddress::Static(account.into());
+        assert_eq!(summary.fee_payers, indexset!(account));
+        assert_eq!(summary.proof_creators, indexset!(account));
+        assert_eq!(summary.proofs_created, 1);
+        assert_eq!(summary.package_publish_instructions, vec![4]);
+    }
+
+    #[test]
+    fn summary_reports_account_withdraws_and_deposits() {
+        let sender = account(1);
+        let recipient = account(2);
+        let manifest = ManifestBuilder::new()
+            .lock_fee(sender, 10)
+            .withdraw_from_account(sender, RORK, 5)
+            .take_from_worktop(RORK, 5, "bucket")
+            .create_proof_from_bucket_of_all("bucket", "proof")
+            .drop_proof("proof")
+            .try_deposit_or_abort(recipient, None, "bucket")
+            .build();
+
+        let summary = summarize(&manifest);
+
+        assert_eq!(summary.proofs_created, 1);
+        assert_eq!(
+            summary.account_withdraws,
+            indexmap!(sender => NetWithdraws::empty().set_fungible(RORK, 5))
+        );
+        assert_eq!(
+            summary.account_deposits.keys().collect::<Vec<_>>(),
+            vec![&recipient]
+        );
+        assert_eq!(
+            summary.account_deposits[&recipient]
+                .specified_resources
+                .get(&RORK)
+                .and_then(|bounds| bounds.get_exact_amount()),
+            Some(dec!(5))
+        );
+    }
+
+    #[test]
+    fn summary_ignores_fee_locks_on_non_accounts() {
+        let manifest = ManifestBuilder::new()
+            .call_method(FAUCET, ACCOUNT_LOCK_FEE_IDENT, manifest_args!(dec!(10)))
+            .build();
+
+        let summary = summarize(&manifest);
+
+        assert!(summary.fee_payers.is_empty());
+    }
+}
@@ -10,10 +10,12 @@ pub mod dumper;
 pub mod e2e;
 pub mod generator;
 pub mod lexer;
+pub mod lint;
 mod manifest_enums;
 mod manifest_instruction_effects;
 mod manifest_instructions;
 mod manifest_naming;
+mod manifest_summary;
 mod manifest_traits;
 pub mod parser;
 mod static_manifest_interpreter;
@@ -24,10 +26,13 @@ pub use any_instruction::*;
 pub use any_manifest::*;
 pub use blob_provider::*;
 pub use compiler::*;
-pub use decompiler::{decompile, decompile_any, DecompileError};
+pub use decompiler::{
+    decompile, decompile_any, decompile_with_address_names, AddressNames, DecompileError,
+};
 pub use manifest_enums::*;
 pub use manifest_instruction_effects::*;
 pub use manifest_instructions::*;
 pub use manifest_naming::*;
+pub use manifest_summary::*;
 pub use manifest_traits::*;
 pub use static_manifest_interpreter::*;
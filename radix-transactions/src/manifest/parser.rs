@@ -15,12 +15,29 @@ pub const PARSER_MAX_DEPTH: usize = MANIFEST_SBOR_V1_MAX_DEPTH - 4;
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ParserErrorKind {
     UnexpectedEof,
-    UnexpectedToken { expected: TokenType, actual: Token },
-    InvalidArgument { expected: TokenType, actual: Token },
-    InvalidNumberOfValues { expected: usize, actual: usize },
-    InvalidNumberOfTypes { expected: usize, actual: usize },
-    UnknownEnumDiscriminator { actual: String },
-    MaxDepthExceeded { actual: usize, max: usize },
+    UnexpectedToken {
+        expected: TokenType,
+        actual: Box<Token>,
+    },
+    InvalidArgument {
+        expected: TokenType,
+        actual: Box<Token>,
+    },
+    InvalidNumberOfValues {
+        expected: usize,
+        actual: usize,
+    },
+    InvalidNumberOfTypes {
+        expected: usize,
+        actual: usize,
+    },
+    UnknownEnumDiscriminator {
+        actual: String,
+    },
+    MaxDepthExceeded {
+        actual: usize,
+        max: usize,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -34,7 +51,7 @@ impl ParserError {
         Self {
             error_kind: ParserErrorKind::UnexpectedToken {
                 expected,
-                actual: token.token,
+                actual: Box::new(token.token),
             },
             span: token.span,
         }
@@ -44,6 +61,7 @@ impl ParserError {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TokenType {
     Instruction,
+    Variable,
     Value,
     ValueKind,
     EnumDiscriminator,
@@ -54,6 +72,7 @@ impl fmt::Display for TokenType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             TokenType::Instruction => write!(f, "an instruction"),
+            TokenType::Variable => write!(f, "a variable name"),
             TokenType::Value => write!(f, "a manifest SBOR value"),
             TokenType::ValueKind => write!(f, "a manifest SBOR value kind"),
             TokenType::EnumDiscriminator => {
@@ -65,6 +84,11 @@ impl fmt::Display for TokenType {
 }
 
 pub enum InstructionIdent {
+    // ==============
+    // Variable bindings
+    // ==============
+    Let,
+
     // ==============
     // Pseudo-instructions
     // ==============
@@ -143,6 +167,8 @@ pub enum InstructionIdent {
     CreateFungibleResourceWithInitialSupply,
     CreateNonFungibleResource,
     CreateNonFungibleResourceWithInitialSupply,
+    CreateSimpleFungibleResource,
+    CreateSimpleNonFungibleResource,
     CreateAccessController,
     CreateIdentity,
     CreateIdentityAdvanced,
@@ -155,6 +181,7 @@ pub enum InstructionIdent {
     SetMetadata,
     RemoveMetadata,
     LockMetadata,
+    GetMetadata,
     SetComponentRoyalty,
     LockComponentRoyalty,
     ClaimComponentRoyalties,
@@ -175,6 +202,11 @@ pub enum InstructionIdent {
 impl InstructionIdent {
     pub fn from_ident(ident: &str) -> Option<Self> {
         let value = match ident {
+            // ==============
+            // Variable bindings
+            // ==============
+            "LET" => InstructionIdent::Let,
+
             // ==============
             // Pseudo-instructions
             // ==============
@@ -265,6 +297,10 @@ impl InstructionIdent {
             "CREATE_NON_FUNGIBLE_RESOURCE_WITH_INITIAL_SUPPLY" => {
                 InstructionIdent::CreateNonFungibleResourceWithInitialSupply
             }
+            "CREATE_SIMPLE_FUNGIBLE_RESOURCE" => InstructionIdent::CreateSimpleFungibleResource,
+            "CREATE_SIMPLE_NON_FUNGIBLE_RESOURCE" => {
+                InstructionIdent::CreateSimpleNonFungibleResource
+            }
             "CREATE_IDENTITY" => InstructionIdent::CreateIdentity,
             "CREATE_IDENTITY_ADVANCED" => InstructionIdent::CreateIdentityAdvanced,
             "CREATE_ACCOUNT" => InstructionIdent::CreateAccount,
@@ -277,6 +313,7 @@ impl InstructionIdent {
             "SET_METADATA" => InstructionIdent::SetMetadata,
             "REMOVE_METADATA" => InstructionIdent::RemoveMetadata,
             "LOCK_METADATA" => InstructionIdent::LockMetadata,
+            "GET_METADATA" => InstructionIdent::GetMetadata,
             "SET_COMPONENT_ROYALTY" => InstructionIdent::SetComponentRoyalty,
             "LOCK_COMPONENT_ROYALTY" => InstructionIdent::LockComponentRoyalty,
             "CLAIM_COMPONENT_ROYALTIES" => InstructionIdent::ClaimComponentRoyalties,
@@ -484,6 +521,59 @@ impl Parser {
         Ok(instructions)
     }
 
+    /// Parses the manifest, recovering from invalid instructions by resuming at the next
+    /// instruction, so that all errors are reported along with the valid instructions.
+    pub fn parse_manifest_with_recovery(&mut self) -> (Vec<InstructionWithSpan>, Vec<ParserError>) {
+        let (instructions, errors) = self.parse_manifest_with_skipped_spans();
+        (
+            instructions,
+            errors.into_iter().map(|(error, _)| error).collect(),
+        )
+    }
+
+    /// As [`Self::parse_manifest_with_recovery`], but returns each error with the span of the
+    /// tokens skipped to recover from it.
+    fn parse_manifest_with_skipped_spans(
+        &mut self,
+    ) -> (Vec<InstructionWithSpan>, Vec<(ParserError, Span)>) {
+        let mut instructions = Vec::new();
+        let mut errors = Vec::new();
+
+        while !self.is_eof() {
+            let start = self.current;
+            match self.parse_instruction() {
+                Ok(instruction) => instructions.push(instruction),
+                Err(error) => {
+                    self.stack_depth = 0;
+                    self.current = start + 1;
+                    self.skip_to_next_instruction();
+                    let skipped_span = Span {
+                        start: self.tokens[start].span.start,
+                        end: self.tokens[self.current - 1].span.end,
+                    };
+                    errors.push((error, skipped_span));
+                }
+            }
+        }
+
+        (instructions, errors)
+    }
+
+    /// Skips tokens until the start of the next instruction, which is either after a `;` or at an
+    /// instruction name, e.g. if the `;` of the invalid instruction is missing.
+    fn skip_to_next_instruction(&mut self) {
+        while let Some(token) = self.tokens.get(self.current) {
+            match &token.token {
+                Token::Semicolon => {
+                    self.current += 1;
+                    return;
+                }
+                Token::Ident(ident) if InstructionIdent::from_ident(ident).is_some() => return,
+                _ => self.current += 1,
+            }
+        }
+    }
+
     fn parse_instruction_arguments(&mut self) -> Result<Vec<ValueWithSpan>, ParserError> {
         let mut args = Vec::new();
         while self.peek()?.token != Token::Semicolon {
@@ -527,6 +617,18 @@ impl Parser {
         let instruction_start = token.span.start;
 
         let instruction = match instruction_ident {
+            //===============
+            // Variable bindings
+            //===============
+            InstructionIdent::Let => {
+                let variable = self.parse_variable_name()?;
+                self.advance_exact(Token::Equals)?;
+                Instruction::Let {
+                    variable,
+                    value: self.parse_value()?,
+                }
+            }
+
             //===============
             // Pseudo-instructions
             //===============
@@ -769,6 +871,20 @@ impl Parser {
                     args: self.parse_instruction_arguments()?,
                 }
             }
+            InstructionIdent::CreateSimpleFungibleResource => {
+                Instruction::CreateSimpleFungibleResource {
+                    divisibility: self.parse_value()?,
+                    metadata: self.parse_value()?,
+                    initial_supply: self.parse_value()?,
+                }
+            }
+            InstructionIdent::CreateSimpleNonFungibleResource => {
+                Instruction::CreateSimpleNonFungibleResource {
+                    id_type: self.parse_value()?,
+                    metadata: self.parse_value()?,
+                    initial_supply: self.parse_value()?,
+                }
+            }
             InstructionIdent::CreateAccessController => Instruction::CreateAccessController {
                 args: self.parse_instruction_arguments()?,
             },
@@ -800,6 +916,10 @@ impl Parser {
                 address: self.parse_value()?,
                 args: self.parse_instruction_arguments()?,
             },
+            InstructionIdent::GetMetadata => Instruction::GetMetadata {
+                address: self.parse_value()?,
+                args: self.parse_instruction_arguments()?,
+            },
             InstructionIdent::SetComponentRoyalty => Instruction::SetComponentRoyalty {
                 address: self.parse_value()?,
                 args: self.parse_instruction_arguments()?,
@@ -860,6 +980,17 @@ impl Parser {
         })
     }
 
+    fn parse_variable_name(&mut self) -> Result<ValueWithSpan, ParserError> {
+        let token = self.advance()?;
+        match &token.token {
+            Token::Ident(name) => Ok(ValueWithSpan {
+                value: Value::Variable(name.clone()),
+                span: token.span,
+            }),
+            _ => Err(ParserError::unexpected_token(token, TokenType::Variable)),
+        }
+    }
+
     pub fn parse_value(&mut self) -> Result<ValueWithSpan, ParserError> {
         self.track_stack_depth_increase()?;
         let token = self.advance()?;
@@ -879,6 +1010,7 @@ impl Parser {
             Token::I64Literal(value) => Value::I64(*value),
             Token::I128Literal(value) => Value::I128(*value),
             Token::StringLiteral(value) => Value::String(value.clone()),
+            Token::Variable(name) => Value::Variable(name.clone()),
             Token::Ident(ident_str) => {
                 let value_ident = ManifestValueIdent::from_ident(ident_str).ok_or(
                     ParserError::unexpected_token(token.clone(), TokenType::Value),
@@ -1108,6 +1240,108 @@ impl Parser {
     }
 }
 
+/// The instructions and errors of a manifest parsed with [`parse_with_diagnostics`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedManifestWithDiagnostics {
+    /// The valid instructions, in order. Invalid instructions are left out.
+    pub instructions: Vec<InstructionWithSpan>,
+    /// The lexer and parser errors, in order of their position.
+    pub errors: Vec<CompileError>,
+}
+
+/// Lexes and parses the manifest, collecting all errors instead of stopping at the first one, e.g.
+/// to show diagnostics for a manifest being edited.
+///
+/// The lexer resumes after invalid characters, and the parser at the instruction after an invalid
+/// one. An instruction with a lexer error is left out, along with its parser error, which is
+/// usually caused by the lexer error.
+pub fn parse_with_diagnostics(s: &str) -> ParsedManifestWithDiagnostics {
+    let (tokens, lexer_errors) = lexer::tokenize_with_recovery(s);
+    let (instructions, parser_errors) = match Parser::new(tokens, PARSER_MAX_DEPTH) {
+        Ok(mut parser) => parser.parse_manifest_with_skipped_spans(),
+        // There are no tokens, which is only worth reporting if the lexer didn't fail.
+        Err(error) if lexer_errors.is_empty() => (vec![], vec![(error.clone(), error.span)]),
+        Err(_) => (vec![], vec![]),
+    };
+
+    combine_diagnostics(instructions, parser_errors, lexer_errors)
+}
+
+/// Parses an edited manifest with diagnostics, reusing the result of parsing it before the edit.
+///
+/// The instructions and errors which end before `first_changed_index`, the index of the first
+/// changed Unicode char, are kept, and only the text after them is lexed and parsed again. The
+/// result is the same as [`parse_with_diagnostics`] of the edited text.
+pub fn reparse_with_diagnostics(
+    previous: &ParsedManifestWithDiagnostics,
+    s: &str,
+    first_changed_index: usize,
+) -> ParsedManifestWithDiagnostics {
+    let kept_count = previous
+        .instructions
+        .iter()
+        .take_while(|instruction| instruction.span.end.full_index <= first_changed_index)
+        .count();
+    let Some(last_kept) = kept_count
+        .checked_sub(1)
+        .map(|index| &previous.instructions[index])
+    else {
+        return parse_with_diagnostics(s);
+    };
+
+    // The last kept instruction ends with a `;`, so the remaining tokens start after it.
+    let resume_at = last_kept.span.end;
+    let (tokens, lexer_errors) = lexer::tokenize_with_recovery_from(s, resume_at);
+    let (instructions, parser_errors) = match Parser::new(tokens, PARSER_MAX_DEPTH) {
+        Ok(mut parser) => parser.parse_manifest_with_skipped_spans(),
+        // There are no tokens after the kept instructions.
+        Err(_) => (vec![], vec![]),
+    };
+    let reparsed = combine_diagnostics(instructions, parser_errors, lexer_errors);
+
+    ParsedManifestWithDiagnostics {
+        instructions: previous.instructions[..kept_count]
+            .iter()
+            .cloned()
+            .chain(reparsed.instructions)
+            .collect(),
+        errors: previous
+            .errors
+            .iter()
+            .filter(|error| error.span().end.full_index <= resume_at.full_index)
+            .cloned()
+            .chain(reparsed.errors)
+            .collect(),
+    }
+}
+
+fn combine_diagnostics(
+    mut instructions: Vec<InstructionWithSpan>,
+    parser_errors: Vec<(ParserError, Span)>,
+    lexer_errors: Vec<lexer::LexerError>,
+) -> ParsedManifestWithDiagnostics {
+    let has_lexer_error = |span: &Span| {
+        lexer_errors.iter().any(|lexer_error| {
+            span.start.full_index <= lexer_error.span.start.full_index
+                && lexer_error.span.start.full_index <= span.end.full_index
+        })
+    };
+    // An instruction missing an invalid token may still parse, but isn't valid.
+    instructions.retain(|instruction| !has_lexer_error(&instruction.span));
+    let mut errors = parser_errors
+        .into_iter()
+        .filter(|(_, skipped_span)| !has_lexer_error(skipped_span))
+        .map(|(error, _)| CompileError::ParserError(error))
+        .collect::<Vec<_>>();
+    errors.extend(lexer_errors.into_iter().map(CompileError::LexerError));
+    errors.sort_by_key(|error| error.span().start.full_index);
+
+    ParsedManifestWithDiagnostics {
+        instructions,
+        errors,
+    }
+}
+
 pub fn parser_error_diagnostics(
     s: &str,
     err: ParserError,
@@ -1193,6 +1427,122 @@ mod tests {
         }};
     }
 
+    #[test]
+    fn test_parse_with_diagnostics_collects_all_errors() {
+        let manifest = r#"DROP_ALL_PROOFS;
+TAKE_ALL_FROM_WORKTOP Address(;
+DROP_NAMED_PROOFS;
+DROP_PROOF Proof("a", "b");
+DROP_PROOF Proof(1u32 2zz);
+DROP_AUTH_ZONE_PROOFS"#;
+
+        let parsed = parse_with_diagnostics(manifest);
+
+        assert_eq!(
+            parsed
+                .instructions
+                .iter()
+                .map(|instruction| instruction.instruction.clone())
+                .collect::<Vec<_>>(),
+            vec![Instruction::DropAllProofs, Instruction::DropNamedProofs]
+        );
+        let errors = parsed
+            .errors
+            .iter()
+            .map(|error| {
+                let kind = match error {
+                    CompileError::LexerError(_) => "lexer",
+                    CompileError::ParserError(_) => "parser",
+                    CompileError::GeneratorError(_) => "generator",
+                };
+                (kind, error.span().start.line_number())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            vec![("parser", 2), ("parser", 4), ("lexer", 5), ("parser", 6)]
+        );
+    }
+
+    #[test]
+    fn test_parse_with_diagnostics_resumes_at_the_next_instruction() {
+        let manifest = r#"DROP_ALL_PROOFS;;
+DROP_NAMED_PROOFS;
+DROP_PROOF
+DROP_AUTH_ZONE_PROOFS;"#;
+
+        let parsed = parse_with_diagnostics(manifest);
+
+        assert_eq!(
+            parsed
+                .instructions
+                .iter()
+                .map(|instruction| instruction.instruction.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                Instruction::DropAllProofs,
+                Instruction::DropNamedProofs,
+                Instruction::DropAuthZoneProofs
+            ]
+        );
+        assert_eq!(
+            parsed
+                .errors
+                .iter()
+                .map(|error| error.span().start.line_number())
+                .collect::<Vec<_>>(),
+            vec![1, 4]
+        );
+    }
+
+    #[test]
+    fn test_reparse_with_diagnostics_matches_parse_with_diagnostics() {
+        let manifest = r#"DROP_ALL_PROOFS;
+DROP_PROOF Proof(1u32 2zz);
+DROP_NAMED_PROOFS;
+DROP_AUTH_ZONE_PROOFS;"#;
+        let previous = parse_with_diagnostics(manifest);
+        // The manifest is ASCII, so byte indices are char indices.
+        let last_instruction_index = manifest.find("DROP_AUTH_ZONE_PROOFS").unwrap();
+
+        for (edited, first_changed_index) in [
+            // Edits after some valid instructions and errors
+            (
+                manifest.replace("DROP_AUTH_ZONE_PROOFS;", "DROP_ALL_PROOFS"),
+                last_instruction_index,
+            ),
+            (
+                manifest.replace("DROP_NAMED_PROOFS;\n", "DROP_NAMED_PROOFS; $"),
+                last_instruction_index - 1,
+            ),
+            (format!("{manifest} DROP_ALL_PROOFS;"), manifest.len()),
+            // Edits of the first instruction
+            (manifest.replace("DROP_ALL_PROOFS;", "DROP_ALL_PROOF;"), 14),
+            (manifest.replace("DROP_ALL_PROOFS;", ""), 0),
+        ] {
+            assert_eq!(
+                reparse_with_diagnostics(&previous, &edited, first_changed_index),
+                parse_with_diagnostics(&edited),
+                "{edited}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_with_diagnostics_of_valid_manifest_matches_parse_manifest() {
+        let manifest = r#"DROP_ALL_PROOFS; DROP_PROOF Proof("a");"#;
+
+        let parsed = parse_with_diagnostics(manifest);
+
+        assert_eq!(parsed.errors, vec![]);
+        assert_eq!(
+            Ok(parsed.instructions),
+            Parser::new(tokenize(manifest).unwrap(), PARSER_MAX_DEPTH)
+                .unwrap()
+                .parse_manifest()
+        );
+    }
+
     #[test]
     fn test_literals() {
         parse_value_ok!(r#"true"#, Value::Bool(true));
@@ -1464,7 +1814,7 @@ mod tests {
             ParserError {
                 error_kind: ParserErrorKind::UnexpectedToken {
                     expected: TokenType::Exact(Token::GreaterThan),
-                    actual: Token::CloseParenthesis,
+                    actual: Box::new(Token::CloseParenthesis),
                 },
                 span: span!(start = (8, 0, 8), end = (9, 0, 9))
             }
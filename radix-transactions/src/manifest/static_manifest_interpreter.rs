@@ -138,6 +138,25 @@ impl<'a, M: ReadableManifest + ?Sized> StaticManifestInterpreter<'a, M> {
                     );
                 }
             }
+            if self.validation_ruleset.validate_blob_hashes
+                && radix_common::crypto::hash(content) != *hash
+            {
+                return ControlFlow::Break(
+                    ManifestValidationError::BlobHashMismatch(ManifestBlobRef(hash.0)).into(),
+                );
+            }
+            if let Some(max_blob_size) = self.validation_ruleset.max_blob_size {
+                if content.len() > max_blob_size {
+                    return ControlFlow::Break(
+                        ManifestValidationError::BlobTooLarge {
+                            blob_ref: ManifestBlobRef(hash.0),
+                            size: content.len(),
+                            limit: max_blob_size,
+                        }
+                        .into(),
+                    );
+                }
+            }
             visitor.on_register_blob(OnRegisterBlob {
                 blob_ref: ManifestBlobRef(hash.0),
                 content: content.as_ref(),
@@ -944,6 +963,13 @@ impl NextInstructionRequirement {
 pub struct ValidationRuleset {
     pub validate_no_duplicate_blobs: bool,
     pub validate_blob_refs: bool,
+    /// Checks that each blob is registered under the hash of its content. Blobs of prepared
+    /// transactions are always hashed from their content, but manifests can be built with
+    /// pre-hashed blobs.
+    pub validate_blob_hashes: bool,
+    /// The maximum size of a single blob, if any. Blobs of a transaction are otherwise only limited
+    /// by the size of its payload.
+    pub max_blob_size: Option<usize>,
     pub validate_bucket_proof_lock: bool,
     pub validate_no_dangling_nodes: bool,
     pub validate_dynamic_address_in_command_part: bool,
@@ -974,6 +1000,8 @@ impl ValidationRuleset {
         Self {
             validate_no_duplicate_blobs: true,
             validate_blob_refs: true,
+            validate_blob_hashes: true,
+            max_blob_size: None,
             validate_bucket_proof_lock: true,
             validate_no_dangling_nodes: true,
             validate_dynamic_address_in_command_part: true,
@@ -985,6 +1013,8 @@ impl ValidationRuleset {
         Self {
             validate_no_duplicate_blobs: false,
             validate_blob_refs: false,
+            validate_blob_hashes: false,
+            max_blob_size: None,
             validate_bucket_proof_lock: true,
             validate_no_dangling_nodes: false,
             validate_dynamic_address_in_command_part: false,
@@ -996,6 +1026,9 @@ impl ValidationRuleset {
         Self {
             validate_no_duplicate_blobs: true,
             validate_blob_refs: true,
+            // Transaction blobs are hashed during preparation
+            validate_blob_hashes: false,
+            max_blob_size: None,
             validate_bucket_proof_lock: true,
             validate_no_dangling_nodes: true,
             validate_dynamic_address_in_command_part: true,
@@ -1008,6 +1041,12 @@ impl ValidationRuleset {
 pub enum ManifestValidationError {
     DuplicateBlob(ManifestBlobRef),
     BlobNotRegistered(ManifestBlobRef),
+    BlobHashMismatch(ManifestBlobRef),
+    BlobTooLarge {
+        blob_ref: ManifestBlobRef,
+        size: usize,
+        limit: usize,
+    },
     BucketNotYetCreated(ManifestBucket),
     BucketAlreadyUsed(ManifestBucket, String),
     BucketConsumedWhilstLockedByProof(ManifestBucket, String),
@@ -37,6 +37,11 @@ impl Position {
     pub fn line_number(self) -> usize {
         self.line_idx + 1
     }
+
+    /// The 1-indexed column, in Unicode code points, as typically shown by editors.
+    pub fn column_number(self) -> usize {
+        self.line_char_index + 1
+    }
 }
 
 #[macro_export]
@@ -80,6 +85,7 @@ pub enum Token {
     StringLiteral(String),
 
     Ident(String),
+    Variable(String),
 
     /* Punctuations */
     OpenParenthesis,
@@ -89,6 +95,7 @@ pub enum Token {
     Comma,
     Semicolon,
     FatArrow,
+    Equals,
 }
 
 impl fmt::Display for Token {
@@ -107,6 +114,7 @@ impl fmt::Display for Token {
             Token::U128Literal(value) => write!(f, "'{:?}u128'", value),
             Token::StringLiteral(value) => write!(f, "{:?}", value),
             Token::Ident(value) => write!(f, "'{}'", value),
+            Token::Variable(value) => write!(f, "'${}'", value),
             Token::OpenParenthesis => write!(f, "'('"),
             Token::CloseParenthesis => write!(f, "')'",),
             Token::LessThan => write!(f, "'<'"),
@@ -114,6 +122,7 @@ impl fmt::Display for Token {
             Token::Comma => write!(f, "','"),
             Token::Semicolon => write!(f, "';'",),
             Token::FatArrow => write!(f, "'=>'"),
+            Token::Equals => write!(f, "'='"),
         }
     }
 }
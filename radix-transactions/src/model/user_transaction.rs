@@ -126,6 +126,31 @@ impl UserTransaction {
             UserTransaction::V2(t) => t.extract_manifests_with_names(names).into(),
         }
     }
+
+    /// The blobs of all intents of the transaction, by the hash of their content. A blob used by
+    /// multiple intents is only returned once.
+    pub fn blobs(&self) -> IndexMap<Hash, &[u8]> {
+        let intent_blobs = match self {
+            UserTransaction::V1(t) => vec![&t.signed_intent.intent.blobs],
+            UserTransaction::V2(t) => {
+                let transaction_intent = &t.signed_transaction_intent.transaction_intent;
+                core::iter::once(&transaction_intent.root_intent_core.blobs)
+                    .chain(
+                        transaction_intent
+                            .non_root_subintents
+                            .0
+                            .iter()
+                            .map(|subintent| &subintent.intent_core.blobs),
+                    )
+                    .collect()
+            }
+        };
+        intent_blobs
+            .into_iter()
+            .flat_map(|blobs| blobs.blobs.iter())
+            .map(|blob| (hash(&blob.0), blob.0.as_slice()))
+            .collect()
+    }
 }
 
 impl UserTransaction {
@@ -459,4 +484,34 @@ mod tests {
 
         assert_eq!(notarized, decoded_notarized);
     }
+
+    #[test]
+    fn blobs_of_all_intents_are_returned_once() {
+        let shared_blob = vec![1u8; 8];
+        let root_blob = vec![2u8; 8];
+        let subintent = PartialTransactionV2Builder::new_with_test_defaults()
+            .manifest_builder(|mut builder| {
+                builder.add_blob(shared_blob.clone());
+                builder.yield_to_parent(())
+            })
+            .build();
+        let transaction: UserTransaction = TransactionV2Builder::new_with_test_defaults()
+            .add_children([subintent])
+            .manifest_builder(|mut builder| {
+                builder.add_blob(shared_blob.clone());
+                builder.add_blob(root_blob.clone());
+                builder.yield_to_child("child_0", ())
+            })
+            .default_notarize()
+            .build_minimal_no_validate()
+            .into();
+
+        assert_eq!(
+            transaction.blobs(),
+            indexmap!(
+                hash(&shared_blob) => shared_blob.as_slice(),
+                hash(&root_blob) => root_blob.as_slice(),
+            )
+        );
+    }
 }
@@ -5,9 +5,17 @@ pub struct IntentHeaderV2 {
     pub network_id: u8,
 
     // Nullifier stuff
+    /// The intent can only be committed from this epoch onwards.
+    ///
+    /// Each subintent carries its own header, so the epoch and timestamp bounds below can be used
+    /// as a timelock on a subintent, independently of the transaction it ends up committed in.
+    /// The transaction is rejected if any of its intents is outside of its bounds.
     pub start_epoch_inclusive: Epoch,
+    /// The intent can only be committed before this epoch.
     pub end_epoch_exclusive: Epoch,
+    /// If set, the intent can only be committed at or after this proposer timestamp.
     pub min_proposer_timestamp_inclusive: Option<Instant>,
+    /// If set, the intent can only be committed before this proposer timestamp.
     pub max_proposer_timestamp_exclusive: Option<Instant>,
 
     /// This field is intended to enable a network user to generate an identical
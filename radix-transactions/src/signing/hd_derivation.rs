@@ -0,0 +1,332 @@
+//! Hierarchical deterministic derivation of private keys from a BIP39 mnemonic, so that tooling
+//! can derive the same accounts as a wallet.
+//!
+//! Secp256k1 keys are derived as per BIP32, and Ed25519 keys as per SLIP-10, which only supports
+//! hardened derivation.
+
+use crate::internal_prelude::*;
+use crate::signing::{KeyType, PrivateKey};
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+use zeroize::Zeroizing;
+
+/// The bit set on the index of a hardened derivation path component.
+pub const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+/// The SLIP-44 coin type of Radix.
+pub const RADIX_COIN_TYPE: u32 = 1022;
+
+const SECP256K1_SEED_KEY: &[u8] = b"Bitcoin seed";
+const ED25519_SEED_KEY: &[u8] = b"ed25519 seed";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HdDerivationError {
+    InvalidMnemonic(String),
+    InvalidDerivationPath(String),
+    /// Ed25519 keys can only be derived along hardened paths.
+    NonHardenedEd25519Derivation,
+    /// The derived key is not a valid private key. This happens with a negligible probability, in
+    /// which case the next index should be used instead.
+    InvalidDerivedKey,
+}
+
+/// A derivation path, such as `m/44'/1022'/1'/525'/1460'/0'`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DerivationPath(Vec<u32>);
+
+impl DerivationPath {
+    pub fn new(indices: Vec<u32>) -> Self {
+        Self(indices)
+    }
+
+    /// The path of the account at the given index, as derived by the Babylon wallet.
+    pub fn babylon_account(network_id: u8, index: u32) -> Self {
+        Self(vec![
+            44 | HARDENED_OFFSET,
+            RADIX_COIN_TYPE | HARDENED_OFFSET,
+            network_id as u32 | HARDENED_OFFSET,
+            525 | HARDENED_OFFSET,
+            1460 | HARDENED_OFFSET,
+            index | HARDENED_OFFSET,
+        ])
+    }
+
+    /// The path of the account at the given index, as derived by the Olympia wallet.
+    pub fn olympia_account(index: u32) -> Self {
+        Self(vec![
+            44 | HARDENED_OFFSET,
+            RADIX_COIN_TYPE | HARDENED_OFFSET,
+            HARDENED_OFFSET,
+            0,
+            index | HARDENED_OFFSET,
+        ])
+    }
+
+    pub fn indices(&self) -> &[u32] {
+        &self.0
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = HdDerivationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || HdDerivationError::InvalidDerivationPath(s.to_owned());
+
+        let mut components = s.split('/');
+        if components.next() != Some("m") {
+            return Err(invalid());
+        }
+        components
+            .map(|component| {
+                let (index, hardened) = match component
+                    .strip_suffix('\'')
+                    .or_else(|| component.strip_suffix('H'))
+                {
+                    Some(index) => (index, true),
+                    None => (component, false),
+                };
+                let index = index.parse::<u32>().map_err(|_| invalid())?;
+                if index >= HARDENED_OFFSET {
+                    return Err(invalid());
+                }
+                Ok(if hardened {
+                    index | HARDENED_OFFSET
+                } else {
+                    index
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "m")?;
+        for index in &self.0 {
+            if index & HARDENED_OFFSET != 0 {
+                write!(f, "/{}'", index & !HARDENED_OFFSET)?;
+            } else {
+                write!(f, "/{}", index)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Converts a BIP39 mnemonic and passphrase to a seed.
+pub fn mnemonic_to_seed(
+    mnemonic: &str,
+    passphrase: &str,
+) -> Result<Zeroizing<[u8; 64]>, HdDerivationError> {
+    let mnemonic = bip39::Mnemonic::parse(mnemonic)
+        .map_err(|error| HdDerivationError::InvalidMnemonic(error.to_string()))?;
+    Ok(Zeroizing::new(mnemonic.to_seed(passphrase)))
+}
+
+/// Derives the private key at the given path from a BIP39 mnemonic and passphrase.
+pub fn derive_private_key_from_mnemonic(
+    mnemonic: &str,
+    passphrase: &str,
+    key_type: KeyType,
+    path: &DerivationPath,
+) -> Result<PrivateKey, HdDerivationError> {
+    let seed = mnemonic_to_seed(mnemonic, passphrase)?;
+    derive_private_key(seed.as_ref(), key_type, path)
+}
+
+/// Derives the private key at the given path from a seed.
+pub fn derive_private_key(
+    seed: &[u8],
+    key_type: KeyType,
+    path: &DerivationPath,
+) -> Result<PrivateKey, HdDerivationError> {
+    match key_type {
+        KeyType::Secp256k1 => derive_secp256k1_private_key(seed, path).map(Into::into),
+        KeyType::Ed25519 => derive_ed25519_private_key(seed, path).map(Into::into),
+    }
+}
+
+fn derive_secp256k1_private_key(
+    seed: &[u8],
+    path: &DerivationPath,
+) -> Result<Secp256k1PrivateKey, HdDerivationError> {
+    let (key, mut chain_code) = split_hmac(SECP256K1_SEED_KEY, &[seed]);
+    let mut key = secp256k1::SecretKey::from_slice(key.as_ref())
+        .map_err(|_| HdDerivationError::InvalidDerivedKey)?;
+
+    for index in path.indices() {
+        let (tweak, child_chain_code) = if index & HARDENED_OFFSET != 0 {
+            split_hmac(
+                chain_code.as_ref(),
+                &[&[0u8], &key.secret_bytes(), &index.to_be_bytes()],
+            )
+        } else {
+            let public_key = Secp256k1PrivateKey::from_bytes(&key.secret_bytes())
+                .map_err(|_| HdDerivationError::InvalidDerivedKey)?
+                .public_key();
+            split_hmac(
+                chain_code.as_ref(),
+                &[public_key.as_ref(), &index.to_be_bytes()],
+            )
+        };
+        let tweak = secp256k1::Scalar::from_be_bytes(*tweak)
+            .map_err(|_| HdDerivationError::InvalidDerivedKey)?;
+        key = key
+            .add_tweak(&tweak)
+            .map_err(|_| HdDerivationError::InvalidDerivedKey)?;
+        chain_code = child_chain_code;
+    }
+
+    Secp256k1PrivateKey::from_bytes(&key.secret_bytes())
+        .map_err(|_| HdDerivationError::InvalidDerivedKey)
+}
+
+fn derive_ed25519_private_key(
+    seed: &[u8],
+    path: &DerivationPath,
+) -> Result<Ed25519PrivateKey, HdDerivationError> {
+    let (mut key, mut chain_code) = split_hmac(ED25519_SEED_KEY, &[seed]);
+
+    for index in path.indices() {
+        if index & HARDENED_OFFSET == 0 {
+            return Err(HdDerivationError::NonHardenedEd25519Derivation);
+        }
+        (key, chain_code) = split_hmac(
+            chain_code.as_ref(),
+            &[&[0u8], key.as_ref(), &index.to_be_bytes()],
+        );
+    }
+
+    Ed25519PrivateKey::from_bytes(key.as_ref()).map_err(|_| HdDerivationError::InvalidDerivedKey)
+}
+
+/// Computes the HMAC-SHA512 of the concatenated data, and splits it into its left and right
+/// halves (i.e. the key and chain code).
+fn split_hmac(key: &[u8], data: &[&[u8]]) -> (Zeroizing<[u8; 32]>, Zeroizing<[u8; 32]>) {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    for data in data {
+        mac.update(data);
+    }
+    let mut output = Zeroizing::new([0u8; 64]);
+    output.copy_from_slice(&mac.finalize().into_bytes());
+
+    let mut left = Zeroizing::new([0u8; 32]);
+    let mut right = Zeroizing::new([0u8; 32]);
+    left.copy_from_slice(&output[..32]);
+    right.copy_from_slice(&output[32..]);
+    (left, right)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_SEED: &str = "000102030405060708090a0b0c0d0e0f";
+
+    fn derive_hex(key_type: KeyType, path: &str) -> String {
+        let seed = hex::decode(TEST_SEED).unwrap();
+        let path = DerivationPath::from_str(path).unwrap();
+        hex::encode(
+            derive_private_key(&seed, key_type, &path)
+                .unwrap()
+                .to_bytes(),
+        )
+    }
+
+    #[test]
+    fn secp256k1_derivation_matches_bip32_test_vector() {
+        for (path, expected_private_key) in [
+            (
+                "m",
+                "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35",
+            ),
+            (
+                "m/0'",
+                "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea",
+            ),
+            (
+                "m/0'/1",
+                "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368",
+            ),
+            (
+                "m/0'/1/2'",
+                "cbce0d719ecf7431d88e6a89fa1483e02e35092af60c042b1df2ff59fa424dca",
+            ),
+            (
+                "m/0'/1/2'/2/1000000000",
+                "471b76e389e528d6de6d816857e012c5455051cad6660850e58372a6c3e6e7c8",
+            ),
+        ] {
+            assert_eq!(derive_hex(KeyType::Secp256k1, path), expected_private_key);
+        }
+    }
+
+    #[test]
+    fn ed25519_derivation_matches_slip10_test_vector() {
+        for (path, expected_private_key) in [
+            (
+                "m",
+                "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+            ),
+            (
+                "m/0'",
+                "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+            ),
+            (
+                "m/0'/1'",
+                "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2",
+            ),
+            (
+                "m/0H/1H/2H/2H/1000000000H",
+                "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793",
+            ),
+        ] {
+            assert_eq!(derive_hex(KeyType::Ed25519, path), expected_private_key);
+        }
+    }
+
+    #[test]
+    fn ed25519_derivation_rejects_non_hardened_path() {
+        let seed = hex::decode(TEST_SEED).unwrap();
+        let path = DerivationPath::from_str("m/0'/1").unwrap();
+
+        assert_eq!(
+            derive_private_key(&seed, KeyType::Ed25519, &path).err(),
+            Some(HdDerivationError::NonHardenedEd25519Derivation)
+        );
+    }
+
+    #[test]
+    fn mnemonic_is_converted_to_seed() {
+        let seed = mnemonic_to_seed(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            "TREZOR",
+        )
+        .unwrap();
+
+        assert_eq!(
+            hex::encode(seed.as_ref()),
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        );
+        assert!(matches!(
+            mnemonic_to_seed("abandon abandon abandon", ""),
+            Err(HdDerivationError::InvalidMnemonic(_))
+        ));
+    }
+
+    #[test]
+    fn derivation_path_round_trips_through_string() {
+        let path = DerivationPath::babylon_account(1, 5);
+
+        assert_eq!(path.to_string(), "m/44'/1022'/1'/525'/1460'/5'");
+        assert_eq!(DerivationPath::from_str(&path.to_string()).unwrap(), path);
+        assert_eq!(
+            DerivationPath::olympia_account(2).to_string(),
+            "m/44'/1022'/0'/0/2'"
+        );
+        assert!(DerivationPath::from_str("44'/1022'").is_err());
+        assert!(DerivationPath::from_str("m/2147483648").is_err());
+    }
+}
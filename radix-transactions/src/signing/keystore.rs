@@ -0,0 +1,423 @@
+//! Encrypted keystore files, so that tooling can sign with a private key without handling it as
+//! raw hex.
+//!
+//! A keystore is a JSON document of the following shape:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "key_type": "Secp256k1",
+//!   "public_key": "<hex-encoded public key>",
+//!   "kdf": { "algorithm": "scrypt", "log_n": 17, "r": 8, "p": 1, "salt": "<hex>" },
+//!   "cipher": { "algorithm": "aes-256-gcm", "nonce": "<hex>" },
+//!   "ciphertext": "<hex-encoded encrypted private key>"
+//! }
+//! ```
+//!
+//! The private key is encrypted with AES-256-GCM, under a key derived from the password with
+//! scrypt. The public key is stored in the clear, so that a keystore can be identified without its
+//! password, and is checked against the decrypted private key.
+
+use crate::internal_prelude::*;
+use crate::signing::{KeyType, PrivateKey};
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde_json::{json, Value};
+use std::path::Path;
+use zeroize::Zeroizing;
+
+/// The version of the keystore documents produced by [`encrypt_private_key`].
+pub const KEYSTORE_VERSION: u64 = 1;
+
+const KDF_ALGORITHM: &str = "scrypt";
+const CIPHER_ALGORITHM: &str = "aes-256-gcm";
+const SALT_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 12;
+const DERIVED_KEY_LENGTH: usize = 32;
+
+/// The most memory the key derivation of a keystore being decrypted may use, which is
+/// `128 * r * 2^log_n` bytes for scrypt. This is 8 times that of the default parameters.
+pub const MAX_KDF_MEMORY_BYTES: u64 = 1 << 30;
+/// The most parallel iterations the key derivation of a keystore being decrypted may use.
+pub const MAX_KDF_P: u32 = 16;
+
+impl KeyType {
+    fn name(&self) -> &'static str {
+        match self {
+            KeyType::Secp256k1 => "Secp256k1",
+            KeyType::Ed25519 => "Ed25519",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Secp256k1" => Some(KeyType::Secp256k1),
+            "Ed25519" => Some(KeyType::Ed25519),
+            _ => None,
+        }
+    }
+}
+
+/// The cost parameters of the scrypt key derivation, see [`scrypt::Params`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeystoreKdfParams {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+}
+
+impl KeystoreKdfParams {
+    /// Whether the parameters are within [`MAX_KDF_MEMORY_BYTES`] and [`MAX_KDF_P`], so that an
+    /// untrusted keystore can't make its decryption use an unbounded amount of memory or time.
+    pub fn is_within_limits(&self) -> bool {
+        let memory_bytes = 1u64
+            .checked_shl(self.log_n.into())
+            .and_then(|n| n.checked_mul(128))
+            .and_then(|bytes| bytes.checked_mul(self.r.into()));
+        memory_bytes.is_some_and(|bytes| bytes <= MAX_KDF_MEMORY_BYTES) && self.p <= MAX_KDF_P
+    }
+}
+
+impl Default for KeystoreKdfParams {
+    fn default() -> Self {
+        Self {
+            log_n: scrypt::Params::RECOMMENDED_LOG_N,
+            r: scrypt::Params::RECOMMENDED_R,
+            p: scrypt::Params::RECOMMENDED_P,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum KeystoreError {
+    IoError(std::io::Error),
+    /// The document is not valid JSON.
+    InvalidJson(String),
+    /// The document is missing the given field, or it has the wrong type.
+    MissingField(&'static str),
+    /// The given field is not valid hex.
+    InvalidHex(&'static str),
+    UnsupportedVersion(u64),
+    UnsupportedKeyType(String),
+    UnsupportedKdf(String),
+    UnsupportedCipher(String),
+    InvalidKdfParams,
+    /// The key derivation would use more than [`MAX_KDF_MEMORY_BYTES`] of memory, or more than
+    /// [`MAX_KDF_P`] parallel iterations.
+    KdfParamsTooExpensive,
+    /// The password is wrong, or the keystore was tampered with.
+    DecryptionFailed,
+    InvalidPrivateKey,
+    /// The decrypted private key doesn't match the public key of the keystore.
+    PublicKeyMismatch,
+}
+
+impl From<std::io::Error> for KeystoreError {
+    fn from(error: std::io::Error) -> Self {
+        Self::IoError(error)
+    }
+}
+
+impl PrivateKey {
+    /// Generates a private key from the randomness of the operating system.
+    pub fn generate(key_type: KeyType) -> Self {
+        loop {
+            let mut bytes = Zeroizing::new([0u8; 32]);
+            OsRng.fill_bytes(bytes.as_mut());
+            // Not every 32 bytes are a valid Secp256k1 key, though it's astronomically unlikely.
+            if let Some(private_key) = Self::from_bytes(key_type, bytes.as_ref()) {
+                return private_key;
+            }
+        }
+    }
+}
+
+/// Encrypts the private key with the password, to a keystore document of version
+/// [`KEYSTORE_VERSION`].
+pub fn encrypt_private_key(
+    private_key: &PrivateKey,
+    password: &str,
+    kdf_params: KeystoreKdfParams,
+) -> Result<String, KeystoreError> {
+    let mut salt = [0u8; SALT_LENGTH];
+    OsRng.fill_bytes(&mut salt);
+    let mut nonce = [0u8; NONCE_LENGTH];
+    OsRng.fill_bytes(&mut nonce);
+
+    let cipher = derive_cipher(password, &salt, kdf_params)?;
+    let plaintext = Zeroizing::new(private_key.to_bytes());
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), plaintext.as_slice())
+        .expect("Encrypting a private key should not fail");
+
+    let keystore = json!({
+        "version": KEYSTORE_VERSION,
+        "key_type": private_key.key_type().name(),
+        "public_key": hex::encode(public_key_bytes(&private_key.public_key())),
+        "kdf": {
+            "algorithm": KDF_ALGORITHM,
+            "log_n": kdf_params.log_n,
+            "r": kdf_params.r,
+            "p": kdf_params.p,
+            "salt": hex::encode(salt),
+        },
+        "cipher": {
+            "algorithm": CIPHER_ALGORITHM,
+            "nonce": hex::encode(nonce),
+        },
+        "ciphertext": hex::encode(ciphertext),
+    });
+    Ok(serde_json::to_string_pretty(&keystore).expect("JSON value should be serializable"))
+}
+
+/// Decrypts the private key of a keystore document with the password.
+pub fn decrypt_private_key(keystore: &str, password: &str) -> Result<PrivateKey, KeystoreError> {
+    let keystore: Value = serde_json::from_str(keystore)
+        .map_err(|error| KeystoreError::InvalidJson(error.to_string()))?;
+
+    let version = get_u64(&keystore, "version")?;
+    if version != KEYSTORE_VERSION {
+        return Err(KeystoreError::UnsupportedVersion(version));
+    }
+    let key_type = get_str(&keystore, "key_type")?;
+    let key_type = KeyType::from_name(key_type)
+        .ok_or_else(|| KeystoreError::UnsupportedKeyType(key_type.to_owned()))?;
+    let public_key = get_hex(&keystore, "public_key")?;
+
+    let kdf = keystore
+        .get("kdf")
+        .ok_or(KeystoreError::MissingField("kdf"))?;
+    let kdf_algorithm = get_str(kdf, "algorithm")?;
+    if kdf_algorithm != KDF_ALGORITHM {
+        return Err(KeystoreError::UnsupportedKdf(kdf_algorithm.to_owned()));
+    }
+    let kdf_params = KeystoreKdfParams {
+        log_n: get_u64(kdf, "log_n")?
+            .try_into()
+            .map_err(|_| KeystoreError::InvalidKdfParams)?,
+        r: get_u64(kdf, "r")?
+            .try_into()
+            .map_err(|_| KeystoreError::InvalidKdfParams)?,
+        p: get_u64(kdf, "p")?
+            .try_into()
+            .map_err(|_| KeystoreError::InvalidKdfParams)?,
+    };
+    if !kdf_params.is_within_limits() {
+        return Err(KeystoreError::KdfParamsTooExpensive);
+    }
+    let salt = get_hex(kdf, "salt")?;
+
+    let cipher_params = keystore
+        .get("cipher")
+        .ok_or(KeystoreError::MissingField("cipher"))?;
+    let cipher_algorithm = get_str(cipher_params, "algorithm")?;
+    if cipher_algorithm != CIPHER_ALGORITHM {
+        return Err(KeystoreError::UnsupportedCipher(
+            cipher_algorithm.to_owned(),
+        ));
+    }
+    let nonce = get_hex(cipher_params, "nonce")?;
+    if nonce.len() != NONCE_LENGTH {
+        return Err(KeystoreError::InvalidHex("nonce"));
+    }
+    let ciphertext = get_hex(&keystore, "ciphertext")?;
+
+    let cipher = derive_cipher(password, &salt, kdf_params)?;
+    let plaintext = Zeroizing::new(
+        cipher
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| KeystoreError::DecryptionFailed)?,
+    );
+    let private_key =
+        PrivateKey::from_bytes(key_type, &plaintext).ok_or(KeystoreError::InvalidPrivateKey)?;
+    if public_key_bytes(&private_key.public_key()) != public_key {
+        return Err(KeystoreError::PublicKeyMismatch);
+    }
+    Ok(private_key)
+}
+
+/// Encrypts the private key with the password, and writes the keystore to the given path.
+///
+/// On Unix, the file is made readable and writable by its owner only, including when it replaces
+/// an existing file.
+pub fn save_keystore(
+    path: impl AsRef<Path>,
+    private_key: &PrivateKey,
+    password: &str,
+    kdf_params: KeystoreKdfParams,
+) -> Result<(), KeystoreError> {
+    use std::io::Write;
+
+    let keystore = encrypt_private_key(private_key, password, kdf_params)?;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(path)?;
+    // The mode only applies to newly created files, so an existing file is restricted before the
+    // keystore is written to it.
+    #[cfg(unix)]
+    file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+    file.write_all(keystore.as_bytes())?;
+    Ok(())
+}
+
+/// Reads the keystore at the given path, and decrypts its private key with the password.
+pub fn load_keystore(path: impl AsRef<Path>, password: &str) -> Result<PrivateKey, KeystoreError> {
+    let keystore = std::fs::read_to_string(path)?;
+    decrypt_private_key(&keystore, password)
+}
+
+fn derive_cipher(
+    password: &str,
+    salt: &[u8],
+    kdf_params: KeystoreKdfParams,
+) -> Result<Aes256Gcm, KeystoreError> {
+    let params = scrypt::Params::new(
+        kdf_params.log_n,
+        kdf_params.r,
+        kdf_params.p,
+        DERIVED_KEY_LENGTH,
+    )
+    .map_err(|_| KeystoreError::InvalidKdfParams)?;
+    let mut derived_key = Zeroizing::new([0u8; DERIVED_KEY_LENGTH]);
+    scrypt::scrypt(password.as_bytes(), salt, &params, derived_key.as_mut())
+        .expect("Derived key length should be valid");
+    Ok(
+        Aes256Gcm::new_from_slice(derived_key.as_ref())
+            .expect("Derived key length should be valid"),
+    )
+}
+
+fn public_key_bytes(public_key: &PublicKey) -> Vec<u8> {
+    match public_key {
+        PublicKey::Secp256k1(public_key) => public_key.to_vec(),
+        PublicKey::Ed25519(public_key) => public_key.to_vec(),
+    }
+}
+
+fn get_u64(value: &Value, field: &'static str) -> Result<u64, KeystoreError> {
+    value
+        .get(field)
+        .and_then(Value::as_u64)
+        .ok_or(KeystoreError::MissingField(field))
+}
+
+fn get_str<'v>(value: &'v Value, field: &'static str) -> Result<&'v str, KeystoreError> {
+    value
+        .get(field)
+        .and_then(Value::as_str)
+        .ok_or(KeystoreError::MissingField(field))
+}
+
+fn get_hex(value: &Value, field: &'static str) -> Result<Vec<u8>, KeystoreError> {
+    hex::decode(get_str(value, field)?).map_err(|_| KeystoreError::InvalidHex(field))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Cheap parameters, to keep the tests fast.
+    const TEST_KDF_PARAMS: KeystoreKdfParams = KeystoreKdfParams {
+        log_n: 4,
+        r: 8,
+        p: 1,
+    };
+
+    #[test]
+    fn keystore_round_trips_private_key() {
+        for key_type in [KeyType::Secp256k1, KeyType::Ed25519] {
+            let private_key = PrivateKey::generate(key_type);
+
+            let keystore = encrypt_private_key(&private_key, "password", TEST_KDF_PARAMS).unwrap();
+            let decrypted_private_key = decrypt_private_key(&keystore, "password").unwrap();
+
+            assert_eq!(decrypted_private_key.key_type(), key_type);
+            assert_eq!(decrypted_private_key.to_bytes(), private_key.to_bytes());
+        }
+    }
+
+    #[test]
+    fn keystore_with_wrong_password_is_rejected() {
+        let private_key = PrivateKey::generate(KeyType::Ed25519);
+        let keystore = encrypt_private_key(&private_key, "password", TEST_KDF_PARAMS).unwrap();
+
+        assert!(matches!(
+            decrypt_private_key(&keystore, "wrong password"),
+            Err(KeystoreError::DecryptionFailed)
+        ));
+    }
+
+    #[test]
+    fn keystore_with_tampered_public_key_is_rejected() {
+        let private_key = PrivateKey::generate(KeyType::Secp256k1);
+        let keystore = encrypt_private_key(&private_key, "password", TEST_KDF_PARAMS).unwrap();
+        let mut keystore: Value = serde_json::from_str(&keystore).unwrap();
+        keystore["public_key"] = json!(hex::encode(public_key_bytes(
+            &PrivateKey::generate(KeyType::Secp256k1).public_key()
+        )));
+
+        assert!(matches!(
+            decrypt_private_key(&keystore.to_string(), "password"),
+            Err(KeystoreError::PublicKeyMismatch)
+        ));
+    }
+
+    #[test]
+    fn keystore_with_too_expensive_kdf_params_is_rejected() {
+        let private_key = PrivateKey::generate(KeyType::Secp256k1);
+        let keystore = encrypt_private_key(&private_key, "password", TEST_KDF_PARAMS).unwrap();
+        for (field, value) in [("log_n", 40), ("r", 1 << 20), ("p", 1 << 20)] {
+            let mut keystore: Value = serde_json::from_str(&keystore).unwrap();
+            keystore["kdf"][field] = json!(value);
+
+            assert!(matches!(
+                decrypt_private_key(&keystore.to_string(), "password"),
+                Err(KeystoreError::KdfParamsTooExpensive)
+            ));
+        }
+    }
+
+    #[test]
+    fn default_kdf_params_are_within_limits() {
+        assert!(KeystoreKdfParams::default().is_within_limits());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn keystore_overwriting_a_file_restricts_its_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("key.json");
+        std::fs::write(&path, "").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        save_keystore(
+            &path,
+            &PrivateKey::generate(KeyType::Ed25519),
+            "password",
+            TEST_KDF_PARAMS,
+        )
+        .unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn keystore_is_saved_and_loaded() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("key.json");
+        let private_key = PrivateKey::generate(KeyType::Ed25519);
+
+        save_keystore(&path, &private_key, "password", TEST_KDF_PARAMS).unwrap();
+        let loaded_private_key = load_keystore(&path, "password").unwrap();
+
+        assert_eq!(loaded_private_key.to_bytes(), private_key.to_bytes());
+    }
+}
@@ -1,3 +1,11 @@
+#[cfg(feature = "hd_derivation")]
+mod hd_derivation;
+#[cfg(feature = "keystore")]
+mod keystore;
 mod signer;
 
+#[cfg(feature = "hd_derivation")]
+pub use hd_derivation::*;
+#[cfg(feature = "keystore")]
+pub use keystore::*;
 pub use signer::*;
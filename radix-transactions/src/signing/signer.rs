@@ -1,6 +1,12 @@
 use crate::internal_prelude::*;
 use radix_common::prelude::IsHash;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyType {
+    Secp256k1,
+    Ed25519,
+}
+
 pub enum PrivateKey {
     Secp256k1(Secp256k1PrivateKey),
     Ed25519(Ed25519PrivateKey),
@@ -13,6 +19,27 @@ impl PrivateKey {
             PrivateKey::Ed25519(key) => key.public_key().into(),
         }
     }
+
+    pub fn key_type(&self) -> KeyType {
+        match self {
+            PrivateKey::Secp256k1(_) => KeyType::Secp256k1,
+            PrivateKey::Ed25519(_) => KeyType::Ed25519,
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            PrivateKey::Secp256k1(key) => key.to_bytes(),
+            PrivateKey::Ed25519(key) => key.to_bytes(),
+        }
+    }
+
+    pub fn from_bytes(key_type: KeyType, slice: &[u8]) -> Option<Self> {
+        match key_type {
+            KeyType::Secp256k1 => Secp256k1PrivateKey::from_bytes(slice).ok().map(Into::into),
+            KeyType::Ed25519 => Ed25519PrivateKey::from_bytes(slice).ok().map(Into::into),
+        }
+    }
 }
 
 impl From<Secp256k1PrivateKey> for PrivateKey {
@@ -94,3 +121,29 @@ impl Signer for PrivateKey {
         self.public_key()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ed25519_private_key_signs_through_signer_trait() {
+        let key = PrivateKey::from(Ed25519PrivateKey::from_u64(1).unwrap());
+        let message_hash = hash("message");
+
+        let SignatureWithPublicKeyV1::Ed25519 {
+            public_key,
+            signature,
+        } = key.sign_with_public_key(&message_hash)
+        else {
+            panic!("Expected an Ed25519 signature");
+        };
+
+        assert_eq!(PublicKey::Ed25519(public_key), key.public_key());
+        assert!(verify_ed25519(message_hash, &public_key, &signature));
+        assert_eq!(
+            key.sign_without_public_key(&message_hash),
+            SignatureV1::Ed25519(signature)
+        );
+    }
+}
@@ -336,6 +336,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_custom_config_limits() {
+        // e.g. a private network with a shorter epoch window and fewer signers
+        let validator = TransactionValidator::new_with_static_config(
+            TransactionValidationConfig {
+                max_epoch_range: 10,
+                max_signer_signatures_per_intent: 2,
+                ..TransactionValidationConfig::latest()
+            },
+            NetworkDefinition::simulator().id,
+        );
+
+        assert!(
+            create_transaction(Epoch::zero(), Epoch::of(10), 5, vec![1, 2], 3)
+                .prepare_and_validate(&validator)
+                .is_ok()
+        );
+        assert_matches!(
+            create_transaction(Epoch::zero(), Epoch::of(11), 5, vec![1, 2], 3)
+                .prepare_and_validate(&validator)
+                .expect_err("Should be an error"),
+            TransactionValidationError::IntentValidationError(
+                TransactionValidationErrorLocation::RootTransactionIntent(_),
+                IntentValidationError::HeaderValidationError(
+                    HeaderValidationError::InvalidEpochRange
+                ),
+            ),
+        );
+        assert_matches!(
+            create_transaction(Epoch::zero(), Epoch::of(10), 5, vec![1, 2, 3], 4)
+                .prepare_and_validate(&validator)
+                .expect_err("Should be an error"),
+            TransactionValidationError::SignatureValidationError(
+                TransactionValidationErrorLocation::RootTransactionIntent(_),
+                SignatureValidationError::TooManySignatures { total: 3, limit: 2 }
+            ),
+        );
+    }
+
     #[test]
     fn test_duplicate_signers() {
         assert_invalid_tx!(
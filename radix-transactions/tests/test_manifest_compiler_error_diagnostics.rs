@@ -294,6 +294,18 @@ fn test_manifest_generator_error_undefined_proof() {
     check_manifest!("manifest_generator_error_undefined_proof_1");
 }
 
+#[test]
+fn test_manifest_generator_error_undefined_variable() {
+    // UndefinedVariable
+    check_manifest!("manifest_generator_error_undefined_variable_1");
+}
+
+#[test]
+fn test_manifest_generator_error_variable_already_defined() {
+    // VariableAlreadyDefined
+    check_manifest!("manifest_generator_error_variable_already_defined_1");
+}
+
 #[test]
 fn test_manifest_generator_error_bucket_not_found() {
     // IdValidationError(BucketNotFound)
@@ -0,0 +1,520 @@
+//! Export of a package ABI to a stable, versioned JSON document, for off-chain tooling in other
+//! languages.
+//!
+//! The document has the following shape:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "blueprints": [
+//!     {
+//!       "name": "Hello",
+//!       "functions": [
+//!         {
+//!           "name": "free_token",
+//!           "export": "Hello_free_token",
+//!           "receiver": { "kind": "SelfRefMut", "direct_access": false },
+//!           "inputs": [],
+//!           "output": "Bucket",
+//!           "output_type_id": { "well_known": 161, "name": "Bucket" }
+//!         }
+//!       ],
+//!       "events": [
+//!         { "name": "HelloEvent", "type": "HelloEvent", "type_id": { "index": 0 } }
+//!       ],
+//!       "types": [
+//!         {
+//!           "index": 0,
+//!           "name": "HelloEvent",
+//!           "kind": "Tuple",
+//!           "fields": [
+//!             { "name": "amount", "type_id": { "well_known": 192, "name": "Decimal" } }
+//!           ]
+//!         }
+//!       ]
+//!     }
+//!   ],
+//!   "package_definition": "<hex-encoded SBOR of the PackageDefinition>"
+//! }
+//! ```
+//!
+//! The `receiver` of a function is `null`. Types are rendered in Rust syntax, from the names in
+//! the blueprint schema. Each rendered type comes with a `type_id`, which is either the `index` of
+//! a type in the blueprint's `types`, a `well_known` SBOR type id (with its rendered name), or the
+//! index of a `generic` parameter of the blueprint. The document also carries the full package
+//! definition, from which [`package_definition_from_abi_json`] parses it back.
+
+use radix_blueprint_schema_init::{FunctionSchemaInit, Receiver, RefTypes, TypeRef};
+use radix_common::prelude::*;
+use radix_engine_interface::blueprints::package::{BlueprintDefinitionInit, PackageDefinition};
+use serde_json::{json, Value};
+
+/// The version of the ABI JSON document produced by [`package_definition_to_abi_json`].
+pub const PACKAGE_ABI_JSON_VERSION: u64 = 1;
+
+#[derive(Debug)]
+pub enum PackageAbiJsonError {
+    /// The document is not valid JSON.
+    InvalidJson(String),
+    /// The document is missing the given field, or it has the wrong type.
+    MissingField(&'static str),
+    /// The document was produced by an unsupported version of the exporter.
+    UnsupportedVersion(u64),
+    /// The package definition is not valid hex.
+    InvalidPackageDefinitionHex,
+    /// The package definition could not be decoded.
+    PackageDefinitionDecodeError(DecodeError),
+}
+
+/// Serializes the ABI of a package to a JSON document of version [`PACKAGE_ABI_JSON_VERSION`].
+pub fn package_definition_to_abi_json(definition: &PackageDefinition) -> Value {
+    let blueprints = definition
+        .blueprints
+        .iter()
+        .map(|(blueprint_name, blueprint_definition)| {
+            blueprint_to_abi_json(blueprint_name, blueprint_definition)
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "version": PACKAGE_ABI_JSON_VERSION,
+        "blueprints": blueprints,
+        "package_definition": hex::encode(
+            scrypto_encode(definition).expect("Package definition should be encodable")
+        ),
+    })
+}
+
+/// Parses the package definition back from an ABI JSON document.
+pub fn package_definition_from_abi_json(
+    abi_json: &str,
+) -> Result<PackageDefinition, PackageAbiJsonError> {
+    let value: Value = serde_json::from_str(abi_json)
+        .map_err(|error| PackageAbiJsonError::InvalidJson(error.to_string()))?;
+
+    let version = value
+        .get("version")
+        .and_then(Value::as_u64)
+        .ok_or(PackageAbiJsonError::MissingField("version"))?;
+    if version != PACKAGE_ABI_JSON_VERSION {
+        return Err(PackageAbiJsonError::UnsupportedVersion(version));
+    }
+
+    let package_definition = value
+        .get("package_definition")
+        .and_then(Value::as_str)
+        .ok_or(PackageAbiJsonError::MissingField("package_definition"))?;
+    let package_definition = hex::decode(package_definition)
+        .map_err(|_| PackageAbiJsonError::InvalidPackageDefinitionHex)?;
+    scrypto_decode(&package_definition).map_err(PackageAbiJsonError::PackageDefinitionDecodeError)
+}
+
+fn blueprint_to_abi_json(
+    blueprint_name: &str,
+    blueprint_definition: &BlueprintDefinitionInit,
+) -> Value {
+    let blueprint_schema = &blueprint_definition.schema;
+    let schema = blueprint_schema.schema.v1();
+
+    let functions = blueprint_schema
+        .functions
+        .functions
+        .iter()
+        .map(|(function_name, function_schema)| {
+            function_to_abi_json(schema, function_name, function_schema)
+        })
+        .collect::<Vec<_>>();
+    let events = blueprint_schema
+        .events
+        .event_schema
+        .iter()
+        .map(|(event_name, type_ref)| {
+            json!({
+                "name": event_name,
+                "type": render_type_ref(schema, type_ref),
+                "type_id": type_ref_to_json(schema, type_ref),
+            })
+        })
+        .collect::<Vec<_>>();
+    let types = schema
+        .type_kinds
+        .iter()
+        .zip(schema.type_metadata.iter())
+        .enumerate()
+        .map(|(index, (type_kind, type_metadata))| {
+            type_to_abi_json(schema, index, type_kind, type_metadata)
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "name": blueprint_name,
+        "functions": functions,
+        "events": events,
+        "types": types,
+    })
+}
+
+fn function_to_abi_json(
+    schema: &Schema<ScryptoCustomSchema>,
+    function_name: &str,
+    function_schema: &FunctionSchemaInit,
+) -> Value {
+    let receiver = function_schema.receiver.as_ref().map(|receiver_info| {
+        let kind = match receiver_info.receiver {
+            Receiver::SelfRef => "SelfRef",
+            Receiver::SelfRefMut => "SelfRefMut",
+        };
+        json!({
+            "kind": kind,
+            "direct_access": receiver_info.ref_types.contains(RefTypes::DIRECT_ACCESS),
+        })
+    });
+
+    // The input of a function is a tuple (struct) of its arguments.
+    let inputs = match &function_schema.input {
+        TypeRef::Static(type_id) => {
+            let field_types = match schema.resolve_type_kind(*type_id) {
+                Some(TypeKind::Tuple { field_types }) => field_types.as_slice(),
+                _ => &[],
+            };
+            let field_names = match schema
+                .resolve_type_metadata(*type_id)
+                .and_then(|metadata| metadata.child_names.as_ref())
+            {
+                Some(ChildNames::NamedFields(field_names)) => Some(field_names),
+                _ => None,
+            };
+            field_types
+                .iter()
+                .enumerate()
+                .map(|(index, field_type)| {
+                    let name = field_names
+                        .and_then(|field_names| field_names.get(index))
+                        .map(|name| name.to_string())
+                        .unwrap_or_else(|| format!("arg{index}"));
+                    json!({
+                        "name": name,
+                        "type": render_type(schema, *field_type),
+                        "type_id": type_id_to_json(schema, *field_type),
+                    })
+                })
+                .collect::<Vec<_>>()
+        }
+        TypeRef::Generic(_) => Vec::new(),
+    };
+
+    json!({
+        "name": function_name,
+        "export": function_schema.export,
+        "receiver": receiver,
+        "inputs": inputs,
+        "output": render_type_ref(schema, &function_schema.output),
+        "output_type_id": type_ref_to_json(schema, &function_schema.output),
+    })
+}
+
+/// Describes a type of the blueprint schema, referring to its child types by type id.
+fn type_to_abi_json(
+    schema: &Schema<ScryptoCustomSchema>,
+    index: usize,
+    type_kind: &LocalTypeKind<ScryptoCustomSchema>,
+    type_metadata: &TypeMetadata,
+) -> Value {
+    let mut type_json = json!({
+        "index": index,
+        "name": type_metadata.type_name,
+    });
+    let details = match type_kind {
+        TypeKind::Array { element_type } => json!({
+            "kind": "Array",
+            "element_type_id": type_id_to_json(schema, *element_type),
+        }),
+        TypeKind::Tuple { field_types } => json!({
+            "kind": "Tuple",
+            "fields": fields_to_abi_json(schema, field_types, type_metadata.child_names.as_ref()),
+        }),
+        TypeKind::Enum { variants } => {
+            let variant_names = match &type_metadata.child_names {
+                Some(ChildNames::EnumVariants(variant_names)) => Some(variant_names),
+                _ => None,
+            };
+            let variants = variants
+                .iter()
+                .map(|(discriminator, field_types)| {
+                    let variant_metadata =
+                        variant_names.and_then(|variant_names| variant_names.get(discriminator));
+                    json!({
+                        "discriminator": discriminator,
+                        "name": variant_metadata.and_then(|metadata| metadata.type_name.as_ref()),
+                        "fields": fields_to_abi_json(schema,
+                            field_types,
+                            variant_metadata.and_then(|metadata| metadata.child_names.as_ref()),
+                        ),
+                    })
+                })
+                .collect::<Vec<_>>();
+            json!({
+                "kind": "Enum",
+                "variants": variants,
+            })
+        }
+        TypeKind::Map {
+            key_type,
+            value_type,
+        } => json!({
+            "kind": "Map",
+            "key_type_id": type_id_to_json(schema, *key_type),
+            "value_type_id": type_id_to_json(schema, *value_type),
+        }),
+        TypeKind::Custom(custom_type_kind) => json!({
+            "kind": "Custom",
+            "custom_kind": match custom_type_kind {
+                ScryptoCustomTypeKind::Reference => "Reference",
+                ScryptoCustomTypeKind::Own => "Own",
+                ScryptoCustomTypeKind::Decimal => "Decimal",
+                ScryptoCustomTypeKind::PreciseDecimal => "PreciseDecimal",
+                ScryptoCustomTypeKind::NonFungibleLocalId => "NonFungibleLocalId",
+            },
+        }),
+        TypeKind::Any => json!({ "kind": "Any" }),
+        TypeKind::Bool => json!({ "kind": "Bool" }),
+        TypeKind::I8 => json!({ "kind": "I8" }),
+        TypeKind::I16 => json!({ "kind": "I16" }),
+        TypeKind::I32 => json!({ "kind": "I32" }),
+        TypeKind::I64 => json!({ "kind": "I64" }),
+        TypeKind::I128 => json!({ "kind": "I128" }),
+        TypeKind::U8 => json!({ "kind": "U8" }),
+        TypeKind::U16 => json!({ "kind": "U16" }),
+        TypeKind::U32 => json!({ "kind": "U32" }),
+        TypeKind::U64 => json!({ "kind": "U64" }),
+        TypeKind::U128 => json!({ "kind": "U128" }),
+        TypeKind::String => json!({ "kind": "String" }),
+    };
+    if let (Some(type_json), Value::Object(details)) = (type_json.as_object_mut(), details) {
+        type_json.extend(details);
+    }
+    type_json
+}
+
+fn fields_to_abi_json(
+    schema: &Schema<ScryptoCustomSchema>,
+    field_types: &[LocalTypeId],
+    child_names: Option<&ChildNames>,
+) -> Vec<Value> {
+    let field_names = match child_names {
+        Some(ChildNames::NamedFields(field_names)) => Some(field_names),
+        _ => None,
+    };
+    field_types
+        .iter()
+        .enumerate()
+        .map(|(index, field_type)| {
+            json!({
+                "name": field_names.and_then(|field_names| field_names.get(index)),
+                "type_id": type_id_to_json(schema, *field_type),
+            })
+        })
+        .collect()
+}
+
+fn type_id_to_json(schema: &Schema<ScryptoCustomSchema>, type_id: LocalTypeId) -> Value {
+    match type_id {
+        LocalTypeId::WellKnown(well_known_type_id) => {
+            json!({
+                "well_known": well_known_type_id.as_index(),
+                "name": render_type(schema, type_id),
+            })
+        }
+        LocalTypeId::SchemaLocalIndex(index) => json!({ "index": index }),
+    }
+}
+
+fn type_ref_to_json(
+    schema: &Schema<ScryptoCustomSchema>,
+    type_ref: &TypeRef<LocalTypeId>,
+) -> Value {
+    match type_ref {
+        TypeRef::Static(type_id) => type_id_to_json(schema, *type_id),
+        TypeRef::Generic(index) => json!({ "generic": index }),
+    }
+}
+
+fn render_type_ref(
+    schema: &Schema<ScryptoCustomSchema>,
+    type_ref: &TypeRef<LocalTypeId>,
+) -> String {
+    match type_ref {
+        TypeRef::Static(type_id) => render_type(schema, *type_id),
+        TypeRef::Generic(index) => format!("Generic<{index}>"),
+    }
+}
+
+/// Renders a type in Rust syntax. Named types are rendered by name, so that recursive types
+/// terminate.
+fn render_type(schema: &Schema<ScryptoCustomSchema>, type_id: LocalTypeId) -> String {
+    let Some(type_kind) = schema.resolve_type_kind(type_id) else {
+        return "Unknown".to_owned();
+    };
+    let type_name = schema.resolve_type_name_from_metadata(type_id);
+
+    match (type_name, type_kind) {
+        (Some("Option"), TypeKind::Enum { variants }) => match variants.get(&1u8) {
+            Some(some_fields) if some_fields.len() == 1 => {
+                format!("Option<{}>", render_type(schema, some_fields[0]))
+            }
+            _ => "Option".to_owned(),
+        },
+        (Some(type_name), _) => type_name.to_owned(),
+        (None, TypeKind::Any) => "Any".to_owned(),
+        (None, TypeKind::Bool) => "bool".to_owned(),
+        (None, TypeKind::I8) => "i8".to_owned(),
+        (None, TypeKind::I16) => "i16".to_owned(),
+        (None, TypeKind::I32) => "i32".to_owned(),
+        (None, TypeKind::I64) => "i64".to_owned(),
+        (None, TypeKind::I128) => "i128".to_owned(),
+        (None, TypeKind::U8) => "u8".to_owned(),
+        (None, TypeKind::U16) => "u16".to_owned(),
+        (None, TypeKind::U32) => "u32".to_owned(),
+        (None, TypeKind::U64) => "u64".to_owned(),
+        (None, TypeKind::U128) => "u128".to_owned(),
+        (None, TypeKind::String) => "String".to_owned(),
+        (None, TypeKind::Array { element_type }) => {
+            format!("Vec<{}>", render_type(schema, *element_type))
+        }
+        (None, TypeKind::Tuple { field_types }) => match field_types.as_slice() {
+            [] => "()".to_owned(),
+            [field_type] => format!("({},)", render_type(schema, *field_type)),
+            field_types => format!(
+                "({})",
+                field_types
+                    .iter()
+                    .map(|field_type| render_type(schema, *field_type))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        },
+        (None, TypeKind::Enum { .. }) => "Enum".to_owned(),
+        (
+            None,
+            TypeKind::Map {
+                key_type,
+                value_type,
+            },
+        ) => format!(
+            "IndexMap<{}, {}>",
+            render_type(schema, *key_type),
+            render_type(schema, *value_type)
+        ),
+        (None, TypeKind::Custom(custom_type_kind)) => match custom_type_kind {
+            ScryptoCustomTypeKind::Reference => "Reference",
+            ScryptoCustomTypeKind::Own => "Own",
+            ScryptoCustomTypeKind::Decimal => "Decimal",
+            ScryptoCustomTypeKind::PreciseDecimal => "PreciseDecimal",
+            ScryptoCustomTypeKind::NonFungibleLocalId => "NonFungibleLocalId",
+        }
+        .to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use radix_engine::blueprints::resource::ResourceNativePackage;
+    use radix_engine_interface::blueprints::resource::*;
+
+    #[test]
+    fn abi_json_round_trips_package_definition() {
+        let definition = ResourceNativePackage::definition();
+
+        let abi_json = package_definition_to_abi_json(&definition).to_string();
+
+        let parsed_definition = package_definition_from_abi_json(&abi_json).unwrap();
+        assert_eq!(parsed_definition, definition);
+    }
+
+    #[test]
+    fn abi_json_describes_functions() {
+        let definition = ResourceNativePackage::definition();
+
+        let abi_json = package_definition_to_abi_json(&definition);
+
+        assert_eq!(abi_json["version"], json!(PACKAGE_ABI_JSON_VERSION));
+        let fungible_vault = abi_json["blueprints"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|blueprint| blueprint["name"] == json!(FUNGIBLE_VAULT_BLUEPRINT))
+            .unwrap();
+        let take = fungible_vault["functions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|function| function["name"] == json!(VAULT_TAKE_IDENT))
+            .unwrap();
+        assert_eq!(
+            take["receiver"],
+            json!({ "kind": "SelfRefMut", "direct_access": false })
+        );
+        assert_eq!(
+            take["inputs"],
+            json!([{
+                "name": "amount",
+                "type": "Decimal",
+                "type_id": { "well_known": DECIMAL_TYPE.as_index(), "name": "Decimal" },
+            }])
+        );
+        assert_eq!(take["output"], json!("Bucket"));
+        assert_eq!(
+            take["output_type_id"],
+            json!({ "well_known": OWN_BUCKET_TYPE.as_index(), "name": "Bucket" })
+        );
+    }
+
+    #[test]
+    fn abi_json_includes_all_referenced_types() {
+        fn assert_type_ids_resolve(value: &Value, types: &[Value], local_type_ids: &mut usize) {
+            match value {
+                Value::Object(object) => {
+                    for (key, child) in object {
+                        if key.ends_with("type_id") {
+                            if let Some(index) = child.get("index") {
+                                let index = index.as_u64().unwrap() as usize;
+                                assert_eq!(types[index]["index"], json!(index));
+                                *local_type_ids += 1;
+                            }
+                        } else {
+                            assert_type_ids_resolve(child, types, local_type_ids);
+                        }
+                    }
+                }
+                Value::Array(array) => {
+                    for child in array {
+                        assert_type_ids_resolve(child, types, local_type_ids);
+                    }
+                }
+                _ => {}
+            }
+        }
+        let definition = ResourceNativePackage::definition();
+
+        let abi_json = package_definition_to_abi_json(&definition);
+
+        let mut local_type_ids = 0;
+        for blueprint in abi_json["blueprints"].as_array().unwrap() {
+            let types = blueprint["types"].as_array().unwrap();
+            assert_type_ids_resolve(blueprint, types, &mut local_type_ids);
+        }
+        assert!(local_type_ids > 0);
+    }
+
+    #[test]
+    fn abi_json_with_unsupported_version_is_rejected() {
+        let mut abi_json = package_definition_to_abi_json(&PackageDefinition::default());
+        abi_json["version"] = json!(PACKAGE_ABI_JSON_VERSION + 1);
+
+        assert!(matches!(
+            package_definition_from_abi_json(&abi_json.to_string()),
+            Err(PackageAbiJsonError::UnsupportedVersion(_))
+        ));
+    }
+}
@@ -98,3 +98,38 @@ mod kv_entry_clone {
         }
     }
 }
+
+#[blueprint]
+mod extern_blueprint_calls {
+    extern_blueprint!(
+        "package_rdx1pkgxxxxxxxxxfaucetxxxxxxxxx000034355863xxxxxxxxxfaucet",
+        Faucet as ExternFaucet {
+            fn new(
+                address_reservation: GlobalAddressReservation,
+                bucket: FungibleBucket
+            ) -> Global<ExternFaucet>;
+
+            fn free(&self) -> Bucket;
+
+            fn lock_fee(&self, amount: Decimal);
+        }
+    );
+
+    struct ExternBlueprintCalls {
+        faucet: Global<ExternFaucet>,
+    }
+
+    impl ExternBlueprintCalls {
+        pub fn instantiate_faucet(
+            address_reservation: GlobalAddressReservation,
+            bucket: FungibleBucket,
+        ) -> Global<ExternFaucet> {
+            Blueprint::<ExternFaucet>::new(address_reservation, bucket)
+        }
+
+        pub fn free_from_faucet(&self) -> Bucket {
+            self.faucet.lock_fee(dec!(10));
+            self.faucet.free()
+        }
+    }
+}
@@ -742,6 +742,7 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
         let fn_schemas = generated_schema_info.fn_schemas;
 
         // Getting the event types and other named types from attribute
+        let mut is_non_reentrant = false;
         let (event_type_names, event_type_paths, registered_type_names, registered_type_paths) = {
             let mut event_type_paths = BTreeMap::<String, Path>::new();
             for attribute in blueprint.attributes {
@@ -757,6 +758,8 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
                         }
                     }
                 } else if attribute.path.is_ident("types") {
+                } else if attribute.path.is_ident("non_reentrant") {
+                    is_non_reentrant = true;
                 }
                 // None of the attributes to apply at the top-level of blueprint macros matched. So,
                 // we provide an error to the user that they're using an incorrect attribute macro
@@ -792,6 +795,20 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
             )
         };
 
+        let feature_set_expr = if is_non_reentrant {
+            quote! {
+                {
+                    let mut feature_set = IndexSet::default();
+                    feature_set.insert(scrypto::blueprints::package::BLUEPRINT_NON_REENTRANT_FEATURE.to_string());
+                    feature_set
+                }
+            }
+        } else {
+            quote! {
+                IndexSet::default()
+            }
+        };
+
         quote! {
             #function_auth_statements
 
@@ -883,7 +900,7 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
                 let return_data = scrypto::blueprints::package::BlueprintDefinitionInit {
                     blueprint_type: scrypto::blueprints::package::BlueprintType::default(),
                     is_transient: false,
-                    feature_set: IndexSet::default(),
+                    feature_set: #feature_set_expr,
                     dependencies,
                     schema,
                     auth_config,
@@ -1349,10 +1366,12 @@ fn generate_dispatcher(bp_ident: &Ident, items: &[ImplItem]) -> Result<Vec<Token
                 stmts.push(stmt);
 
                 let fn_ident = format_ident!("{}_{}", bp_ident, ident);
+                // The exports may unwind when compiled natively for tests, where the host reports
+                // errors and panics by unwinding out of the blueprint.
                 let extern_function = {
                     quote! {
                         #[no_mangle]
-                        pub extern "C" fn #fn_ident(args: ::scrypto::engine::wasm_api::Buffer) -> ::scrypto::engine::wasm_api::Slice {
+                        pub extern "C-unwind" fn #fn_ident(args: ::scrypto::engine::wasm_api::Buffer) -> ::scrypto::engine::wasm_api::Slice {
                             use sbor::rust::ops::{Deref, DerefMut};
 
                             // Set up panic hook
@@ -1971,7 +1990,7 @@ mod tests {
                     pub struct Test_y_Input { i : u32 }
 
                     #[no_mangle]
-                    pub extern "C" fn Test_x(args: ::scrypto::engine::wasm_api::Buffer) -> ::scrypto::engine::wasm_api::Slice {
+                    pub extern "C-unwind" fn Test_x(args: ::scrypto::engine::wasm_api::Buffer) -> ::scrypto::engine::wasm_api::Slice {
                         use sbor::rust::ops::{Deref, DerefMut};
 
                         // Set up panic hook
@@ -1985,7 +2004,7 @@ mod tests {
                     }
 
                     #[no_mangle]
-                    pub extern "C" fn Test_y(args: ::scrypto::engine::wasm_api::Buffer) -> ::scrypto::engine::wasm_api::Slice {
+                    pub extern "C-unwind" fn Test_y(args: ::scrypto::engine::wasm_api::Buffer) -> ::scrypto::engine::wasm_api::Slice {
                         use sbor::rust::ops::{Deref, DerefMut};
 
                         // Set up panic hook
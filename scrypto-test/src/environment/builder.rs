@@ -10,9 +10,12 @@ use radix_engine::system::system::*;
 use radix_engine::system::system_callback::*;
 use radix_engine::system::system_modules::auth::*;
 use radix_engine::system::system_modules::costing::*;
+use radix_engine::system::system_modules::debugger::DebuggerModule;
+use radix_engine::system::system_modules::execution_audit::ExecutionAuditModule;
 use radix_engine::system::system_modules::execution_trace::ExecutionTraceModule;
 use radix_engine::system::system_modules::kernel_trace::KernelTraceModule;
 use radix_engine::system::system_modules::limits::LimitsModule;
+use radix_engine::system::system_modules::metrics::MetricsModule;
 use radix_engine::system::system_modules::transaction_runtime::TransactionRuntimeModule;
 use radix_engine::system::system_modules::*;
 use radix_engine::track::*;
@@ -225,6 +228,7 @@ where
 
                 let costing_module = CostingModule {
                     current_depth: 0,
+                    current_instruction: None,
                     fee_reserve: SystemLoanFeeReserve::default(),
                     fee_table: FeeTable::new(system_version),
                     tx_payload_len: 0,
@@ -252,6 +256,9 @@ where
                         limits_module,
                         costing_module,
                         ExecutionTraceModule::new(MAX_EXECUTION_TRACE_DEPTH),
+                        ExecutionAuditModule::default(),
+                        DebuggerModule::default(),
+                        MetricsModule::default(),
                     ),
                     SystemFinalization::no_nullifications(),
                 )
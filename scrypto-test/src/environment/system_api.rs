@@ -227,6 +227,12 @@ implement_system_api! {
             node_id: &NodeId,
             key: &Vec<u8>,
         ) -> Result<Vec<u8>, RuntimeError>,
+        key_value_store_scan_keys: (
+            &mut self,
+            node_id: &NodeId,
+            cursor: Option<Vec<u8>>,
+            limit: u32,
+        ) -> Result<KeyValueStoreKeys, RuntimeError>,
     },
     SystemObjectApi: {
         new_object: (
@@ -288,6 +294,7 @@ implement_system_api! {
     },
     SystemExecutionTraceApi: {
         update_instruction_index: (&mut self, new_index: usize) -> Result<(), RuntimeError>,
+        clear_instruction_index: (&mut self) -> Result<(), RuntimeError>,
     },
     SystemTransactionRuntimeApi: {
         bech32_encode_address: (&mut self, address: GlobalAddress) -> Result<String, RuntimeError>,
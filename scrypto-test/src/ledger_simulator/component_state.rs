@@ -0,0 +1,180 @@
+use crate::prelude::*;
+use radix_engine::system::system_db_reader::SystemDatabaseReader;
+use radix_engine::system::type_info::TypeInfoSubstate;
+use radix_engine_interface::api::ModuleId;
+
+/// A capture of the state of a Scrypto component, including the entries of the key value stores
+/// and the balances of the vaults it owns, directly or through other key value stores.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentStateSnapshot {
+    pub state: ScryptoValue,
+    /// The entries of the owned key value stores, by the SBOR encoding of their keys.
+    pub kv_stores: IndexMap<NodeId, IndexMap<Vec<u8>, ScryptoValue>>,
+    pub vault_balances: IndexMap<NodeId, Decimal>,
+}
+
+/// A change between two [`ComponentStateSnapshot`]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ComponentStateChange {
+    /// A field of the state of the component changed.
+    Field {
+        index: usize,
+        old: ScryptoValue,
+        new: ScryptoValue,
+    },
+    /// An entry of a key value store was inserted, updated or removed.
+    KeyValueEntry {
+        kv_store_id: NodeId,
+        key: ScryptoValue,
+        old: Option<ScryptoValue>,
+        new: Option<ScryptoValue>,
+    },
+    /// The balance of a vault changed, or a vault was created or dropped.
+    VaultBalance {
+        vault_id: NodeId,
+        old: Option<Decimal>,
+        new: Option<Decimal>,
+    },
+}
+
+impl ComponentStateSnapshot {
+    pub fn capture<S: SubstateDatabase>(
+        substate_db: &S,
+        component_address: ComponentAddress,
+    ) -> Self {
+        let reader = SystemDatabaseReader::new(substate_db);
+        let state = reader
+            .read_object_field(
+                component_address.as_node_id(),
+                ModuleId::Main,
+                ComponentField::State0.field_index(),
+            )
+            .expect("Component state should exist");
+
+        let mut kv_stores = index_map_new();
+        let mut vault_balances = index_map_new();
+        let mut to_visit: Vec<NodeId> = state.owned_nodes().iter().rev().cloned().collect();
+        while let Some(node_id) = to_visit.pop() {
+            if node_id.is_internal_fungible_vault() {
+                let balance: FungibleVaultBalanceFieldPayload = reader
+                    .read_typed_object_field(
+                        &node_id,
+                        ModuleId::Main,
+                        FungibleVaultField::Balance.into(),
+                    )
+                    .expect("Fungible vault balance should exist");
+                vault_balances.insert(
+                    node_id,
+                    balance.fully_update_and_into_latest_version().amount(),
+                );
+            } else if node_id.is_internal_non_fungible_vault() {
+                let balance: NonFungibleVaultBalanceFieldPayload = reader
+                    .read_typed_object_field(
+                        &node_id,
+                        ModuleId::Main,
+                        NonFungibleVaultField::Balance.into(),
+                    )
+                    .expect("Non fungible vault balance should exist");
+                vault_balances.insert(
+                    node_id,
+                    balance.fully_update_and_into_latest_version().amount,
+                );
+            } else if let Ok(TypeInfoSubstate::KeyValueStore(..)) = reader.get_type_info(&node_id) {
+                let mut entries = index_map_new();
+                let mut children = Vec::new();
+                for (key, value) in reader.key_value_store_iter(&node_id, None).unwrap() {
+                    let value = IndexedScryptoValue::from_vec(value).unwrap();
+                    children.extend(value.owned_nodes().iter().cloned());
+                    entries.insert(key, value.to_scrypto_value());
+                }
+                kv_stores.insert(node_id, entries);
+                to_visit.extend(children.into_iter().rev());
+            }
+        }
+
+        Self {
+            state: state.to_scrypto_value(),
+            kv_stores,
+            vault_balances,
+        }
+    }
+
+    /// Decodes the state of the component.
+    pub fn state<T: ScryptoDecode>(&self) -> T {
+        scrypto_decode(&scrypto_encode(&self.state).unwrap()).unwrap()
+    }
+
+    /// Returns the changes from this snapshot to the given later one.
+    pub fn diff(&self, later: &Self) -> Vec<ComponentStateChange> {
+        let mut changes = Vec::new();
+
+        match (&self.state, &later.state) {
+            (
+                ScryptoValue::Tuple { fields: old_fields },
+                ScryptoValue::Tuple { fields: new_fields },
+            ) if old_fields.len() == new_fields.len() => {
+                for (index, (old, new)) in old_fields.iter().zip(new_fields).enumerate() {
+                    if old != new {
+                        changes.push(ComponentStateChange::Field {
+                            index,
+                            old: old.clone(),
+                            new: new.clone(),
+                        });
+                    }
+                }
+            }
+            (old, new) => {
+                if old != new {
+                    changes.push(ComponentStateChange::Field {
+                        index: 0,
+                        old: old.clone(),
+                        new: new.clone(),
+                    });
+                }
+            }
+        }
+
+        let empty_entries = index_map_new();
+        let kv_store_ids: IndexSet<&NodeId> = self
+            .kv_stores
+            .keys()
+            .chain(later.kv_stores.keys())
+            .collect();
+        for kv_store_id in kv_store_ids {
+            let old_entries = self.kv_stores.get(kv_store_id).unwrap_or(&empty_entries);
+            let new_entries = later.kv_stores.get(kv_store_id).unwrap_or(&empty_entries);
+            let keys: IndexSet<&Vec<u8>> = old_entries.keys().chain(new_entries.keys()).collect();
+            for key in keys {
+                let old = old_entries.get(key);
+                let new = new_entries.get(key);
+                if old != new {
+                    changes.push(ComponentStateChange::KeyValueEntry {
+                        kv_store_id: *kv_store_id,
+                        key: scrypto_decode(key).unwrap(),
+                        old: old.cloned(),
+                        new: new.cloned(),
+                    });
+                }
+            }
+        }
+
+        let vault_ids: IndexSet<&NodeId> = self
+            .vault_balances
+            .keys()
+            .chain(later.vault_balances.keys())
+            .collect();
+        for vault_id in vault_ids {
+            let old = self.vault_balances.get(vault_id).cloned();
+            let new = later.vault_balances.get(vault_id).cloned();
+            if old != new {
+                changes.push(ComponentStateChange::VaultBalance {
+                    vault_id: *vault_id,
+                    old,
+                    new,
+                });
+            }
+        }
+
+        changes
+    }
+}
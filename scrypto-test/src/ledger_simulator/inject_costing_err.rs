@@ -522,6 +522,17 @@ impl<
             .kernel_scan_keys::<K>(node_id, partition_num, count)
     }
 
+    fn kernel_scan_keys_after<K: SubstateKeyContent>(
+        &mut self,
+        node_id: &NodeId,
+        partition_num: PartitionNumber,
+        after: Option<&SubstateKey>,
+        count: u32,
+    ) -> Result<Vec<SubstateKey>, RuntimeError> {
+        self.api
+            .kernel_scan_keys_after::<K>(node_id, partition_num, after, count)
+    }
+
     fn kernel_drain_substates<K: SubstateKeyContent>(
         &mut self,
         node_id: &NodeId,
@@ -578,6 +589,10 @@ impl<
     fn kernel_get_owned_nodes(&mut self) -> Result<Vec<NodeId>, RuntimeError> {
         self.api.kernel_get_owned_nodes()
     }
+
+    fn kernel_get_call_frame_data_stack_uncosted(&self) -> Vec<&E::CallFrameData> {
+        self.api.kernel_get_call_frame_data_stack_uncosted()
+    }
 }
 
 impl<
@@ -618,6 +633,10 @@ impl<
         self.api
             .kernel_read_substate_uncosted(node_id, partition_num, substate_key)
     }
+
+    fn kernel_get_owned_nodes_uncosted(&self) -> Vec<NodeId> {
+        self.api.kernel_get_owned_nodes_uncosted()
+    }
 }
 
 impl<
@@ -675,4 +694,8 @@ impl<
         self.api
             .kernel_read_substate_uncosted(node_id, partition_num, substate_key)
     }
+
+    fn kernel_get_owned_nodes_uncosted(&self) -> Vec<NodeId> {
+        self.api.kernel_get_owned_nodes_uncosted()
+    }
 }
@@ -603,6 +603,26 @@ impl<E: NativeVmExtension, D: TestDatabase> LedgerSimulator<E, D> {
             .sum_balance_changes(node_id, commit.vault_balance_changes())
     }
 
+    /// Panics unless the vaults owned (directly or indirectly) by the given entity changed by the
+    /// given amount of the resource in total.
+    pub fn expect_balance_change(
+        &mut self,
+        commit: &CommitResult,
+        address: impl Into<GlobalAddress>,
+        resource_address: ResourceAddress,
+        expected_change: BalanceChange,
+    ) {
+        let address = address.into();
+        let actual_change = self
+            .sum_descendant_balance_changes(commit, address.as_node_id())
+            .swap_remove(&resource_address);
+        assert_eq!(
+            actual_change,
+            Some(expected_change),
+            "Unexpected balance change of {resource_address:?} for {address:?}"
+        );
+    }
+
     pub fn get_component_vaults(
         &mut self,
         component_address: ComponentAddress,
@@ -703,6 +723,13 @@ impl<E: NativeVmExtension, D: TestDatabase> LedgerSimulator<E, D> {
         component_state.unwrap().into_payload()
     }
 
+    pub fn component_state_snapshot(
+        &self,
+        component_address: ComponentAddress,
+    ) -> ComponentStateSnapshot {
+        ComponentStateSnapshot::capture(self.substate_db(), component_address)
+    }
+
     pub fn get_non_fungible_data<T: NonFungibleData>(
         &self,
         resource: ResourceAddress,
@@ -747,6 +774,20 @@ impl<E: NativeVmExtension, D: TestDatabase> LedgerSimulator<E, D> {
         total_supply
     }
 
+    pub fn get_non_fungible_resource_total_supply(&self, resource: ResourceAddress) -> Decimal {
+        let total_supply = self
+            .substate_db()
+            .get_substate::<NonFungibleResourceManagerTotalSupplyFieldSubstate>(
+                resource,
+                MAIN_BASE_PARTITION,
+                NonFungibleResourceManagerField::TotalSupply,
+            )
+            .unwrap()
+            .into_payload()
+            .fully_update_and_into_latest_version();
+        total_supply
+    }
+
     pub fn load_account_from_faucet(&mut self, account_address: ComponentAddress) {
         let manifest = ManifestBuilder::new()
             .lock_fee_from_faucet()
@@ -1243,11 +1284,11 @@ impl<E: NativeVmExtension, D: TestDatabase> LedgerSimulator<E, D> {
         initial_proofs: impl IntoIterator<Item = NonFungibleGlobalId>,
         costing_parameters: CostingParameters,
     ) -> TransactionReceipt {
-        let mut config = self.resolve_suggested_config(&manifest);
-        config.system_overrides = Some(SystemOverrides {
-            costing_parameters: Some(costing_parameters),
-            ..config.system_overrides.unwrap_or_default()
-        });
+        let config = self
+            .resolve_suggested_config(&manifest)
+            .update_system_overrides(|overrides| {
+                overrides.set_costing_parameters(Some(costing_parameters))
+            });
         self.execute_manifest_with_execution_config(manifest, initial_proofs, config)
     }
 
@@ -1418,6 +1459,10 @@ impl<E: NativeVmExtension, D: TestDatabase> LedgerSimulator<E, D> {
 
         let execution_config =
             self.override_configured_execution_config_properties(execution_config);
+        assert!(
+            !execution_config.has_transaction_quotas(),
+            "Transaction quotas are node-local and must not be set for committed transactions"
+        );
 
         if executable
             .costing_parameters()
@@ -1503,18 +1548,18 @@ impl<E: NativeVmExtension, D: TestDatabase> LedgerSimulator<E, D> {
         preview_transaction: PreviewTransactionV2,
         flags: PreviewFlags,
     ) -> TransactionReceipt {
-        let validated = preview_transaction
-            .prepare_and_validate(&self.transaction_validator)
-            .expect("Preview transaction should be valid");
-        let execution_config = if flags.disable_auth {
-            ExecutionConfig::for_preview_no_auth(NetworkDefinition::simulator())
-        } else {
-            ExecutionConfig::for_preview(NetworkDefinition::simulator())
-        };
-        let execution_config =
-            self.override_configured_execution_config_properties(execution_config);
-        let executable = validated.create_executable(flags);
-        self.execute_transaction_no_commit(executable, execution_config)
+        let execution_config = self.override_configured_execution_config_properties(
+            preview_execution_config(NetworkDefinition::simulator(), &flags),
+        );
+        execute_preview_v2(
+            &self.database,
+            &self.vm_modules,
+            &self.transaction_validator,
+            preview_transaction,
+            flags,
+            &execution_config,
+        )
+        .expect("Preview transaction should be valid")
     }
 
     /// Calls a package blueprint function with the given arguments, paying the fee from the faucet.
@@ -1,7 +1,13 @@
 mod compile;
+mod component_state;
 mod inject_costing_err;
 mod ledger_simulator;
+#[cfg(feature = "native-test")]
+mod native_test_interpreter;
 
 pub use compile::*;
+pub use component_state::*;
 pub use inject_costing_err::*;
 pub use ledger_simulator::*;
+#[cfg(feature = "native-test")]
+pub use native_test_interpreter::*;
@@ -0,0 +1,333 @@
+use crate::prelude::*;
+use radix_engine::vm::wasm_runtime::ScryptoRuntime;
+use radix_engine_interface::api::actor_api::EventFlags;
+use scrypto::engine::native_host::{self, NativeHost};
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+
+/// The signature of the functions and methods exported by blueprints.
+pub type NativeExport = extern "C-unwind" fn(Buffer) -> Slice;
+
+/// The signature of the schemas exported by blueprints.
+pub type NativeSchemaExport = extern "C" fn() -> Slice;
+
+/// Runs blueprints compiled natively rather than to WASM, with the `native-test` feature of
+/// scrypto, through the same runtime as WASM blueprints. It can be used as the code of a native
+/// package with [`OverridePackageCode`]:
+///
+/// ```ignore
+/// let interpreter = NativeTestInterpreter::new()
+///     .with_blueprint("Hello", hello::Hello_schema)
+///     .with_export("Hello_instantiate_hello", hello::Hello_instantiate_hello);
+/// let definition = interpreter.package_definition();
+/// let mut ledger = LedgerSimulatorBuilder::new()
+///     .with_custom_extension(OverridePackageCode::new(CODE_ID, interpreter))
+///     .build();
+/// let package_address = ledger.publish_native_package(CODE_ID, definition);
+/// ```
+///
+/// As the blueprints aren't executed as WASM, no WASM execution costs are charged.
+#[derive(Clone, Default)]
+pub struct NativeTestInterpreter {
+    schemas: IndexMap<String, NativeSchemaExport>,
+    exports: IndexMap<String, NativeExport>,
+}
+
+impl NativeTestInterpreter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_blueprint(mut self, blueprint_name: &str, schema: NativeSchemaExport) -> Self {
+        self.schemas.insert(blueprint_name.to_owned(), schema);
+        self
+    }
+
+    pub fn with_export(mut self, export_name: &str, export: NativeExport) -> Self {
+        self.exports.insert(export_name.to_owned(), export);
+        self
+    }
+
+    /// Returns the definition of the package of the blueprints, from their schemas.
+    pub fn package_definition(&self) -> PackageDefinition {
+        let blueprints = self
+            .schemas
+            .iter()
+            .map(|(blueprint_name, schema)| {
+                schema();
+                let definition = native_host::take_return_value()
+                    .expect("Schema export should return the blueprint definition");
+                (
+                    blueprint_name.clone(),
+                    scrypto_decode(&definition).expect("Blueprint definition should decode"),
+                )
+            })
+            .collect();
+
+        PackageDefinition { blueprints }
+    }
+}
+
+impl VmInvoke for NativeTestInterpreter {
+    fn invoke<
+        Y: SystemApi<RuntimeError>
+            + KernelNodeApi
+            + KernelSubstateApi<SystemLockData>
+            + SystemBasedKernelInternalApi,
+        V: VmApi,
+    >(
+        &mut self,
+        export_name: &str,
+        input: &IndexedScryptoValue,
+        api: &mut Y,
+        vm_api: &V,
+    ) -> Result<IndexedScryptoValue, RuntimeError> {
+        let export = *self.exports.get(export_name).ok_or_else(|| {
+            InvokeError::SelfError(WasmRuntimeError::UnknownExport(export_name.to_owned()))
+        })?;
+        let package_address = api.actor_get_blueprint_id()?.package_address;
+        let mut runtime = ScryptoRuntime::new(
+            api,
+            package_address,
+            export_name.to_owned(),
+            vm_api.get_scrypto_version(),
+        );
+        let args = runtime.allocate_buffer(input.as_slice().to_vec())?;
+
+        let mut host = RuntimeHost {
+            runtime: &mut runtime,
+            error: None,
+        };
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            native_host::with_native_host(&mut host, || export(args))
+        }));
+        if let Some(error) = host.error {
+            return Err(error.into());
+        }
+        if let Err(cause) = result {
+            let message = if let Some(s) = cause.downcast_ref::<&'static str>() {
+                (*s).to_string()
+            } else if let Some(s) = cause.downcast_ref::<String>() {
+                s.clone()
+            } else {
+                "Panic".to_string()
+            };
+            runtime.sys_panic(message.into_bytes())?;
+        }
+
+        let output = native_host::take_return_value()
+            .expect("Export should return a value if it doesn't panic");
+        IndexedScryptoValue::from_vec(output).map_err(|e| {
+            RuntimeError::SystemUpstreamError(SystemUpstreamError::OutputDecodeError(e))
+        })
+    }
+}
+
+/// The payload with which the host unwinds out of a blueprint when a call fails.
+struct NativeHostError;
+
+struct RuntimeHost<'r> {
+    runtime: &'r mut dyn WasmRuntime,
+    error: Option<InvokeError<WasmRuntimeError>>,
+}
+
+impl<'r> NativeHost for RuntimeHost<'r> {
+    fn invoke(&mut self, function_name: &str, args: &[u64]) -> u64 {
+        // As with a trapped WASM instance, a blueprint which is unwinding can't make calls, such
+        // as those to close substates when they're dropped.
+        if std::thread::panicking() {
+            return 0;
+        }
+
+        match self.dispatch(function_name, args) {
+            Ok(rtn) => rtn,
+            Err(error) => {
+                self.error = Some(error);
+                resume_unwind(Box::new(NativeHostError))
+            }
+        }
+    }
+}
+
+fn read_memory(ptr: u64, len: u64) -> Vec<u8> {
+    if len == 0 {
+        return Vec::new();
+    }
+
+    // SAFETY: the blueprint runs in this process, and passes pointers to its own memory.
+    unsafe { std::slice::from_raw_parts(ptr as usize as *const u8, len as usize) }.to_vec()
+}
+
+impl<'r> RuntimeHost<'r> {
+    fn dispatch(
+        &mut self,
+        function_name: &str,
+        args: &[u64],
+    ) -> Result<u64, InvokeError<WasmRuntimeError>> {
+        let runtime = &mut *self.runtime;
+        let memory = |index: usize| read_memory(args[index], args[index + 1]);
+        let handle = |index: usize| args[index] as u32;
+
+        let rtn = match function_name {
+            BUFFER_CONSUME_FUNCTION_NAME => {
+                let data = runtime.buffer_consume(handle(0))?;
+                // SAFETY: the blueprint allocates the destination with the length of the buffer.
+                unsafe {
+                    std::ptr::copy_nonoverlapping(
+                        data.as_ptr(),
+                        args[1] as usize as *mut u8,
+                        data.len(),
+                    )
+                };
+                0
+            }
+            BLUEPRINT_CALL_FUNCTION_NAME => {
+                runtime
+                    .blueprint_call(memory(0), memory(2), memory(4), memory(6))?
+                    .0
+            }
+            ADDRESS_ALLOCATE_FUNCTION_NAME => runtime.address_allocate(memory(0), memory(2))?.0,
+            ADDRESS_GET_RESERVATION_ADDRESS_FUNCTION_NAME => {
+                runtime.address_get_reservation_address(memory(0))?.0
+            }
+            OBJECT_NEW_FUNCTION_NAME => runtime.object_new(memory(0), memory(2))?.0,
+            OBJECT_GLOBALIZE_FUNCTION_NAME => {
+                runtime.globalize_object(memory(0), memory(2), memory(4))?.0
+            }
+            OBJECT_INSTANCE_OF_FUNCTION_NAME => {
+                runtime.instance_of(memory(0), memory(2), memory(4))? as u64
+            }
+            OBJECT_GET_BLUEPRINT_ID_FUNCTION_NAME => runtime.blueprint_id(memory(0))?.0,
+            OBJECT_GET_OUTER_OBJECT_FUNCTION_NAME => runtime.get_outer_object(memory(0))?.0,
+            OBJECT_CALL_FUNCTION_NAME => runtime.object_call(memory(0), memory(2), memory(4))?.0,
+            OBJECT_CALL_DIRECT_FUNCTION_NAME => {
+                runtime
+                    .object_call_direct(memory(0), memory(2), memory(4))?
+                    .0
+            }
+            OBJECT_CALL_MODULE_FUNCTION_NAME => {
+                runtime
+                    .object_call_module(memory(0), handle(2), memory(3), memory(5))?
+                    .0
+            }
+            ACTOR_GET_PACKAGE_ADDRESS_FUNCTION_NAME => runtime.actor_get_package_address()?.0,
+            ACTOR_GET_BLUEPRINT_NAME_FUNCTION_NAME => runtime.actor_get_blueprint_name()?.0,
+            ACTOR_GET_OBJECT_ID_FUNCTION_NAME => runtime.actor_get_node_id(handle(0))?.0,
+            ACTOR_OPEN_FIELD_FUNCTION_NAME => {
+                runtime.actor_open_field(handle(0), args[1] as u8, handle(2))? as u64
+            }
+            ACTOR_EMIT_EVENT_FUNCTION_NAME => {
+                let flags = handle(4);
+                let event_flags = EventFlags::from_bits(flags).ok_or(InvokeError::SelfError(
+                    WasmRuntimeError::InvalidEventFlags(flags),
+                ))?;
+                runtime.actor_emit_event(memory(0), memory(2), event_flags)?;
+                0
+            }
+            KEY_VALUE_STORE_NEW_FUNCTION_NAME => runtime.key_value_store_new(memory(0))?.0,
+            KEY_VALUE_STORE_OPEN_ENTRY_FUNCTION_NAME => {
+                runtime.key_value_store_open_entry(memory(0), memory(2), handle(4))? as u64
+            }
+            KEY_VALUE_STORE_REMOVE_ENTRY_FUNCTION_NAME => {
+                runtime
+                    .key_value_store_remove_entry(memory(0), memory(2))?
+                    .0
+            }
+            KEY_VALUE_STORE_SCAN_KEYS_FUNCTION_NAME => {
+                runtime
+                    .key_value_store_scan_keys(memory(0), memory(2), handle(4))?
+                    .0
+            }
+            KEY_VALUE_ENTRY_READ_FUNCTION_NAME => runtime.key_value_entry_get(handle(0))?.0,
+            KEY_VALUE_ENTRY_WRITE_FUNCTION_NAME => {
+                runtime.key_value_entry_set(handle(0), memory(1))?;
+                0
+            }
+            KEY_VALUE_ENTRY_REMOVE_FUNCTION_NAME => runtime.key_value_entry_remove(handle(0))?.0,
+            KEY_VALUE_ENTRY_CLOSE_FUNCTION_NAME => {
+                runtime.key_value_entry_close(handle(0))?;
+                0
+            }
+            FIELD_ENTRY_READ_FUNCTION_NAME => runtime.field_entry_read(handle(0))?.0,
+            FIELD_ENTRY_WRITE_FUNCTION_NAME => {
+                runtime.field_entry_write(handle(0), memory(1))?;
+                0
+            }
+            FIELD_ENTRY_CLOSE_FUNCTION_NAME => {
+                runtime.field_entry_close(handle(0))?;
+                0
+            }
+            COSTING_GET_EXECUTION_COST_UNIT_LIMIT_FUNCTION_NAME => {
+                runtime.costing_get_execution_cost_unit_limit()? as u64
+            }
+            COSTING_GET_EXECUTION_COST_UNIT_PRICE_FUNCTION_NAME => {
+                runtime.costing_get_execution_cost_unit_price()?.0
+            }
+            COSTING_GET_FINALIZATION_COST_UNIT_LIMIT_FUNCTION_NAME => {
+                runtime.costing_get_finalization_cost_unit_limit()? as u64
+            }
+            COSTING_GET_FINALIZATION_COST_UNIT_PRICE_FUNCTION_NAME => {
+                runtime.costing_get_finalization_cost_unit_price()?.0
+            }
+            COSTING_GET_USD_PRICE_FUNCTION_NAME => runtime.costing_get_usd_price()?.0,
+            COSTING_GET_TIP_PERCENTAGE_FUNCTION_NAME => {
+                runtime.costing_get_tip_percentage()? as u64
+            }
+            COSTING_GET_FEE_BALANCE_FUNCTION_NAME => runtime.costing_get_fee_balance()?.0,
+            SYS_LOG_FUNCTION_NAME => {
+                runtime.sys_log(memory(0), memory(2))?;
+                0
+            }
+            SYS_BECH32_ENCODE_ADDRESS_FUNCTION_NAME => {
+                runtime.sys_bech32_encode_address(memory(0))?.0
+            }
+            SYS_GET_TRANSACTION_HASH_FUNCTION_NAME => runtime.sys_get_transaction_hash()?.0,
+            SYS_GENERATE_RUID_FUNCTION_NAME => runtime.sys_generate_ruid()?.0,
+            SYS_PANIC_FUNCTION_NAME => {
+                runtime.sys_panic(memory(0))?;
+                0
+            }
+            CRYPTO_UTILS_BLS12381_V1_VERIFY_FUNCTION_NAME => {
+                runtime.crypto_utils_bls12381_v1_verify(memory(0), memory(2), memory(4))? as u64
+            }
+            CRYPTO_UTILS_BLS12381_V1_AGGREGATE_VERIFY_FUNCTION_NAME => {
+                runtime.crypto_utils_bls12381_v1_aggregate_verify(memory(0), memory(2))? as u64
+            }
+            CRYPTO_UTILS_BLS12381_V1_FAST_AGGREGATE_VERIFY_FUNCTION_NAME => runtime
+                .crypto_utils_bls12381_v1_fast_aggregate_verify(memory(0), memory(2), memory(4))?
+                as u64,
+            CRYPTO_UTILS_BLS12381_G2_SIGNATURE_AGGREGATE_FUNCTION_NAME => {
+                runtime
+                    .crypto_utils_bls12381_g2_signature_aggregate(memory(0))?
+                    .0
+            }
+            CRYPTO_UTILS_KECCAK256_HASH_FUNCTION_NAME => {
+                runtime.crypto_utils_keccak256_hash(memory(0))?.0
+            }
+            CRYPTO_UTILS_BLAKE2B_256_HASH_FUNCTION_NAME => {
+                runtime.crypto_utils_blake2b_256_hash(memory(0))?.0
+            }
+            CRYPTO_UTILS_ED25519_VERIFY_FUNCTION_NAME => {
+                runtime.crypto_utils_ed25519_verify(memory(0), memory(2), memory(4))? as u64
+            }
+            CRYPTO_UTILS_SECP256K1_ECDSA_VERIFY_FUNCTION_NAME => {
+                runtime.crypto_utils_secp256k1_ecdsa_verify(memory(0), memory(2), memory(4))? as u64
+            }
+            CRYPTO_UTILS_SECP256K1_ECDSA_VERIFY_AND_KEY_RECOVER_FUNCTION_NAME => {
+                runtime
+                    .crypto_utils_secp256k1_ecdsa_verify_and_key_recover(memory(0), memory(2))?
+                    .0
+            }
+            CRYPTO_UTILS_SECP256K1_ECDSA_VERIFY_AND_KEY_RECOVER_UNCOMPRESSED_FUNCTION_NAME => {
+                runtime
+                    .crypto_utils_secp256k1_ecdsa_verify_and_key_recover_uncompressed(
+                        memory(0),
+                        memory(2),
+                    )?
+                    .0
+            }
+            _ => panic!("Unknown host function `{}`", function_name),
+        };
+
+        Ok(rtn)
+    }
+}
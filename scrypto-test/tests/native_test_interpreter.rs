@@ -0,0 +1,134 @@
+#![cfg(feature = "native-test")]
+
+use scrypto_test::prelude::*;
+use tuple_return::tuple_return;
+
+mod blueprints {
+    use scrypto::prelude::*;
+
+    #[blueprint]
+    mod panicker {
+        struct Panicker;
+
+        impl Panicker {
+            pub fn panic() {
+                panic!("Native panic");
+            }
+
+            pub fn withdraw_more_than_balance() {
+                let bucket = ResourceBuilder::new_fungible(OwnerRole::None).mint_initial_supply(1);
+                let mut vault = Vault::with_bucket(bucket.into());
+                vault.take(2).burn();
+            }
+        }
+    }
+}
+
+use blueprints::panicker;
+
+const NATIVE_TEST_CODE_ID: u64 = 1024;
+
+fn ledger_with_native_package() -> (
+    LedgerSimulator<OverridePackageCode<NativeTestInterpreter>, InMemorySubstateDatabase>,
+    PackageAddress,
+) {
+    let interpreter = NativeTestInterpreter::new()
+        .with_blueprint("TupleReturn", tuple_return::TupleReturn_schema)
+        .with_export(
+            "TupleReturn_instantiate",
+            tuple_return::TupleReturn_instantiate,
+        )
+        .with_blueprint("Panicker", panicker::Panicker_schema)
+        .with_export("Panicker_panic", panicker::Panicker_panic)
+        .with_export(
+            "Panicker_withdraw_more_than_balance",
+            panicker::Panicker_withdraw_more_than_balance,
+        );
+    let definition = interpreter.package_definition();
+    let mut ledger = LedgerSimulatorBuilder::new()
+        .with_custom_extension(OverridePackageCode::new(NATIVE_TEST_CODE_ID, interpreter))
+        .build();
+    let package_address = ledger.publish_native_package(NATIVE_TEST_CODE_ID, definition);
+
+    (ledger, package_address)
+}
+
+#[test]
+fn natively_compiled_blueprint_can_be_called() {
+    // Arrange
+    let (mut ledger, package_address) = ledger_with_native_package();
+
+    // Act
+    let receipt = ledger.execute_manifest(
+        ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_function(
+                package_address,
+                "TupleReturn",
+                "instantiate",
+                manifest_args!(),
+            )
+            .build(),
+        vec![],
+    );
+
+    // Assert
+    let result = receipt.expect_commit_success();
+    let (component_address, value): (ComponentAddress, u64) = result.output(1);
+    assert_eq!(result.new_component_addresses()[0], component_address);
+    assert_eq!(value, 18);
+}
+
+#[test]
+fn panic_in_natively_compiled_blueprint_fails_transaction() {
+    // Arrange
+    let (mut ledger, package_address) = ledger_with_native_package();
+
+    // Act
+    let receipt = ledger.execute_manifest(
+        ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_function(package_address, "Panicker", "panic", manifest_args!())
+            .build(),
+        vec![],
+    );
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::PanicMessage(message))
+                if message == "Native panic"
+        )
+    });
+}
+
+#[test]
+fn system_error_in_natively_compiled_blueprint_fails_transaction() {
+    // Arrange
+    let (mut ledger, package_address) = ledger_with_native_package();
+
+    // Act
+    let receipt = ledger.execute_manifest(
+        ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_function(
+                package_address,
+                "Panicker",
+                "withdraw_more_than_balance",
+                manifest_args!(),
+            )
+            .build(),
+        vec![],
+    );
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::VaultError(
+                VaultError::ResourceError(ResourceError::InsufficientBalance { .. })
+            ))
+        )
+    });
+}
@@ -115,6 +115,25 @@ impl<
 
         scrypto_decode(&rtn).unwrap()
     }
+
+    /// Scans up to `limit` entries of this map after the `cursor` key, or from the start if it's
+    /// `None`, and returns the keys of those holding a value, along with the cursor of the next
+    /// page or `None` once all keys have been returned.
+    ///
+    /// Removed entries count towards the `limit`, so a page may hold fewer keys than `limit`
+    /// before the end of the map is reached.
+    pub fn keys(&self, cursor: Option<&K>, limit: u32) -> (Vec<K>, Option<K>) {
+        let cursor = cursor.map(|key| scrypto_encode(key).unwrap());
+        let page = ScryptoVmV1Api::kv_store_scan_keys(self.id.as_node_id(), cursor, limit);
+        let keys = page
+            .keys
+            .iter()
+            .map(|key| scrypto_decode(key).unwrap())
+            .collect();
+        let next_cursor = page.next_cursor.map(|key| scrypto_decode(&key).unwrap());
+
+        (keys, next_cursor)
+    }
 }
 
 //========
@@ -1,2 +1,4 @@
+#[cfg(all(feature = "native-test", not(target_arch = "wasm32")))]
+pub mod native_host;
 pub mod scrypto_env;
 pub mod wasm_api;
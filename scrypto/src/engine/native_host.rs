@@ -0,0 +1,88 @@
+//! Bindings of the Scrypto runtime for blueprints compiled natively rather than to WASM, for tests.
+//!
+//! Each function of [`wasm_api`](super::wasm_api) is forwarded to the [`NativeHost`] installed on
+//! the current thread, with its arguments and return value passed as they would be across the WASM
+//! boundary, except that pointers refer to native memory.
+
+use radix_engine_interface::types::Buffer;
+use sbor::rust::cell::RefCell;
+use sbor::rust::vec::Vec;
+
+/// The host of natively compiled blueprints, which implements the functions they import.
+pub trait NativeHost {
+    /// Invokes the function of the given name.
+    ///
+    /// Errors are reported by unwinding, so that the blueprint doesn't carry on executing.
+    fn invoke(&mut self, function_name: &str, args: &[u64]) -> u64;
+}
+
+thread_local! {
+    static HOST: RefCell<Option<*mut (dyn NativeHost + 'static)>> = const { RefCell::new(None) };
+    static RETURN_VALUE: RefCell<Option<Vec<u8>>> = const { RefCell::new(None) };
+}
+
+/// Runs the given function with the given host installed on the current thread, restoring the
+/// previously installed host afterwards, even if the function unwinds.
+pub fn with_native_host<R>(host: &mut dyn NativeHost, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<*mut (dyn NativeHost + 'static)>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            HOST.with(|host| *host.borrow_mut() = self.0);
+        }
+    }
+
+    // SAFETY: the host is only used while it's installed, which is within the lifetime of the
+    // borrow, as it's uninstalled when `_restore` is dropped.
+    let host: *mut (dyn NativeHost + 'static) = unsafe { core::mem::transmute(host) };
+    let _restore = Restore(HOST.with(|current| current.borrow_mut().replace(host)));
+
+    f()
+}
+
+/// Invokes the function of the given name on the host installed on the current thread.
+pub fn invoke_native_host(function_name: &str, args: &[u64]) -> u64 {
+    let host = HOST
+        .with(|host| *host.borrow())
+        .unwrap_or_else(|| panic!("No native host is installed to invoke `{}`", function_name));
+
+    // SAFETY: the host is installed, so it's still borrowed by `with_native_host`. The borrow of
+    // the thread local is released before invoking, so that the host can run nested blueprints.
+    unsafe { (*host).invoke(function_name, args) }
+}
+
+/// Records the value returned by a natively compiled export, as native pointers don't fit in a
+/// [`Slice`](radix_engine_interface::types::Slice).
+pub fn set_return_value(value: Vec<u8>) {
+    RETURN_VALUE.with(|return_value| *return_value.borrow_mut() = Some(value));
+}
+
+/// Takes the value returned by the last natively compiled export.
+pub fn take_return_value() -> Option<Vec<u8>> {
+    RETURN_VALUE.with(|return_value| return_value.borrow_mut().take())
+}
+
+/// A type returned by the host, as passed across the WASM boundary.
+pub trait FromNativeHost {
+    fn from_native_host(value: u64) -> Self;
+}
+
+impl FromNativeHost for () {
+    fn from_native_host(_value: u64) -> Self {}
+}
+
+impl FromNativeHost for u32 {
+    fn from_native_host(value: u64) -> Self {
+        value as u32
+    }
+}
+
+impl FromNativeHost for Buffer {
+    fn from_native_host(value: u64) -> Self {
+        Buffer(value)
+    }
+}
+
+pub fn from_native_host<T: FromNativeHost>(value: u64) -> T {
+    T::from_native_host(value)
+}
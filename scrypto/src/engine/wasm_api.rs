@@ -13,6 +13,7 @@ pub fn copy_buffer(buffer: Buffer) -> Vec<u8> {
     vec
 }
 
+#[cfg(any(target_arch = "wasm32", not(feature = "native-test")))]
 pub fn forget_vec(vec: Vec<u8>) -> Slice {
     let ptr = vec.as_ptr() as usize;
     let len = vec.len();
@@ -26,6 +27,17 @@ pub fn forget_vec(vec: Vec<u8>) -> Slice {
     Slice::new(ptr as u32, len as u32)
 }
 
+/// Hands the vector to the native host, as native pointers don't fit in a [`Slice`].
+#[cfg(all(feature = "native-test", not(target_arch = "wasm32")))]
+pub fn forget_vec(vec: Vec<u8>) -> Slice {
+    let len = vec.len();
+    assert!(len <= 0xffffffff);
+
+    super::native_host::set_return_value(vec);
+
+    Slice::new(0, len as u32)
+}
+
 /// Api make blueprint function calls
 pub mod blueprint {
     pub use radix_engine_interface::types::{Buffer, BufferId, Slice};
@@ -200,6 +212,16 @@ pub mod kv_store {
             key: *const u8,
             key_len: usize,
         ) -> Buffer;
+
+        /// Returns a page of the keys of a key value store which hold a value, continuing after
+        /// the given encoded cursor key
+        pub fn kv_store_scan_keys(
+            key_value_store_id_ptr: *const u8,
+            key_value_store_id_len: usize,
+            cursor_ptr: *const u8,
+            cursor_len: usize,
+            limit: u32,
+        ) -> Buffer;
     }
 }
 
@@ -381,7 +403,18 @@ macro_rules! wasm_extern_c {
         }
 
         $(
-            #[cfg(not(target_arch = "wasm32"))]
+            #[cfg(all(feature = "native-test", not(target_arch = "wasm32")))]
+            $(#[$meta])*
+            pub unsafe fn $fn_ident ( $($arg_name: $arg_type),* ) $(-> $rtn_type)? {
+                $crate::engine::native_host::from_native_host(
+                    $crate::engine::native_host::invoke_native_host(
+                        stringify!($fn_ident),
+                        &[$($arg_name as u64),*],
+                    )
+                )
+            }
+
+            #[cfg(all(not(feature = "native-test"), not(target_arch = "wasm32")))]
             $(#[$meta])*
             pub unsafe fn $fn_ident ( $(_: $arg_type),* ) $(-> $rtn_type)? {
                 unimplemented!("Not implemented for non-wasm targets")
@@ -70,7 +70,11 @@ extern crate self as scrypto;
 
 /// Sets up panic hook.
 pub fn set_up_panic_hook() {
-    #[cfg(not(feature = "alloc"))]
+    // Natively compiled blueprints unwind on panic, and their host reports the panic.
+    #[cfg(all(
+        not(feature = "alloc"),
+        any(target_arch = "wasm32", not(feature = "native-test"))
+    ))]
     std::panic::set_hook(Box::new(|info| {
         let mut message = String::new();
 
@@ -5,10 +5,19 @@
 /// use scrypto::prelude::*;
 ///
 /// error!("Input number: {}", 100);
+///
+/// // Structured fields are appended to the message, as in "Input received number=100"
+/// error!(number = 100; "Input received");
 /// ```
 #[cfg(feature = "log-error")]
 #[macro_export]
 macro_rules! error {
+    ($($key: ident = $value: expr),+; $($args: expr),+) => {{
+        $crate::runtime::Logger::error($crate::runtime::Logger::format_with_fields(
+            ::scrypto::prelude::sbor::rust::format!($($args),+),
+            &[$((::core::stringify!($key), &$value as &dyn ::core::fmt::Debug)),+],
+        ));
+    }};
     ($($args: expr),+) => {{
         $crate::runtime::Logger::error(::scrypto::prelude::sbor::rust::format!($($args),+));
     }};
@@ -17,6 +26,14 @@ macro_rules! error {
 #[cfg(not(feature = "log-error"))]
 #[macro_export]
 macro_rules! error {
+    ($($key: ident = $value: expr),+; $($args: expr),+) => {{
+        // See the comment below
+        #[allow(unreachable_code)]
+        if false {
+            let _ = ::scrypto::prelude::sbor::rust::format!($($args),+);
+            $(let _ = &$value;)+
+        }
+    }};
     ($($args: expr),+) => {{
         // The operation below does nothing at runtime but consumes the expressions,
         // allowing tools (e.g., rust-analyzer) to parse them correctly to identify
@@ -39,10 +56,19 @@ macro_rules! error {
 /// use scrypto::prelude::*;
 ///
 /// warn!("Input number: {}", 100);
+///
+/// // Structured fields are appended to the message, as in "Input received number=100"
+/// warn!(number = 100; "Input received");
 /// ```
 #[cfg(feature = "log-warn")]
 #[macro_export]
 macro_rules! warn {
+    ($($key: ident = $value: expr),+; $($args: expr),+) => {{
+        $crate::runtime::Logger::warn($crate::runtime::Logger::format_with_fields(
+            ::scrypto::prelude::sbor::rust::format!($($args),+),
+            &[$((::core::stringify!($key), &$value as &dyn ::core::fmt::Debug)),+],
+        ));
+    }};
     ($($args: expr),+) => {{
         $crate::runtime::Logger::warn(::scrypto::prelude::sbor::rust::format!($($args),+));
     }};
@@ -51,6 +77,13 @@ macro_rules! warn {
 #[cfg(not(feature = "log-warn"))]
 #[macro_export]
 macro_rules! warn {
+    ($($key: ident = $value: expr),+; $($args: expr),+) => {{
+        // See `error` macro comment
+        if false {
+            let _ = ::scrypto::prelude::sbor::rust::format!($($args),+);
+            $(let _ = &$value;)+
+        }
+    }};
     ($($args: expr),+) => {{
         // See `error` macro comment
         if false {
@@ -66,10 +99,19 @@ macro_rules! warn {
 /// use scrypto::prelude::*;
 ///
 /// info!("Input number: {}", 100);
+///
+/// // Structured fields are appended to the message, as in "Input received number=100"
+/// info!(number = 100; "Input received");
 /// ```
 #[cfg(feature = "log-info")]
 #[macro_export]
 macro_rules! info {
+    ($($key: ident = $value: expr),+; $($args: expr),+) => {{
+        $crate::runtime::Logger::info($crate::runtime::Logger::format_with_fields(
+            ::scrypto::prelude::sbor::rust::format!($($args),+),
+            &[$((::core::stringify!($key), &$value as &dyn ::core::fmt::Debug)),+],
+        ));
+    }};
     ($($args: expr),+) => {{
         $crate::runtime::Logger::info(::scrypto::prelude::sbor::rust::format!($($args),+));
     }};
@@ -78,6 +120,13 @@ macro_rules! info {
 #[cfg(not(feature = "log-info"))]
 #[macro_export]
 macro_rules! info {
+    ($($key: ident = $value: expr),+; $($args: expr),+) => {{
+        // See `error` macro comment
+        if false {
+            let _ = ::scrypto::prelude::sbor::rust::format!($($args),+);
+            $(let _ = &$value;)+
+        }
+    }};
     ($($args: expr),+) => {{
         // See `error` macro comment
         if false {
@@ -93,10 +142,19 @@ macro_rules! info {
 /// use scrypto::prelude::*;
 ///
 /// debug!("Input number: {}", 100);
+///
+/// // Structured fields are appended to the message, as in "Input received number=100"
+/// debug!(number = 100; "Input received");
 /// ```
 #[cfg(feature = "log-debug")]
 #[macro_export]
 macro_rules! debug {
+    ($($key: ident = $value: expr),+; $($args: expr),+) => {{
+        $crate::runtime::Logger::debug($crate::runtime::Logger::format_with_fields(
+            ::scrypto::prelude::sbor::rust::format!($($args),+),
+            &[$((::core::stringify!($key), &$value as &dyn ::core::fmt::Debug)),+],
+        ));
+    }};
     ($($args: expr),+) => {{
         $crate::runtime::Logger::debug(::scrypto::prelude::sbor::rust::format!($($args),+));
     }};
@@ -105,6 +163,13 @@ macro_rules! debug {
 #[cfg(not(feature = "log-debug"))]
 #[macro_export]
 macro_rules! debug {
+    ($($key: ident = $value: expr),+; $($args: expr),+) => {{
+        // See `error` macro comment
+        if false {
+            let _ = ::scrypto::prelude::sbor::rust::format!($($args),+);
+            $(let _ = &$value;)+
+        }
+    }};
     ($($args: expr),+) => {{
         // See `error` macro comment
         if false {
@@ -120,10 +185,19 @@ macro_rules! debug {
 /// use scrypto::prelude::*;
 ///
 /// trace!("Input number: {}", 100);
+///
+/// // Structured fields are appended to the message, as in "Input received number=100"
+/// trace!(number = 100; "Input received");
 /// ```
 #[cfg(feature = "log-trace")]
 #[macro_export]
 macro_rules! trace {
+    ($($key: ident = $value: expr),+; $($args: expr),+) => {{
+        $crate::runtime::Logger::trace($crate::runtime::Logger::format_with_fields(
+            ::scrypto::prelude::sbor::rust::format!($($args),+),
+            &[$((::core::stringify!($key), &$value as &dyn ::core::fmt::Debug)),+],
+        ));
+    }};
     ($($args: expr),+) => {{
         $crate::runtime::Logger::trace(::scrypto::prelude::sbor::rust::format!($($args),+));
     }};
@@ -132,6 +206,13 @@ macro_rules! trace {
 #[cfg(not(feature = "log-trace"))]
 #[macro_export]
 macro_rules! trace {
+    ($($key: ident = $value: expr),+; $($args: expr),+) => {{
+        // See `error` macro comment
+        if false {
+            let _ = ::scrypto::prelude::sbor::rust::format!($($args),+);
+            $(let _ = &$value;)+
+        }
+    }};
     ($($args: expr),+) => {{
         // See `error` macro comment
         if false {
@@ -37,6 +37,15 @@ pub trait ScryptoUncheckedProof {
     /// in your custom validation logic!
     fn skip_checking(self) -> Self::CheckedProofType;
 
+    /// Validates this proof against the mode, and converts it into a checked proof.
+    ///
+    /// Unlike [`check`](Self::check), this doesn't panic if the proof is invalid. It drops the
+    /// proof and returns the reason instead.
+    fn validate(
+        self,
+        mode: ProofValidationMode,
+    ) -> Result<Self::CheckedProofType, ProofValidationError>;
+
     fn resource_address(&self) -> ResourceAddress;
 
     fn resource_manager(&self) -> Self::ResourceManagerType;
@@ -90,6 +99,53 @@ pub trait ScryptoNonFungibleProof {
     fn non_fungibles<T: NonFungibleData>(&self) -> Vec<NonFungible<T>>;
 }
 
+//=================
+// Proof validation
+//=================
+
+/// What a proof is validated against, by [`ScryptoUncheckedProof::validate`].
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub enum ProofValidationMode {
+    /// The proof is of the resource.
+    ResourceAddress(ResourceAddress),
+    /// The proof is of the resource, and of at least the amount.
+    Contains(ResourceAddress, Decimal),
+    /// The proof is of the non-fungible resource, and contains all of the non-fungibles.
+    ContainsNonFungibles(ResourceAddress, IndexSet<NonFungibleLocalId>),
+}
+
+impl ProofValidationMode {
+    pub fn resource_address(&self) -> ResourceAddress {
+        match self {
+            Self::ResourceAddress(resource_address)
+            | Self::Contains(resource_address, _)
+            | Self::ContainsNonFungibles(resource_address, _) => *resource_address,
+        }
+    }
+}
+
+impl From<NonFungibleGlobalId> for ProofValidationMode {
+    fn from(id: NonFungibleGlobalId) -> Self {
+        let (resource_address, local_id) = id.into_parts();
+        Self::ContainsNonFungibles(resource_address, indexset!(local_id))
+    }
+}
+
+/// The reason a proof failed [`ScryptoUncheckedProof::validate`].
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub enum ProofValidationError {
+    InvalidResourceAddress {
+        expected: ResourceAddress,
+        actual: ResourceAddress,
+    },
+    InsufficientAmount {
+        required: Decimal,
+        actual: Decimal,
+    },
+    NotNonFungible(ResourceAddress),
+    MissingNonFungibles(IndexSet<NonFungibleLocalId>),
+}
+
 //=====================
 // Checked proof types
 //=====================
@@ -162,6 +218,17 @@ impl ScryptoUncheckedProof for Proof {
         CheckedProof(self)
     }
 
+    fn validate(self, mode: ProofValidationMode) -> Result<CheckedProof, ProofValidationError> {
+        let proof = CheckedProof(self);
+        match proof.validate_against(&mode) {
+            Ok(()) => Ok(proof),
+            Err(error) => {
+                proof.drop();
+                Err(error)
+            }
+        }
+    }
+
     fn resource_address(&self) -> ResourceAddress {
         let rtn = ScryptoVmV1Api::object_call(
             self.0.as_node_id(),
@@ -240,6 +307,13 @@ impl ScryptoUncheckedProof for FungibleProof {
         CheckedFungibleProof(Proof::skip_checking(self.0))
     }
 
+    fn validate(
+        self,
+        mode: ProofValidationMode,
+    ) -> Result<Self::CheckedProofType, ProofValidationError> {
+        Proof::validate(self.0, mode).map(CheckedFungibleProof)
+    }
+
     fn resource_address(&self) -> ResourceAddress {
         self.0.resource_address()
     }
@@ -285,6 +359,13 @@ impl ScryptoUncheckedProof for NonFungibleProof {
         CheckedNonFungibleProof(Proof::skip_checking(self.0))
     }
 
+    fn validate(
+        self,
+        mode: ProofValidationMode,
+    ) -> Result<Self::CheckedProofType, ProofValidationError> {
+        Proof::validate(self.0, mode).map(CheckedNonFungibleProof)
+    }
+
     fn resource_address(&self) -> ResourceAddress {
         self.0.resource_address()
     }
@@ -347,6 +428,52 @@ impl ScryptoProof for CheckedProof {
     }
 }
 
+impl CheckedProof {
+    fn validate_against(&self, mode: &ProofValidationMode) -> Result<(), ProofValidationError> {
+        let resource_address = self.resource_address();
+        if resource_address != mode.resource_address() {
+            return Err(ProofValidationError::InvalidResourceAddress {
+                expected: mode.resource_address(),
+                actual: resource_address,
+            });
+        }
+
+        match mode {
+            ProofValidationMode::ResourceAddress(..) => Ok(()),
+            ProofValidationMode::Contains(_, amount) => {
+                let actual = self.amount();
+                if actual >= *amount {
+                    Ok(())
+                } else {
+                    Err(ProofValidationError::InsufficientAmount {
+                        required: *amount,
+                        actual,
+                    })
+                }
+            }
+            ProofValidationMode::ContainsNonFungibles(_, ids) => {
+                if !resource_address
+                    .as_node_id()
+                    .is_global_non_fungible_resource_manager()
+                {
+                    return Err(ProofValidationError::NotNonFungible(resource_address));
+                }
+                let contained_ids = self.as_non_fungible().non_fungible_local_ids();
+                let missing_ids: IndexSet<NonFungibleLocalId> = ids
+                    .iter()
+                    .filter(|id| !contained_ids.contains(*id))
+                    .cloned()
+                    .collect();
+                if missing_ids.is_empty() {
+                    Ok(())
+                } else {
+                    Err(ProofValidationError::MissingNonFungibles(missing_ids))
+                }
+            }
+        }
+    }
+}
+
 impl ScryptoGenericProof for CheckedProof {
     fn as_fungible(&self) -> CheckedFungibleProof {
         assert!(
@@ -454,6 +581,9 @@ impl ScryptoNonFungibleProof for CheckedNonFungibleProof {
 
     /// Returns all the non-fungible units contained.
     ///
+    /// Each unit's data can then be read with [`NonFungible::data`], e.g. to branch on a badge's
+    /// data after checking its resource address.
+    ///
     /// # Panics
     /// Panics if this is not a non-fungible proof.
     fn non_fungibles<T: NonFungibleData>(&self) -> Vec<NonFungible<T>> {
@@ -484,7 +614,7 @@ impl ScryptoNonFungibleProof for CheckedNonFungibleProof {
         NonFungibleGlobalId::new(self.resource_address(), self.non_fungible_local_id())
     }
 
-    /// Returns a singleton non-fungible.
+    /// Returns a singleton non-fungible, whose data can be read with [`NonFungible::data`].
     ///
     /// # Panics
     /// Panics if this is not a singleton proof
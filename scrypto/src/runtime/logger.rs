@@ -1,5 +1,7 @@
 use crate::engine::scrypto_env::ScryptoVmV1Api;
 use radix_engine_interface::types::Level;
+use sbor::rust::fmt;
+use sbor::rust::fmt::Write;
 use sbor::rust::string::String;
 
 /// A utility for logging messages.
@@ -31,4 +33,13 @@ impl Logger {
     pub fn error(message: String) {
         ScryptoVmV1Api::sys_log(Level::Error, message);
     }
+
+    /// Appends the structured fields of a message as ` key=value` pairs, with the values
+    /// formatted with `Debug`, so that they can be parsed back from the logs.
+    pub fn format_with_fields(mut message: String, fields: &[(&str, &dyn fmt::Debug)]) -> String {
+        for (key, value) in fields {
+            write!(message, " {}={:?}", key, value).expect("Writing to a string can't fail");
+        }
+        message
+    }
 }
@@ -1,6 +1,6 @@
 use crate::component::ObjectStubHandle;
 use crate::engine::wasm_api::{addr, copy_buffer};
-use crate::prelude::{AnyComponent, Global};
+use crate::prelude::{AnyComponent, Clock, Global};
 use radix_common::constants::CONSENSUS_MANAGER;
 use radix_common::crypto::Hash;
 use radix_common::data::scrypto::{scrypto_decode, scrypto_encode, ScryptoDescribe, ScryptoEncode};
@@ -13,7 +13,7 @@ use radix_common::types::NonFungibleGlobalId;
 use radix_engine_interface::api::actor_api::EventFlags;
 use radix_engine_interface::api::*;
 use radix_engine_interface::blueprints::consensus_manager::{
-    ConsensusManagerGetCurrentEpochInput, CONSENSUS_MANAGER_GET_CURRENT_EPOCH_IDENT,
+    ConsensusManagerGetCurrentEpochInput, TimePrecision, CONSENSUS_MANAGER_GET_CURRENT_EPOCH_IDENT,
 };
 use radix_engine_interface::blueprints::resource::{
     AccessRule, AuthZoneAssertAccessRuleInput, AUTH_ZONE_ASSERT_ACCESS_RULE_IDENT,
@@ -103,6 +103,10 @@ impl Runtime {
     }
 
     /// Emits an application event
+    ///
+    /// The event type must be declared on the blueprint with `#[events(...)]`, which adds its
+    /// schema to the blueprint definition. The engine validates the emitted payload against that
+    /// schema, and fails the transaction if the event is not declared or does not match.
     pub fn emit_event<T: ScryptoEncode + ScryptoDescribe + ScryptoEvent>(event: T) {
         ScryptoVmV1Api::actor_emit_event(
             T::EVENT_NAME.to_owned(),
@@ -125,15 +129,46 @@ impl Runtime {
         ScryptoVmV1Api::sys_get_transaction_hash()
     }
 
-    /// Returns the transaction hash.
+    /// Generates a RUID, which is unique within the transaction and across transactions.
     pub fn generate_ruid() -> [u8; 32] {
         ScryptoVmV1Api::sys_generate_ruid()
     }
 
+    /// Generates 32 deterministic pseudo-random bytes, separated by the given seed domain.
+    ///
+    /// The bytes are derived from the transaction hash and the transaction's RUID counter, so
+    /// every call returns different bytes, and all validators derive the same ones.
+    ///
+    /// This is NOT a source of secure randomness: the outcome is known to anyone who knows the
+    /// transaction, and the submitter can try out transactions until the outcome suits them.
+    /// Blueprints which need unpredictable randomness (e.g. lotteries) should use a commit-reveal
+    /// scheme or an oracle instead.
+    pub fn random(seed_domain: &[u8]) -> [u8; 32] {
+        let mut bytes = Vec::with_capacity(32 + seed_domain.len());
+        bytes.extend_from_slice(&Self::generate_ruid());
+        bytes.extend_from_slice(seed_domain);
+        hash(bytes).0
+    }
+
     pub fn bech32_encode_address<A: Into<GlobalAddress>>(address: A) -> String {
         ScryptoVmV1Api::sys_bech32_encode_address(address.into())
     }
 
+    /// Returns the HRP suffix of the addresses of the network the transaction is executing on,
+    /// e.g. `rdx` on mainnet or `tdx_2_` on stokenet.
+    ///
+    /// This is a stop-gap, read from the bech32 encoding of a well-known address, until the
+    /// network is exposed to blueprints by the system.
+    pub fn network() -> String {
+        let address = Self::bech32_encode_address(RESOURCE_PACKAGE);
+        let (hrp, _) = address
+            .rsplit_once('1')
+            .expect("Bech32 addresses have a separator");
+        hrp.strip_prefix("package_")
+            .expect("Package addresses have a package HRP")
+            .to_string()
+    }
+
     pub fn panic(message: String) -> ! {
         ScryptoVmV1Api::sys_panic(message);
         loop {}
@@ -150,6 +185,13 @@ impl Runtime {
         scrypto_decode(&rtn).unwrap()
     }
 
+    /// Returns the current timestamp (in seconds), rounded down to the specified precision.
+    ///
+    /// This is a shorthand for [`Clock::current_time`].
+    pub fn current_time(precision: TimePrecision) -> Instant {
+        Clock::current_time(precision)
+    }
+
     pub fn get_execution_cost_unit_limit() -> u32 {
         ScryptoVmV1Api::costing_get_execution_cost_unit_limit()
     }